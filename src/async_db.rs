@@ -0,0 +1,215 @@
+//! An async front end to [`DB`] for use inside a tokio runtime.
+//!
+//! LevelDB calls block on disk I/O, which stalls every task sharing the
+//! runtime thread. [`AsyncDB`] runs each call on tokio's blocking thread pool
+//! through [`tokio::task::spawn_blocking`] instead.
+
+use tokio::sync::mpsc;
+use tokio::task;
+
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+use crate::write_batch::WriteBatch;
+
+/// Key-value pairs sent by [`AsyncDB::scan`].
+pub type ScanBatch = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// A handle that runs database calls on tokio's blocking thread pool.
+///
+/// Every call is handed to [`tokio::task::spawn_blocking`] and runs to
+/// completion even if the future awaiting it is dropped, so cancelling a
+/// request never leaves a write half done; it only discards the result.
+///
+/// Reads see the latest data, and writes use the `sync` setting given to
+/// [`AsyncDB::sync_writes`]. Options are not taken per call, since a
+/// snapshot referenced by `ReadOptions` could be released while a cancelled
+/// read still runs.
+///
+/// `AsyncDB` is cheap to clone; clones share the database, like clones of
+/// [`DB`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> Result<(), String> {
+/// use bleveldb::{AsyncDB, DB};
+/// use bleveldb::options::Options;
+///
+/// let db = AsyncDB::new(DB::open("world/db", &Options::default())?);
+/// db.put(b"key".to_vec(), b"value".to_vec()).await?;
+/// assert_eq!(db.get(b"key".to_vec()).await?, Some(b"value".to_vec()));
+///
+/// let mut batches = db.scan(None, None, 1000);
+/// while let Some(batch) = batches.recv().await {
+///     println!("{} entries", batch?.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AsyncDB {
+    db: DB,
+    sync: bool,
+}
+
+impl AsyncDB {
+    /// Wrap a database handle.
+    pub fn new(db: DB) -> Self {
+        AsyncDB { db, sync: false }
+    }
+
+    /// Set whether writes are flushed to disk before they complete.
+    pub fn sync_writes(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Return the wrapped database handle, for calls that are cheap enough
+    /// to make on the runtime thread.
+    pub fn db(&self) -> &DB {
+        &self.db
+    }
+
+    /// Run `f` with the database on the blocking thread pool.
+    async fn run<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&DB) -> Result<T, String> + Send + 'static,
+    ) -> Result<T, String> {
+        let db = self.db.clone();
+        task::spawn_blocking(move || f(&db))
+            .await
+            .map_err(|e| format!("Database task failed: {}", e))?
+    }
+
+    /// Read the value of a key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Vec<u8>))` - The value
+    /// * `Ok(None)` - If the key does not exist
+    /// * `Err(String)` - If the read failed
+    pub async fn get(&self, key: Vec<u8>) -> Result<Option<Vec<u8>>, String> {
+        self.run(move |db| db.get(key, &ReadOptions::new())).await
+    }
+
+    /// Store a value.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the value was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub async fn put(&self, key: Vec<u8>, value: Vec<u8>) -> Result<(), String> {
+        let sync = self.sync;
+        self.run(move |db| db.put(key, value, &Self::options(sync)))
+            .await
+    }
+
+    /// Delete a key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the key was deleted or did not exist
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub async fn delete(&self, key: Vec<u8>) -> Result<(), String> {
+        let sync = self.sync;
+        self.run(move |db| db.delete(key, &Self::options(sync)))
+            .await
+    }
+
+    /// Apply a batch atomically.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the batch was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub async fn write(&self, batch: WriteBatch) -> Result<(), String> {
+        let sync = self.sync;
+        self.run(move |db| db.write(&batch, &Self::options(sync)))
+            .await
+    }
+
+    /// Compact the key range `[start, limit]`; `None` leaves that end open.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Once the compaction finished
+    /// * `Err(String)` - If the database is read-only
+    pub async fn compact_range(
+        &self,
+        start: Option<Vec<u8>>,
+        limit: Option<Vec<u8>>,
+    ) -> Result<(), String> {
+        self.run(move |db| db.compact_range(start.as_deref(), limit.as_deref()))
+            .await
+    }
+
+    /// Stream the entries of `[start, end)` in key order, in batches of up
+    /// to `batch_size` entries; `None` leaves that end open.
+    ///
+    /// The scan runs on the blocking thread pool and sees one consistent
+    /// view of the database. Only two batches are buffered, so a slow
+    /// consumer pauses the scan rather than filling memory. Dropping the
+    /// receiver stops the scan after the batch in progress. A read error is
+    /// sent as the last item.
+    ///
+    /// Must be called from within a tokio runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    pub fn scan(
+        &self,
+        start: Option<Vec<u8>>,
+        end: Option<Vec<u8>>,
+        batch_size: usize,
+    ) -> mpsc::Receiver<Result<ScanBatch, String>> {
+        assert!(batch_size > 0, "batch_size must not be zero");
+        let (sender, receiver) = mpsc::channel(2);
+        let db = self.db.clone();
+        task::spawn_blocking(move || {
+            let read_options = ReadOptions::new();
+            read_options.fill_cache(false);
+            let mut iter = db.iter(&read_options);
+            match &start {
+                Some(start) => iter.seek(start),
+                None => iter.seek_to_first(),
+            }
+            let mut batch = Vec::with_capacity(batch_size);
+            while let (Some(key), Some(value)) = (iter.key_ref(), iter.value_ref()) {
+                if end.as_deref().is_some_and(|end| key >= end) {
+                    break;
+                }
+                batch.push((key.to_vec(), value.to_vec()));
+                if batch.len() == batch_size {
+                    let full = std::mem::replace(&mut batch, Vec::with_capacity(batch_size));
+                    if sender.blocking_send(Ok(full)).is_err() {
+                        return;
+                    }
+                }
+                iter.next_native();
+            }
+            match iter.status() {
+                Ok(()) if batch.is_empty() => {}
+                Ok(()) => {
+                    let _ = sender.blocking_send(Ok(batch));
+                }
+                Err(e) => {
+                    let _ = sender.blocking_send(Err(e));
+                }
+            }
+        });
+        receiver
+    }
+
+    fn options(sync: bool) -> WriteOptions {
+        let options = WriteOptions::new();
+        options.sync(sync);
+        options
+    }
+}
+
+impl From<DB> for AsyncDB {
+    fn from(db: DB) -> Self {
+        AsyncDB::new(db)
+    }
+}
@@ -0,0 +1,258 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::db::{DB, DEFAULT_BATCH_BYTES};
+use crate::iterator::DBIterator;
+use crate::options::{Options, ReadOptions, WriteOptions};
+use crate::write_batch::WriteBatch;
+
+/// How many times [`DB::checkpoint`] restarts when the database changes under it.
+const CHECKPOINT_ATTEMPTS: usize = 5;
+
+/// Summary of a completed [`DB::backup_to`] call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackupReport {
+    /// Number of key/value pairs copied.
+    pub keys: u64,
+    /// Total size of the copied keys and values, in bytes.
+    pub bytes: u64,
+    /// Number of write batches committed to the destination.
+    pub batches: u64,
+}
+
+impl DB {
+    /// Copy the database into a fresh database at `dest` while it stays in use.
+    ///
+    /// Copying a world directory while the game or another tool is writing to
+    /// it can produce a corrupt backup. This method instead reads every entry
+    /// under a snapshot and writes it into a new database through batches of
+    /// about [`DEFAULT_BATCH_BYTES`], so writes made to the source during the
+    /// backup neither block it nor leak into it.
+    ///
+    /// The destination is created with `options`, except that it always uses
+    /// the compression of the source database and must not already exist. If
+    /// the backup fails, the partially written destination is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest` - The directory to create the backup in
+    /// * `options` - Options used to create the destination database
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BackupReport)` - How much data was copied
+    /// * `Err(String)` - If the destination could not be created or the copy failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let report = db.backup_to("backups/world-db", &Options::new()).unwrap();
+    /// println!("Backed up {} keys ({} bytes)", report.keys, report.bytes);
+    /// ```
+    pub fn backup_to(
+        &self,
+        dest: impl AsRef<Path>,
+        options: &Options,
+    ) -> Result<BackupReport, String> {
+        let dest = dest.as_ref();
+        let existed = dest.exists();
+
+        let dest_options = options.duplicate();
+        dest_options.create_if_missing(true);
+        dest_options.error_if_exists(true);
+        dest_options.compression(self.compression());
+
+        let target = DB::open(dest, &dest_options)?;
+        let copied = self.copy_into(&target).and_then(|report| {
+            target.close()?;
+            Ok(report)
+        });
+
+        copied.inspect_err(|_| {
+            let _ = DB::destroy(dest, &dest_options);
+            if !existed {
+                let _ = fs::remove_dir_all(dest);
+            }
+        })
+    }
+
+    /// Create an openable copy of the database at `dest` using hard links.
+    ///
+    /// The write-ahead log is synced first (see [`DB::flush`]). Table files
+    /// never change once written, so they are hard-linked into `dest` instead
+    /// of copied, which makes checkpointing a multi-gigabyte world nearly
+    /// instant. `CURRENT`, the manifest and the log files are copied. Where
+    /// hard links are unavailable, such as across filesystems or on FAT/exFAT
+    /// SD cards, table files are copied too.
+    ///
+    /// The result is an independent database: writes to either side never
+    /// show up in the other. If LevelDB flushes or compacts while the files are
+    /// gathered, the checkpoint is started over. Writes made concurrently with
+    /// the call may or may not be included; use [`DB::backup_to`] if the copy
+    /// must match an exact point in time.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest` - The directory to create; it must not exist or be empty
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the checkpoint was created
+    /// * `Err(String)` - If `dest` is not empty or the files could not be linked or copied
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// # use std::path::Path;
+    /// let db = DB::open("world/db", &Options::default()).unwrap();
+    /// db.checkpoint(Path::new("world/db-before-edit")).unwrap();
+    /// ```
+    pub fn checkpoint(&self, dest: &Path) -> Result<(), String> {
+        self.flush()?;
+
+        let fail = |e: io::Error| {
+            format!(
+                "failed to checkpoint {} into {}: {}",
+                self.path().display(),
+                dest.display(),
+                e
+            )
+        };
+
+        let created = !dest.exists();
+        if !created && fs::read_dir(dest).map_err(fail)?.next().is_some() {
+            return Err(format!(
+                "checkpoint destination {} is not empty",
+                dest.display()
+            ));
+        }
+        fs::create_dir_all(dest).map_err(fail)?;
+
+        let mut result = Err(fail(io::Error::other(
+            "the database kept changing while it was copied",
+        )));
+        for _ in 0..CHECKPOINT_ATTEMPTS {
+            match copy_checkpoint_files(self.path(), dest) {
+                Ok(true) => return Ok(()),
+                // A file vanished or the manifest moved on; gather everything again
+                Ok(false) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    result = Err(fail(e));
+                    break;
+                }
+            }
+            clear_dir(dest).map_err(fail)?;
+        }
+
+        let _ = clear_dir(dest);
+        if created {
+            let _ = fs::remove_dir(dest);
+        }
+        result
+    }
+
+    /// Stream every entry visible in a fresh snapshot into `target`.
+    fn copy_into(&self, target: &DB) -> Result<BackupReport, String> {
+        let (_snapshot, read_options) = self.consistent_read_options(&ReadOptions::new());
+        read_options.fill_cache(false);
+        let write_options = WriteOptions::new();
+
+        let mut iter = DBIterator::new(self, &read_options);
+        let mut batch = WriteBatch::new();
+        let mut pending_bytes = 0;
+        let mut report = BackupReport::default();
+
+        iter.seek_to_first();
+        while let (Some(key), Some(value)) = (iter.key_ref(), iter.value_ref()) {
+            batch.put(key, value);
+            pending_bytes += key.len() + value.len();
+            report.keys += 1;
+            report.bytes += (key.len() + value.len()) as u64;
+
+            if pending_bytes >= DEFAULT_BATCH_BYTES {
+                target.write(&batch, &write_options)?;
+                batch.clear();
+                report.batches += 1;
+                pending_bytes = 0;
+            }
+            iter.next_native();
+        }
+        iter.status()?;
+
+        if pending_bytes > 0 {
+            target.write(&batch, &write_options)?;
+            report.batches += 1;
+        }
+        Ok(report)
+    }
+}
+
+/// Link or copy the files making up the database at `source` into `dest`.
+///
+/// Returns `Ok(false)` if `CURRENT` or the manifest changed while the files
+/// were gathered, in which case the copy may be inconsistent.
+fn copy_checkpoint_files(source: &Path, dest: &Path) -> io::Result<bool> {
+    let current = fs::read(source.join("CURRENT"))?;
+    let manifest_name = String::from_utf8_lossy(&current).trim_end().to_string();
+    let manifest = source.join(&manifest_name);
+    let manifest_len = fs::metadata(&manifest)?.len();
+
+    // Tables first: as long as the manifest does not grow afterwards, every
+    // table it references was present when the directory was listed.
+    let mut logs = Vec::new();
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let name = entry.file_name();
+        let name_str = name.to_string_lossy();
+        if name_str.ends_with(".ldb") || name_str.ends_with(".sst") {
+            hard_link_or_copy(&entry.path(), &dest.join(&name))?;
+        } else if name_str.ends_with(".log") {
+            logs.push(name);
+        }
+    }
+
+    let copied_len = fs::copy(&manifest, dest.join(&manifest_name))?;
+    for name in logs {
+        fs::copy(source.join(&name), dest.join(&name))?;
+    }
+
+    let unchanged = copied_len == manifest_len
+        && fs::metadata(&manifest)?.len() == manifest_len
+        && fs::read(source.join("CURRENT"))? == current;
+    if unchanged {
+        fs::write(dest.join("CURRENT"), &current)?;
+    }
+    Ok(unchanged)
+}
+
+/// Hard-link `from` to `to`, copying instead where links are not supported.
+fn hard_link_or_copy(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::hard_link(from, to) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(e),
+        Err(_) => fs::copy(from, to).map(|_| ()),
+    }
+}
+
+/// Remove everything inside `dir`, keeping the directory itself.
+fn clear_dir(dir: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
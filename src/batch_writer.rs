@@ -0,0 +1,204 @@
+use crate::db::DB;
+use crate::options::WriteOptions;
+use crate::write_batch::WriteBatch;
+
+/// A writer that groups puts and deletes into batches and commits them automatically.
+///
+/// Importers usually accumulate records into a [`WriteBatch`] and commit it
+/// every so often. `BatchWriter` does that bookkeeping: it commits the
+/// pending batch as soon as it holds `max_ops` operations or its
+/// [`approximate_size`](WriteBatch::approximate_size) reaches `max_bytes`,
+/// and commits whatever is left in [`finish`](BatchWriter::finish).
+///
+/// Each commit is atomic, but the writer as a whole is not: when a commit
+/// fails, the batches committed before it stay in the database. The error is
+/// returned by the call that triggered the commit and by every later call,
+/// including `finish`, and says how many operations were written before the
+/// failure. The operations of the failed batch are discarded.
+///
+/// Dropping the writer without calling `finish` still commits the pending
+/// operations, but any error is lost (it is logged as a warning when the
+/// `logging` feature is enabled), so prefer calling `finish`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::{BatchWriter, DB};
+/// # use bleveldb::options::{Options, WriteOptions};
+/// # let db = DB::open("test_db", &Options::default()).unwrap();
+/// let mut writer = BatchWriter::new(&db, WriteOptions::new(), 10_000, 4 << 20);
+/// for i in 0..1_000_000u32 {
+///     writer.put(&i.to_be_bytes(), b"value").unwrap();
+/// }
+/// let written = writer.finish().unwrap();
+/// println!("Wrote {} operations", written);
+/// ```
+pub struct BatchWriter<'db> {
+    db: &'db DB,
+    options: WriteOptions,
+    batch: WriteBatch,
+    max_ops: usize,
+    max_bytes: usize,
+    /// Operations in successfully committed batches.
+    written: u64,
+    /// Number of successfully committed batches.
+    batches: u64,
+    /// The first commit error, returned by every later call.
+    error: Option<String>,
+}
+
+impl<'db> BatchWriter<'db> {
+    /// Create a writer that commits to `db` with `options`.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database to write to
+    /// * `options` - Write options used for every commit
+    /// * `max_ops` - Commit once the pending batch holds this many operations
+    /// * `max_bytes` - Commit once the pending batch's encoded size reaches this many bytes
+    ///
+    /// A threshold of 0 commits after every operation.
+    pub fn new(db: &'db DB, options: WriteOptions, max_ops: usize, max_bytes: usize) -> Self {
+        Self {
+            db,
+            options,
+            batch: WriteBatch::new(),
+            max_ops,
+            max_bytes,
+            written: 0,
+            batches: 0,
+            error: None,
+        }
+    }
+
+    /// Queue a put, committing the pending batch if a threshold is reached.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to insert or update
+    /// * `value` - The value to associate with the key
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the operation was queued (and committed, if a threshold was reached)
+    /// * `Err(String)` - If this or an earlier commit failed
+    pub fn put(&mut self, key: impl AsRef<[u8]>, value: impl AsRef<[u8]>) -> Result<(), String> {
+        self.check()?;
+        self.batch.put(key, value);
+        self.commit_if_full()
+    }
+
+    /// Queue a delete, committing the pending batch if a threshold is reached.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to delete
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the operation was queued (and committed, if a threshold was reached)
+    /// * `Err(String)` - If this or an earlier commit failed
+    pub fn delete(&mut self, key: impl AsRef<[u8]>) -> Result<(), String> {
+        self.check()?;
+        self.batch.delete(key);
+        self.commit_if_full()
+    }
+
+    /// Commit the pending operations now, regardless of the thresholds.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the pending operations were committed, or there were none
+    /// * `Err(String)` - If this or an earlier commit failed
+    pub fn flush(&mut self) -> Result<(), String> {
+        self.check()?;
+        self.commit()
+    }
+
+    /// Commit the remaining operations and consume the writer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - The total number of operations written
+    /// * `Err(String)` - If any commit failed, with the number of operations written before it
+    pub fn finish(mut self) -> Result<u64, String> {
+        self.flush()?;
+        Ok(self.written)
+    }
+
+    /// Return how many operations have been committed so far.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Return how many batches have been committed so far.
+    pub fn batches(&self) -> u64 {
+        self.batches
+    }
+
+    /// Return how many operations are queued but not yet committed.
+    pub fn pending(&self) -> usize {
+        self.batch.len()
+    }
+
+    /// Fail with the stored error if an earlier commit failed.
+    fn check(&self) -> Result<(), String> {
+        match &self.error {
+            Some(e) => Err(e.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn commit_if_full(&mut self) -> Result<(), String> {
+        if self.batch.len() >= self.max_ops || self.batch.approximate_size() >= self.max_bytes {
+            self.commit()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Write the pending batch, remembering the error if it fails.
+    fn commit(&mut self) -> Result<(), String> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+        let pending = self.batch.len() as u64;
+        let result = self.db.write(&self.batch, &self.options);
+        self.batch.clear();
+        match result {
+            Ok(()) => {
+                self.written += pending;
+                self.batches += 1;
+                Ok(())
+            }
+            Err(e) => {
+                let e = format!(
+                    "{} ({} operations in {} batches were written before the failure)",
+                    e, self.written, self.batches
+                );
+                self.error = Some(e.clone());
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Drop for BatchWriter<'_> {
+    /// Commit any pending operations.
+    ///
+    /// Errors cannot be returned from here; call [`BatchWriter::finish`] to see them.
+    fn drop(&mut self) {
+        if self.error.is_some() || self.batch.is_empty() {
+            return;
+        }
+        #[cfg(feature = "logging")]
+        log::warn!(
+            "BatchWriter dropped without finish(), committing {} pending operations",
+            self.batch.len()
+        );
+        if let Err(_e) = self.commit() {
+            #[cfg(feature = "logging")]
+            log::warn!("BatchWriter failed to commit on drop: {}", _e);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use super::actors::{ACTOR_PREFIX, digest_key, encode_digest, parse_digest};
+use crate::bedrock::{ActorId, Dimension};
+use crate::db::DB;
+use crate::options::ReadOptions;
+use crate::write_batch::WriteBatch;
+
+/// Hands out actor ids that do not collide with the actors of a database.
+///
+/// Actors are stored under ids that are only unique within one world, so
+/// chunks imported from another world, or copies of a chunk, need new ones.
+/// The remapper reads every `actorprefix` id of the destination once and
+/// then allocates ids counting up from the largest of them (read as a
+/// big-endian number), skipping any that are taken. Ids it allocated are
+/// remembered too, so one remapper can serve a whole import whose batches
+/// have not been written yet.
+///
+/// With the `nbt` feature, copied actors also get their `UniqueID` field
+/// set to the new id, so the game sees them as distinct actors.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::DB;
+/// # use bleveldb::options::{Options, ReadOptions, WriteOptions};
+/// use bleveldb::bedrock::{ActorIdRemapper, Dimension};
+/// use bleveldb::WriteBatch;
+///
+/// # let src = DB::open("other/db", &Options::default()).unwrap();
+/// # let dst = DB::open("world/db", &Options::default()).unwrap();
+/// let read_opts = ReadOptions::new();
+/// let mut remapper = ActorIdRemapper::new(&dst, &read_opts).unwrap();
+/// let mut batch = WriteBatch::new();
+/// for x in 0..4 {
+///     let chunk = (x, 0, Dimension::Overworld);
+///     remapper.queue_actors(&src, &read_opts, chunk, chunk, &mut batch).unwrap();
+/// }
+/// dst.write(&batch, &WriteOptions::new()).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ActorIdRemapper {
+    used: HashSet<ActorId>,
+    next: u64,
+}
+
+impl ActorIdRemapper {
+    /// Collect the actor ids stored in `dst`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dst` - The database the actors are written to
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ActorIdRemapper)` - A remapper that avoids every id in `dst`
+    /// * `Err(String)` - If reading the database failed
+    pub fn new(dst: &DB, options: &ReadOptions) -> Result<ActorIdRemapper, String> {
+        let mut used = HashSet::new();
+        let mut iter = dst.iter(options);
+        iter.seek(ACTOR_PREFIX);
+        while let Some(key) = iter.key_ref() {
+            let Some(id) = key.strip_prefix(ACTOR_PREFIX) else {
+                break;
+            };
+            if let Ok(id) = <[u8; 8]>::try_from(id) {
+                used.insert(ActorId(id));
+            }
+            iter.next_native();
+        }
+        iter.status()?;
+
+        let next = used
+            .iter()
+            .map(|id| u64::from_be_bytes(id.0))
+            .max()
+            .map_or(1, |last| last.checked_add(1).unwrap_or(1));
+        Ok(ActorIdRemapper { used, next })
+    }
+
+    /// Return whether `id` is stored in the destination or was allocated.
+    pub fn is_used(&self, id: ActorId) -> bool {
+        self.used.contains(&id)
+    }
+
+    /// Allocate an id that is neither stored in the destination nor
+    /// allocated before.
+    pub fn allocate(&mut self) -> ActorId {
+        loop {
+            let id = ActorId(self.next.to_be_bytes());
+            // 0 is skipped after wrapping around, as the game never uses it
+            self.next = self.next.checked_add(1).unwrap_or(1);
+            if self.used.insert(id) {
+                return id;
+            }
+        }
+    }
+
+    /// Queue the actors of a source chunk under fresh ids.
+    ///
+    /// Every actor the source digest lists is read from `src` and added to
+    /// `batch` under a newly allocated id, together with a digest for the
+    /// destination chunk that lists exactly the new ids. Ids in the source
+    /// digest without an actor record are dropped. A chunk without a digest
+    /// queues nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The database to read the actors from
+    /// * `src_options` - Read options for `src`, typically holding a snapshot
+    /// * `from` - Source chunk x, z and dimension
+    /// * `to` - Destination chunk x, z and dimension
+    /// * `batch` - The batch the actors and digest are added to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(ActorId, ActorId)>)` - The old and new id of each queued actor
+    /// * `Err(String)` - If reading failed or the source digest is malformed
+    pub fn queue_actors(
+        &mut self,
+        src: &DB,
+        src_options: &ReadOptions,
+        from: (i32, i32, Dimension),
+        to: (i32, i32, Dimension),
+        batch: &mut WriteBatch,
+    ) -> Result<Vec<(ActorId, ActorId)>, String> {
+        let (from_x, from_z, from_dim) = from;
+        let Some(digest) = src.get(digest_key(from_x, from_z, from_dim), src_options)? else {
+            return Ok(Vec::new());
+        };
+
+        let mut actors = Vec::new();
+        for id in parse_digest(&digest)? {
+            if let Some(data) = src.get(id.key(), src_options)? {
+                actors.push((id, data));
+            }
+        }
+        Ok(self.queue_records(actors, to, batch))
+    }
+
+    /// Queue actor records under fresh ids, together with a digest for the
+    /// destination chunk listing them.
+    pub(crate) fn queue_records(
+        &mut self,
+        actors: Vec<(ActorId, Vec<u8>)>,
+        to: (i32, i32, Dimension),
+        batch: &mut WriteBatch,
+    ) -> Vec<(ActorId, ActorId)> {
+        let (to_x, to_z, to_dim) = to;
+        let mut remapped = Vec::with_capacity(actors.len());
+        for (id, data) in actors {
+            let new_id = self.allocate();
+            batch.put(new_id.key(), rewrite_unique_id(data, new_id));
+            remapped.push((id, new_id));
+        }
+        let new_ids: Vec<ActorId> = remapped.iter().map(|&(_, new_id)| new_id).collect();
+        batch.put(digest_key(to_x, to_z, to_dim), encode_digest(&new_ids));
+        remapped
+    }
+}
+
+/// Set the `UniqueID` of an actor record to `id`, leaving records that are
+/// not a compound with a `UniqueID` unchanged.
+#[cfg(feature = "nbt")]
+fn rewrite_unique_id(data: Vec<u8>, id: ActorId) -> Vec<u8> {
+    use super::nbt::{self, Value};
+
+    let Ok(mut actor) = nbt::decode(&data) else {
+        return data;
+    };
+    match actor.get_mut("UniqueID") {
+        Some(unique_id @ Value::Long(_)) => {
+            *unique_id = Value::Long(i64::from_be_bytes(id.0));
+            nbt::encode(&actor)
+        }
+        _ => data,
+    }
+}
+
+#[cfg(not(feature = "nbt"))]
+fn rewrite_unique_id(data: Vec<u8>, _id: ActorId) -> Vec<u8> {
+    data
+}
@@ -0,0 +1,274 @@
+use std::fmt;
+
+use super::Dimension;
+use super::chunk_key::chunk_prefix;
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+use crate::write_batch::WriteBatch;
+
+/// Key prefix of a chunk's actor digest, followed by the chunk prefix.
+pub(crate) const DIGEST_PREFIX: &[u8] = b"digp";
+/// Key prefix of an actor's NBT record, followed by its id.
+pub(crate) const ACTOR_PREFIX: &[u8] = b"actorprefix";
+
+/// The id of an actor (entity) stored in a Bedrock world.
+///
+/// Since 1.18.30 every actor has its own `actorprefix` record, and each
+/// chunk lists the ids of its actors in a `digp` digest. The id is kept as
+/// the 8 bytes that appear in both keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ActorId(pub [u8; 8]);
+
+impl ActorId {
+    /// Return the key of the actor's `actorprefix` record.
+    pub fn key(&self) -> Vec<u8> {
+        let mut key = Vec::with_capacity(ACTOR_PREFIX.len() + 8);
+        key.extend_from_slice(ACTOR_PREFIX);
+        key.extend_from_slice(&self.0);
+        key
+    }
+}
+
+impl fmt::Display for ActorId {
+    /// Write the id as 16 hex digits, in key order.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+/// An actor listed in a chunk's digest, as returned by [`DB::actors_in_chunk`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Actor {
+    /// The actor's id.
+    pub id: ActorId,
+    /// The actor's NBT record, or `None` if the digest lists a missing actor.
+    pub data: Option<Vec<u8>>,
+}
+
+/// Return the key of the actor digest of a chunk.
+pub(crate) fn digest_key(x: i32, z: i32, dim: Dimension) -> Vec<u8> {
+    let mut key = DIGEST_PREFIX.to_vec();
+    key.extend_from_slice(&chunk_prefix(x, z, dim));
+    key
+}
+
+/// Return the chunk position and dimension a digest key belongs to.
+pub(crate) fn parse_digest_key(key: &[u8]) -> Option<(i32, i32, Dimension)> {
+    let prefix = key.strip_prefix(DIGEST_PREFIX)?;
+    let dimension = match prefix.len() {
+        8 => Dimension::Overworld,
+        12 => match i32::from_le_bytes(prefix[8..12].try_into().unwrap()) {
+            0 => return None,
+            id => Dimension::parse(id),
+        },
+        _ => return None,
+    };
+    let x = i32::from_le_bytes(prefix[..4].try_into().unwrap());
+    let z = i32::from_le_bytes(prefix[4..8].try_into().unwrap());
+    Some((x, z, dimension))
+}
+
+/// Split a digest value into actor ids.
+pub(crate) fn parse_digest(value: &[u8]) -> Result<Vec<ActorId>, String> {
+    if !value.len().is_multiple_of(8) {
+        return Err(format!(
+            "Actor digest of {} bytes is not a list of 8-byte ids",
+            value.len()
+        ));
+    }
+    Ok(value
+        .chunks_exact(8)
+        .map(|id| ActorId(id.try_into().unwrap()))
+        .collect())
+}
+
+/// Join actor ids into a digest value.
+pub(crate) fn encode_digest(ids: &[ActorId]) -> Vec<u8> {
+    ids.iter().flat_map(|id| id.0).collect()
+}
+
+impl DB {
+    /// Read the ids of the actors in a chunk from its `digp` digest.
+    ///
+    /// A chunk without a digest has no actors, so a missing record yields an
+    /// empty list just like an empty one.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<ActorId>)` - The ids listed in the digest, in stored order
+    /// * `Err(String)` - If the read failed or the digest is malformed
+    pub fn actor_digest(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Vec<ActorId>, String> {
+        match self.get(digest_key(x, z, dim), options)? {
+            Some(value) => parse_digest(&value),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Read the NBT record of an actor.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The actor to read
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Vec<u8>))` - The raw `actorprefix` record
+    /// * `Ok(None)` - If the actor is not stored
+    /// * `Err(String)` - If an error occurred during the read operation
+    pub fn actor_data(
+        &self,
+        id: ActorId,
+        options: &ReadOptions,
+    ) -> Result<Option<Vec<u8>>, String> {
+        self.get(id.key(), options)
+    }
+
+    /// Read the digest of a chunk together with the record of every actor in it.
+    ///
+    /// Both are read from one snapshot. Digests occasionally list actors
+    /// whose record is gone; those are returned with no data rather than
+    /// dropped, so callers can repair the digest.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the reads
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Actor>)` - Each actor in the digest, in stored order
+    /// * `Err(String)` - If a read failed or the digest is malformed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let read_opts = ReadOptions::new();
+    /// for actor in db.actors_in_chunk(0, 0, Dimension::Overworld, &read_opts).unwrap() {
+    ///     match actor.data {
+    ///         Some(nbt) => println!("{}: {} bytes", actor.id, nbt.len()),
+    ///         None => println!("{}: missing", actor.id),
+    ///     }
+    /// }
+    /// ```
+    pub fn actors_in_chunk(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Vec<Actor>, String> {
+        let (_snapshot, read_options) = self.consistent_read_options(options);
+        self.actor_digest(x, z, dim, &read_options)?
+            .into_iter()
+            .map(|id| {
+                let data = self.actor_data(id, &read_options)?;
+                Ok(Actor { id, data })
+            })
+            .collect()
+    }
+
+    /// Store an actor and list it in the digest of its chunk.
+    ///
+    /// The record and the updated digest are written in one batch. The id is
+    /// only appended if the digest does not list it yet, so this also
+    /// replaces the record of an existing actor. The digest is read and
+    /// rewritten under the same mutex as [`DB::put_if`]; writes through other
+    /// handles or processes are not excluded.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `id` - The actor's id
+    /// * `data` - The actor's NBT record
+    /// * `options` - Write options used for the batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the actor and digest were written
+    /// * `Err(String)` - If the database is read-only, the digest is malformed or a write failed
+    pub fn put_actor(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        id: ActorId,
+        data: impl AsRef<[u8]>,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        self.check_writable()?;
+        let _guard = self.lock_writes();
+
+        let mut ids = self.actor_digest(x, z, dim, &ReadOptions::new())?;
+        let mut batch = WriteBatch::new();
+        if !ids.contains(&id) {
+            ids.push(id);
+            batch.put(digest_key(x, z, dim), encode_digest(&ids));
+        }
+        batch.put(id.key(), data);
+        self.write(&batch, options)
+    }
+
+    /// Delete an actor and remove it from the digest of its chunk.
+    ///
+    /// The record is deleted and the digest rewritten in one batch, under the
+    /// same mutex as [`DB::put_actor`]. An empty digest is kept as an empty
+    /// record, as the game does.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `id` - The actor to remove
+    /// * `options` - Write options used for the batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - If the digest listed the actor
+    /// * `Ok(false)` - If it did not; the actor's record is deleted regardless
+    /// * `Err(String)` - If the database is read-only, the digest is malformed or a write failed
+    pub fn remove_actor(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        id: ActorId,
+        options: &WriteOptions,
+    ) -> Result<bool, String> {
+        self.check_writable()?;
+        let _guard = self.lock_writes();
+
+        let mut ids = self.actor_digest(x, z, dim, &ReadOptions::new())?;
+        let listed = ids.len();
+        ids.retain(|&listed_id| listed_id != id);
+        let mut batch = WriteBatch::new();
+        if ids.len() != listed {
+            batch.put(digest_key(x, z, dim), encode_digest(&ids));
+        }
+        batch.delete(id.key());
+        self.write(&batch, options)?;
+        Ok(ids.len() != listed)
+    }
+}
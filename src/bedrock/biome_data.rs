@@ -0,0 +1,419 @@
+use std::fmt;
+
+use crate::bedrock::{ChunkKey, Dimension, RecordTag};
+use crate::db::DB;
+use crate::options::ReadOptions;
+
+/// Number of columns in a chunk.
+const COLUMNS: usize = 256;
+/// Number of blocks in a subchunk.
+const SECTION_VOLUME: usize = 4096;
+/// Size of a heightmap: one `u16` per column.
+const HEIGHTMAP_BYTES: usize = COLUMNS * 2;
+/// Header byte of a biome section that repeats the section below it.
+const COPY_HEADER: u8 = 0xFF;
+/// Index widths a paletted biome section can use.
+const VALID_BITS: [u8; 8] = [1, 2, 3, 4, 5, 6, 8, 16];
+
+/// Why a `Data2D` or `Data3D` record could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BiomeDataError {
+    /// The record ended early; `offset` is where more data was expected.
+    Truncated {
+        /// Byte offset at which the record ran out.
+        offset: usize,
+    },
+    /// A `Data2D` record is not exactly 768 bytes long.
+    InvalidLength(usize),
+    /// A biome section uses an index width the game never writes.
+    InvalidBitsPerValue {
+        /// Index of the section, counting from the bottom.
+        section: usize,
+        /// The width from the section header.
+        bits: u8,
+    },
+    /// A biome section refers past the end of its palette.
+    PaletteIndexOutOfRange {
+        /// Index of the section, counting from the bottom.
+        section: usize,
+        /// The offending palette index.
+        index: u16,
+    },
+    /// The bottom section says it repeats the section below it.
+    CopyWithoutPrevious,
+}
+
+impl fmt::Display for BiomeDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BiomeDataError::Truncated { offset } => {
+                write!(f, "record is truncated at byte {}", offset)
+            }
+            BiomeDataError::InvalidLength(len) => {
+                write!(f, "Data2D record is {} bytes instead of 768", len)
+            }
+            BiomeDataError::InvalidBitsPerValue { section, bits } => {
+                write!(f, "biome section {} uses {} bits per value", section, bits)
+            }
+            BiomeDataError::PaletteIndexOutOfRange { section, index } => {
+                write!(
+                    f,
+                    "biome section {} refers to palette entry {}",
+                    section, index
+                )
+            }
+            BiomeDataError::CopyWithoutPrevious => {
+                write!(f, "the bottom biome section repeats a section below it")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BiomeDataError {}
+
+/// Return the index of column `(x, z)` in a heightmap or 2D biome array.
+fn column_index(x: usize, z: usize) -> usize {
+    assert!(
+        x < 16 && z < 16,
+        "column ({}, {}) is outside the chunk",
+        x,
+        z
+    );
+    z * 16 + x
+}
+
+fn read_heights(record: &[u8]) -> Result<[u16; COLUMNS], BiomeDataError> {
+    let bytes = record
+        .get(..HEIGHTMAP_BYTES)
+        .ok_or(BiomeDataError::Truncated {
+            offset: record.len(),
+        })?;
+    let mut heights = [0u16; COLUMNS];
+    for (height, pair) in heights.iter_mut().zip(bytes.chunks_exact(2)) {
+        *height = u16::from_le_bytes([pair[0], pair[1]]);
+    }
+    Ok(heights)
+}
+
+fn write_heights(heights: &[u16; COLUMNS], out: &mut Vec<u8>) {
+    for height in heights {
+        out.extend_from_slice(&height.to_le_bytes());
+    }
+}
+
+/// The heightmap and 2D biomes of a chunk, stored under [`RecordTag::Data2D`]
+/// by versions before 1.18.
+///
+/// Both arrays hold one entry per column, indexed by `z * 16 + x`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Data2D {
+    /// Height of the highest block of each column.
+    pub heights: [u16; COLUMNS],
+    /// Biome id of each column.
+    pub biomes: [u8; COLUMNS],
+}
+
+impl Data2D {
+    /// Parse a `Data2D` record.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - The raw record: 256 little-endian heights followed by 256 biome ids
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Data2D)` - The decoded record
+    /// * `Err(BiomeDataError)` - If the record is not exactly 768 bytes long
+    pub fn parse(record: &[u8]) -> Result<Data2D, BiomeDataError> {
+        if record.len() != HEIGHTMAP_BYTES + COLUMNS {
+            return Err(BiomeDataError::InvalidLength(record.len()));
+        }
+        let heights = read_heights(record)?;
+        let biomes = record[HEIGHTMAP_BYTES..].try_into().unwrap();
+        Ok(Data2D { heights, biomes })
+    }
+
+    /// Encode the record in the layout [`Data2D::parse`] reads.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEIGHTMAP_BYTES + COLUMNS);
+        write_heights(&self.heights, &mut out);
+        out.extend_from_slice(&self.biomes);
+        out
+    }
+
+    /// Return the height of column `(x, z)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `z` is not below 16.
+    pub fn height_at(&self, x: usize, z: usize) -> u16 {
+        self.heights[column_index(x, z)]
+    }
+
+    /// Return the biome of column `(x, z)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `z` is not below 16.
+    pub fn biome_at(&self, x: usize, z: usize) -> u8 {
+        self.biomes[column_index(x, z)]
+    }
+}
+
+/// The biomes of one 16×16×16 subchunk in a [`Data3D`] record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BiomeSection {
+    /// Every block has the same biome.
+    Single(u32),
+    /// Each block has an index into `palette`.
+    ///
+    /// `indices` holds 4096 entries in the order used by subchunk block
+    /// storage, `(x * 16 + z) * 16 + y`.
+    Paletted {
+        /// The biome ids used in the section.
+        palette: Vec<u32>,
+        /// Palette index of every block.
+        indices: Vec<u16>,
+    },
+    /// The section has the same biomes as the one below it.
+    SameAsBelow,
+}
+
+/// The heightmap and 3D biomes of a chunk, stored under [`RecordTag::Data3D`]
+/// since 1.18.
+///
+/// Biomes are kept per subchunk, starting at the bottom of the dimension.
+/// Records carry as many sections as the chunk had when it was saved, so
+/// the number varies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Data3D {
+    /// Height of the highest block of each column, counted from the bottom
+    /// of the dimension and indexed by `z * 16 + x`.
+    pub heights: [u16; COLUMNS],
+    /// Biome sections from the bottom up.
+    pub sections: Vec<BiomeSection>,
+}
+
+impl Data3D {
+    /// Parse a `Data3D` record.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - The raw record: a heightmap followed by paletted biome sections
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Data3D)` - The decoded record
+    /// * `Err(BiomeDataError)` - If the record is truncated or malformed
+    pub fn parse(record: &[u8]) -> Result<Data3D, BiomeDataError> {
+        let heights = read_heights(record)?;
+        let mut reader = Reader {
+            record,
+            offset: HEIGHTMAP_BYTES,
+        };
+        let mut sections = Vec::new();
+        while reader.offset < record.len() {
+            let section = sections.len();
+            let header = reader.u8()?;
+            if header == COPY_HEADER {
+                if section == 0 {
+                    return Err(BiomeDataError::CopyWithoutPrevious);
+                }
+                sections.push(BiomeSection::SameAsBelow);
+                continue;
+            }
+            let bits = header >> 1;
+            if bits == 0 {
+                sections.push(BiomeSection::Single(reader.u32()?));
+                continue;
+            }
+            if !VALID_BITS.contains(&bits) {
+                return Err(BiomeDataError::InvalidBitsPerValue { section, bits });
+            }
+
+            let per_word = 32 / bits as usize;
+            let mask = (1u32 << bits) - 1;
+            let mut indices = Vec::with_capacity(SECTION_VOLUME);
+            for _ in 0..SECTION_VOLUME.div_ceil(per_word) {
+                let word = reader.u32()?;
+                for slot in 0..per_word {
+                    if indices.len() < SECTION_VOLUME {
+                        indices.push(((word >> (slot * bits as usize)) & mask) as u16);
+                    }
+                }
+            }
+            let palette_len = reader.u32()? as usize;
+            let mut palette = Vec::with_capacity(palette_len.min(SECTION_VOLUME));
+            for _ in 0..palette_len {
+                palette.push(reader.u32()?);
+            }
+            if let Some(&index) = indices.iter().find(|&&i| i as usize >= palette.len()) {
+                return Err(BiomeDataError::PaletteIndexOutOfRange { section, index });
+            }
+            sections.push(BiomeSection::Paletted { palette, indices });
+        }
+        Ok(Data3D { heights, sections })
+    }
+
+    /// Encode the record in the layout [`Data3D::parse`] reads.
+    ///
+    /// Paletted sections are written with the smallest index width that
+    /// fits their palette.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a paletted section does not have exactly 4096 indices or
+    /// its palette has more than 65536 entries.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEIGHTMAP_BYTES + self.sections.len());
+        write_heights(&self.heights, &mut out);
+        for section in &self.sections {
+            match section {
+                BiomeSection::SameAsBelow => out.push(COPY_HEADER),
+                BiomeSection::Single(biome) => {
+                    out.push(0);
+                    out.extend_from_slice(&biome.to_le_bytes());
+                }
+                BiomeSection::Paletted { palette, indices } => {
+                    assert_eq!(
+                        indices.len(),
+                        SECTION_VOLUME,
+                        "biome section needs 4096 indices"
+                    );
+                    let bits = VALID_BITS
+                        .into_iter()
+                        .find(|&bits| palette.len() <= 1 << bits)
+                        .expect("biome palette has more than 65536 entries");
+                    out.push(bits << 1);
+                    let per_word = 32 / bits as usize;
+                    for group in indices.chunks(per_word) {
+                        let word = group.iter().enumerate().fold(0u32, |word, (slot, &i)| {
+                            word | (i as u32) << (slot * bits as usize)
+                        });
+                        out.extend_from_slice(&word.to_le_bytes());
+                    }
+                    out.extend_from_slice(&(palette.len() as u32).to_le_bytes());
+                    for biome in palette {
+                        out.extend_from_slice(&biome.to_le_bytes());
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Return the height of column `(x, z)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `z` is not below 16.
+    pub fn height_at(&self, x: usize, z: usize) -> u16 {
+        self.heights[column_index(x, z)]
+    }
+
+    /// Return the biome at a block of the chunk.
+    ///
+    /// `y` counts blocks from the bottom of the first section, which is the
+    /// bottom of the dimension (y = -64 in the Overworld since 1.18).
+    ///
+    /// # Returns
+    ///
+    /// The biome id, or `None` if the record has no section at that height.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `z` is not below 16.
+    pub fn biome_at(&self, x: usize, y: usize, z: usize) -> Option<u32> {
+        assert!(
+            x < 16 && z < 16,
+            "column ({}, {}) is outside the chunk",
+            x,
+            z
+        );
+        let mut section = y / 16;
+        loop {
+            match self.sections.get(section)? {
+                BiomeSection::Single(biome) => return Some(*biome),
+                BiomeSection::Paletted { palette, indices } => {
+                    let index = indices[(x * 16 + z) * 16 + y % 16];
+                    return palette.get(index as usize).copied();
+                }
+                BiomeSection::SameAsBelow => section = section.checked_sub(1)?,
+            }
+        }
+    }
+}
+
+/// Cursor over a record that reports truncation as [`BiomeDataError`].
+struct Reader<'a> {
+    record: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], BiomeDataError> {
+        let bytes =
+            self.record
+                .get(self.offset..self.offset + len)
+                .ok_or(BiomeDataError::Truncated {
+                    offset: self.offset,
+                })?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, BiomeDataError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, BiomeDataError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
+
+impl DB {
+    /// Read and parse the `Data3D` record of a chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Data3D))` - The decoded record
+    /// * `Ok(None)` - If the chunk has no `Data3D` record, e.g. because it predates 1.18
+    /// * `Err(String)` - If the read failed or the record is malformed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// if let Some(data) = db.get_data3d(0, 0, Dimension::Overworld, &ReadOptions::new()).unwrap() {
+    ///     // The biome at y = 64, which is 128 blocks above the bottom
+    ///     println!("{:?}", data.biome_at(8, 128, 8));
+    /// }
+    /// ```
+    pub fn get_data3d(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Option<Data3D>, String> {
+        let Some(record) = self.get(ChunkKey::new(x, z, dim, RecordTag::Data3D), options)? else {
+            return Ok(None);
+        };
+        Data3D::parse(&record).map(Some).map_err(|e| {
+            format!(
+                "Invalid Data3D record in chunk ({}, {}) of the {}: {}",
+                x, z, dim, e
+            )
+        })
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::BTreeMap;
+
+use crate::bedrock::{BiomeSection, ChunkKey, Data2D, Data3D, Dimension, RecordTag};
+use crate::db::DB;
+use crate::options::ReadOptions;
+
+/// Number of blocks in a biome section.
+const SECTION_VOLUME: u64 = 4096;
+
+/// Names of the biome ids used by Bedrock, in ascending id order.
+const BIOME_NAMES: &[(u32, &str)] = &[
+    (0, "ocean"),
+    (1, "plains"),
+    (2, "desert"),
+    (3, "extreme_hills"),
+    (4, "forest"),
+    (5, "taiga"),
+    (6, "swampland"),
+    (7, "river"),
+    (8, "hell"),
+    (9, "the_end"),
+    (10, "legacy_frozen_ocean"),
+    (11, "frozen_river"),
+    (12, "ice_plains"),
+    (13, "ice_mountains"),
+    (14, "mushroom_island"),
+    (15, "mushroom_island_shore"),
+    (16, "beach"),
+    (17, "desert_hills"),
+    (18, "forest_hills"),
+    (19, "taiga_hills"),
+    (20, "extreme_hills_edge"),
+    (21, "jungle"),
+    (22, "jungle_hills"),
+    (23, "jungle_edge"),
+    (24, "deep_ocean"),
+    (25, "stone_beach"),
+    (26, "cold_beach"),
+    (27, "birch_forest"),
+    (28, "birch_forest_hills"),
+    (29, "roofed_forest"),
+    (30, "cold_taiga"),
+    (31, "cold_taiga_hills"),
+    (32, "mega_taiga"),
+    (33, "mega_taiga_hills"),
+    (34, "extreme_hills_plus_trees"),
+    (35, "savanna"),
+    (36, "savanna_plateau"),
+    (37, "mesa"),
+    (38, "mesa_plateau_stone"),
+    (39, "mesa_plateau"),
+    (40, "warm_ocean"),
+    (41, "deep_warm_ocean"),
+    (42, "lukewarm_ocean"),
+    (43, "deep_lukewarm_ocean"),
+    (44, "cold_ocean"),
+    (45, "deep_cold_ocean"),
+    (46, "frozen_ocean"),
+    (47, "deep_frozen_ocean"),
+    (48, "bamboo_jungle"),
+    (49, "bamboo_jungle_hills"),
+    (129, "sunflower_plains"),
+    (130, "desert_mutated"),
+    (131, "extreme_hills_mutated"),
+    (132, "flower_forest"),
+    (133, "taiga_mutated"),
+    (134, "swampland_mutated"),
+    (140, "ice_plains_spikes"),
+    (149, "jungle_mutated"),
+    (151, "jungle_edge_mutated"),
+    (155, "birch_forest_mutated"),
+    (156, "birch_forest_hills_mutated"),
+    (157, "roofed_forest_mutated"),
+    (158, "cold_taiga_mutated"),
+    (160, "redwood_taiga_mutated"),
+    (161, "redwood_taiga_hills_mutated"),
+    (162, "extreme_hills_plus_trees_mutated"),
+    (163, "savanna_mutated"),
+    (164, "savanna_plateau_mutated"),
+    (165, "mesa_bryce"),
+    (166, "mesa_plateau_stone_mutated"),
+    (167, "mesa_plateau_mutated"),
+    (178, "soulsand_valley"),
+    (179, "crimson_forest"),
+    (180, "warped_forest"),
+    (181, "basalt_deltas"),
+    (182, "jagged_peaks"),
+    (183, "frozen_peaks"),
+    (184, "snowy_slopes"),
+    (185, "grove"),
+    (186, "meadow"),
+    (187, "lush_caves"),
+    (188, "dripstone_caves"),
+    (189, "stony_peaks"),
+    (190, "deep_dark"),
+    (191, "mangrove_swamp"),
+    (192, "cherry_grove"),
+    (193, "pale_garden"),
+];
+
+/// Return the name the game uses for a biome id, or `None` for ids it does
+/// not define.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bleveldb::bedrock::biome_name;
+///
+/// assert_eq!(biome_name(1), Some("plains"));
+/// assert_eq!(biome_name(1000), None);
+/// ```
+pub fn biome_name(id: u32) -> Option<&'static str> {
+    BIOME_NAMES
+        .binary_search_by_key(&id, |&(known, _)| known)
+        .ok()
+        .map(|index| BIOME_NAMES[index].1)
+}
+
+/// How often each biome occurs in a dimension, as returned by
+/// [`DB::biome_histogram`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BiomeHistogram {
+    /// Number of blocks per biome id, from chunks with a `Data3D` record.
+    pub blocks: BTreeMap<u32, u64>,
+    /// Number of columns per biome id, from chunks that only have a
+    /// `Data2D` record.
+    pub columns: BTreeMap<u32, u64>,
+    /// Number of chunks visited.
+    pub chunks: u64,
+    /// Number of chunks without a `Data3D` or `Data2D` record.
+    pub missing: u64,
+    /// Number of chunks whose biome record could not be parsed.
+    pub invalid: u64,
+}
+
+impl BiomeHistogram {
+    /// Return the biome ids by the number of blocks, most common first,
+    /// paired with their names where known.
+    pub fn ranked_blocks(&self) -> Vec<(u32, Option<&'static str>, u64)> {
+        let mut ranked: Vec<_> = self
+            .blocks
+            .iter()
+            .map(|(&id, &count)| (id, biome_name(id), count))
+            .collect();
+        ranked.sort_by_key(|&(_, _, count)| std::cmp::Reverse(count));
+        ranked
+    }
+
+    /// Add the counts of another histogram to this one, such as to combine
+    /// the histograms of several dimensions.
+    pub fn merge(&mut self, other: BiomeHistogram) {
+        for (biome, count) in other.blocks {
+            *self.blocks.entry(biome).or_default() += count;
+        }
+        for (biome, count) in other.columns {
+            *self.columns.entry(biome).or_default() += count;
+        }
+        self.chunks += other.chunks;
+        self.missing += other.missing;
+        self.invalid += other.invalid;
+    }
+
+    fn add_data3d(&mut self, data: &Data3D) {
+        let mut below: Option<&BiomeSection> = None;
+        for section in &data.sections {
+            let section = match section {
+                BiomeSection::SameAsBelow => match below {
+                    Some(section) => section,
+                    None => continue,
+                },
+                section => section,
+            };
+            match section {
+                BiomeSection::Single(biome) => {
+                    *self.blocks.entry(*biome).or_default() += SECTION_VOLUME;
+                }
+                BiomeSection::Paletted { palette, indices } => {
+                    for &index in indices {
+                        if let Some(&biome) = palette.get(index as usize) {
+                            *self.blocks.entry(biome).or_default() += 1;
+                        }
+                    }
+                }
+                // `below` never holds a `SameAsBelow` section
+                BiomeSection::SameAsBelow => continue,
+            }
+            below = Some(section);
+        }
+    }
+
+    fn add_data2d(&mut self, data: &Data2D) {
+        for &biome in &data.biomes {
+            *self.columns.entry(biome as u32).or_default() += 1;
+        }
+    }
+}
+
+/// Biome records of the chunk the scan is in.
+#[derive(Default)]
+struct PendingChunk {
+    position: Option<(i32, i32)>,
+    data3d: Option<Vec<u8>>,
+    data2d: Option<Vec<u8>>,
+}
+
+impl PendingChunk {
+    fn finish(&mut self, histogram: &mut BiomeHistogram) {
+        if self.position.take().is_none() {
+            return;
+        }
+        histogram.chunks += 1;
+        if let Some(record) = self.data3d.take() {
+            self.data2d = None;
+            match Data3D::parse(&record) {
+                Ok(data) => histogram.add_data3d(&data),
+                Err(_) => histogram.invalid += 1,
+            }
+        } else if let Some(record) = self.data2d.take() {
+            match Data2D::parse(&record) {
+                Ok(data) => histogram.add_data2d(&data),
+                Err(_) => histogram.invalid += 1,
+            }
+        } else {
+            histogram.missing += 1;
+        }
+    }
+}
+
+impl DB {
+    /// Count how often each biome occurs in a dimension.
+    ///
+    /// Every chunk is read once, in key order. Chunks with a `Data3D` record
+    /// add the biome of each of their blocks; older chunks that only have a
+    /// `Data2D` record add the biome of each column instead, since they have
+    /// no per-block biomes. Chunks without either record, or with one that
+    /// fails to parse, are skipped and counted.
+    ///
+    /// # Arguments
+    ///
+    /// * `dim` - The dimension to scan
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BiomeHistogram)` - The counts per biome id
+    /// * `Err(String)` - If reading the database failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let histogram = db.biome_histogram(Dimension::Overworld, &ReadOptions::new()).unwrap();
+    /// for (id, name, blocks) in histogram.ranked_blocks().into_iter().take(5) {
+    ///     println!("{} ({}): {} blocks", name.unwrap_or("unknown"), id, blocks);
+    /// }
+    /// ```
+    pub fn biome_histogram(
+        &self,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<BiomeHistogram, String> {
+        let dim = Dimension::parse(dim.id());
+        let mut histogram = BiomeHistogram::default();
+        let mut pending = PendingChunk::default();
+        let mut iter = self.iter(options);
+        iter.seek_to_first();
+        while let (Some(key), Some(value)) = (iter.key_ref(), iter.value_ref()) {
+            if let Ok(key) = ChunkKey::parse(key)
+                && key.dimension() == dim
+            {
+                let position = (key.x(), key.z());
+                if pending.position != Some(position) {
+                    pending.finish(&mut histogram);
+                    pending.position = Some(position);
+                }
+                match key.tag() {
+                    RecordTag::Data3D => pending.data3d = Some(value.to_vec()),
+                    RecordTag::Data2D => pending.data2d = Some(value.to_vec()),
+                    _ => {}
+                }
+            }
+            iter.next_native();
+        }
+        iter.status()?;
+        pending.finish(&mut histogram);
+        Ok(histogram)
+    }
+
+    /// Count how often each biome occurs in a dimension, reading the chunks
+    /// in parallel.
+    ///
+    /// Gives the same result as [`DB::biome_histogram`], with the chunks
+    /// spread over rayon's thread pool by [`DB::par_chunks`].
+    ///
+    /// # Arguments
+    ///
+    /// * `dim` - The dimension to scan
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BiomeHistogram)` - The counts per biome id
+    /// * `Err(String)` - If reading the database failed
+    #[cfg(feature = "rayon")]
+    pub fn par_biome_histogram(
+        &self,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<BiomeHistogram, String> {
+        self.par_chunks(
+            dim,
+            BiomeHistogram::default,
+            |histogram, chunk| {
+                PendingChunk {
+                    position: Some((chunk.x, chunk.z)),
+                    data3d: chunk.get(RecordTag::Data3D).map(<[u8]>::to_vec),
+                    data2d: chunk.get(RecordTag::Data2D).map(<[u8]>::to_vec),
+                }
+                .finish(histogram)
+            },
+            |mut a, b| {
+                a.merge(b);
+                a
+            },
+            options,
+        )
+    }
+}
@@ -0,0 +1,175 @@
+use super::nbt::{self, Value};
+use super::{ChunkKey, Dimension, RecordTag};
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+
+impl DB {
+    /// Read the block entities of a chunk.
+    ///
+    /// A chunk keeps all of its block entities (chests, signs, spawners and
+    /// so on) in one [`RecordTag::BlockEntity`] record, as NBT compounds
+    /// written back to back.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Value>)` - One compound per block entity, in stored order; empty if the chunk has none
+    /// * `Err(String)` - If the read failed or the record is not valid NBT
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// for entity in db.block_entities(0, 0, Dimension::Overworld, &ReadOptions::new()).unwrap() {
+    ///     println!("{:?}", entity.get("id"));
+    /// }
+    /// ```
+    pub fn block_entities(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Vec<Value>, String> {
+        let key = ChunkKey::new(x, z, dim, RecordTag::BlockEntity);
+        match self.get(key, options)? {
+            Some(record) => nbt::decode_all(&record)
+                .map_err(|e| format!("Invalid block entities in chunk ({}, {}): {}", x, z, e)),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Replace the block entities of a chunk.
+    ///
+    /// The compounds are encoded back to back into the chunk's
+    /// [`RecordTag::BlockEntity`] record. An empty slice deletes the record,
+    /// as the game does not keep empty ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `entities` - The block entities to store
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written or deleted
+    /// * `Err(String)` - If an error occurred during the write operation
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions, WriteOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let mut entities = db.block_entities(0, 0, Dimension::Overworld, &ReadOptions::new()).unwrap();
+    /// entities.retain(|entity| entity.get("id").and_then(|id| id.as_str()) != Some("MobSpawner"));
+    /// db.set_block_entities(0, 0, Dimension::Overworld, &entities, &WriteOptions::new()).unwrap();
+    /// ```
+    pub fn set_block_entities(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        entities: &[Value],
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        let key = ChunkKey::new(x, z, dim, RecordTag::BlockEntity);
+        if entities.is_empty() {
+            return self.delete(key, options);
+        }
+        let mut record = Vec::new();
+        for entity in entities {
+            nbt::encode_into(entity, &mut record);
+        }
+        self.put(key, record, options)
+    }
+
+    /// Iterate over the block entities of every chunk in a dimension.
+    ///
+    /// The whole keyspace is scanned for [`RecordTag::BlockEntity`] records
+    /// of `dim`, and each of their compounds is yielded with the position of
+    /// its chunk. Chunks come out in key order, like in
+    /// [`DB::chunk_positions`].
+    ///
+    /// A record that is not valid NBT yields one `Err` naming its chunk, and
+    /// the iteration goes on with the next chunk. A read error ends the
+    /// iteration with one final `Err`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dim` - The dimension to scan
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// for entry in db.all_block_entities(Dimension::Overworld, &ReadOptions::new()) {
+    ///     let ((x, z), entity) = entry.unwrap();
+    ///     if entity.get("id").and_then(|id| id.as_str()) == Some("Chest") {
+    ///         println!("Chest in chunk ({}, {})", x, z);
+    ///     }
+    /// }
+    /// ```
+    pub fn all_block_entities(
+        &self,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> impl Iterator<Item = Result<((i32, i32), Value), String>> + '_ {
+        let dim = Dimension::parse(dim.id());
+        let mut iter = self.iter(options);
+        iter.seek_to_first();
+        let mut pending = Vec::new().into_iter();
+        let mut position = (0, 0);
+        let mut checked = false;
+        std::iter::from_fn(move || {
+            loop {
+                if let Some(entity) = pending.next() {
+                    return Some(Ok((position, entity)));
+                }
+                let Some(key) = iter.key_ref() else {
+                    if checked {
+                        return None;
+                    }
+                    checked = true;
+                    return iter.status().err().map(Err);
+                };
+                let chunk = ChunkKey::parse(key)
+                    .ok()
+                    .filter(|key| key.dimension() == dim && key.tag() == RecordTag::BlockEntity);
+                if let Some(chunk) = chunk {
+                    position = (chunk.x(), chunk.z());
+                    let decoded = nbt::decode_all(iter.value_ref().unwrap_or_default());
+                    iter.next_native();
+                    match decoded {
+                        Ok(entities) => pending = entities.into_iter(),
+                        Err(e) => {
+                            return Some(Err(format!(
+                                "Invalid block entities in chunk ({}, {}): {}",
+                                position.0, position.1, e
+                            )));
+                        }
+                    }
+                } else {
+                    iter.next_native();
+                }
+            }
+        })
+    }
+}
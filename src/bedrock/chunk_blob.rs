@@ -0,0 +1,312 @@
+use super::actors::{digest_key, parse_digest};
+use super::chunk_key::chunk_prefix;
+use crate::bedrock::{ActorId, ActorIdRemapper, ChunkKey, CopyReport, Dimension};
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+use crate::util::{Crc32, encode_varint, read_varint};
+use crate::write_batch::WriteBatch;
+
+/// Magic bytes every chunk blob starts with.
+const MAGIC: [u8; 8] = *b"BLDBCHNK";
+
+/// Version of the chunk blob format written by this crate.
+const FORMAT_VERSION: u8 = 1;
+
+/// Every record of one chunk, including its actors, detached from the
+/// database it was read from.
+///
+/// Records are kept by the part of their key after the chunk coordinates
+/// and dimension (the tag, plus the index for subchunks), so the chunk can
+/// be written back at any position. [`ChunkBlob::to_bytes`] turns it into a
+/// self-describing byte string for bug reports and backups:
+///
+/// ```text
+/// blob    := magic version x z dim varint(record_count) record*
+///            varint(actor_count) actor* crc32
+/// magic   := "BLDBCHNK"
+/// version := u8                               (currently 1)
+/// x, z    := i32 LE                           (chunk coordinates)
+/// dim     := i32 LE                           (dimension id)
+/// record  := varint(suffix_len) suffix varint(value_len) value
+/// actor   := id(8 bytes) varint(data_len) data
+/// crc32   := CRC-32 of every preceding byte, u32 LE
+/// ```
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::DB;
+/// # use bleveldb::bedrock::{ChunkBlob, Dimension};
+/// # use bleveldb::options::{Options, ReadOptions, WriteOptions};
+/// # let db = DB::open("world/db", &Options::default()).unwrap();
+/// let blob = db.export_chunk(3, -2, Dimension::Overworld, &ReadOptions::new()).unwrap();
+/// std::fs::write("chunk.bin", blob.to_bytes()).unwrap();
+///
+/// let blob = ChunkBlob::from_bytes(&std::fs::read("chunk.bin").unwrap()).unwrap();
+/// db.import_chunk(&blob, Some((100, 100, Dimension::Overworld)), &WriteOptions::new())
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkBlob {
+    /// Chunk x coordinate the blob was exported from.
+    pub x: i32,
+    /// Chunk z coordinate the blob was exported from.
+    pub z: i32,
+    /// Dimension the blob was exported from.
+    pub dimension: Dimension,
+    /// Each record as its key suffix and value, in key order.
+    pub records: Vec<(Vec<u8>, Vec<u8>)>,
+    /// Each actor as its id and NBT record, in digest order.
+    pub actors: Vec<(ActorId, Vec<u8>)>,
+}
+
+impl ChunkBlob {
+    /// Encode the blob in the format described on [`ChunkBlob`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        out.push(FORMAT_VERSION);
+        out.extend_from_slice(&self.x.to_le_bytes());
+        out.extend_from_slice(&self.z.to_le_bytes());
+        out.extend_from_slice(&self.dimension.id().to_le_bytes());
+        encode_varint(self.records.len() as u64, &mut out);
+        for (suffix, value) in &self.records {
+            encode_varint(suffix.len() as u64, &mut out);
+            out.extend_from_slice(suffix);
+            encode_varint(value.len() as u64, &mut out);
+            out.extend_from_slice(value);
+        }
+        encode_varint(self.actors.len() as u64, &mut out);
+        for (id, data) in &self.actors {
+            out.extend_from_slice(&id.0);
+            encode_varint(data.len() as u64, &mut out);
+            out.extend_from_slice(data);
+        }
+        let mut crc = Crc32::new();
+        crc.update(&out);
+        out.extend_from_slice(&crc.finish().to_le_bytes());
+        out
+    }
+
+    /// Decode a blob written by [`ChunkBlob::to_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The encoded blob
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ChunkBlob)` - The decoded blob
+    /// * `Err(String)` - If the bytes are not a chunk blob, use an unknown version, or are truncated or corrupt
+    pub fn from_bytes(bytes: &[u8]) -> Result<ChunkBlob, String> {
+        if bytes.len() < MAGIC.len() + 1 || bytes[..MAGIC.len()] != MAGIC {
+            return Err("not a bleveldb chunk blob".to_string());
+        }
+        if bytes[MAGIC.len()] != FORMAT_VERSION {
+            return Err(format!(
+                "unsupported chunk blob version {}",
+                bytes[MAGIC.len()]
+            ));
+        }
+        let Some((body, checksum)) = bytes.split_last_chunk::<4>() else {
+            return Err("corrupt chunk blob: truncated".to_string());
+        };
+        let mut crc = Crc32::new();
+        crc.update(body);
+        if u32::from_le_bytes(*checksum) != crc.finish() {
+            return Err("corrupt chunk blob: checksum mismatch".to_string());
+        }
+
+        let mut input = &body[MAGIC.len() + 1..];
+        let x = i32::from_le_bytes(take(&mut input, 4)?.try_into().unwrap());
+        let z = i32::from_le_bytes(take(&mut input, 4)?.try_into().unwrap());
+        let dimension =
+            Dimension::parse(i32::from_le_bytes(take(&mut input, 4)?.try_into().unwrap()));
+
+        let count = read_count(&mut input)?;
+        let mut records = Vec::with_capacity(count.min(input.len()));
+        for _ in 0..count {
+            let suffix = take_field(&mut input)?;
+            let value = take_field(&mut input)?;
+            records.push((suffix, value));
+        }
+        let count = read_count(&mut input)?;
+        let mut actors = Vec::with_capacity(count.min(input.len()));
+        for _ in 0..count {
+            let id = ActorId(take(&mut input, 8)?.try_into().unwrap());
+            actors.push((id, take_field(&mut input)?));
+        }
+        if !input.is_empty() {
+            return Err(format!(
+                "corrupt chunk blob: {} unexpected bytes",
+                input.len()
+            ));
+        }
+        Ok(ChunkBlob {
+            x,
+            z,
+            dimension,
+            records,
+            actors,
+        })
+    }
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+    if input.len() < len {
+        return Err("corrupt chunk blob: truncated".to_string());
+    }
+    let (head, rest) = input.split_at(len);
+    *input = rest;
+    Ok(head)
+}
+
+fn read_count(input: &mut &[u8]) -> Result<usize, String> {
+    read_varint(input)
+        .map(|n| n as usize)
+        .map_err(|_| "corrupt chunk blob: truncated".to_string())
+}
+
+fn take_field(input: &mut &[u8]) -> Result<Vec<u8>, String> {
+    let len = read_count(input)?;
+    Ok(take(input, len)?.to_vec())
+}
+
+impl DB {
+    /// Read every record of a chunk and its actors into a [`ChunkBlob`].
+    ///
+    /// Everything is read from one snapshot. Ids in the actor digest without
+    /// an actor record are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the reads
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ChunkBlob)` - The chunk's records and actors
+    /// * `Err(String)` - If the chunk does not exist, its digest is malformed or a read failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let blob = db.export_chunk(0, 0, Dimension::Nether, &ReadOptions::new()).unwrap();
+    /// println!("{} records, {} actors", blob.records.len(), blob.actors.len());
+    /// ```
+    pub fn export_chunk(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<ChunkBlob, String> {
+        let (_snapshot, read_options) = self.consistent_read_options(options);
+        let prefix_len = chunk_prefix(x, z, dim).len();
+        let mut records = Vec::new();
+        self.scan_chunk(x, z, dim, &read_options, |key, value| {
+            records.push((key.as_bytes()[prefix_len..].to_vec(), value.to_vec()));
+        })?;
+        if records.is_empty() {
+            return Err(format!(
+                "Chunk ({}, {}) of the {} does not exist",
+                x, z, dim
+            ));
+        }
+
+        let mut actors = Vec::new();
+        if let Some(digest) = self.get(digest_key(x, z, dim), &read_options)? {
+            for id in parse_digest(&digest)? {
+                if let Some(data) = self.get(id.key(), &read_options)? {
+                    actors.push((id, data));
+                }
+            }
+        }
+        Ok(ChunkBlob {
+            x,
+            z,
+            dimension: Dimension::parse(dim.id()),
+            records,
+            actors,
+        })
+    }
+
+    /// Write a [`ChunkBlob`] back into the database.
+    ///
+    /// The chunk is written at `target`, or where it was exported from if
+    /// `target` is `None`. Any records, digest and actors the chunk has there
+    /// are deleted first, in the same atomic batch as the new records. Actors
+    /// get fresh ids from an [`ActorIdRemapper`], as with
+    /// [`copy_chunk`](crate::bedrock::copy_chunk).
+    ///
+    /// # Arguments
+    ///
+    /// * `blob` - The chunk to write
+    /// * `target` - Destination chunk x, z and dimension, if not the original position
+    /// * `options` - Write options used for the batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CopyReport)` - What was written and replaced
+    /// * `Err(String)` - If the database is read-only, the blob holds a record that is
+    ///   not a chunk record, or reading or writing failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions, WriteOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let blob = db.export_chunk(0, 0, Dimension::Overworld, &ReadOptions::new()).unwrap();
+    /// // ... edit the chunk in game, then undo the changes
+    /// db.import_chunk(&blob, None, &WriteOptions::new()).unwrap();
+    /// ```
+    pub fn import_chunk(
+        &self,
+        blob: &ChunkBlob,
+        target: Option<(i32, i32, Dimension)>,
+        options: &WriteOptions,
+    ) -> Result<CopyReport, String> {
+        let (x, z, dim) = target.unwrap_or((blob.x, blob.z, blob.dimension));
+        let dim = Dimension::parse(dim.id());
+        self.check_writable()?;
+        let _guard = self.lock_writes();
+        let (_snapshot, read_options) = self.consistent_read_options(&ReadOptions::new());
+
+        let mut batch = WriteBatch::new();
+        let mut report = CopyReport {
+            replaced: self.queue_chunk_delete(x, z, dim, &read_options, &mut batch)?,
+            ..Default::default()
+        };
+        let prefix = chunk_prefix(x, z, dim);
+        for (suffix, value) in &blob.records {
+            let mut key = prefix.clone();
+            key.extend_from_slice(suffix);
+            match ChunkKey::parse(&key) {
+                Ok(parsed) if (parsed.x(), parsed.z(), parsed.dimension()) == (x, z, dim) => {}
+                _ => {
+                    return Err(format!(
+                        "Chunk blob holds a record with invalid key suffix {:02x?}",
+                        suffix
+                    ));
+                }
+            }
+            batch.put(key, value);
+            report.records += 1;
+        }
+        if !blob.actors.is_empty() {
+            let mut remapper = ActorIdRemapper::new(self, &read_options)?;
+            report.actors = remapper
+                .queue_records(blob.actors.clone(), (x, z, dim), &mut batch)
+                .len();
+        }
+        self.write(&batch, options)?;
+        Ok(report)
+    }
+}
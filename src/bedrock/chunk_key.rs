@@ -0,0 +1,402 @@
+use std::fmt;
+
+use super::Dimension;
+
+/// Longest chunk key: x, z, dimension, tag and subchunk index.
+const MAX_KEY_LEN: usize = 14;
+
+/// The kind of per-chunk record a [`ChunkKey`] points at.
+///
+/// The tag is the byte following the coordinates (and dimension) in the key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordTag {
+    /// Heightmap and 3D biomes (tag 43), used since 1.18.
+    Data3D,
+    /// Chunk format version (tag 44).
+    Version,
+    /// Heightmap and 2D biomes (tag 45), used before 1.18.
+    Data2D,
+    /// Heightmap and 2D biomes of very old worlds (tag 46).
+    Data2DLegacy,
+    /// Block storage of one 16×16×16 subchunk (tag 47); keys carry a y index.
+    SubChunkPrefix,
+    /// Whole-chunk terrain of pre-1.0 worlds (tag 48).
+    LegacyTerrain,
+    /// Block entity NBT (tag 49).
+    BlockEntity,
+    /// Entity NBT of worlds from before the actor digest (tag 50).
+    Entity,
+    /// Scheduled block ticks (tag 51).
+    PendingTicks,
+    /// Extra block data of old worlds (tag 52).
+    LegacyBlockExtraData,
+    /// Biome state, such as snow accumulation (tag 53).
+    BiomeState,
+    /// World generation progress (tag 54).
+    FinalizedState,
+    /// Data left over from converting a Java edition world (tag 55).
+    ConversionData,
+    /// Education edition border blocks (tag 56).
+    BorderBlocks,
+    /// Bounding boxes of structure spawners (tag 57).
+    HardcodedSpawners,
+    /// Random block ticks (tag 58).
+    RandomTicks,
+    /// Checksums of the chunk's other records (tag 59).
+    Checksums,
+    /// Seed the chunk was generated with (tag 60).
+    GenerationSeed,
+    /// Marks chunks generated before 1.18 that need blending (tag 61).
+    GeneratedPreCavesAndCliffsBlending,
+    /// Biome heights used for blending (tag 62).
+    BlendingBiomeHeight,
+    /// Hash of the chunk's metadata entry (tag 63).
+    MetaDataHash,
+    /// Blending state (tag 64).
+    BlendingData,
+    /// Version of the chunk's actor digest (tag 65).
+    ActorDigestVersion,
+    /// Chunk format version of worlds from before 1.16.100 (tag 118).
+    LegacyVersion,
+}
+
+impl RecordTag {
+    /// Every known tag, in ascending byte order.
+    pub const ALL: [RecordTag; 24] = [
+        RecordTag::Data3D,
+        RecordTag::Version,
+        RecordTag::Data2D,
+        RecordTag::Data2DLegacy,
+        RecordTag::SubChunkPrefix,
+        RecordTag::LegacyTerrain,
+        RecordTag::BlockEntity,
+        RecordTag::Entity,
+        RecordTag::PendingTicks,
+        RecordTag::LegacyBlockExtraData,
+        RecordTag::BiomeState,
+        RecordTag::FinalizedState,
+        RecordTag::ConversionData,
+        RecordTag::BorderBlocks,
+        RecordTag::HardcodedSpawners,
+        RecordTag::RandomTicks,
+        RecordTag::Checksums,
+        RecordTag::GenerationSeed,
+        RecordTag::GeneratedPreCavesAndCliffsBlending,
+        RecordTag::BlendingBiomeHeight,
+        RecordTag::MetaDataHash,
+        RecordTag::BlendingData,
+        RecordTag::ActorDigestVersion,
+        RecordTag::LegacyVersion,
+    ];
+
+    /// Return the byte stored in the key for this tag.
+    pub fn to_byte(self) -> u8 {
+        match self {
+            RecordTag::LegacyVersion => 118,
+            // Every other tag is numbered consecutively from 43
+            tag => 43 + RecordTag::ALL.iter().position(|&t| t == tag).unwrap() as u8,
+        }
+    }
+
+    /// Return the tag stored as `byte`, or `None` if it is not a known tag.
+    pub fn from_byte(byte: u8) -> Option<RecordTag> {
+        match byte {
+            43..=65 => Some(RecordTag::ALL[(byte - 43) as usize]),
+            118 => Some(RecordTag::LegacyVersion),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RecordTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Why a key could not be parsed by [`ChunkKey::parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyParseError {
+    /// The key is not 9, 10, 13 or 14 bytes long.
+    InvalidLength(usize),
+    /// The tag byte is not a known [`RecordTag`].
+    UnknownTag(u8),
+    /// A `SubChunkPrefix` key lacks its trailing y index.
+    MissingSubChunkIndex,
+    /// A key for a record other than `SubChunkPrefix` has a trailing index byte.
+    UnexpectedSubChunkIndex(RecordTag),
+    /// The key spells out dimension id 0, which Overworld keys omit.
+    ExplicitOverworld,
+}
+
+impl fmt::Display for KeyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyParseError::InvalidLength(len) => {
+                write!(f, "a {}-byte key is not a chunk key", len)
+            }
+            KeyParseError::UnknownTag(tag) => write!(f, "unknown chunk record tag {}", tag),
+            KeyParseError::MissingSubChunkIndex => {
+                write!(f, "subchunk key without a y index")
+            }
+            KeyParseError::UnexpectedSubChunkIndex(tag) => {
+                write!(f, "{} key with a trailing subchunk index", tag)
+            }
+            KeyParseError::ExplicitOverworld => {
+                write!(f, "chunk key with an explicit Overworld dimension field")
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeyParseError {}
+
+/// The key of a per-chunk record in a Bedrock world.
+///
+/// Chunk keys are laid out as
+///
+/// ```text
+/// x: i32 LE | z: i32 LE | [dimension: i32 LE] | tag: u8 | [subchunk y: i8]
+/// ```
+///
+/// The dimension is left out for the Overworld (see [`Dimension`]), and only
+/// `SubChunkPrefix` records carry the subchunk y index, which gives keys of 9,
+/// 10, 13 or 14 bytes.
+///
+/// `ChunkKey` keeps its encoded form alongside the decoded fields, so it
+/// can be passed wherever a key is expected without re-encoding it.
+///
+/// Keys are ordered as their encoded bytes compare, which is the order
+/// leveldb stores them in, so seeking through a sorted list of keys moves
+/// forward only. This is not numeric coordinate order: the coordinates are
+/// little-endian, so x = 256 sorts before x = 1, negative coordinates sort
+/// after positive ones, and so do negative subchunk indices.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::{DB, options::{Options, ReadOptions}};
+/// use bleveldb::bedrock::{ChunkKey, Dimension, RecordTag};
+///
+/// # let db = DB::open("world/db", &Options::default()).unwrap();
+/// // The Nether chunk at (3, -7)
+/// let key = ChunkKey::new(3, -7, Dimension::Nether, RecordTag::Version);
+/// let version = db.get(&key, &ReadOptions::new()).unwrap();
+///
+/// let parsed = ChunkKey::parse(key.as_bytes()).unwrap();
+/// assert_eq!(parsed, key);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChunkKey {
+    x: i32,
+    z: i32,
+    dimension: Dimension,
+    tag: RecordTag,
+    subchunk: Option<i8>,
+    encoded: [u8; MAX_KEY_LEN],
+    len: u8,
+}
+
+impl ChunkKey {
+    /// Create the key of a chunk record.
+    ///
+    /// For `SubChunkPrefix` records use [`ChunkKey::subchunk`] instead, which
+    /// also takes the y index.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dimension` - The dimension of the chunk
+    /// * `tag` - The record the key points at
+    pub fn new(x: i32, z: i32, dimension: Dimension, tag: RecordTag) -> Self {
+        Self::build(x, z, dimension, tag, None)
+    }
+
+    /// Create the key of a subchunk's `SubChunkPrefix` record.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dimension` - The dimension of the chunk
+    /// * `y` - Subchunk y index; negative below y=0 since 1.18
+    pub fn subchunk(x: i32, z: i32, dimension: Dimension, y: i8) -> Self {
+        Self::build(x, z, dimension, RecordTag::SubChunkPrefix, Some(y))
+    }
+
+    fn build(x: i32, z: i32, dimension: Dimension, tag: RecordTag, subchunk: Option<i8>) -> Self {
+        // Keep keys canonical, so equal encodings always compare equal
+        let dimension = Dimension::parse(dimension.id());
+        let mut encoded = [0u8; MAX_KEY_LEN];
+        encoded[..4].copy_from_slice(&x.to_le_bytes());
+        encoded[4..8].copy_from_slice(&z.to_le_bytes());
+        let mut len = 8;
+        if dimension.has_key_field() {
+            encoded[8..12].copy_from_slice(&dimension.id().to_le_bytes());
+            len = 12;
+        }
+        encoded[len] = tag.to_byte();
+        len += 1;
+        if let Some(y) = subchunk {
+            encoded[len] = y as u8;
+            len += 1;
+        }
+        Self {
+            x,
+            z,
+            dimension,
+            tag,
+            subchunk,
+            encoded,
+            len: len as u8,
+        }
+    }
+
+    /// Parse a chunk record key.
+    ///
+    /// The layout is inferred from the length: 9 and 10 bytes are Overworld
+    /// keys, 13 and 14 bytes carry a dimension, and the longer form of each
+    /// is only valid for `SubChunkPrefix` records. Keys of any other length,
+    /// with an unknown tag or with an explicit Overworld id are rejected,
+    /// which filters out almost all of the string keys Bedrock stores next to
+    /// chunk data. A short string key can still happen to look like a chunk
+    /// key, so callers scanning a whole world should check that the chunk
+    /// also has other records.
+    ///
+    /// Worlds saved before 1.0 use the same layout: they differ only in the
+    /// tags they store, keeping their terrain in one `LegacyTerrain` record
+    /// per chunk instead of subchunks, so their keys parse too. See
+    /// [`DB::detect_world_format`](crate::DB::detect_world_format) to tell
+    /// such worlds apart.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The raw key
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ChunkKey)` - The decoded key
+    /// * `Err(KeyParseError)` - If `key` is not a valid chunk key
+    pub fn parse(key: &[u8]) -> Result<ChunkKey, KeyParseError> {
+        let (dimension, rest) = match key.len() {
+            9 | 10 => (Dimension::Overworld, &key[8..]),
+            13 | 14 => match i32::from_le_bytes(key[8..12].try_into().unwrap()) {
+                0 => return Err(KeyParseError::ExplicitOverworld),
+                id => (Dimension::parse(id), &key[12..]),
+            },
+            len => return Err(KeyParseError::InvalidLength(len)),
+        };
+        let tag = RecordTag::from_byte(rest[0]).ok_or(KeyParseError::UnknownTag(rest[0]))?;
+        let subchunk = rest.get(1).map(|&y| y as i8);
+        match (tag, subchunk) {
+            (RecordTag::SubChunkPrefix, None) => return Err(KeyParseError::MissingSubChunkIndex),
+            (RecordTag::SubChunkPrefix, Some(_)) | (_, None) => {}
+            (tag, Some(_)) => return Err(KeyParseError::UnexpectedSubChunkIndex(tag)),
+        }
+
+        let x = i32::from_le_bytes(key[..4].try_into().unwrap());
+        let z = i32::from_le_bytes(key[4..8].try_into().unwrap());
+        Ok(Self::build(x, z, dimension, tag, subchunk))
+    }
+
+    /// Return the encoded key as a new `Vec`.
+    pub fn encode(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    /// Borrow the encoded key.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.encoded[..self.len as usize]
+    }
+
+    /// Return the chunk x coordinate.
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    /// Return the chunk z coordinate.
+    pub fn z(&self) -> i32 {
+        self.z
+    }
+
+    /// Return the dimension of the chunk.
+    pub fn dimension(&self) -> Dimension {
+        self.dimension
+    }
+
+    /// Return the record tag.
+    pub fn tag(&self) -> RecordTag {
+        self.tag
+    }
+
+    /// Return the subchunk y index of a `SubChunkPrefix` key.
+    pub fn subchunk_index(&self) -> Option<i8> {
+        self.subchunk
+    }
+
+    /// Return the smallest key that sorts after this one: the encoded key
+    /// followed by a zero byte.
+    ///
+    /// Use it as the exclusive end of a range that should include this key.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::bedrock::{ChunkKey, Dimension, RecordTag};
+    ///
+    /// let key = ChunkKey::new(1, 2, Dimension::Overworld, RecordTag::Version);
+    /// let end = key.successor();
+    /// assert!(key.as_bytes() < end.as_slice());
+    /// ```
+    pub fn successor(&self) -> Vec<u8> {
+        let mut key = self.encode();
+        key.push(0);
+        key
+    }
+}
+
+impl Ord for ChunkKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl PartialOrd for ChunkKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl AsRef<[u8]> for ChunkKey {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Debug for ChunkKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("ChunkKey");
+        debug
+            .field("x", &self.x)
+            .field("z", &self.z)
+            .field("dimension", &self.dimension)
+            .field("tag", &self.tag);
+        if let Some(y) = self.subchunk {
+            debug.field("subchunk", &y);
+        }
+        debug.finish()
+    }
+}
+
+/// Return the coordinate and dimension prefix shared by every record of a chunk.
+///
+/// Other per-chunk keys, such as the actor digest, append this prefix to a
+/// string instead of a tag.
+pub(crate) fn chunk_prefix(x: i32, z: i32, dimension: Dimension) -> Vec<u8> {
+    let mut prefix = Vec::with_capacity(12);
+    prefix.extend_from_slice(&x.to_le_bytes());
+    prefix.extend_from_slice(&z.to_le_bytes());
+    dimension.encode_into(&mut prefix);
+    prefix
+}
@@ -0,0 +1,355 @@
+use super::actors::{digest_key, parse_digest};
+use super::chunk_key::chunk_prefix;
+use crate::bedrock::{ActorIdRemapper, ChunkKey, Dimension, RecordTag};
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+use crate::write_batch::WriteBatch;
+
+/// What [`DB::delete_chunk`] removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeletedChunkReport {
+    /// Number of chunk records deleted per tag, in ascending tag order.
+    /// Tags without records are left out.
+    pub records: Vec<(RecordTag, usize)>,
+    /// Whether the chunk had an actor digest.
+    pub digest: bool,
+    /// Number of `actorprefix` records deleted.
+    pub actors: usize,
+}
+
+impl DeletedChunkReport {
+    /// Return how many records with `tag` were deleted.
+    pub fn count(&self, tag: RecordTag) -> usize {
+        self.records
+            .iter()
+            .find(|(t, _)| *t == tag)
+            .map_or(0, |(_, n)| *n)
+    }
+
+    /// Return the total number of keys deleted.
+    pub fn total(&self) -> usize {
+        self.records.iter().map(|(_, n)| n).sum::<usize>() + self.digest as usize + self.actors
+    }
+
+    fn add(&mut self, tag: RecordTag) {
+        match self.records.iter_mut().find(|(t, _)| *t == tag) {
+            Some((_, n)) => *n += 1,
+            None => self.records.push((tag, 1)),
+        }
+    }
+}
+
+/// Which records [`copy_chunk`] copies and how it treats the destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyOptions {
+    /// Copy the chunk's actors (and legacy `Entity` records). Default `true`.
+    pub entities: bool,
+    /// Copy the chunk's `BlockEntity` record. Default `true`.
+    pub block_entities: bool,
+    /// Replace a destination chunk that already has records. When `false`,
+    /// copying onto an existing chunk fails. Default `false`.
+    pub overwrite: bool,
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        Self {
+            entities: true,
+            block_entities: true,
+            overwrite: false,
+        }
+    }
+}
+
+/// Summary of a completed [`copy_chunk`] call.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CopyReport {
+    /// Number of chunk records written to the destination.
+    pub records: usize,
+    /// Number of actors copied under new ids.
+    pub actors: usize,
+    /// What was deleted from the destination before copying, if it was overwritten.
+    pub replaced: DeletedChunkReport,
+}
+
+/// Copy a chunk to another position, dimension or database.
+///
+/// Every record of the source chunk is read from one snapshot and written
+/// under the destination's coordinates in a single atomic batch. Actors are
+/// given fresh ids by an [`ActorIdRemapper`], so the copy never collides
+/// with existing actors, and the destination digest lists the new ids. Ids
+/// in the digest without an actor record are dropped.
+///
+/// When the destination chunk has records and `opts.overwrite` is set, all
+/// of them, its digest and its actors are deleted in the same batch.
+///
+/// # Arguments
+///
+/// * `src` - The database to copy from
+/// * `dst` - The database to copy to; may be the same as `src`
+/// * `from` - Source chunk x, z and dimension
+/// * `to` - Destination chunk x, z and dimension
+/// * `opts` - Which records to copy and whether to overwrite
+///
+/// # Returns
+///
+/// * `Ok(CopyReport)` - What was copied and replaced
+/// * `Err(String)` - If the source chunk does not exist, the destination is occupied
+///   without `overwrite`, `from` and `to` are the same chunk of the same database,
+///   `dst` is read-only, or reading or writing failed
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::DB;
+/// # use bleveldb::options::Options;
+/// use bleveldb::bedrock::{copy_chunk, CopyOptions, Dimension};
+///
+/// # let world = DB::open("world/db", &Options::default()).unwrap();
+/// # let other = DB::open("other/db", &Options::default()).unwrap();
+/// let opts = CopyOptions { entities: false, ..Default::default() };
+/// let report = copy_chunk(&world, &other, (0, 0, Dimension::Overworld), (5, 5, Dimension::Overworld), opts)
+///     .unwrap();
+/// println!("Copied {} records", report.records);
+/// ```
+pub fn copy_chunk(
+    src: &DB,
+    dst: &DB,
+    from: (i32, i32, Dimension),
+    to: (i32, i32, Dimension),
+    opts: CopyOptions,
+) -> Result<CopyReport, String> {
+    copy_chunk_impl(src, dst, from, to, opts, None)
+}
+
+/// Copy a chunk like [`copy_chunk`], allocating actor ids from `remapper`.
+///
+/// Building an [`ActorIdRemapper`] reads every actor id of the destination,
+/// so copying many chunks is cheaper with one remapper shared by all the
+/// calls. The remapper must have been created for `dst`.
+///
+/// # Arguments
+///
+/// * `src` - The database to copy from
+/// * `dst` - The database to copy to; may be the same as `src`
+/// * `from` - Source chunk x, z and dimension
+/// * `to` - Destination chunk x, z and dimension
+/// * `opts` - Which records to copy and whether to overwrite
+/// * `remapper` - Allocates the ids of the copied actors
+///
+/// # Returns
+///
+/// * `Ok(CopyReport)` - What was copied and replaced
+/// * `Err(String)` - For the same reasons as [`copy_chunk`]
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::DB;
+/// # use bleveldb::options::{Options, ReadOptions};
+/// use bleveldb::bedrock::{copy_chunk_with, ActorIdRemapper, CopyOptions, Dimension};
+///
+/// # let world = DB::open("world/db", &Options::default()).unwrap();
+/// # let other = DB::open("other/db", &Options::default()).unwrap();
+/// let mut remapper = ActorIdRemapper::new(&other, &ReadOptions::new()).unwrap();
+/// for x in 0..8 {
+///     let chunk = (x, 0, Dimension::Overworld);
+///     copy_chunk_with(&world, &other, chunk, chunk, CopyOptions::default(), &mut remapper).unwrap();
+/// }
+/// ```
+pub fn copy_chunk_with(
+    src: &DB,
+    dst: &DB,
+    from: (i32, i32, Dimension),
+    to: (i32, i32, Dimension),
+    opts: CopyOptions,
+    remapper: &mut ActorIdRemapper,
+) -> Result<CopyReport, String> {
+    copy_chunk_impl(src, dst, from, to, opts, Some(remapper))
+}
+
+fn copy_chunk_impl(
+    src: &DB,
+    dst: &DB,
+    from: (i32, i32, Dimension),
+    to: (i32, i32, Dimension),
+    opts: CopyOptions,
+    remapper: Option<&mut ActorIdRemapper>,
+) -> Result<CopyReport, String> {
+    let (from_x, from_z, from_dim) = from;
+    let (to_x, to_z, to_dim) = to;
+    if std::ptr::eq(src.inner.as_ref(), dst.inner.as_ref())
+        && (from_x, from_z, from_dim.id()) == (to_x, to_z, to_dim.id())
+    {
+        return Err("Cannot copy a chunk onto itself".to_string());
+    }
+    dst.check_writable()?;
+    let _guard = dst.lock_writes();
+    let (_src_snapshot, src_options) = src.consistent_read_options(&ReadOptions::new());
+    let (_dst_snapshot, dst_options) = dst.consistent_read_options(&ReadOptions::new());
+
+    let mut batch = WriteBatch::new();
+    let mut report = CopyReport::default();
+    let replaced = dst.queue_chunk_delete(to_x, to_z, to_dim, &dst_options, &mut batch)?;
+    if replaced.total() > 0 {
+        if !opts.overwrite {
+            return Err(format!(
+                "Chunk ({}, {}) of the {} already exists",
+                to_x, to_z, to_dim
+            ));
+        }
+        report.replaced = replaced;
+    }
+
+    let mut found = false;
+    src.scan_chunk(from_x, from_z, from_dim, &src_options, |key, value| {
+        found = true;
+        let skip = match key.tag() {
+            RecordTag::Entity => !opts.entities,
+            RecordTag::BlockEntity => !opts.block_entities,
+            _ => false,
+        };
+        if !skip {
+            let new_key = match key.subchunk_index() {
+                Some(y) => ChunkKey::subchunk(to_x, to_z, to_dim, y),
+                None => ChunkKey::new(to_x, to_z, to_dim, key.tag()),
+            };
+            batch.put(new_key, value);
+            report.records += 1;
+        }
+    })?;
+    if !found {
+        return Err(format!(
+            "Chunk ({}, {}) of the {} does not exist",
+            from_x, from_z, from_dim
+        ));
+    }
+
+    if opts.entities {
+        let mut own_remapper;
+        let remapper = match remapper {
+            Some(remapper) => remapper,
+            None => {
+                own_remapper = ActorIdRemapper::new(dst, &dst_options)?;
+                &mut own_remapper
+            }
+        };
+        report.actors = remapper
+            .queue_actors(src, &src_options, from, to, &mut batch)?
+            .len();
+    }
+
+    dst.write(&batch, &WriteOptions::new())?;
+    Ok(report)
+}
+
+impl DB {
+    /// Call `f` with every record of a chunk, in key order.
+    ///
+    /// Overworld keys share their 8-byte coordinate prefix with the chunk's
+    /// records in other dimensions, so keys are parsed and filtered by
+    /// dimension rather than matched by prefix alone.
+    pub(crate) fn scan_chunk(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+        mut f: impl FnMut(ChunkKey, &[u8]),
+    ) -> Result<(), String> {
+        let dim = Dimension::parse(dim.id());
+        let prefix = chunk_prefix(x, z, dim);
+        let mut iter = self.iter(options);
+        iter.seek(&prefix);
+        while let (Some(key), Some(value)) = (iter.key_ref(), iter.value_ref()) {
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            if let Ok(chunk_key) = ChunkKey::parse(key)
+                && chunk_key.dimension() == dim
+            {
+                f(chunk_key, value);
+            }
+            iter.next_native();
+        }
+        iter.status()
+    }
+
+    /// Delete every record of a chunk in one batch.
+    ///
+    /// This removes all records stored under the chunk's key (version,
+    /// biomes, every subchunk, block entities, ticks and so on), the chunk's
+    /// `digp` actor digest and the `actorprefix` record of every actor the
+    /// digest lists. The records are collected from one snapshot and removed
+    /// in a single atomic write, under the same mutex as [`DB::put_actor`].
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Write options used for the batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DeletedChunkReport)` - What was deleted; all zero if the chunk did not exist
+    /// * `Err(String)` - If the database is read-only, the digest is malformed, or reading or writing failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, WriteOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// // Let the game regenerate the chunk at (10, -3)
+    /// let report = db.delete_chunk(10, -3, Dimension::Overworld, &WriteOptions::new()).unwrap();
+    /// println!("Deleted {} keys", report.total());
+    /// ```
+    pub fn delete_chunk(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &WriteOptions,
+    ) -> Result<DeletedChunkReport, String> {
+        self.check_writable()?;
+        let _guard = self.lock_writes();
+        let (_snapshot, read_options) = self.consistent_read_options(&ReadOptions::new());
+
+        let mut batch = WriteBatch::new();
+        let report = self.queue_chunk_delete(x, z, dim, &read_options, &mut batch)?;
+        self.write(&batch, options)?;
+        Ok(report)
+    }
+
+    /// Add deletes for every record of a chunk and its actors to `batch`.
+    pub(crate) fn queue_chunk_delete(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        read_options: &ReadOptions,
+        batch: &mut WriteBatch,
+    ) -> Result<DeletedChunkReport, String> {
+        let mut report = DeletedChunkReport::default();
+        self.scan_chunk(x, z, dim, read_options, |key, _| {
+            report.add(key.tag());
+            batch.delete(key);
+        })?;
+        report.records.sort_by_key(|(tag, _)| tag.to_byte());
+
+        let digest = digest_key(x, z, dim);
+        if let Some(value) = self.get(&digest, read_options)? {
+            for id in parse_digest(&value)? {
+                if self.get(id.key(), read_options)?.is_some() {
+                    batch.delete(id.key());
+                    report.actors += 1;
+                }
+            }
+            batch.delete(digest);
+            report.digest = true;
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,308 @@
+use std::fs;
+use std::path::Path;
+
+use crate::bedrock::{ChunkKey, RecordTag};
+use crate::db::{DB, DEFAULT_BATCH_BYTES};
+use crate::options::{Compression, Options, ReadOptions, WriteOptions};
+use crate::util::unique_temp_dir;
+use crate::write_batch::WriteBatch;
+
+/// Compressions [`DB::compression_report`] measures, in report order.
+pub const COMPRESSIONS: [Compression; 4] = [
+    Compression::No,
+    Compression::Snappy,
+    Compression::Zstd,
+    Compression::ZlibRaw,
+];
+
+/// Bytes of each kind of record, starting in key order, that
+/// [`DB::compression_report`] compresses to measure it.
+const SAMPLE_BYTES: u64 = 1024 * 1024;
+
+/// Sizes of one kind of record in a [`CompressionReport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressionClass {
+    /// The tag of the chunk records, or `None` for every key outside chunks.
+    pub tag: Option<RecordTag>,
+    /// Number of records.
+    pub entries: u64,
+    /// Bytes of keys and values, uncompressed.
+    pub logical_bytes: u64,
+    /// Share of the database files attributed to these records, in
+    /// proportion to their estimated size with the current compression.
+    pub on_disk_bytes: u64,
+    /// Bytes of keys and values that were compressed to measure the
+    /// estimates.
+    pub sampled_bytes: u64,
+    /// Shannon entropy of the bytes of the sampled values, in bits per byte.
+    /// Close to 8 for data no compressor can shrink; unlike the estimates it
+    /// does not depend on which compressions LevelDB was built with.
+    pub entropy: f64,
+    /// Estimated table size of these records with each of
+    /// [`COMPRESSIONS`], in that order.
+    pub estimated_bytes: Vec<(Compression, u64)>,
+}
+
+impl CompressionClass {
+    /// Return the estimated table size of these records with `compression`.
+    pub fn estimated(&self, compression: Compression) -> u64 {
+        self.estimated_bytes
+            .iter()
+            .find(|(c, _)| *c == compression)
+            .map_or(0, |(_, bytes)| *bytes)
+    }
+
+    /// Return the estimated size with `compression` relative to the
+    /// uncompressed size, or `None` if there is nothing to measure.
+    pub fn ratio(&self, compression: Compression) -> Option<f64> {
+        (self.logical_bytes > 0)
+            .then(|| self.estimated(compression) as f64 / self.logical_bytes as f64)
+    }
+}
+
+/// What [`DB::compression_report`] measured.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressionReport {
+    /// The compression the database was opened with.
+    pub compression: Compression,
+    /// Every kind of record found: chunk records by ascending tag, then the
+    /// keys outside chunks.
+    pub classes: Vec<CompressionClass>,
+    /// Bytes of table and log files, as reported by [`DB::disk_usage`].
+    pub on_disk_bytes: u64,
+}
+
+impl CompressionReport {
+    /// Return the sizes of one kind of record, if the database holds any.
+    pub fn class(&self, tag: Option<RecordTag>) -> Option<&CompressionClass> {
+        self.classes.iter().find(|class| class.tag == tag)
+    }
+
+    /// Return the number of records.
+    pub fn entries(&self) -> u64 {
+        self.classes.iter().map(|class| class.entries).sum()
+    }
+
+    /// Return the bytes of all keys and values, uncompressed.
+    pub fn logical_bytes(&self) -> u64 {
+        self.classes.iter().map(|class| class.logical_bytes).sum()
+    }
+
+    /// Return the estimated size of the fully compacted tables with
+    /// `compression`.
+    pub fn estimated_bytes(&self, compression: Compression) -> u64 {
+        self.classes
+            .iter()
+            .map(|class| class.estimated(compression))
+            .sum()
+    }
+
+    /// Return how many bytes switching to `compression` and compacting is
+    /// estimated to save; negative if the database would grow.
+    pub fn estimated_savings(&self, compression: Compression) -> i64 {
+        self.on_disk_bytes as i64 - self.estimated_bytes(compression) as i64
+    }
+}
+
+impl DB {
+    /// Measure how well the records of each kind compress, to decide whether
+    /// recompressing the database is worth it.
+    ///
+    /// LevelDB does not expose the metadata of its table blocks, so one pass
+    /// over the database counts the keys and values of every chunk record
+    /// tag, and of the keys outside chunks, keeping the first MiB of each in
+    /// key order as a sample. Each sample is written to a scratch database
+    /// for every compression in [`COMPRESSIONS`] and compacted, and the
+    /// table space it takes is scaled up to all records of its kind.
+    /// Compressions the linked LevelDB was built without measure as
+    /// uncompressed; [`CompressionClass::entropy`] tells how compressible the
+    /// values are regardless.
+    ///
+    /// The current database files, from [`DB::disk_usage`], are attributed
+    /// to each kind in proportion to its estimate with the compression the
+    /// database was opened with.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CompressionReport)` - The sizes and estimates per kind of record
+    /// * `Err(String)` - If reading the database or the scratch databases failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Compression, Options};
+    /// let db = DB::open("world/db", &Options::bedrock()).unwrap();
+    /// let report = db.compression_report().unwrap();
+    /// for class in &report.classes {
+    ///     println!(
+    ///         "{:?}: {} bytes, {:.0}% with zlib",
+    ///         class.tag,
+    ///         class.logical_bytes,
+    ///         class.ratio(Compression::ZlibRaw).unwrap_or(0.0) * 100.0
+    ///     );
+    /// }
+    /// println!("{} bytes saved", report.estimated_savings(Compression::ZlibRaw));
+    /// ```
+    pub fn compression_report(&self) -> Result<CompressionReport, String> {
+        let read_options = ReadOptions::new();
+        read_options.fill_cache(false);
+
+        // Samples are keyed by class, so each class is one key range
+        let mut classes: Vec<CompressionClass> = Vec::new();
+        let mut histograms: Vec<[u64; 256]> = Vec::new();
+        let mut samples = Vec::new();
+        let mut iter = self.iter(&read_options);
+        iter.seek_to_first();
+        while let (Some(key), Some(value)) = (iter.key_ref(), iter.value_ref()) {
+            let tag = ChunkKey::parse(key).ok().map(|key| key.tag());
+            let index = match classes.iter().position(|class| class.tag == tag) {
+                Some(index) => index,
+                None => {
+                    classes.push(CompressionClass {
+                        tag,
+                        entries: 0,
+                        logical_bytes: 0,
+                        on_disk_bytes: 0,
+                        sampled_bytes: 0,
+                        entropy: 0.0,
+                        estimated_bytes: Vec::new(),
+                    });
+                    histograms.push([0; 256]);
+                    classes.len() - 1
+                }
+            };
+            let class = &mut classes[index];
+            let bytes = (key.len() + value.len()) as u64;
+            class.entries += 1;
+            class.logical_bytes += bytes;
+            if class.sampled_bytes < SAMPLE_BYTES {
+                class.sampled_bytes += bytes;
+                let mut sample_key = vec![class_byte(tag)];
+                sample_key.extend_from_slice(key);
+                samples.push((sample_key, value.to_vec()));
+                for &byte in value {
+                    histograms[index][byte as usize] += 1;
+                }
+            }
+            iter.next_native();
+        }
+        iter.status()?;
+        drop(iter);
+        for (class, histogram) in classes.iter_mut().zip(&histograms) {
+            class.entropy = entropy(histogram);
+        }
+        classes.sort_by_key(|class| class.tag.map_or(u16::MAX, |tag| tag.to_byte() as u16));
+
+        for compression in COMPRESSIONS {
+            let sizes = measure_samples(&samples, &classes, compression)?;
+            for (class, sampled_size) in classes.iter_mut().zip(sizes) {
+                let estimate = if class.sampled_bytes == 0 {
+                    0
+                } else {
+                    (sampled_size as f64 * class.logical_bytes as f64 / class.sampled_bytes as f64)
+                        as u64
+                };
+                class.estimated_bytes.push((compression, estimate));
+            }
+        }
+
+        let usage = self
+            .disk_usage()
+            .map_err(|e| format!("Failed to read database size: {}", e))?;
+        let on_disk_bytes = usage.table_bytes + usage.log_bytes;
+        let compression = self.compression();
+        let current: u64 = classes
+            .iter()
+            .map(|class| class.estimated(compression))
+            .sum();
+        for class in &mut classes {
+            class.on_disk_bytes = if current == 0 {
+                0
+            } else {
+                (on_disk_bytes as f64 * class.estimated(compression) as f64 / current as f64) as u64
+            };
+        }
+
+        Ok(CompressionReport {
+            compression,
+            classes,
+            on_disk_bytes,
+        })
+    }
+}
+
+/// Return the Shannon entropy, in bits per byte, of bytes counted in
+/// `histogram`.
+fn entropy(histogram: &[u64; 256]) -> f64 {
+    let total: u64 = histogram.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    histogram
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// The first byte of the sample keys of a class.
+fn class_byte(tag: Option<RecordTag>) -> u8 {
+    tag.map_or(0, RecordTag::to_byte)
+}
+
+/// Write `samples` to a scratch database with `compression`, compact it and
+/// return the table space each class takes, in the order of `classes`.
+fn measure_samples(
+    samples: &[(Vec<u8>, Vec<u8>)],
+    classes: &[CompressionClass],
+    compression: Compression,
+) -> Result<Vec<u64>, String> {
+    let dir = unique_temp_dir("bleveldb-compression")
+        .map_err(|e| format!("Failed to create scratch directory: {}", e))?;
+    let sizes = measure_samples_in(&dir, samples, classes, compression);
+    let _ = fs::remove_dir_all(&dir);
+    sizes
+}
+
+fn measure_samples_in(
+    dir: &Path,
+    samples: &[(Vec<u8>, Vec<u8>)],
+    classes: &[CompressionClass],
+    compression: Compression,
+) -> Result<Vec<u64>, String> {
+    let options = Options::new();
+    options.create_if_missing(true);
+    options.compression(compression);
+    let scratch = DB::open(dir.join("db"), &options)?;
+
+    let write_options = WriteOptions::new();
+    let mut batch = WriteBatch::new();
+    for (key, value) in samples {
+        batch.put(key, value);
+        if batch.approximate_size() >= DEFAULT_BATCH_BYTES {
+            scratch.write(&batch, &write_options)?;
+            batch.clear();
+        }
+    }
+    scratch.write(&batch, &write_options)?;
+    scratch.compact_all()?;
+
+    let bounds: Vec<([u8; 1], [u8; 1])> = classes
+        .iter()
+        .map(|class| {
+            let byte = class_byte(class.tag);
+            ([byte], [byte + 1])
+        })
+        .collect();
+    let ranges: Vec<(&[u8], &[u8])> = bounds
+        .iter()
+        .map(|(start, limit)| (start.as_slice(), limit.as_slice()))
+        .collect();
+    let sizes = scratch.approximate_sizes(&ranges);
+    scratch.close()?;
+    Ok(sizes)
+}
@@ -0,0 +1,126 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Why a directory cannot be opened as a world database, as reported by
+/// [`validate_db_dir`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DbDirIssue {
+    /// The path does not exist or is not a directory.
+    NotADirectory(PathBuf),
+    /// The path is a world directory rather than its database.
+    WorldRoot {
+        /// The database directory inside the world.
+        db_path: PathBuf,
+    },
+    /// The directory has no `CURRENT` file.
+    MissingCurrent,
+    /// `CURRENT` does not name a manifest.
+    InvalidCurrent(String),
+    /// The manifest named by `CURRENT` does not exist.
+    MissingManifest(String),
+    /// The directory has no table (`.ldb`, `.sst`) or log (`.log`) files.
+    NoDataFiles,
+    /// The directory could not be read.
+    Io(String),
+}
+
+impl fmt::Display for DbDirIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DbDirIssue::NotADirectory(path) => {
+                write!(f, "{} is not a directory", path.display())
+            }
+            DbDirIssue::WorldRoot { db_path } => write!(
+                f,
+                "this looks like a world root, did you mean {}?",
+                db_path.display()
+            ),
+            DbDirIssue::MissingCurrent => write!(
+                f,
+                "no CURRENT file; the directory is not a database or was copied incompletely"
+            ),
+            DbDirIssue::InvalidCurrent(content) => {
+                write!(f, "CURRENT does not name a manifest: {:?}", content)
+            }
+            DbDirIssue::MissingManifest(name) => write!(
+                f,
+                "CURRENT names {}, which is missing; the directory was copied incompletely",
+                name
+            ),
+            DbDirIssue::NoDataFiles => write!(
+                f,
+                "no .ldb, .sst or .log files; the directory was copied incompletely"
+            ),
+            DbDirIssue::Io(message) => write!(f, "failed to read the directory: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for DbDirIssue {}
+
+/// Check that a directory looks like a complete LevelDB database before
+/// opening it.
+///
+/// LevelDB reports a misplaced or half-copied database with terse errors
+/// about missing files. This check looks for the same problems up front
+/// and explains them, including the common mistake of passing the world
+/// directory instead of its `db` subdirectory.
+///
+/// # Arguments
+///
+/// * `path` - The directory to check
+///
+/// # Returns
+///
+/// * `Ok(())` - If the directory has a `CURRENT` file, the manifest it names and some data files
+/// * `Err(DbDirIssue)` - The first problem found
+///
+/// # Examples
+///
+/// ```no_run
+/// use bleveldb::bedrock::validate_db_dir;
+///
+/// if let Err(issue) = validate_db_dir("minecraftWorlds/abc123=") {
+///     eprintln!("Cannot open the world: {}", issue);
+/// }
+/// ```
+pub fn validate_db_dir(path: impl AsRef<Path>) -> Result<(), DbDirIssue> {
+    let path = path.as_ref();
+    if !path.is_dir() {
+        return Err(DbDirIssue::NotADirectory(path.to_path_buf()));
+    }
+
+    let current = match fs::read_to_string(path.join("CURRENT")) {
+        Ok(current) => current,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let db_path = path.join("db");
+            if db_path.join("CURRENT").is_file() || path.join("level.dat").is_file() {
+                return Err(DbDirIssue::WorldRoot { db_path });
+            }
+            return Err(DbDirIssue::MissingCurrent);
+        }
+        Err(e) => return Err(DbDirIssue::Io(e.to_string())),
+    };
+    let manifest = current.trim_end_matches(['\r', '\n']);
+    if !manifest.starts_with("MANIFEST-") || manifest.contains(['/', '\\']) {
+        return Err(DbDirIssue::InvalidCurrent(current));
+    }
+    if !path.join(manifest).is_file() {
+        return Err(DbDirIssue::MissingManifest(manifest.to_string()));
+    }
+
+    let entries = fs::read_dir(path).map_err(|e| DbDirIssue::Io(e.to_string()))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| DbDirIssue::Io(e.to_string()))?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if [".ldb", ".sst", ".log"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+        {
+            return Ok(());
+        }
+    }
+    Err(DbDirIssue::NoDataFiles)
+}
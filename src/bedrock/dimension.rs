@@ -0,0 +1,86 @@
+use std::fmt;
+
+/// A dimension of a Bedrock world, as identified in chunk keys.
+///
+/// Chunk keys of the Overworld carry no dimension field at all; the other
+/// dimensions add their id as a 4-byte little-endian integer. Use
+/// [`Dimension::parse`] to turn an id into a `Dimension`: it maps the vanilla
+/// ids to their variants, so `Custom` only ever holds other ids.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bleveldb::bedrock::Dimension;
+///
+/// assert_eq!(Dimension::parse(1), Dimension::Nether);
+/// assert_eq!(Dimension::End.to_string(), "the_end");
+///
+/// let mut key = Vec::new();
+/// Dimension::Overworld.encode_into(&mut key);
+/// assert!(key.is_empty());
+/// Dimension::End.encode_into(&mut key);
+/// assert_eq!(key, [2, 0, 0, 0]);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dimension {
+    /// The Overworld (id 0).
+    #[default]
+    Overworld,
+    /// The Nether (id 1).
+    Nether,
+    /// The End (id 2).
+    End,
+    /// A dimension added by an add-on or a future version.
+    Custom(i32),
+}
+
+impl Dimension {
+    /// The three vanilla dimensions.
+    pub const VANILLA: [Dimension; 3] = [Dimension::Overworld, Dimension::Nether, Dimension::End];
+
+    /// Return the dimension with the given id.
+    pub fn parse(id: i32) -> Dimension {
+        match id {
+            0 => Dimension::Overworld,
+            1 => Dimension::Nether,
+            2 => Dimension::End,
+            id => Dimension::Custom(id),
+        }
+    }
+
+    /// Return the numeric id of the dimension.
+    pub fn id(self) -> i32 {
+        match self {
+            Dimension::Overworld => 0,
+            Dimension::Nether => 1,
+            Dimension::End => 2,
+            Dimension::Custom(id) => id,
+        }
+    }
+
+    /// Append the dimension field of a chunk key to `buf`.
+    ///
+    /// Nothing is written for the Overworld, whose keys have no dimension field.
+    pub fn encode_into(self, buf: &mut Vec<u8>) {
+        if self.has_key_field() {
+            buf.extend_from_slice(&self.id().to_le_bytes());
+        }
+    }
+
+    /// Return `true` if chunk keys of this dimension carry a dimension field.
+    pub(crate) fn has_key_field(self) -> bool {
+        self.id() != 0
+    }
+}
+
+impl fmt::Display for Dimension {
+    /// Write the name the game uses for the dimension, like `the_end`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dimension::Overworld => f.write_str("overworld"),
+            Dimension::Nether => f.write_str("nether"),
+            Dimension::End => f.write_str("the_end"),
+            Dimension::Custom(id) => write!(f, "dimension_{}", id),
+        }
+    }
+}
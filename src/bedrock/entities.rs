@@ -0,0 +1,209 @@
+use super::actors::{DIGEST_PREFIX, parse_digest, parse_digest_key};
+use super::nbt::{self, Value};
+use super::{ActorId, ChunkKey, Dimension, RecordTag};
+use crate::db::DB;
+use crate::options::ReadOptions;
+
+/// Where an entity's NBT is stored, which decides how to write it back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityStorage {
+    /// In the chunk's [`RecordTag::Entity`] record, next to the chunk's other
+    /// entities; worlds saved before 1.18.30 keep all entities this way.
+    Legacy,
+    /// In its own `actorprefix` record, listed in the chunk's digest.
+    Actor(ActorId),
+}
+
+/// An entity of a chunk, as returned by [`DB::entities_in_chunk`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entity {
+    /// The entity's NBT compound.
+    pub nbt: Value,
+    /// Where the entity is stored.
+    pub format: EntityStorage,
+}
+
+/// Decode a chunk's legacy entity record into its entities.
+fn legacy_entities(record: &[u8], (x, z): (i32, i32)) -> Result<Vec<Entity>, String> {
+    let entities = nbt::decode_all(record)
+        .map_err(|e| format!("Invalid entities in chunk ({}, {}): {}", x, z, e))?;
+    Ok(entities
+        .into_iter()
+        .map(|nbt| Entity {
+            nbt,
+            format: EntityStorage::Legacy,
+        })
+        .collect())
+}
+
+/// Decode the record of an actor.
+fn actor_entity(id: ActorId, data: &[u8]) -> Result<Entity, String> {
+    let nbt = nbt::decode(data).map_err(|e| format!("Invalid actor {}: {}", id, e))?;
+    Ok(Entity {
+        nbt,
+        format: EntityStorage::Actor(id),
+    })
+}
+
+impl DB {
+    /// Read the entities of a chunk from both storage formats.
+    ///
+    /// Entities are read from the chunk's legacy [`RecordTag::Entity`]
+    /// record and from the actors listed in its `digp` digest. A world
+    /// upgraded to 1.18.30 keeps using the legacy record for chunks that
+    /// have not been saved since, so a chunk can have either, or briefly
+    /// both. Each entity records where it came from in [`Entity::format`].
+    /// Actors listed in the digest whose record is missing are skipped.
+    ///
+    /// Both formats are read from one snapshot unless `options` already
+    /// carries one.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Entity>)` - The legacy entities in stored order, followed by the actors in digest order
+    /// * `Err(String)` - If a read failed or a record is malformed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::{Dimension, EntityStorage};
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// for entity in db.entities_in_chunk(0, 0, Dimension::Overworld, &ReadOptions::new()).unwrap() {
+    ///     let legacy = entity.format == EntityStorage::Legacy;
+    ///     println!("{:?} (legacy: {})", entity.nbt.get("identifier"), legacy);
+    /// }
+    /// ```
+    pub fn entities_in_chunk(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Vec<Entity>, String> {
+        let (_snapshot, read_options) = self.consistent_read_options(options);
+        let key = ChunkKey::new(x, z, dim, RecordTag::Entity);
+        let mut entities = match self.get(key, &read_options)? {
+            Some(record) => legacy_entities(&record, (x, z))?,
+            None => Vec::new(),
+        };
+        for actor in self.actors_in_chunk(x, z, dim, &read_options)? {
+            if let Some(data) = actor.data {
+                entities.push(actor_entity(actor.id, &data)?);
+            }
+        }
+        Ok(entities)
+    }
+
+    /// Iterate over the entities of every chunk in a dimension.
+    ///
+    /// The legacy [`RecordTag::Entity`] records are yielded first, in key
+    /// order, followed by the actors of each `digp` digest of the dimension.
+    /// Both passes read from one snapshot unless `options` already carries
+    /// one, so an entity that moves between formats while the iteration runs
+    /// is seen once.
+    ///
+    /// A malformed record or digest yields one `Err`, and the iteration goes
+    /// on with the next one. A read error ends the pass it happened in with
+    /// one `Err`, so a truncated scan can be told apart from a complete one.
+    ///
+    /// # Arguments
+    ///
+    /// * `dim` - The dimension to scan
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let villagers = db
+    ///     .all_entities(Dimension::Overworld, &ReadOptions::new())
+    ///     .filter_map(Result::ok)
+    ///     .filter(|(_, entity)| {
+    ///         entity.nbt.get("identifier").and_then(|id| id.as_str()) == Some("minecraft:villager_v2")
+    ///     })
+    ///     .count();
+    /// println!("{} villagers", villagers);
+    /// ```
+    pub fn all_entities(
+        &self,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> impl Iterator<Item = Result<((i32, i32), Entity), String>> + '_ {
+        let dim = Dimension::parse(dim.id());
+        let (snapshot, read_options) = self.consistent_read_options(options);
+        let mut records = self.iter(&read_options);
+        records.seek_to_first();
+        let mut digests = self.prefix_iter(DIGEST_PREFIX, &read_options);
+        let mut pending: std::vec::IntoIter<Result<Entity, String>> = Vec::new().into_iter();
+        let mut position = (0, 0);
+        let (mut records_checked, mut digests_checked) = (false, false);
+        std::iter::from_fn(move || {
+            // Keep the snapshot alive for as long as the iteration runs
+            let _snapshot = &snapshot;
+            loop {
+                if let Some(entity) = pending.next() {
+                    return Some(entity.map(|entity| (position, entity)));
+                }
+                if let Some(key) = records.key_ref() {
+                    let chunk = ChunkKey::parse(key)
+                        .ok()
+                        .filter(|key| key.dimension() == dim && key.tag() == RecordTag::Entity);
+                    if let Some(chunk) = chunk {
+                        position = (chunk.x(), chunk.z());
+                        let record = records.value_ref().unwrap_or_default();
+                        pending = match legacy_entities(record, position) {
+                            Ok(entities) => entities.into_iter().map(Ok).collect(),
+                            Err(e) => vec![Err(e)],
+                        }
+                        .into_iter();
+                    }
+                    records.next_native();
+                    continue;
+                }
+
+                if !records_checked {
+                    records_checked = true;
+                    if let Err(e) = records.status() {
+                        return Some(Err(e));
+                    }
+                }
+
+                let Some((key, digest)) = digests.next() else {
+                    if digests_checked {
+                        return None;
+                    }
+                    digests_checked = true;
+                    return digests.status().err().map(Err);
+                };
+                let Some((x, z, _)) = parse_digest_key(&key).filter(|&(_, _, d)| d == dim) else {
+                    continue;
+                };
+                position = (x, z);
+                pending = match parse_digest(&digest) {
+                    Ok(ids) => ids
+                        .into_iter()
+                        .filter_map(|id| match self.actor_data(id, &read_options) {
+                            Ok(Some(data)) => Some(actor_entity(id, &data)),
+                            Ok(None) => None,
+                            Err(e) => Some(Err(e)),
+                        })
+                        .collect(),
+                    Err(e) => vec![Err(format!("Chunk ({}, {}): {}", x, z, e))],
+                }
+                .into_iter();
+            }
+        })
+    }
+}
@@ -0,0 +1,140 @@
+use super::{ChunkKey, Dimension, RecordTag};
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+
+/// How far world generation has progressed in a chunk, as stored in its
+/// [`RecordTag::FinalizedState`] record.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bleveldb::bedrock::FinalizedState;
+///
+/// assert_eq!(FinalizedState::from_u32(2), FinalizedState::Done);
+/// assert_eq!(FinalizedState::Unknown(7).to_u32(), 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FinalizedState {
+    /// The terrain exists but nothing has been ticked yet (0).
+    NeedsInstaticking,
+    /// The chunk still has to be populated with features and mobs (1).
+    NeedsPopulation,
+    /// Generation is complete (2).
+    Done,
+    /// A value the game does not currently write.
+    Unknown(u32),
+}
+
+impl FinalizedState {
+    /// Return the state with the given stored value.
+    pub fn from_u32(value: u32) -> FinalizedState {
+        match value {
+            0 => FinalizedState::NeedsInstaticking,
+            1 => FinalizedState::NeedsPopulation,
+            2 => FinalizedState::Done,
+            value => FinalizedState::Unknown(value),
+        }
+    }
+
+    /// Return the value stored for the state.
+    pub fn to_u32(self) -> u32 {
+        match self {
+            FinalizedState::NeedsInstaticking => 0,
+            FinalizedState::NeedsPopulation => 1,
+            FinalizedState::Done => 2,
+            FinalizedState::Unknown(value) => value,
+        }
+    }
+}
+
+impl DB {
+    /// Read the generation progress of a chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(FinalizedState))` - The stored state
+    /// * `Ok(None)` - If the chunk has no `FinalizedState` record
+    /// * `Err(String)` - If the read failed or the record is not a 4-byte integer
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::{Dimension, FinalizedState};
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let state = db.finalized_state(0, 0, Dimension::Overworld, &ReadOptions::new()).unwrap();
+    /// if state != Some(FinalizedState::Done) {
+    ///     println!("Chunk (0, 0) is not fully generated");
+    /// }
+    /// ```
+    pub fn finalized_state(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Option<FinalizedState>, String> {
+        let key = ChunkKey::new(x, z, dim, RecordTag::FinalizedState);
+        let Some(value) = self.get(key, options)? else {
+            return Ok(None);
+        };
+        let bytes: [u8; 4] = value.as_slice().try_into().map_err(|_| {
+            format!(
+                "FinalizedState record of chunk ({}, {}) is {} bytes instead of 4",
+                x,
+                z,
+                value.len()
+            )
+        })?;
+        Ok(Some(FinalizedState::from_u32(u32::from_le_bytes(bytes))))
+    }
+
+    /// Set the generation progress of a chunk.
+    ///
+    /// Setting a generated chunk back to [`FinalizedState::NeedsPopulation`]
+    /// makes the game populate it again the next time it is loaded, which
+    /// repair tools use to restore missing trees, ores and structures.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `state` - The new state
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the state was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::{Dimension, FinalizedState};
+    /// # use bleveldb::options::{Options, WriteOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// db.set_finalized_state(0, 0, Dimension::Overworld, FinalizedState::NeedsPopulation, &WriteOptions::new())
+    ///     .unwrap();
+    /// ```
+    pub fn set_finalized_state(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        state: FinalizedState,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        let key = ChunkKey::new(x, z, dim, RecordTag::FinalizedState);
+        self.put(key, state.to_u32().to_le_bytes(), options)
+    }
+}
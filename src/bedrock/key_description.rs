@@ -0,0 +1,187 @@
+use std::fmt;
+
+use super::actors::{ACTOR_PREFIX, parse_digest_key};
+use super::maps::parse_map_key;
+use super::structures::{STRUCTURE_PREFIX, split_identifier};
+use crate::bedrock::{ActorId, ChunkKey, Dimension, KnownKey, PlayerKey, RecordTag, VillageKey};
+
+/// Number of key bytes [`KeyDescription::Unknown`] shows.
+const PREVIEW_BYTES: usize = 32;
+
+/// What a key of a Bedrock world database holds, as returned by
+/// [`describe_key`].
+///
+/// The `Display` impl writes a single line meant for dumps and error
+/// messages, like `chunk(12,-7) overworld SubChunkPrefix y=3`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyDescription {
+    /// A record of a chunk.
+    ChunkRecord {
+        /// Chunk x and z coordinates.
+        pos: (i32, i32),
+        /// The chunk's dimension.
+        dim: Dimension,
+        /// The kind of record.
+        tag: RecordTag,
+        /// The subchunk index of `SubChunkPrefix` records.
+        subchunk: Option<i8>,
+    },
+    /// A player's record, including `~local_player`.
+    Player(PlayerKey),
+    /// A map item's record, by map id.
+    Map(i64),
+    /// One of the records of a village.
+    Village(VillageKey),
+    /// A saved structure template.
+    Structure {
+        /// The structure's namespace, like `mystructure`.
+        namespace: String,
+        /// The structure's name.
+        name: String,
+    },
+    /// The actor digest of a chunk.
+    ActorDigest {
+        /// Chunk x and z coordinates.
+        pos: (i32, i32),
+        /// The chunk's dimension.
+        dim: Dimension,
+    },
+    /// An actor's record.
+    ActorData(ActorId),
+    /// One of the world-wide records under a fixed key.
+    KnownFlat(KnownKey),
+    /// A key of none of the above forms.
+    Unknown(Vec<u8>),
+}
+
+impl fmt::Display for KeyDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyDescription::ChunkRecord {
+                pos,
+                dim,
+                tag,
+                subchunk,
+            } => {
+                write!(f, "chunk({},{}) {} {}", pos.0, pos.1, dim, tag)?;
+                if let Some(y) = subchunk {
+                    write!(f, " y={}", y)?;
+                }
+                Ok(())
+            }
+            KeyDescription::Player(key) => write!(f, "player {}", key),
+            KeyDescription::Map(id) => write!(f, "map {}", id),
+            KeyDescription::Village(key) => {
+                write!(f, "village {}", key.uuid)?;
+                if let Some(dim) = key.dimension {
+                    write!(f, " {}", dim)?;
+                }
+                write!(f, " {}", key.record.suffix())
+            }
+            KeyDescription::Structure { namespace, name } => {
+                write!(f, "structure {}:{}", namespace, name)
+            }
+            KeyDescription::ActorDigest { pos, dim } => {
+                write!(f, "actor digest chunk({},{}) {}", pos.0, pos.1, dim)
+            }
+            KeyDescription::ActorData(id) => write!(f, "actor {}", id),
+            KeyDescription::KnownFlat(key) => write!(f, "known {}", key),
+            KeyDescription::Unknown(key) => {
+                write!(f, "unknown ({} bytes) ", key.len())?;
+                for byte in key.iter().take(PREVIEW_BYTES) {
+                    write!(f, "{:02x}", byte)?;
+                }
+                if key.len() > PREVIEW_BYTES {
+                    f.write_str("...")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Classify a key of a Bedrock world database.
+///
+/// String keys are recognized before chunk keys, since some of them, like
+/// `map_12345`, also have the length and tag byte of a chunk key.
+/// `~local_player` is described as a player rather than a known key.
+///
+/// # Arguments
+///
+/// * `key` - Any key of the database
+///
+/// # Returns
+///
+/// The description of the key, [`KeyDescription::Unknown`] if it fits no
+/// known form.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::DB;
+/// # use bleveldb::options::{Options, ReadOptions};
+/// use bleveldb::bedrock::describe_key;
+///
+/// # let db = DB::open("world/db", &Options::default()).unwrap();
+/// for (key, value) in db.iter(&ReadOptions::new()).take(20) {
+///     println!("{} ({} bytes)", describe_key(&key), value.len());
+/// }
+/// ```
+pub fn describe_key(key: &[u8]) -> KeyDescription {
+    if let Some(description) = describe_string_key(key) {
+        return description;
+    }
+    match ChunkKey::parse(key) {
+        Ok(chunk) => KeyDescription::ChunkRecord {
+            pos: (chunk.x(), chunk.z()),
+            dim: chunk.dimension(),
+            tag: chunk.tag(),
+            subchunk: chunk.subchunk_index(),
+        },
+        Err(_) => KeyDescription::Unknown(key.to_vec()),
+    }
+}
+
+/// Describe `key` if it is one of the string keys, leaving chunk keys out.
+fn describe_string_key(key: &[u8]) -> Option<KeyDescription> {
+    if let Some((x, z, dim)) = parse_digest_key(key) {
+        return Some(KeyDescription::ActorDigest { pos: (x, z), dim });
+    }
+    if let Some(id) = key.strip_prefix(ACTOR_PREFIX)
+        && let Ok(id) = <[u8; 8]>::try_from(id)
+    {
+        return Some(KeyDescription::ActorData(ActorId(id)));
+    }
+    if let Some(id) = parse_map_key(key) {
+        return Some(KeyDescription::Map(id));
+    }
+    if let Some(player) = PlayerKey::parse(key) {
+        return Some(KeyDescription::Player(player));
+    }
+    if let Some(village) = VillageKey::parse(key) {
+        return Some(KeyDescription::Village(village));
+    }
+    if let Some(identifier) = std::str::from_utf8(key)
+        .ok()
+        .and_then(|key| key.strip_prefix(STRUCTURE_PREFIX))
+        && identifier.contains(':')
+        && let Some((namespace, name)) = split_identifier(identifier)
+    {
+        return Some(KeyDescription::Structure {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        });
+    }
+    KnownKey::parse(key).map(KeyDescription::KnownFlat)
+}
+
+/// Parse `key` as a chunk record key.
+///
+/// Unlike a bare [`ChunkKey::parse`], string keys that happen to have the
+/// length and tag byte of a chunk key, like `map_12345`, are rejected.
+pub(crate) fn parse_chunk_record_key(key: &[u8]) -> Option<ChunkKey> {
+    if describe_string_key(key).is_some() {
+        return None;
+    }
+    ChunkKey::parse(key).ok()
+}
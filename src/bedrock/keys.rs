@@ -0,0 +1,180 @@
+//! Keys of the world-wide records Bedrock stores under plain strings.
+//!
+//! Use [`KnownKey`] with [`DB::get_known`] and [`DB::put_known`] to access
+//! them without spelling out the key.
+
+use std::fmt;
+
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+
+/// Biome state that is not tied to a chunk, such as snow levels.
+pub const BIOME_DATA: &[u8] = b"BiomeData";
+/// Data of the Overworld, such as its limbo entities.
+pub const OVERWORLD: &[u8] = b"Overworld";
+/// Data of the Nether.
+pub const NETHER: &[u8] = b"Nether";
+/// Data of the End, including the dragon fight.
+pub const THE_END: &[u8] = b"TheEnd";
+/// Which mob events, such as raids, are enabled.
+pub const MOB_EVENTS: &[u8] = b"mobevents";
+/// Locations of nether portals.
+pub const PORTALS: &[u8] = b"portals";
+/// Scoreboard objectives and scores.
+pub const SCOREBOARD: &[u8] = b"scoreboard";
+/// Scheduled commands of the wandering trader.
+pub const SCHEDULER_WT: &[u8] = b"schedulerWT";
+/// Entities that are not saved with a chunk, such as the wandering trader.
+pub const AUTONOMOUS_ENTITIES: &[u8] = b"AutonomousEntities";
+/// Chunk metadata shared between chunks, indexed by the hashes in `MetaDataHash` records.
+pub const LEVEL_CHUNK_META_DATA_DICTIONARY: &[u8] = b"LevelChunkMetaDataDictionary";
+/// Layers of a flat world.
+pub const GAME_FLAT_WORLD_LAYERS: &[u8] = b"game_flatworldlayers";
+/// The host player of a single-player world.
+pub const LOCAL_PLAYER: &[u8] = b"~local_player";
+
+/// A world-wide record stored under a fixed key.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::DB;
+/// # use bleveldb::options::{Options, ReadOptions};
+/// use bleveldb::bedrock::KnownKey;
+///
+/// # let db = DB::open("world/db", &Options::default()).unwrap();
+/// for key in KnownKey::all() {
+///     if db.get_known(key, &ReadOptions::new()).unwrap().is_some() {
+///         println!("The world has {}", key);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KnownKey {
+    /// `BiomeData`
+    BiomeData,
+    /// `Overworld`
+    Overworld,
+    /// `Nether`
+    Nether,
+    /// `TheEnd`
+    TheEnd,
+    /// `mobevents`
+    MobEvents,
+    /// `portals`
+    Portals,
+    /// `scoreboard`
+    Scoreboard,
+    /// `schedulerWT`
+    SchedulerWT,
+    /// `AutonomousEntities`
+    AutonomousEntities,
+    /// `LevelChunkMetaDataDictionary`
+    LevelChunkMetaDataDictionary,
+    /// `game_flatworldlayers`
+    GameFlatWorldLayers,
+    /// `~local_player`
+    LocalPlayer,
+}
+
+impl KnownKey {
+    const ALL: [KnownKey; 12] = [
+        KnownKey::BiomeData,
+        KnownKey::Overworld,
+        KnownKey::Nether,
+        KnownKey::TheEnd,
+        KnownKey::MobEvents,
+        KnownKey::Portals,
+        KnownKey::Scoreboard,
+        KnownKey::SchedulerWT,
+        KnownKey::AutonomousEntities,
+        KnownKey::LevelChunkMetaDataDictionary,
+        KnownKey::GameFlatWorldLayers,
+        KnownKey::LocalPlayer,
+    ];
+
+    /// Iterate over every known key.
+    pub fn all() -> impl Iterator<Item = KnownKey> {
+        Self::ALL.into_iter()
+    }
+
+    /// Return the raw key.
+    pub fn key(self) -> &'static [u8] {
+        match self {
+            KnownKey::BiomeData => BIOME_DATA,
+            KnownKey::Overworld => OVERWORLD,
+            KnownKey::Nether => NETHER,
+            KnownKey::TheEnd => THE_END,
+            KnownKey::MobEvents => MOB_EVENTS,
+            KnownKey::Portals => PORTALS,
+            KnownKey::Scoreboard => SCOREBOARD,
+            KnownKey::SchedulerWT => SCHEDULER_WT,
+            KnownKey::AutonomousEntities => AUTONOMOUS_ENTITIES,
+            KnownKey::LevelChunkMetaDataDictionary => LEVEL_CHUNK_META_DATA_DICTIONARY,
+            KnownKey::GameFlatWorldLayers => GAME_FLAT_WORLD_LAYERS,
+            KnownKey::LocalPlayer => LOCAL_PLAYER,
+        }
+    }
+
+    /// Return the known key equal to `key`, if any.
+    pub fn parse(key: &[u8]) -> Option<KnownKey> {
+        Self::all().find(|known| known.key() == key)
+    }
+}
+
+impl AsRef<[u8]> for KnownKey {
+    fn as_ref(&self) -> &[u8] {
+        self.key()
+    }
+}
+
+impl fmt::Display for KnownKey {
+    /// Write the key as stored, like `mobevents`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Every known key is ASCII
+        f.write_str(std::str::from_utf8(self.key()).unwrap())
+    }
+}
+
+impl DB {
+    /// Read a world-wide record.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The record to read
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Vec<u8>))` - The stored record, usually NBT
+    /// * `Ok(None)` - If the world does not have the record
+    /// * `Err(String)` - If an error occurred during the read operation
+    pub fn get_known(
+        &self,
+        key: KnownKey,
+        options: &ReadOptions,
+    ) -> Result<Option<Vec<u8>>, String> {
+        self.get(key, options)
+    }
+
+    /// Write a world-wide record.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The record to write
+    /// * `data` - The new contents of the record
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub fn put_known(
+        &self,
+        key: KnownKey,
+        data: impl AsRef<[u8]>,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        self.put(key, data, options)
+    }
+}
@@ -0,0 +1,254 @@
+use std::fmt;
+
+use crate::bedrock::{ChunkKey, Dimension, RecordTag};
+use crate::db::DB;
+use crate::options::ReadOptions;
+
+/// Height of a chunk before 1.0.
+const HEIGHT: usize = 128;
+/// Number of blocks in a chunk.
+const VOLUME: usize = 16 * 16 * HEIGHT;
+/// Number of columns in a chunk.
+const COLUMNS: usize = 256;
+/// Offset of the block data nibbles.
+const DATA_OFFSET: usize = VOLUME;
+/// Offset of the sky light nibbles.
+const SKY_LIGHT_OFFSET: usize = DATA_OFFSET + VOLUME / 2;
+/// Offset of the block light nibbles.
+const BLOCK_LIGHT_OFFSET: usize = SKY_LIGHT_OFFSET + VOLUME / 2;
+/// Offset of the heightmap, one byte per column.
+const HEIGHTMAP_OFFSET: usize = BLOCK_LIGHT_OFFSET + VOLUME / 2;
+/// Offset of the biomes, four bytes per column.
+const BIOMES_OFFSET: usize = HEIGHTMAP_OFFSET + COLUMNS;
+/// Size of a `LegacyTerrain` record.
+const RECORD_LEN: usize = BIOMES_OFFSET + COLUMNS * 4;
+
+/// Why a `LegacyTerrain` record could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LegacyTerrainError {
+    /// The record is not exactly 83200 bytes long.
+    InvalidLength(usize),
+}
+
+impl fmt::Display for LegacyTerrainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LegacyTerrainError::InvalidLength(len) => write!(
+                f,
+                "LegacyTerrain record is {} bytes instead of {}",
+                len, RECORD_LEN
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LegacyTerrainError {}
+
+/// Return the index of block `(x, y, z)` in the block arrays.
+fn block_index(x: usize, y: usize, z: usize) -> usize {
+    assert!(
+        x < 16 && y < HEIGHT && z < 16,
+        "block ({}, {}, {}) is outside the chunk",
+        x,
+        y,
+        z
+    );
+    (x * 16 + z) * HEIGHT + y
+}
+
+/// Return the index of column `(x, z)` in the heightmap and biome arrays.
+fn column_index(x: usize, z: usize) -> usize {
+    assert!(
+        x < 16 && z < 16,
+        "column ({}, {}) is outside the chunk",
+        x,
+        z
+    );
+    z * 16 + x
+}
+
+/// The terrain of a chunk saved before 1.0, stored under
+/// [`RecordTag::LegacyTerrain`].
+///
+/// The record has a fixed layout for a chunk 128 blocks high:
+///
+/// | Offset | Size  | Content                                          |
+/// |--------|-------|--------------------------------------------------|
+/// | 0      | 32768 | Block ids, indexed by `(x * 16 + z) * 128 + y`   |
+/// | 32768  | 16384 | Block data, one nibble per block                 |
+/// | 49152  | 16384 | Sky light, one nibble per block                  |
+/// | 65536  | 16384 | Block light, one nibble per block                |
+/// | 81920  | 256   | Heightmap, indexed by `z * 16 + x`               |
+/// | 82176  | 1024  | Biome id and grass color (RGB) of each column    |
+///
+/// Nibble arrays use the block index halved, with even indices in the low
+/// nibble.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::DB;
+/// # use bleveldb::bedrock::Dimension;
+/// # use bleveldb::options::{Options, ReadOptions};
+/// # let db = DB::open("world/db", &Options::default()).unwrap();
+/// if let Some(terrain) = db.legacy_terrain(0, 0, Dimension::Overworld, &ReadOptions::new()).unwrap() {
+///     let y = terrain.height_at(8, 8) as usize;
+///     println!("Top block: {}", terrain.block_id_at(8, y.saturating_sub(1), 8));
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct LegacyTerrain {
+    record: Vec<u8>,
+}
+
+impl fmt::Debug for LegacyTerrain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LegacyTerrain").finish_non_exhaustive()
+    }
+}
+
+impl LegacyTerrain {
+    /// Parse a `LegacyTerrain` record.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - The raw record in the fixed layout described on [`LegacyTerrain`]
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(LegacyTerrain)` - The decoded record
+    /// * `Err(LegacyTerrainError)` - If the record is not exactly 83200 bytes long
+    pub fn parse(record: &[u8]) -> Result<LegacyTerrain, LegacyTerrainError> {
+        if record.len() != RECORD_LEN {
+            return Err(LegacyTerrainError::InvalidLength(record.len()));
+        }
+        Ok(LegacyTerrain {
+            record: record.to_vec(),
+        })
+    }
+
+    fn nibble(&self, offset: usize, index: usize) -> u8 {
+        let byte = self.record[offset + index / 2];
+        if index.is_multiple_of(2) {
+            byte & 0x0F
+        } else {
+            byte >> 4
+        }
+    }
+
+    /// Return the numeric id of the block at `(x, y, z)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `z` is not below 16 or `y` is not below 128.
+    pub fn block_id_at(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.record[block_index(x, y, z)]
+    }
+
+    /// Return the data value (variant) of the block at `(x, y, z)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `z` is not below 16 or `y` is not below 128.
+    pub fn block_data_at(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.nibble(DATA_OFFSET, block_index(x, y, z))
+    }
+
+    /// Return the sky light level at `(x, y, z)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `z` is not below 16 or `y` is not below 128.
+    pub fn sky_light_at(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.nibble(SKY_LIGHT_OFFSET, block_index(x, y, z))
+    }
+
+    /// Return the block light level at `(x, y, z)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `z` is not below 16 or `y` is not below 128.
+    pub fn block_light_at(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.nibble(BLOCK_LIGHT_OFFSET, block_index(x, y, z))
+    }
+
+    /// Return the heightmap entry of column `(x, z)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `z` is not below 16.
+    pub fn height_at(&self, x: usize, z: usize) -> u8 {
+        self.record[HEIGHTMAP_OFFSET + column_index(x, z)]
+    }
+
+    /// Return the biome id of column `(x, z)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `z` is not below 16.
+    pub fn biome_at(&self, x: usize, z: usize) -> u8 {
+        self.record[BIOMES_OFFSET + column_index(x, z) * 4]
+    }
+
+    /// Return the grass color of column `(x, z)` as red, green and blue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `z` is not below 16.
+    pub fn grass_color_at(&self, x: usize, z: usize) -> [u8; 3] {
+        let offset = BIOMES_OFFSET + column_index(x, z) * 4 + 1;
+        self.record[offset..offset + 3].try_into().unwrap()
+    }
+
+    /// Return the raw record.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.record
+    }
+}
+
+impl DB {
+    /// Read and parse the `LegacyTerrain` record of a chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(LegacyTerrain))` - The decoded record
+    /// * `Ok(None)` - If the chunk has no `LegacyTerrain` record, e.g. because it was saved by 1.0 or later
+    /// * `Err(String)` - If the read failed or the record is malformed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// if let Some(terrain) = db.legacy_terrain(0, 0, Dimension::Overworld, &ReadOptions::new()).unwrap() {
+    ///     println!("Biome at (0, 0): {}", terrain.biome_at(0, 0));
+    /// }
+    /// ```
+    pub fn legacy_terrain(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Option<LegacyTerrain>, String> {
+        let key = ChunkKey::new(x, z, dim, RecordTag::LegacyTerrain);
+        let Some(record) = self.get(key, options)? else {
+            return Ok(None);
+        };
+        LegacyTerrain::parse(&record).map(Some).map_err(|e| {
+            format!(
+                "Invalid LegacyTerrain record in chunk ({}, {}) of the {}: {}",
+                x, z, dim, e
+            )
+        })
+    }
+}
@@ -0,0 +1,119 @@
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+
+/// Key prefix of map item records, followed by the decimal map id.
+const MAP_PREFIX: &str = "map_";
+
+/// Return the key of the map with the given id.
+pub(crate) fn map_key(id: i64) -> String {
+    format!("{}{}", MAP_PREFIX, id)
+}
+
+/// Parse the id out of a map key.
+///
+/// Only keys the game could have written are accepted: the id must be a
+/// decimal `i64` without a sign prefix or leading zeros, so that every
+/// accepted key round-trips through [`map_key`].
+pub(crate) fn parse_map_key(key: &[u8]) -> Option<i64> {
+    let digits = std::str::from_utf8(key).ok()?.strip_prefix(MAP_PREFIX)?;
+    let id: i64 = digits.parse().ok()?;
+    (id.to_string() == digits).then_some(id)
+}
+
+impl DB {
+    /// Iterate over the ids of every map item stored in the world.
+    ///
+    /// Keys that start with `map_` but do not end in a valid id are skipped.
+    /// Ids come out in key order, which compares them as strings, not as
+    /// numbers. Read errors end the iteration early.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let mut ids: Vec<i64> = db.map_ids(&ReadOptions::new()).collect();
+    /// ids.sort();
+    /// ```
+    pub fn map_ids(&self, options: &ReadOptions) -> impl Iterator<Item = i64> + '_ {
+        self.prefix_iter(MAP_PREFIX.as_bytes(), options)
+            .filter_map(|(key, _)| parse_map_key(&key))
+    }
+
+    /// Read the record of a map item.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The map id, as stored in the map item's NBT
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Vec<u8>))` - The map's NBT record
+    /// * `Ok(None)` - If no map with that id is stored
+    /// * `Err(String)` - If an error occurred during the read operation
+    pub fn get_map(&self, id: i64, options: &ReadOptions) -> Result<Option<Vec<u8>>, String> {
+        self.get(map_key(id), options)
+    }
+
+    /// Write the record of a map item.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The map id
+    /// * `data` - The map's NBT record
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub fn put_map(
+        &self,
+        id: i64,
+        data: impl AsRef<[u8]>,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        self.put(map_key(id), data, options)
+    }
+
+    /// Return the largest map id in use.
+    ///
+    /// Ids are compared numerically, so this scans every map key. Tools
+    /// that add maps can use the next id up, provided nothing else writes
+    /// maps to the world in the meantime.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(i64))` - The largest id
+    /// * `Ok(None)` - If the world has no maps
+    /// * `Err(String)` - If an error occurred while reading the database
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions, WriteOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let next_id = db.max_map_id(&ReadOptions::new()).unwrap().map_or(1, |id| id + 1);
+    /// db.put_map(next_id, b"...", &WriteOptions::new()).unwrap();
+    /// ```
+    pub fn max_map_id(&self, options: &ReadOptions) -> Result<Option<i64>, String> {
+        let mut iter = self.prefix_iter(MAP_PREFIX.as_bytes(), options);
+        let max = iter
+            .by_ref()
+            .filter_map(|(key, _)| parse_map_key(&key))
+            .max();
+        iter.status()?;
+        Ok(max)
+    }
+}
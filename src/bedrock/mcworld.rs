@@ -0,0 +1,220 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::WorldDir;
+use crate::options::Options;
+use crate::util::unique_temp_dir;
+
+/// How every file of a saved `.mcworld` is stored. The game imports
+/// deflated and stored entries; deflate is what it writes itself.
+pub const MCWORLD_COMPRESSION: CompressionMethod = CompressionMethod::Deflated;
+
+/// Files next to `db` that [`WorldDir::save_mcworld`] includes when present,
+/// in archive order.
+const WORLD_FILES: [&str; 2] = ["levelname.txt", "world_icon.jpeg"];
+
+/// A directory an archive was extracted to, removed when dropped.
+pub(super) struct ExtractedDir(PathBuf);
+
+impl Drop for ExtractedDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+impl WorldDir {
+    /// Open a world exported as a `.mcworld` archive.
+    ///
+    /// A `.mcworld` file is a zip of a world directory. It is extracted to a
+    /// private temporary directory, which is removed again when the
+    /// `WorldDir` is dropped; clones of [`WorldDir::db`] must be dropped
+    /// before that. Changes are only made to the extracted copy, never to
+    /// the archive.
+    ///
+    /// Archives holding the world at their root are accepted, as are ones
+    /// with the whole world inside a single top-level folder.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The `.mcworld` file
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(WorldDir)` - The opened world
+    /// * `Err(String)` - If the archive cannot be read, has no `db/CURRENT`, or the world cannot be opened
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::bedrock::WorldDir;
+    ///
+    /// let world = WorldDir::open_mcworld("Survival.mcworld").unwrap();
+    /// println!("{:?}", world.name());
+    /// ```
+    pub fn open_mcworld(path: impl AsRef<Path>) -> Result<WorldDir, String> {
+        let dir = unique_temp_dir("bleveldb-mcworld")
+            .map_err(|e| format!("Failed to create extraction directory: {}", e))?;
+        let extracted = ExtractedDir(dir);
+        let root = extract_mcworld(path.as_ref(), &extracted.0)?;
+        let mut world = WorldDir::open_with(root, &Options::bedrock())?;
+        world.extracted = Some(extracted);
+        Ok(world)
+    }
+
+    /// Open a `.mcworld` archive, extracting it to `dir`.
+    ///
+    /// Unlike [`WorldDir::open_mcworld`], the extracted world is kept after
+    /// the `WorldDir` is dropped. `dir` is created if needed and must be
+    /// empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The `.mcworld` file
+    /// * `dir` - The directory to extract the archive to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(WorldDir)` - The opened world, whose [`WorldDir::path`] lies inside `dir`
+    /// * `Err(String)` - If `dir` is not empty, the archive cannot be read or has no `db/CURRENT`, or the world cannot be opened
+    pub fn open_mcworld_in(
+        path: impl AsRef<Path>,
+        dir: impl AsRef<Path>,
+    ) -> Result<WorldDir, String> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let is_empty = fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+            .next()
+            .is_none();
+        if !is_empty {
+            return Err(format!("{} is not empty", dir.display()));
+        }
+        let root = extract_mcworld(path.as_ref(), dir)?;
+        WorldDir::open_with(root, &Options::bedrock())
+    }
+
+    /// Save the world as a `.mcworld` archive the game can import.
+    ///
+    /// The database is copied with [`DB::checkpoint`](crate::DB::checkpoint),
+    /// so it can stay open and in use. The archive holds the world at its
+    /// root, in this order: `level.dat`, `levelname.txt` and
+    /// `world_icon.jpeg` when present, then `db/` with the database files
+    /// sorted by name. Every file is compressed with
+    /// [`MCWORLD_COMPRESSION`]. `level.dat` is written from
+    /// [`WorldDir::level_dat`], so edits not yet saved with
+    /// [`WorldDir::save_level_dat`] are included.
+    ///
+    /// The archive is written next to `dest` and renamed over it once
+    /// complete, so a failed save leaves an existing file at `dest` intact.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest` - The `.mcworld` file to write
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the archive was written
+    /// * `Err(String)` - If the database could not be checkpointed or the archive could not be written
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use bleveldb::bedrock::WorldDir;
+    ///
+    /// let world = WorldDir::open("minecraftWorlds/abc123=").unwrap();
+    /// world.save_mcworld(Path::new("Survival.mcworld")).unwrap();
+    /// ```
+    pub fn save_mcworld(&self, dest: &Path) -> Result<(), String> {
+        let staging = ExtractedDir(
+            unique_temp_dir("bleveldb-mcworld")
+                .map_err(|e| format!("Failed to create staging directory: {}", e))?,
+        );
+        let db_copy = staging.0.join("db");
+        self.db().checkpoint(&db_copy)?;
+
+        let mut temp = dest.as_os_str().to_owned();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+        self.write_mcworld(&db_copy, &temp)
+            .and_then(|()| fs::rename(&temp, dest))
+            .map_err(|e| {
+                let _ = fs::remove_file(&temp);
+                format!("Failed to write {}: {}", dest.display(), e)
+            })
+    }
+
+    /// Zip the world, taking the database files from `db_dir`, into `path`.
+    fn write_mcworld(&self, db_dir: &Path, path: &Path) -> io::Result<()> {
+        let options = SimpleFileOptions::default().compression_method(MCWORLD_COMPRESSION);
+        let mut zip = ZipWriter::new(BufWriter::new(File::create(path)?));
+
+        zip.start_file("level.dat", options)?;
+        zip.write_all(&self.level_dat_bytes())?;
+        for name in WORLD_FILES {
+            match fs::read(self.path().join(name)) {
+                Ok(data) => {
+                    zip.start_file(name, options)?;
+                    zip.write_all(&data)?;
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        zip.add_directory("db/", options)?;
+        let mut files = Vec::new();
+        for entry in fs::read_dir(db_dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                files.push(entry.file_name());
+            }
+        }
+        files.sort();
+        for name in files {
+            zip.start_file(format!("db/{}", name.to_string_lossy()), options)?;
+            io::copy(&mut File::open(db_dir.join(&name))?, &mut zip)?;
+        }
+
+        zip.finish()?
+            .into_inner()
+            .map_err(|e| e.into_error())?
+            .sync_all()
+    }
+}
+
+/// Extract a `.mcworld` archive to `dir` and return the world directory
+/// inside it.
+fn extract_mcworld(path: &Path, dir: &Path) -> Result<PathBuf, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut archive = ZipArchive::new(BufReader::new(file))
+        .map_err(|e| format!("{} is not a zip archive: {}", path.display(), e))?;
+    let names: Vec<PathBuf> = (0..archive.len())
+        .filter_map(|i| archive.by_index_raw(i).ok()?.enclosed_name())
+        .collect();
+    let root = world_root(&names).ok_or_else(|| format!("{} has no db/CURRENT", path.display()))?;
+    archive
+        .extract(dir)
+        .map_err(|e| format!("Failed to extract {}: {}", path.display(), e))?;
+    Ok(dir.join(root))
+}
+
+/// Find the folder holding `db/CURRENT` among the entries of an archive:
+/// its root, or the single folder everything is in.
+fn world_root(names: &[PathBuf]) -> Option<PathBuf> {
+    let current = Path::new("db").join("CURRENT");
+    if names.contains(&current) {
+        return Some(PathBuf::new());
+    }
+    let top = names.first()?.components().next()?;
+    let single_folder = names
+        .iter()
+        .all(|name| name.components().next() == Some(top));
+    let root = PathBuf::from(top.as_os_str());
+    (single_folder && names.iter().any(|name| *name == root.join(&current))).then_some(root)
+}
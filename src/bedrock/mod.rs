@@ -0,0 +1,91 @@
+//! Helpers for the key layout Minecraft Bedrock uses in its world database.
+//!
+//! Most of a Bedrock world lives in per-chunk records whose keys are built
+//! from the chunk coordinates, the dimension and a record tag; see
+//! [`ChunkKey`]. The rest is stored under plain string keys, the fixed ones
+//! of which are listed in [`keys`].
+//!
+//! The `nbt` feature adds the `nbt` module, a codec for the little-endian NBT
+//! most records are made of, and the `subchunk` feature adds `SubChunk` to
+//! decode block storage on top of it. With `nbt`, `world_records` holds
+//! typed forms of the small world-wide records, and the `mcworld`
+//! feature lets [`WorldDir`] open and save `.mcworld` archives.
+
+mod actor_remap;
+mod actors;
+mod biome_data;
+mod biome_histogram;
+#[cfg(feature = "nbt")]
+mod block_entities;
+mod chunk_blob;
+mod chunk_key;
+mod chunks;
+mod compression_report;
+mod db_dir;
+mod dimension;
+#[cfg(feature = "nbt")]
+mod entities;
+mod finalized_state;
+mod key_description;
+pub mod keys;
+mod legacy_terrain;
+mod maps;
+#[cfg(feature = "mcworld")]
+mod mcworld;
+#[cfg(feature = "nbt")]
+pub mod nbt;
+mod orphans;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod players;
+mod record;
+#[cfg(feature = "nbt")]
+mod scoreboard;
+mod structures;
+#[cfg(feature = "subchunk")]
+mod subchunk;
+#[cfg(feature = "nbt")]
+mod ticks;
+mod villages;
+mod wipe;
+mod world;
+mod world_bounds;
+#[cfg(feature = "nbt")]
+mod world_dir;
+mod world_format;
+#[cfg(feature = "nbt")]
+pub mod world_records;
+
+pub use actor_remap::ActorIdRemapper;
+pub use actors::{Actor, ActorId};
+pub use biome_data::{BiomeDataError, BiomeSection, Data2D, Data3D};
+pub use biome_histogram::{BiomeHistogram, biome_name};
+pub use chunk_blob::ChunkBlob;
+pub use chunk_key::{ChunkKey, KeyParseError, RecordTag};
+pub use chunks::{CopyOptions, CopyReport, DeletedChunkReport, copy_chunk, copy_chunk_with};
+pub use compression_report::{COMPRESSIONS, CompressionClass, CompressionReport};
+pub use db_dir::{DbDirIssue, validate_db_dir};
+pub use dimension::Dimension;
+#[cfg(feature = "nbt")]
+pub use entities::{Entity, EntityStorage};
+pub use finalized_state::FinalizedState;
+pub use key_description::{KeyDescription, describe_key};
+pub use keys::KnownKey;
+pub use legacy_terrain::{LegacyTerrain, LegacyTerrainError};
+#[cfg(feature = "mcworld")]
+pub use mcworld::MCWORLD_COMPRESSION;
+pub use orphans::OrphanReport;
+#[cfg(feature = "rayon")]
+pub use parallel::ChunkRecords;
+pub use players::PlayerKey;
+pub use record::{DecodeError, RecordDecode, RecordEncode, RecordIndex};
+#[cfg(feature = "nbt")]
+pub use scoreboard::{DisplaySlot, Objective, Score, ScoreOwner, Scoreboard, ScoreboardEntry};
+#[cfg(feature = "subchunk")]
+pub use subchunk::{BlockStorage, SubChunk, SubChunkError};
+pub use villages::{VillageKey, VillageRecord, VillageRecords};
+pub use wipe::WipeReport;
+pub use world_bounds::ChunkBounds;
+#[cfg(feature = "nbt")]
+pub use world_dir::WorldDir;
+pub use world_format::WorldFormat;
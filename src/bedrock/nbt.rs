@@ -0,0 +1,616 @@
+//! A small codec for the little-endian NBT Bedrock stores in its records.
+//!
+//! Block states, block entities, actors, level.dat and most world-wide
+//! records are NBT compounds. Unlike Java edition NBT, every number is
+//! little-endian and string lengths are unsigned 16-bit.
+//!
+//! Root tags are decoded without their name, which Bedrock leaves empty,
+//! and encoded with an empty name. Compounds keep their entries in stored
+//! order and lists keep their element type, so decoding and re-encoding a
+//! record reproduces it byte for byte.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use bleveldb::bedrock::nbt::{self, Value};
+//!
+//! let state = Value::Compound(vec![
+//!     ("name".to_string(), Value::String("minecraft:stone".to_string())),
+//!     ("version".to_string(), Value::Int(18_100_737)),
+//! ]);
+//! let bytes = nbt::encode(&state);
+//! assert_eq!(nbt::decode(&bytes).unwrap(), state);
+//! ```
+
+use std::fmt;
+
+/// Deepest nesting of lists and compounds accepted by the decoder.
+const MAX_DEPTH: usize = 512;
+
+const TAG_END: u8 = 0;
+const TAG_BYTE: u8 = 1;
+const TAG_SHORT: u8 = 2;
+const TAG_INT: u8 = 3;
+const TAG_LONG: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_DOUBLE: u8 = 6;
+const TAG_BYTE_ARRAY: u8 = 7;
+const TAG_STRING: u8 = 8;
+const TAG_LIST: u8 = 9;
+const TAG_COMPOUND: u8 = 10;
+const TAG_INT_ARRAY: u8 = 11;
+const TAG_LONG_ARRAY: u8 = 12;
+
+/// An NBT value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A signed byte (tag 1); also used for booleans.
+    Byte(i8),
+    /// A 16-bit integer (tag 2).
+    Short(i16),
+    /// A 32-bit integer (tag 3).
+    Int(i32),
+    /// A 64-bit integer (tag 4).
+    Long(i64),
+    /// A 32-bit float (tag 5).
+    Float(f32),
+    /// A 64-bit float (tag 6).
+    Double(f64),
+    /// A byte array (tag 7).
+    ByteArray(Vec<u8>),
+    /// A string (tag 8).
+    String(String),
+    /// A list of values of one type (tag 9).
+    List {
+        /// Tag id of the elements, kept so that empty lists round-trip.
+        element_type: u8,
+        /// The elements; all must have tag `element_type`.
+        items: Vec<Value>,
+    },
+    /// Named values in stored order (tag 10).
+    Compound(Vec<(String, Value)>),
+    /// A 32-bit integer array (tag 11).
+    IntArray(Vec<i32>),
+    /// A 64-bit integer array (tag 12).
+    LongArray(Vec<i64>),
+}
+
+impl Value {
+    /// Create a list, taking the element type from the first item.
+    ///
+    /// An empty list gets the `End` element type, as the game writes it.
+    pub fn list(items: Vec<Value>) -> Value {
+        Value::List {
+            element_type: items.first().map_or(TAG_END, Value::tag_id),
+            items,
+        }
+    }
+
+    /// Return the NBT tag id of the value.
+    pub fn tag_id(&self) -> u8 {
+        match self {
+            Value::Byte(_) => TAG_BYTE,
+            Value::Short(_) => TAG_SHORT,
+            Value::Int(_) => TAG_INT,
+            Value::Long(_) => TAG_LONG,
+            Value::Float(_) => TAG_FLOAT,
+            Value::Double(_) => TAG_DOUBLE,
+            Value::ByteArray(_) => TAG_BYTE_ARRAY,
+            Value::String(_) => TAG_STRING,
+            Value::List { .. } => TAG_LIST,
+            Value::Compound(_) => TAG_COMPOUND,
+            Value::IntArray(_) => TAG_INT_ARRAY,
+            Value::LongArray(_) => TAG_LONG_ARRAY,
+        }
+    }
+
+    /// Return the entry called `name` of a compound.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.as_compound()?
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Return the entry called `name` of a compound for modification.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Value> {
+        match self {
+            Value::Compound(entries) => entries
+                .iter_mut()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Set the entry called `name` of a compound, keeping its position if it exists.
+    ///
+    /// # Returns
+    ///
+    /// The previous value, or `None` if the entry is new.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not a compound.
+    pub fn insert(&mut self, name: impl Into<String>, value: Value) -> Option<Value> {
+        let Value::Compound(entries) = self else {
+            panic!("insert called on a {} tag", self.tag_id());
+        };
+        let name = name.into();
+        match entries.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, slot)) => Some(std::mem::replace(slot, value)),
+            None => {
+                entries.push((name, value));
+                None
+            }
+        }
+    }
+
+    /// Return an integer value of any width.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Value::Byte(v) => Some(v.into()),
+            Value::Short(v) => Some(v.into()),
+            Value::Int(v) => Some(v.into()),
+            Value::Long(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Return a floating point value of either width.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Float(v) => Some(v.into()),
+            Value::Double(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Return the contents of a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Return the items of a list.
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match self {
+            Value::List { items, .. } => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Return the entries of a compound.
+    pub fn as_compound(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Compound(entries) => Some(entries),
+            _ => None,
+        }
+    }
+}
+
+/// Why NBT could not be decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NbtError {
+    /// The data ended early; `offset` is where more data was expected.
+    UnexpectedEnd {
+        /// Byte offset at which the data ran out.
+        offset: usize,
+    },
+    /// A tag id is not a known NBT tag, or `End` where a value was expected.
+    InvalidTag {
+        /// The tag id.
+        tag: u8,
+        /// Byte offset of the tag id.
+        offset: usize,
+    },
+    /// A string is not valid UTF-8.
+    InvalidUtf8 {
+        /// Byte offset of the string's length prefix.
+        offset: usize,
+    },
+    /// A list or array has a negative length.
+    NegativeLength {
+        /// Byte offset of the length.
+        offset: usize,
+    },
+    /// Lists and compounds are nested more than 512 levels deep.
+    TooDeep,
+    /// Data is left over after the root tag.
+    TrailingBytes {
+        /// Byte offset of the first unread byte.
+        offset: usize,
+    },
+}
+
+impl fmt::Display for NbtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NbtError::UnexpectedEnd { offset } => {
+                write!(f, "NBT ends unexpectedly at byte {}", offset)
+            }
+            NbtError::InvalidTag { tag, offset } => {
+                write!(f, "invalid NBT tag {} at byte {}", tag, offset)
+            }
+            NbtError::InvalidUtf8 { offset } => {
+                write!(f, "NBT string at byte {} is not valid UTF-8", offset)
+            }
+            NbtError::NegativeLength { offset } => {
+                write!(f, "negative NBT length at byte {}", offset)
+            }
+            NbtError::TooDeep => write!(f, "NBT is nested too deeply"),
+            NbtError::TrailingBytes { offset } => {
+                write!(f, "unexpected data after the NBT root at byte {}", offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NbtError {}
+
+/// Decode a record holding exactly one root tag.
+///
+/// # Returns
+///
+/// * `Ok(Value)` - The root value
+/// * `Err(NbtError)` - If the data is malformed or has bytes after the root
+pub fn decode(data: &[u8]) -> Result<Value, NbtError> {
+    let (value, len) = decode_prefix(data)?;
+    if len != data.len() {
+        return Err(NbtError::TrailingBytes { offset: len });
+    }
+    Ok(value)
+}
+
+/// Decode the root tag at the start of `data`.
+///
+/// # Returns
+///
+/// * `Ok((Value, usize))` - The root value and the number of bytes it took up
+/// * `Err(NbtError)` - If the data is malformed
+pub fn decode_prefix(data: &[u8]) -> Result<(Value, usize), NbtError> {
+    let mut reader = Reader { data, offset: 0 };
+    let value = reader.root()?;
+    Ok((value, reader.offset))
+}
+
+/// Decode a record made of root tags written back to back, like the
+/// block entities of a chunk.
+///
+/// # Returns
+///
+/// * `Ok(Vec<Value>)` - The root values in stored order; empty for empty data
+/// * `Err(NbtError)` - If any of them is malformed
+pub fn decode_all(data: &[u8]) -> Result<Vec<Value>, NbtError> {
+    let mut reader = Reader { data, offset: 0 };
+    let mut values = Vec::new();
+    while reader.offset < data.len() {
+        values.push(reader.root()?);
+    }
+    Ok(values)
+}
+
+/// The entries of a compound, taken out one known field at a time.
+pub(crate) struct Fields<'a> {
+    what: &'a str,
+    pub(crate) entries: Vec<(String, Value)>,
+}
+
+impl<'a> Fields<'a> {
+    pub(crate) fn new(what: &'a str, value: Value) -> Result<Self, String> {
+        match value {
+            Value::Compound(entries) => Ok(Fields { what, entries }),
+            value => Err(format!(
+                "{} is a tag {} instead of a compound",
+                what,
+                value.tag_id()
+            )),
+        }
+    }
+
+    pub(crate) fn take(&mut self, name: &str) -> Option<Value> {
+        let index = self.entries.iter().position(|(key, _)| key == name)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    pub(crate) fn missing(&self, name: &str) -> String {
+        format!("{} has no valid {}", self.what, name)
+    }
+
+    pub(crate) fn long(&mut self, name: &str) -> Result<i64, String> {
+        match self.take(name) {
+            Some(Value::Long(v)) => Ok(v),
+            _ => Err(self.missing(name)),
+        }
+    }
+
+    pub(crate) fn int(&mut self, name: &str) -> Result<i32, String> {
+        match self.take(name) {
+            Some(Value::Int(v)) => Ok(v),
+            _ => Err(self.missing(name)),
+        }
+    }
+
+    pub(crate) fn float(&mut self, name: &str) -> Result<f32, String> {
+        match self.take(name) {
+            Some(Value::Float(v)) => Ok(v),
+            _ => Err(self.missing(name)),
+        }
+    }
+
+    pub(crate) fn byte(&mut self, name: &str) -> Result<i8, String> {
+        match self.take(name) {
+            Some(Value::Byte(v)) => Ok(v),
+            _ => Err(self.missing(name)),
+        }
+    }
+
+    /// Take a byte used as a boolean; a missing entry is `None`.
+    pub(crate) fn flag(&mut self, name: &str) -> Result<Option<bool>, String> {
+        match self.take(name) {
+            Some(Value::Byte(v)) => Ok(Some(v != 0)),
+            None => Ok(None),
+            Some(_) => Err(self.missing(name)),
+        }
+    }
+
+    pub(crate) fn string(&mut self, name: &str) -> Result<String, String> {
+        match self.take(name) {
+            Some(Value::String(v)) => Ok(v),
+            _ => Err(self.missing(name)),
+        }
+    }
+
+    /// Take a list of compounds; a missing list is empty.
+    pub(crate) fn list<T>(
+        &mut self,
+        name: &str,
+        parse: impl Fn(Value) -> Result<T, String>,
+    ) -> Result<Vec<T>, String> {
+        match self.take(name) {
+            Some(Value::List { items, .. }) => items.into_iter().map(parse).collect(),
+            None => Ok(Vec::new()),
+            Some(_) => Err(self.missing(name)),
+        }
+    }
+}
+
+/// Build a compound of the known fields followed by the unknown ones.
+pub(crate) fn compound(known: Vec<(&str, Value)>, extra: &[(String, Value)]) -> Value {
+    Value::Compound(
+        known
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .chain(extra.iter().cloned())
+            .collect(),
+    )
+}
+
+/// Build a list of compounds, keeping the compound element type when empty.
+pub(crate) fn compound_list(items: Vec<Value>) -> Value {
+    Value::List {
+        element_type: TAG_COMPOUND,
+        items,
+    }
+}
+
+/// Encode `value` as a root tag with an empty name.
+///
+/// # Panics
+///
+/// Panics if a list holds an item whose type differs from the list's element
+/// type, or a string, list or array is too long for NBT.
+pub fn encode(value: &Value) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_into(value, &mut out);
+    out
+}
+
+/// Append `value` to `out` as a root tag with an empty name.
+///
+/// See [`encode`] for the panics.
+pub fn encode_into(value: &Value, out: &mut Vec<u8>) {
+    out.push(value.tag_id());
+    write_string("", out);
+    write_payload(value, out);
+}
+
+fn write_string(s: &str, out: &mut Vec<u8>) {
+    let len = u16::try_from(s.len()).expect("NBT string is longer than 65535 bytes");
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn write_len(len: usize, out: &mut Vec<u8>) {
+    let len = i32::try_from(len).expect("NBT list or array is too long");
+    out.extend_from_slice(&len.to_le_bytes());
+}
+
+fn write_payload(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Byte(v) => out.push(*v as u8),
+        Value::Short(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::Int(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::Long(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::Float(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::Double(v) => out.extend_from_slice(&v.to_le_bytes()),
+        Value::ByteArray(bytes) => {
+            write_len(bytes.len(), out);
+            out.extend_from_slice(bytes);
+        }
+        Value::String(s) => write_string(s, out),
+        Value::List {
+            element_type,
+            items,
+        } => {
+            out.push(*element_type);
+            write_len(items.len(), out);
+            for item in items {
+                assert_eq!(
+                    item.tag_id(),
+                    *element_type,
+                    "NBT list item does not match the list's element type"
+                );
+                write_payload(item, out);
+            }
+        }
+        Value::Compound(entries) => {
+            for (name, value) in entries {
+                out.push(value.tag_id());
+                write_string(name, out);
+                write_payload(value, out);
+            }
+            out.push(TAG_END);
+        }
+        Value::IntArray(values) => {
+            write_len(values.len(), out);
+            values
+                .iter()
+                .for_each(|v| out.extend_from_slice(&v.to_le_bytes()));
+        }
+        Value::LongArray(values) => {
+            write_len(values.len(), out);
+            values
+                .iter()
+                .for_each(|v| out.extend_from_slice(&v.to_le_bytes()));
+        }
+    }
+}
+
+/// Cursor over NBT data that tracks its offset for error reporting.
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], NbtError> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset + N)
+            .ok_or(NbtError::UnexpectedEnd {
+                offset: self.offset,
+            })?;
+        self.offset += N;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&[u8], NbtError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or(NbtError::UnexpectedEnd {
+                offset: self.data.len(),
+            })?;
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn len(&mut self) -> Result<usize, NbtError> {
+        let offset = self.offset;
+        let len = i32::from_le_bytes(self.take()?);
+        usize::try_from(len).map_err(|_| NbtError::NegativeLength { offset })
+    }
+
+    fn string(&mut self) -> Result<String, NbtError> {
+        let offset = self.offset;
+        let len = u16::from_le_bytes(self.take()?) as usize;
+        let bytes = self.bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| NbtError::InvalidUtf8 { offset })
+    }
+
+    fn root(&mut self) -> Result<Value, NbtError> {
+        let offset = self.offset;
+        let [tag] = self.take()?;
+        if tag == TAG_END {
+            return Err(NbtError::InvalidTag { tag, offset });
+        }
+        self.string()?;
+        self.payload(tag, offset, 0)
+    }
+
+    fn payload(&mut self, tag: u8, tag_offset: usize, depth: usize) -> Result<Value, NbtError> {
+        Ok(match tag {
+            TAG_BYTE => Value::Byte(i8::from_le_bytes(self.take()?)),
+            TAG_SHORT => Value::Short(i16::from_le_bytes(self.take()?)),
+            TAG_INT => Value::Int(i32::from_le_bytes(self.take()?)),
+            TAG_LONG => Value::Long(i64::from_le_bytes(self.take()?)),
+            TAG_FLOAT => Value::Float(f32::from_le_bytes(self.take()?)),
+            TAG_DOUBLE => Value::Double(f64::from_le_bytes(self.take()?)),
+            TAG_BYTE_ARRAY => {
+                let len = self.len()?;
+                Value::ByteArray(self.bytes(len)?.to_vec())
+            }
+            TAG_STRING => Value::String(self.string()?),
+            TAG_LIST => {
+                if depth >= MAX_DEPTH {
+                    return Err(NbtError::TooDeep);
+                }
+                let type_offset = self.offset;
+                let [element_type] = self.take()?;
+                let len = self.len()?;
+                if element_type == TAG_END && len > 0 || element_type > TAG_LONG_ARRAY {
+                    return Err(NbtError::InvalidTag {
+                        tag: element_type,
+                        offset: type_offset,
+                    });
+                }
+                // Every element takes at least one byte, which bounds the allocation
+                let mut items = Vec::with_capacity(len.min(self.data.len() - self.offset));
+                for _ in 0..len {
+                    items.push(self.payload(element_type, type_offset, depth + 1)?);
+                }
+                Value::List {
+                    element_type,
+                    items,
+                }
+            }
+            TAG_COMPOUND => {
+                if depth >= MAX_DEPTH {
+                    return Err(NbtError::TooDeep);
+                }
+                let mut entries = Vec::new();
+                loop {
+                    let offset = self.offset;
+                    let [tag] = self.take()?;
+                    if tag == TAG_END {
+                        break;
+                    }
+                    let name = self.string()?;
+                    entries.push((name, self.payload(tag, offset, depth + 1)?));
+                }
+                Value::Compound(entries)
+            }
+            TAG_INT_ARRAY => {
+                let len = self.len()?;
+                let bytes = self.bytes(len.saturating_mul(4))?;
+                Value::IntArray(
+                    bytes
+                        .chunks_exact(4)
+                        .map(|b| i32::from_le_bytes(b.try_into().unwrap()))
+                        .collect(),
+                )
+            }
+            TAG_LONG_ARRAY => {
+                let len = self.len()?;
+                let bytes = self.bytes(len.saturating_mul(8))?;
+                Value::LongArray(
+                    bytes
+                        .chunks_exact(8)
+                        .map(|b| i64::from_le_bytes(b.try_into().unwrap()))
+                        .collect(),
+                )
+            }
+            tag => {
+                return Err(NbtError::InvalidTag {
+                    tag,
+                    offset: tag_offset,
+                });
+            }
+        })
+    }
+}
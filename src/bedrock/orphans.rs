@@ -0,0 +1,234 @@
+use std::collections::HashSet;
+
+use super::actors::{ACTOR_PREFIX, DIGEST_PREFIX, parse_digest};
+use super::maps::{map_key, parse_map_key};
+use crate::bedrock::{ActorId, ChunkKey, PlayerKey, RecordTag};
+use crate::db::{DB, DEFAULT_BATCH_BYTES};
+use crate::options::{ReadOptions, WriteOptions};
+use crate::write_batch::WriteBatch;
+
+/// Records that nothing in the world refers to any more, as found by
+/// [`DB::find_orphans`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrphanReport {
+    /// Actors that no chunk digest lists.
+    pub actors: Vec<ActorId>,
+    /// Maps that no item, and no other map as its parent, refers to.
+    /// Only filled in with the `nbt` feature.
+    pub maps: Vec<i64>,
+    /// Subchunk records of chunks without a `Version` or `LegacyVersion`
+    /// record.
+    pub subchunks: Vec<ChunkKey>,
+    /// Number of records that could hold map references but failed to
+    /// decode. When it is not zero, `maps` is left empty, as any map could
+    /// be referenced from them.
+    pub unreadable: usize,
+}
+
+impl OrphanReport {
+    /// Return the number of orphaned records.
+    pub fn total(&self) -> usize {
+        self.actors.len() + self.maps.len() + self.subchunks.len()
+    }
+
+    /// Return whether no orphans were found.
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+}
+
+/// Map ids seen while scanning, and the ids records refer to.
+#[derive(Default)]
+struct MapIndex {
+    stored: Vec<i64>,
+    referenced: HashSet<i64>,
+    unreadable: usize,
+}
+
+impl MapIndex {
+    /// Collect the map references in a record of one or more NBT compounds.
+    #[cfg(feature = "nbt")]
+    fn scan(&mut self, value: &[u8]) {
+        use super::nbt::{self, Value};
+
+        fn walk(value: &Value, referenced: &mut HashSet<i64>) {
+            match value {
+                Value::Compound(entries) => {
+                    for (name, value) in entries {
+                        if let ("map_uuid" | "parentMapId", Value::Long(id)) =
+                            (name.as_str(), value)
+                        {
+                            referenced.insert(*id);
+                        }
+                        walk(value, referenced);
+                    }
+                }
+                Value::List { items, .. } => {
+                    for item in items {
+                        walk(item, referenced);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match nbt::decode_all(value) {
+            Ok(values) => values
+                .iter()
+                .for_each(|value| walk(value, &mut self.referenced)),
+            Err(_) => self.unreadable += 1,
+        }
+    }
+
+    #[cfg(not(feature = "nbt"))]
+    fn scan(&mut self, _value: &[u8]) {}
+
+    fn orphans(self) -> (Vec<i64>, usize) {
+        if !cfg!(feature = "nbt") || self.unreadable > 0 {
+            return (Vec::new(), self.unreadable);
+        }
+        let orphans = self
+            .stored
+            .into_iter()
+            .filter(|id| !self.referenced.contains(id))
+            .collect();
+        (orphans, 0)
+    }
+}
+
+impl DB {
+    /// Find records that nothing in the world refers to any more.
+    ///
+    /// Crashes and buggy tools leave behind `actorprefix` records that no
+    /// `digp` digest lists, subchunks of chunks that lost their version
+    /// record, and `map_` records of maps no item shows. The game never
+    /// loads these, so they only take up space.
+    ///
+    /// One pass over a snapshot collects every digest and chunk version into
+    /// memory and compares them with the stored actors and subchunks. With
+    /// the `nbt` feature the same pass reads players, actors, entities,
+    /// block entities and maps for `map_uuid` and `parentMapId` references.
+    /// Nothing is deleted; pass the report to [`DB::remove_orphans`] after
+    /// reviewing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(OrphanReport)` - The orphaned records
+    /// * `Err(String)` - If a digest is malformed or reading the database failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions, WriteOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let report = db.find_orphans(&ReadOptions::new()).unwrap();
+    /// println!("{} orphaned actors", report.actors.len());
+    /// if !report.is_empty() {
+    ///     db.remove_orphans(&report, &WriteOptions::new()).unwrap();
+    /// }
+    /// ```
+    pub fn find_orphans(&self, options: &ReadOptions) -> Result<OrphanReport, String> {
+        let (_snapshot, read_options) = self.consistent_read_options(options);
+        read_options.fill_cache(false);
+
+        let mut listed = HashSet::new();
+        let mut actors = Vec::new();
+        let mut versions = HashSet::new();
+        let mut subchunks = Vec::new();
+        let mut maps = MapIndex::default();
+
+        let mut iter = self.iter(&read_options);
+        iter.seek_to_first();
+        while let (Some(key), Some(value)) = (iter.key_ref(), iter.value_ref()) {
+            // String keys first: some, like `map_12345`, also parse as chunk keys
+            if key.starts_with(DIGEST_PREFIX) {
+                listed.extend(parse_digest(value)?);
+            } else if let Some(id) = key.strip_prefix(ACTOR_PREFIX) {
+                if let Ok(id) = <[u8; 8]>::try_from(id) {
+                    actors.push(ActorId(id));
+                    maps.scan(value);
+                }
+            } else if let Some(id) = parse_map_key(key) {
+                maps.stored.push(id);
+                maps.scan(value);
+            } else if PlayerKey::parse(key).is_some() {
+                maps.scan(value);
+            } else if let Ok(chunk_key) = ChunkKey::parse(key) {
+                let chunk = (chunk_key.x(), chunk_key.z(), chunk_key.dimension().id());
+                match chunk_key.tag() {
+                    RecordTag::Version | RecordTag::LegacyVersion => {
+                        versions.insert(chunk);
+                    }
+                    RecordTag::SubChunkPrefix => subchunks.push(chunk_key),
+                    RecordTag::BlockEntity | RecordTag::Entity => maps.scan(value),
+                    _ => {}
+                }
+            }
+            iter.next_native();
+        }
+        iter.status()?;
+
+        actors.retain(|id| !listed.contains(id));
+        subchunks.retain(|key| !versions.contains(&(key.x(), key.z(), key.dimension().id())));
+        let (maps, unreadable) = maps.orphans();
+        Ok(OrphanReport {
+            actors,
+            maps,
+            subchunks,
+            unreadable,
+        })
+    }
+
+    /// Delete the records listed in an [`OrphanReport`].
+    ///
+    /// Deletes are committed in batches of at most [`DEFAULT_BATCH_BYTES`]
+    /// of keys, so the removal as a whole is not atomic. The records are not
+    /// checked again: a report is only valid until the world is next
+    /// modified, so it should not be kept around while the game runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `report` - The records to delete, as returned by [`DB::find_orphans`]
+    /// * `options` - Write options used for every batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - The number of keys deleted
+    /// * `Err(String)` - If the database is read-only or a write failed; earlier batches stay applied
+    pub fn remove_orphans(
+        &self,
+        report: &OrphanReport,
+        options: &WriteOptions,
+    ) -> Result<u64, String> {
+        self.check_writable()?;
+        let keys = report
+            .actors
+            .iter()
+            .map(|id| id.key())
+            .chain(report.maps.iter().map(|&id| map_key(id).into_bytes()))
+            .chain(report.subchunks.iter().map(|key| key.as_bytes().to_vec()));
+
+        let mut batch = WriteBatch::new();
+        let mut pending_bytes = 0;
+        let mut removed = 0;
+        for key in keys {
+            pending_bytes += key.len();
+            batch.delete(key);
+            removed += 1;
+            if pending_bytes >= DEFAULT_BATCH_BYTES {
+                self.write(&batch, options)?;
+                batch.clear();
+                pending_bytes = 0;
+            }
+        }
+        if pending_bytes > 0 {
+            self.write(&batch, options)?;
+        }
+        Ok(removed)
+    }
+}
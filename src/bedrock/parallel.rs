@@ -0,0 +1,160 @@
+use crate::bedrock::{ChunkKey, Dimension, RecordTag};
+use crate::db::DB;
+use crate::options::ReadOptions;
+
+/// Number of leading key bytes holding the chunk coordinates, shared by
+/// every record of a chunk in any dimension.
+const POSITION_LEN: usize = 8;
+
+/// The records of one chunk, as passed to the callback of [`DB::par_chunks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkRecords {
+    /// Chunk x coordinate.
+    pub x: i32,
+    /// Chunk z coordinate.
+    pub z: i32,
+    /// Each record as its key and value, in key order.
+    pub records: Vec<(ChunkKey, Vec<u8>)>,
+}
+
+impl ChunkRecords {
+    /// Return the value of the record with `tag`, or of the lowest subchunk
+    /// in key order for [`RecordTag::SubChunkPrefix`].
+    pub fn get(&self, tag: RecordTag) -> Option<&[u8]> {
+        self.records
+            .iter()
+            .find(|(key, _)| key.tag() == tag)
+            .map(|(_, value)| value.as_slice())
+    }
+}
+
+impl DB {
+    /// Fold every chunk of a dimension into one value per key range in
+    /// parallel and combine the results.
+    ///
+    /// This is [`DB::par_map_reduce`] at chunk granularity: the key space is
+    /// split with [`DB::split_ranges`] into a few ranges per thread of the
+    /// pool, whose boundaries are moved to chunk boundaries so that no chunk
+    /// is split between two workers, and `fold` is called once per chunk with
+    /// all of its records in the dimension. Every range is read from one
+    /// shared snapshot (or the snapshot already set on `options`) by its own
+    /// iterator on rayon's thread pool. Chunks arrive in key order within a
+    /// range, but ranges are processed concurrently, so `reduce` must be
+    /// associative. A panic in any callback is resumed on the calling
+    /// thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `dim` - The dimension to process
+    /// * `identity` - Creates the starting value of each range
+    /// * `fold` - Adds a chunk to a range's value
+    /// * `reduce` - Combines the values of two ranges
+    /// * `options` - Read options controlling the behavior of the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(T)` - The combined value
+    /// * `Err(String)` - If reading any range failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::{Dimension, RecordTag};
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// // Count the chunks that still use the pre-1.18 biome format
+    /// let old = db
+    ///     .par_chunks(
+    ///         Dimension::Overworld,
+    ///         || 0u64,
+    ///         |count, chunk| {
+    ///             if chunk.get(RecordTag::Data3D).is_none() {
+    ///                 *count += 1;
+    ///             }
+    ///         },
+    ///         |a, b| a + b,
+    ///         &ReadOptions::new(),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn par_chunks<T, I, F, R>(
+        &self,
+        dim: Dimension,
+        identity: I,
+        fold: F,
+        reduce: R,
+        options: &ReadOptions,
+    ) -> Result<T, String>
+    where
+        T: Send,
+        I: Fn() -> T + Sync + Send,
+        F: Fn(&mut T, &ChunkRecords) + Sync,
+        R: Fn(T, T) -> T + Sync + Send,
+    {
+        let dim = Dimension::parse(dim.id());
+        let (_snapshot, read_options) = self.consistent_read_options(options);
+        let ranges =
+            chunk_aligned(self.split_ranges(rayon::current_num_threads() * 4, &read_options));
+        self.par_reduce_ranges(
+            ranges,
+            &read_options,
+            &identity,
+            |iter, end| {
+                let mut value = identity();
+                let mut pending: Option<ChunkRecords> = None;
+                while let (Some(key), Some(record)) = (iter.key_ref(), iter.value_ref()) {
+                    if !end.is_empty() && key >= end {
+                        break;
+                    }
+                    if let Ok(key) = ChunkKey::parse(key)
+                        && key.dimension() == dim
+                    {
+                        if pending
+                            .as_ref()
+                            .is_some_and(|chunk| (chunk.x, chunk.z) != (key.x(), key.z()))
+                        {
+                            fold(&mut value, &pending.take().unwrap());
+                        }
+                        pending
+                            .get_or_insert_with(|| ChunkRecords {
+                                x: key.x(),
+                                z: key.z(),
+                                records: Vec::new(),
+                            })
+                            .records
+                            .push((key, record.to_vec()));
+                    }
+                    iter.next_native();
+                }
+                iter.status()?;
+                if let Some(chunk) = pending {
+                    fold(&mut value, &chunk);
+                }
+                Ok(value)
+            },
+            reduce,
+        )
+    }
+}
+
+/// Move the boundaries between `ranges` back to the start of the chunk
+/// coordinates they fall in, dropping ranges that become empty.
+///
+/// Every record of a chunk starts with the same coordinate bytes, so cutting
+/// only at such a prefix keeps the records of each chunk in one range.
+pub(crate) fn chunk_aligned(ranges: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let mut aligned = Vec::with_capacity(ranges.len());
+    let mut start = Vec::new();
+    for (_, end) in ranges {
+        if end.is_empty() {
+            break;
+        }
+        let end = end[..end.len().min(POSITION_LEN)].to_vec();
+        if end > start {
+            aligned.push((std::mem::replace(&mut start, end.clone()), end));
+        }
+    }
+    aligned.push((start, Vec::new()));
+    aligned
+}
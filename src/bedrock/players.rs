@@ -0,0 +1,149 @@
+use std::fmt;
+
+use super::keys::LOCAL_PLAYER;
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+
+/// Key prefix of players identified by their client id.
+const CLIENT_PREFIX: &str = "player_";
+/// Key prefix of players identified by a server-assigned id.
+const SERVER_PREFIX: &str = "player_server_";
+
+/// The key a player's NBT record is stored under.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bleveldb::bedrock::PlayerKey;
+///
+/// let key = PlayerKey::parse(b"player_server_6f3c2e1a-0b8d-4c1e-9a55-3d7e0c2b9f10").unwrap();
+/// assert_eq!(key, PlayerKey::Server("6f3c2e1a-0b8d-4c1e-9a55-3d7e0c2b9f10".to_string()));
+/// assert_eq!(PlayerKey::Local.encode(), b"~local_player");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PlayerKey {
+    /// The host of a single-player world, stored under `~local_player`.
+    Local,
+    /// A player stored under `player_<client id>`, as older versions did.
+    Client(String),
+    /// A player stored under `player_server_<id>`, the format used since 1.16.
+    Server(String),
+}
+
+impl PlayerKey {
+    /// Parse a player key, returning `None` for keys that are not player keys.
+    pub fn parse(key: &[u8]) -> Option<PlayerKey> {
+        if key == LOCAL_PLAYER {
+            return Some(PlayerKey::Local);
+        }
+        let key = std::str::from_utf8(key).ok()?;
+        if let Some(id) = key.strip_prefix(SERVER_PREFIX) {
+            Some(PlayerKey::Server(id.to_string()))
+        } else {
+            key.strip_prefix(CLIENT_PREFIX)
+                .map(|id| PlayerKey::Client(id.to_string()))
+        }
+    }
+
+    /// Return the encoded key.
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            PlayerKey::Local => LOCAL_PLAYER.to_vec(),
+            PlayerKey::Client(id) => format!("{}{}", CLIENT_PREFIX, id).into_bytes(),
+            PlayerKey::Server(id) => format!("{}{}", SERVER_PREFIX, id).into_bytes(),
+        }
+    }
+}
+
+impl fmt::Display for PlayerKey {
+    /// Write the key as it is stored, like `player_server_<id>`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.encode()))
+    }
+}
+
+impl DB {
+    /// Iterate over every player record in the world.
+    ///
+    /// Yields the `player_` and `player_server_` records in key order,
+    /// followed by the local player if the world has one. Keys under the
+    /// `player_` prefix that are not valid UTF-8 are skipped. Read errors end
+    /// the iteration early.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// for (key, nbt) in db.players(&ReadOptions::new()) {
+    ///     println!("{}: {} bytes", key, nbt.len());
+    /// }
+    /// ```
+    pub fn players(
+        &self,
+        options: &ReadOptions,
+    ) -> impl Iterator<Item = (PlayerKey, Vec<u8>)> + '_ {
+        let local_options = options.duplicate();
+        let mut local = Some(move || self.local_player(&local_options).ok().flatten());
+        self.prefix_iter(CLIENT_PREFIX.as_bytes(), options)
+            .filter_map(|(key, value)| Some((PlayerKey::parse(&key)?, value)))
+            .chain(std::iter::from_fn(move || {
+                let value = local.take()?()?;
+                Some((PlayerKey::Local, value))
+            }))
+    }
+
+    /// Read the record of the world's local player.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Vec<u8>))` - The player's NBT record
+    /// * `Ok(None)` - If the world has no local player, e.g. because it came from a server
+    /// * `Err(String)` - If an error occurred during the read operation
+    pub fn local_player(&self, options: &ReadOptions) -> Result<Option<Vec<u8>>, String> {
+        self.get(LOCAL_PLAYER, options)
+    }
+
+    /// Write a player's NBT record under its key.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - The key of the player
+    /// * `data` - The player's NBT record
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::PlayerKey;
+    /// # use bleveldb::options::{Options, ReadOptions, WriteOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// for (key, nbt) in db.players(&ReadOptions::new()).collect::<Vec<_>>() {
+    ///     let edited = nbt; // decode, modify and re-encode the NBT here
+    ///     db.put_player(&key, &edited, &WriteOptions::new()).unwrap();
+    /// }
+    /// ```
+    pub fn put_player(
+        &self,
+        player: &PlayerKey,
+        data: impl AsRef<[u8]>,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        self.put(player.encode(), data, options)
+    }
+}
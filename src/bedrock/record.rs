@@ -0,0 +1,365 @@
+use std::fmt;
+
+use crate::bedrock::{
+    BiomeDataError, ChunkKey, Data2D, Data3D, Dimension, FinalizedState, LegacyTerrain,
+    LegacyTerrainError, RecordTag,
+};
+#[cfg(feature = "subchunk")]
+use crate::bedrock::{SubChunk, SubChunkError};
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+
+/// Why a record could not be decoded by [`RecordDecode::decode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// A fixed-size record has the wrong length.
+    InvalidLength {
+        /// The length the record should have.
+        expected: usize,
+        /// The length it has.
+        actual: usize,
+    },
+    /// A `Data2D` or `Data3D` record is malformed.
+    BiomeData(BiomeDataError),
+    /// A `LegacyTerrain` record is malformed.
+    LegacyTerrain(LegacyTerrainError),
+    /// A `SubChunkPrefix` record is malformed.
+    #[cfg(feature = "subchunk")]
+    SubChunk(SubChunkError),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidLength { expected, actual } => {
+                write!(f, "record is {} bytes instead of {}", actual, expected)
+            }
+            DecodeError::BiomeData(e) => e.fmt(f),
+            DecodeError::LegacyTerrain(e) => e.fmt(f),
+            #[cfg(feature = "subchunk")]
+            DecodeError::SubChunk(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecodeError::InvalidLength { .. } => None,
+            DecodeError::BiomeData(e) => Some(e),
+            DecodeError::LegacyTerrain(e) => Some(e),
+            #[cfg(feature = "subchunk")]
+            DecodeError::SubChunk(e) => Some(e),
+        }
+    }
+}
+
+impl From<BiomeDataError> for DecodeError {
+    fn from(e: BiomeDataError) -> Self {
+        DecodeError::BiomeData(e)
+    }
+}
+
+impl From<LegacyTerrainError> for DecodeError {
+    fn from(e: LegacyTerrainError) -> Self {
+        DecodeError::LegacyTerrain(e)
+    }
+}
+
+#[cfg(feature = "subchunk")]
+impl From<SubChunkError> for DecodeError {
+    fn from(e: SubChunkError) -> Self {
+        DecodeError::SubChunk(e)
+    }
+}
+
+/// A chunk record type that can be decoded from its stored bytes.
+///
+/// Implementations tie a Rust type to the [`RecordTag`] it is stored under,
+/// and to the [`RecordIndex`] that picks one record among those a chunk
+/// stores under the tag, which lets [`DB::get_record`] build the key from
+/// the type alone.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::DB;
+/// # use bleveldb::options::{Options, ReadOptions};
+/// use bleveldb::bedrock::{Data3D, Dimension, FinalizedState};
+///
+/// # let db = DB::open("world/db", &Options::default()).unwrap();
+/// let read_opts = ReadOptions::new();
+/// let biomes = db.get_record::<Data3D>(0, 0, Dimension::Overworld, &read_opts).unwrap();
+/// let state = db.get_record::<FinalizedState>(0, 0, Dimension::Overworld, &read_opts).unwrap();
+/// println!("{:?} {:?}", biomes.map(|b| b.sections.len()), state);
+/// ```
+pub trait RecordDecode: Sized {
+    /// The tag the record is stored under.
+    const TAG: RecordTag;
+
+    /// What picks the record among those of a chunk under `TAG`.
+    type Index: RecordIndex;
+
+    /// Decode a stored record.
+    fn decode(bytes: &[u8]) -> Result<Self, DecodeError>;
+}
+
+/// A chunk record type that can be encoded for storage, the counterpart of
+/// [`RecordDecode`].
+pub trait RecordEncode {
+    /// The tag the record is stored under.
+    const TAG: RecordTag;
+
+    /// What picks the record among those of a chunk under `TAG`.
+    type Index: RecordIndex;
+
+    /// Encode the record as it is stored.
+    fn encode(&self) -> Vec<u8>;
+}
+
+/// Picks one record among those a chunk stores under a tag.
+///
+/// Most records are stored once per chunk and use `()`. `SubChunkPrefix`
+/// records are stored once per subchunk and use the `i8` y index.
+pub trait RecordIndex: Copy {
+    /// Return the key of the record of type `tag` at this index.
+    fn key(self, x: i32, z: i32, dim: Dimension, tag: RecordTag) -> ChunkKey;
+}
+
+impl RecordIndex for () {
+    fn key(self, x: i32, z: i32, dim: Dimension, tag: RecordTag) -> ChunkKey {
+        ChunkKey::new(x, z, dim, tag)
+    }
+}
+
+/// The y index of a `SubChunkPrefix` record; the tag is always that one.
+impl RecordIndex for i8 {
+    fn key(self, x: i32, z: i32, dim: Dimension, _tag: RecordTag) -> ChunkKey {
+        ChunkKey::subchunk(x, z, dim, self)
+    }
+}
+
+impl RecordDecode for Data2D {
+    const TAG: RecordTag = RecordTag::Data2D;
+    type Index = ();
+
+    fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Ok(Data2D::parse(bytes)?)
+    }
+}
+
+impl RecordEncode for Data2D {
+    const TAG: RecordTag = RecordTag::Data2D;
+    type Index = ();
+
+    fn encode(&self) -> Vec<u8> {
+        Data2D::encode(self)
+    }
+}
+
+impl RecordDecode for Data3D {
+    const TAG: RecordTag = RecordTag::Data3D;
+    type Index = ();
+
+    fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Ok(Data3D::parse(bytes)?)
+    }
+}
+
+impl RecordEncode for Data3D {
+    const TAG: RecordTag = RecordTag::Data3D;
+    type Index = ();
+
+    fn encode(&self) -> Vec<u8> {
+        Data3D::encode(self)
+    }
+}
+
+impl RecordDecode for FinalizedState {
+    const TAG: RecordTag = RecordTag::FinalizedState;
+    type Index = ();
+
+    fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let bytes: [u8; 4] = bytes.try_into().map_err(|_| DecodeError::InvalidLength {
+            expected: 4,
+            actual: bytes.len(),
+        })?;
+        Ok(FinalizedState::from_u32(u32::from_le_bytes(bytes)))
+    }
+}
+
+impl RecordEncode for FinalizedState {
+    const TAG: RecordTag = RecordTag::FinalizedState;
+    type Index = ();
+
+    fn encode(&self) -> Vec<u8> {
+        self.to_u32().to_le_bytes().to_vec()
+    }
+}
+
+impl RecordDecode for LegacyTerrain {
+    const TAG: RecordTag = RecordTag::LegacyTerrain;
+    type Index = ();
+
+    fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Ok(LegacyTerrain::parse(bytes)?)
+    }
+}
+
+/// Subchunks are stored once per y index, so they are read with
+/// [`DB::get_record_at`], passing the y index.
+#[cfg(feature = "subchunk")]
+impl RecordDecode for SubChunk {
+    const TAG: RecordTag = RecordTag::SubChunkPrefix;
+    type Index = i8;
+
+    fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Ok(SubChunk::parse(bytes)?)
+    }
+}
+
+#[cfg(feature = "subchunk")]
+impl RecordEncode for SubChunk {
+    const TAG: RecordTag = RecordTag::SubChunkPrefix;
+    type Index = i8;
+
+    fn encode(&self) -> Vec<u8> {
+        SubChunk::encode(self)
+    }
+}
+
+impl DB {
+    /// Read and decode the record of type `T` of a chunk.
+    ///
+    /// Only records stored once per chunk can be read this way; use
+    /// [`DB::get_record_at`] for subchunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(T))` - The decoded record
+    /// * `Ok(None)` - If the chunk has no record with `T::TAG`
+    /// * `Err(String)` - If the read failed or the record could not be decoded
+    pub fn get_record<T: RecordDecode<Index = ()>>(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Option<T>, String> {
+        self.get_record_at::<T>(x, z, dim, (), options)
+    }
+
+    /// Read and decode the record of type `T` of a chunk at `index`, such as
+    /// a subchunk at its y index.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `index` - Which of the chunk's `T::TAG` records to read
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(T))` - The decoded record
+    /// * `Ok(None)` - If the chunk has no such record
+    /// * `Err(String)` - If the read failed or the record could not be decoded
+    pub fn get_record_at<T: RecordDecode>(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        index: T::Index,
+        options: &ReadOptions,
+    ) -> Result<Option<T>, String> {
+        let Some(bytes) = self.get(index.key(x, z, dim, T::TAG), options)? else {
+            return Ok(None);
+        };
+        T::decode(&bytes).map(Some).map_err(|e| {
+            format!(
+                "Invalid {} record in chunk ({}, {}) of the {}: {}",
+                T::TAG,
+                x,
+                z,
+                dim,
+                e
+            )
+        })
+    }
+
+    /// Encode and write a record of a chunk under `T::TAG`.
+    ///
+    /// Only records stored once per chunk can be written this way; use
+    /// [`DB::put_record_at`] for subchunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `record` - The record to write
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, WriteOptions};
+    /// use bleveldb::bedrock::{Dimension, FinalizedState};
+    ///
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// db.put_record(0, 0, Dimension::Overworld, &FinalizedState::Done, &WriteOptions::new())
+    ///     .unwrap();
+    /// ```
+    pub fn put_record<T: RecordEncode<Index = ()>>(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        record: &T,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        self.put_record_at(x, z, dim, (), record, options)
+    }
+
+    /// Encode and write a record of a chunk under `T::TAG` at `index`, such
+    /// as a subchunk at its y index.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `index` - Which of the chunk's `T::TAG` records to write
+    /// * `record` - The record to write
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub fn put_record_at<T: RecordEncode>(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        index: T::Index,
+        record: &T,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        self.put(index.key(x, z, dim, T::TAG), record.encode(), options)
+    }
+}
@@ -0,0 +1,315 @@
+use super::keys::SCOREBOARD;
+use super::nbt::{self, Fields, Value, compound, compound_list};
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+
+/// `IdentityType` of an entry that tracks a player.
+const IDENTITY_PLAYER: i8 = 1;
+/// `IdentityType` of an entry that tracks a non-player entity.
+const IDENTITY_ENTITY: i8 = 2;
+/// `IdentityType` of an entry that tracks a name added with `/scoreboard`.
+const IDENTITY_FAKE: i8 = 3;
+
+/// Who a scoreboard entry keeps scores for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScoreOwner {
+    /// A player, by the `PlayerId` the game assigns them.
+    Player(i64),
+    /// A non-player entity, by its `UniqueID`.
+    Entity(i64),
+    /// A fake player: a plain name that is not tied to any entity.
+    Fake(String),
+    /// An `IdentityType` the game does not define. Any owner fields are kept
+    /// in the entry's `extra`.
+    Unknown(i8),
+}
+
+/// A tracked identity of the scoreboard, from its `Entries` list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreboardEntry {
+    /// The id scores refer to this identity by.
+    pub scoreboard_id: i64,
+    /// Who the entry belongs to.
+    pub owner: ScoreOwner,
+    /// Fields the parser does not know, in stored order.
+    pub extra: Vec<(String, Value)>,
+}
+
+/// A score of an objective.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    /// The [`ScoreboardEntry::scoreboard_id`] of the identity holding the score.
+    pub scoreboard_id: i64,
+    /// The score.
+    pub score: i32,
+    /// Fields the parser does not know, in stored order.
+    pub extra: Vec<(String, Value)>,
+}
+
+/// An objective of the scoreboard, from its `Objectives` list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Objective {
+    /// The name commands refer to the objective by.
+    pub name: String,
+    /// The name shown to players.
+    pub display_name: String,
+    /// The criteria, which is `dummy` for every objective the game creates.
+    pub criteria: String,
+    /// The scores of the objective.
+    pub scores: Vec<Score>,
+    /// Fields the parser does not know, in stored order.
+    pub extra: Vec<(String, Value)>,
+}
+
+/// An objective shown in a display slot, from the `DisplayObjectives` list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplaySlot {
+    /// The slot: `sidebar`, `list` or `belowname`.
+    pub name: String,
+    /// The name of the objective shown.
+    pub objective_name: String,
+    /// The sort order of the shown scores: 0 ascending, 1 descending.
+    pub sort_order: i8,
+    /// Fields the parser does not know, in stored order.
+    pub extra: Vec<(String, Value)>,
+}
+
+/// The world's scoreboard, stored under the `scoreboard` key.
+///
+/// Fields the parser does not know, such as the root `Criteria` list, are
+/// kept in the `extra` list of each struct and written back after the known
+/// ones, so a scoreboard saved by a newer game version survives a read and
+/// write.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::DB;
+/// # use bleveldb::options::{Options, ReadOptions, WriteOptions};
+/// use bleveldb::bedrock::ScoreOwner;
+///
+/// # let db = DB::open("world/db", &Options::default()).unwrap();
+/// // Hand the scores of one player over to another
+/// let mut scoreboard = db.scoreboard(&ReadOptions::new()).unwrap().unwrap_or_default();
+/// for entry in &mut scoreboard.entries {
+///     if entry.owner == ScoreOwner::Player(-42) {
+///         entry.owner = ScoreOwner::Player(-43);
+///     }
+/// }
+/// db.set_scoreboard(&scoreboard, &WriteOptions::new()).unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scoreboard {
+    /// The objectives and their scores.
+    pub objectives: Vec<Objective>,
+    /// The objectives shown in display slots.
+    pub display_slots: Vec<DisplaySlot>,
+    /// The identities that hold scores.
+    pub entries: Vec<ScoreboardEntry>,
+    /// The last scoreboard id handed out, if stored.
+    pub last_unique_id: Option<i64>,
+    /// Fields the parser does not know, in stored order.
+    pub extra: Vec<(String, Value)>,
+}
+
+impl ScoreboardEntry {
+    fn from_nbt(value: Value) -> Result<Self, String> {
+        let mut fields = Fields::new("Scoreboard entry", value)?;
+        let scoreboard_id = fields.long("ScoreboardId")?;
+        let owner = match fields.byte("IdentityType")? {
+            IDENTITY_PLAYER => ScoreOwner::Player(fields.long("PlayerId")?),
+            IDENTITY_ENTITY => ScoreOwner::Entity(fields.long("EntityId")?),
+            IDENTITY_FAKE => ScoreOwner::Fake(fields.string("FakePlayerName")?),
+            other => ScoreOwner::Unknown(other),
+        };
+        Ok(ScoreboardEntry {
+            scoreboard_id,
+            owner,
+            extra: fields.entries,
+        })
+    }
+
+    fn to_nbt(&self) -> Value {
+        let mut known = vec![("ScoreboardId", Value::Long(self.scoreboard_id))];
+        match &self.owner {
+            ScoreOwner::Player(id) => {
+                known.push(("IdentityType", Value::Byte(IDENTITY_PLAYER)));
+                known.push(("PlayerId", Value::Long(*id)));
+            }
+            ScoreOwner::Entity(id) => {
+                known.push(("IdentityType", Value::Byte(IDENTITY_ENTITY)));
+                known.push(("EntityId", Value::Long(*id)));
+            }
+            ScoreOwner::Fake(name) => {
+                known.push(("IdentityType", Value::Byte(IDENTITY_FAKE)));
+                known.push(("FakePlayerName", Value::String(name.clone())));
+            }
+            ScoreOwner::Unknown(identity) => {
+                known.push(("IdentityType", Value::Byte(*identity)));
+            }
+        }
+        compound(known, &self.extra)
+    }
+}
+
+impl Score {
+    fn from_nbt(value: Value) -> Result<Self, String> {
+        let mut fields = Fields::new("Scoreboard score", value)?;
+        Ok(Score {
+            scoreboard_id: fields.long("ScoreboardId")?,
+            score: fields.int("Score")?,
+            extra: fields.entries,
+        })
+    }
+
+    fn to_nbt(&self) -> Value {
+        compound(
+            vec![
+                ("Score", Value::Int(self.score)),
+                ("ScoreboardId", Value::Long(self.scoreboard_id)),
+            ],
+            &self.extra,
+        )
+    }
+}
+
+impl Objective {
+    fn from_nbt(value: Value) -> Result<Self, String> {
+        let mut fields = Fields::new("Scoreboard objective", value)?;
+        Ok(Objective {
+            name: fields.string("Name")?,
+            display_name: fields.string("DisplayName")?,
+            criteria: fields.string("Criteria")?,
+            scores: fields.list("Scores", Score::from_nbt)?,
+            extra: fields.entries,
+        })
+    }
+
+    fn to_nbt(&self) -> Value {
+        compound(
+            vec![
+                ("Criteria", Value::String(self.criteria.clone())),
+                ("DisplayName", Value::String(self.display_name.clone())),
+                ("Name", Value::String(self.name.clone())),
+                (
+                    "Scores",
+                    compound_list(self.scores.iter().map(Score::to_nbt).collect()),
+                ),
+            ],
+            &self.extra,
+        )
+    }
+}
+
+impl DisplaySlot {
+    fn from_nbt(value: Value) -> Result<Self, String> {
+        let mut fields = Fields::new("Scoreboard display slot", value)?;
+        Ok(DisplaySlot {
+            name: fields.string("Name")?,
+            objective_name: fields.string("ObjectiveName")?,
+            sort_order: fields.byte("SortOrder")?,
+            extra: fields.entries,
+        })
+    }
+
+    fn to_nbt(&self) -> Value {
+        compound(
+            vec![
+                ("Name", Value::String(self.name.clone())),
+                ("ObjectiveName", Value::String(self.objective_name.clone())),
+                ("SortOrder", Value::Byte(self.sort_order)),
+            ],
+            &self.extra,
+        )
+    }
+}
+
+impl Scoreboard {
+    /// Parse the root compound of a `scoreboard` record.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Scoreboard)` - The parsed scoreboard
+    /// * `Err(String)` - If a known field is missing or has the wrong type
+    pub fn from_nbt(value: Value) -> Result<Scoreboard, String> {
+        let mut fields = Fields::new("Scoreboard root", value)?;
+        let objectives = fields.list("Objectives", Objective::from_nbt)?;
+        let display_slots = fields.list("DisplayObjectives", DisplaySlot::from_nbt)?;
+        let entries = fields.list("Entries", ScoreboardEntry::from_nbt)?;
+        let last_unique_id = match fields.take("LastUniqueID") {
+            Some(Value::Long(id)) => Some(id),
+            None => None,
+            Some(_) => return Err(fields.missing("LastUniqueID")),
+        };
+        Ok(Scoreboard {
+            objectives,
+            display_slots,
+            entries,
+            last_unique_id,
+            extra: fields.entries,
+        })
+    }
+
+    /// Build the root compound of a `scoreboard` record.
+    pub fn to_nbt(&self) -> Value {
+        let mut known = vec![
+            (
+                "DisplayObjectives",
+                compound_list(self.display_slots.iter().map(DisplaySlot::to_nbt).collect()),
+            ),
+            (
+                "Entries",
+                compound_list(self.entries.iter().map(ScoreboardEntry::to_nbt).collect()),
+            ),
+            (
+                "Objectives",
+                compound_list(self.objectives.iter().map(Objective::to_nbt).collect()),
+            ),
+        ];
+        if let Some(id) = self.last_unique_id {
+            known.push(("LastUniqueID", Value::Long(id)));
+        }
+        compound(known, &self.extra)
+    }
+}
+
+impl DB {
+    /// Read and parse the world's scoreboard.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Scoreboard))` - The parsed scoreboard
+    /// * `Ok(None)` - If the world has no `scoreboard` record
+    /// * `Err(String)` - If the read failed or the record is malformed
+    pub fn scoreboard(&self, options: &ReadOptions) -> Result<Option<Scoreboard>, String> {
+        let Some(record) = self.get(SCOREBOARD, options)? else {
+            return Ok(None);
+        };
+        let value =
+            nbt::decode(&record).map_err(|e| format!("Invalid scoreboard record: {}", e))?;
+        Scoreboard::from_nbt(value).map(Some)
+    }
+
+    /// Write the world's scoreboard, replacing the stored one.
+    ///
+    /// # Arguments
+    ///
+    /// * `scoreboard` - The scoreboard to store
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub fn set_scoreboard(
+        &self,
+        scoreboard: &Scoreboard,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        self.put(SCOREBOARD, nbt::encode(&scoreboard.to_nbt()), options)
+    }
+}
@@ -0,0 +1,134 @@
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+
+/// Key prefix of saved structure templates, followed by the structure's identifier.
+pub(crate) const STRUCTURE_PREFIX: &str = "structuretemplate_";
+/// Namespace the game gives structures saved without one.
+const DEFAULT_NAMESPACE: &str = "mystructure";
+
+/// Split a structure identifier into its namespace and name.
+///
+/// The namespace ends at the first colon, so the name may contain colons
+/// itself. Identifiers without a colon are in the default namespace. Empty
+/// namespaces and names are rejected.
+pub(crate) fn split_identifier(identifier: &str) -> Option<(&str, &str)> {
+    let (namespace, name) = identifier
+        .split_once(':')
+        .unwrap_or((DEFAULT_NAMESPACE, identifier));
+    (!namespace.is_empty() && !name.is_empty()).then_some((namespace, name))
+}
+
+/// Return the key of the structure called `identifier`.
+fn structure_key(identifier: &str) -> Result<String, String> {
+    let (namespace, name) = split_identifier(identifier)
+        .ok_or_else(|| format!("Invalid structure identifier '{}'", identifier))?;
+    Ok(format!("{}{}:{}", STRUCTURE_PREFIX, namespace, name))
+}
+
+impl DB {
+    /// List the structures saved in the world.
+    ///
+    /// Structure keys spell out the namespace even for structures saved
+    /// without one, so every entry has both parts. Keys that lack a namespace
+    /// or a name are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(String, String)>)` - The namespace and name of each structure, in key order
+    /// * `Err(String)` - If an error occurred while reading the database
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// for (namespace, name) in db.structure_templates(&ReadOptions::new()).unwrap() {
+    ///     println!("{}:{}", namespace, name);
+    /// }
+    /// ```
+    pub fn structure_templates(
+        &self,
+        options: &ReadOptions,
+    ) -> Result<Vec<(String, String)>, String> {
+        let mut structures = Vec::new();
+        let mut iter = self.prefix_iter(STRUCTURE_PREFIX.as_bytes(), options);
+        for (key, _) in iter.by_ref() {
+            let Ok(key) = std::str::from_utf8(&key) else {
+                continue;
+            };
+            let identifier = &key[STRUCTURE_PREFIX.len()..];
+            if identifier.contains(':')
+                && let Some((namespace, name)) = split_identifier(identifier)
+            {
+                structures.push((namespace.to_string(), name.to_string()));
+            }
+        }
+        iter.status()?;
+        Ok(structures)
+    }
+
+    /// Read a saved structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - The structure's name, like `mystructure:house`; a name
+    ///   without a namespace is looked up in the `mystructure` namespace
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Vec<u8>))` - The structure's NBT record
+    /// * `Ok(None)` - If no structure with that name is saved
+    /// * `Err(String)` - If the identifier is invalid or the read failed
+    pub fn get_structure(
+        &self,
+        identifier: &str,
+        options: &ReadOptions,
+    ) -> Result<Option<Vec<u8>>, String> {
+        self.get(structure_key(identifier)?, options)
+    }
+
+    /// Save a structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - The structure's name, like `mystructure:house`; a name
+    ///   without a namespace is saved in the `mystructure` namespace
+    /// * `data` - The structure's NBT record
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the structure was written
+    /// * `Err(String)` - If the identifier is invalid, the database is read-only or the write failed
+    pub fn put_structure(
+        &self,
+        identifier: &str,
+        data: impl AsRef<[u8]>,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        self.put(structure_key(identifier)?, data, options)
+    }
+
+    /// Delete a saved structure.
+    ///
+    /// Deleting a structure that does not exist succeeds.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - The structure's name, as for [`DB::get_structure`]
+    /// * `options` - Write options controlling the behavior of the delete operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the structure is gone
+    /// * `Err(String)` - If the identifier is invalid, the database is read-only or the delete failed
+    pub fn delete_structure(&self, identifier: &str, options: &WriteOptions) -> Result<(), String> {
+        self.delete(structure_key(identifier)?, options)
+    }
+}
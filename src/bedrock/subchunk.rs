@@ -0,0 +1,334 @@
+use std::fmt;
+
+use crate::bedrock::nbt::{self, NbtError, Value};
+
+/// Number of blocks in a subchunk.
+const VOLUME: usize = 4096;
+/// Index widths a block storage can use; 0 means every block is palette entry 0.
+const VALID_BITS: [u8; 9] = [0, 1, 2, 3, 4, 5, 6, 8, 16];
+
+/// Why a subchunk record could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubChunkError {
+    /// The record ended early; `offset` is where more data was expected.
+    Truncated {
+        /// Byte offset at which the record ran out.
+        offset: usize,
+    },
+    /// The record uses a format other than versions 8 and 9.
+    UnsupportedVersion(u8),
+    /// A layer uses an index width the game never writes.
+    InvalidBitsPerBlock {
+        /// Index of the layer.
+        layer: usize,
+        /// The width from the layer header.
+        bits: u8,
+    },
+    /// A layer is flagged as using network runtime ids instead of NBT block
+    /// states, which only happens in network packets.
+    RuntimePalette {
+        /// Index of the layer.
+        layer: usize,
+    },
+    /// A layer has a negative palette length.
+    NegativePaletteLength {
+        /// Index of the layer.
+        layer: usize,
+    },
+    /// A block refers past the end of its layer's palette.
+    PaletteIndexOutOfRange {
+        /// Index of the layer.
+        layer: usize,
+        /// The offending palette index.
+        index: u16,
+    },
+    /// A palette entry is not valid NBT.
+    Nbt {
+        /// Index of the layer.
+        layer: usize,
+        /// Byte offset of the palette entry in the record.
+        offset: usize,
+        /// What is wrong with the entry; its offsets are relative to the entry.
+        error: NbtError,
+    },
+}
+
+impl fmt::Display for SubChunkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubChunkError::Truncated { offset } => {
+                write!(f, "record is truncated at byte {}", offset)
+            }
+            SubChunkError::UnsupportedVersion(version) => {
+                write!(f, "unsupported subchunk version {}", version)
+            }
+            SubChunkError::InvalidBitsPerBlock { layer, bits } => {
+                write!(f, "layer {} uses {} bits per block", layer, bits)
+            }
+            SubChunkError::RuntimePalette { layer } => {
+                write!(f, "layer {} uses a runtime id palette", layer)
+            }
+            SubChunkError::NegativePaletteLength { layer } => {
+                write!(f, "layer {} has a negative palette length", layer)
+            }
+            SubChunkError::PaletteIndexOutOfRange { layer, index } => {
+                write!(f, "layer {} refers to palette entry {}", layer, index)
+            }
+            SubChunkError::Nbt {
+                layer,
+                offset,
+                error,
+            } => {
+                write!(
+                    f,
+                    "palette entry of layer {} at byte {}: {}",
+                    layer, offset, error
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SubChunkError {}
+
+/// One layer of blocks in a subchunk.
+///
+/// Layer 0 holds the blocks themselves; layer 1, when present, holds what
+/// is waterlogged into them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockStorage {
+    /// The block states used in the layer, as NBT compounds with a `name`,
+    /// `states` and `version`.
+    pub palette: Vec<Value>,
+    /// Palette index of every block, 4096 entries in the order
+    /// `(x * 16 + z) * 16 + y`.
+    pub indices: Vec<u16>,
+}
+
+/// The blocks of a 16×16×16 subchunk, stored under [`RecordTag::SubChunkPrefix`]
+/// in format versions 8 and 9.
+///
+/// Version 9, written since 1.17.30, adds the subchunk's y index to the record;
+/// it is kept in `y_index` and written back by [`SubChunk::encode`].
+///
+/// [`RecordTag::SubChunkPrefix`]: crate::bedrock::RecordTag::SubChunkPrefix
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::DB;
+/// # use bleveldb::bedrock::{Dimension, SubChunk};
+/// # use bleveldb::options::{Options, ReadOptions};
+/// # let db = DB::open("world/db", &Options::default()).unwrap();
+/// let blob = db.get_subchunk(0, 0, Dimension::Overworld, 4, &ReadOptions::new()).unwrap();
+/// if let Some(blob) = blob {
+///     let subchunk = SubChunk::parse(&blob).unwrap();
+///     let block = subchunk.block_at(0, 0, 0, 0).unwrap();
+///     println!("{:?}", block.get("name"));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubChunk {
+    /// The format version, 8 or 9.
+    pub version: u8,
+    /// The y index stored in version 9 records; `None` for version 8.
+    pub y_index: Option<i8>,
+    /// The block layers.
+    pub layers: Vec<BlockStorage>,
+}
+
+impl SubChunk {
+    /// Parse a subchunk record.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - The raw record: a version, a layer count, the y index for
+    ///   version 9, then the paletted layers
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SubChunk)` - The decoded subchunk
+    /// * `Err(SubChunkError)` - If the record is truncated, malformed or in another format version
+    pub fn parse(record: &[u8]) -> Result<SubChunk, SubChunkError> {
+        let mut reader = Reader { record, offset: 0 };
+        let version = reader.u8()?;
+        if version != 8 && version != 9 {
+            return Err(SubChunkError::UnsupportedVersion(version));
+        }
+        let layer_count = reader.u8()? as usize;
+        let y_index = if version == 9 {
+            Some(reader.u8()? as i8)
+        } else {
+            None
+        };
+
+        let mut layers = Vec::with_capacity(layer_count);
+        for layer in 0..layer_count {
+            let header = reader.u8()?;
+            if header & 1 != 0 {
+                return Err(SubChunkError::RuntimePalette { layer });
+            }
+            let bits = header >> 1;
+            if !VALID_BITS.contains(&bits) {
+                return Err(SubChunkError::InvalidBitsPerBlock { layer, bits });
+            }
+
+            let mut indices = Vec::with_capacity(VOLUME);
+            if bits == 0 {
+                indices.resize(VOLUME, 0);
+            } else {
+                let per_word = 32 / bits as usize;
+                let mask = (1u32 << bits) - 1;
+                let words = reader.take(VOLUME.div_ceil(per_word) * 4)?;
+                for word in words.chunks_exact(4) {
+                    let word = u32::from_le_bytes(word.try_into().unwrap());
+                    for slot in 0..per_word {
+                        if indices.len() < VOLUME {
+                            indices.push(((word >> (slot * bits as usize)) & mask) as u16);
+                        }
+                    }
+                }
+            }
+
+            let palette_len = usize::try_from(reader.i32()?)
+                .map_err(|_| SubChunkError::NegativePaletteLength { layer })?;
+            // Every entry takes at least one byte, which bounds the allocation
+            let mut palette = Vec::with_capacity(palette_len.min(record.len() - reader.offset));
+            for _ in 0..palette_len {
+                let offset = reader.offset;
+                let (state, len) =
+                    nbt::decode_prefix(&record[offset..]).map_err(|error| SubChunkError::Nbt {
+                        layer,
+                        offset,
+                        error,
+                    })?;
+                reader.offset += len;
+                palette.push(state);
+            }
+            if let Some(&index) = indices.iter().find(|&&i| i as usize >= palette.len()) {
+                return Err(SubChunkError::PaletteIndexOutOfRange { layer, index });
+            }
+            layers.push(BlockStorage { palette, indices });
+        }
+        Ok(SubChunk {
+            version,
+            y_index,
+            layers,
+        })
+    }
+
+    /// Encode the subchunk in the layout [`SubChunk::parse`] reads.
+    ///
+    /// Layers are written with the smallest index width that fits their
+    /// palette, and at least 1 bit so that version 8 readers accept them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the version is not 8 or 9, `y_index` is missing for
+    /// version 9, there are more than 255 layers, or a layer does not have
+    /// exactly 4096 indices or has more than 65536 palette entries.
+    pub fn encode(&self) -> Vec<u8> {
+        assert!(
+            self.version == 8 || self.version == 9,
+            "cannot encode subchunk version {}",
+            self.version
+        );
+        let mut out = vec![
+            self.version,
+            u8::try_from(self.layers.len()).expect("subchunk has more than 255 layers"),
+        ];
+        if self.version == 9 {
+            let y_index = self.y_index.expect("version 9 subchunk needs a y index");
+            out.push(y_index as u8);
+        }
+        for layer in &self.layers {
+            assert_eq!(
+                layer.indices.len(),
+                VOLUME,
+                "subchunk layer needs 4096 indices"
+            );
+            let bits = VALID_BITS[1..]
+                .iter()
+                .copied()
+                .find(|&bits| layer.palette.len() <= 1 << bits)
+                .expect("block palette has more than 65536 entries");
+            out.push(bits << 1);
+            let per_word = 32 / bits as usize;
+            for group in layer.indices.chunks(per_word) {
+                let word = group.iter().enumerate().fold(0u32, |word, (slot, &i)| {
+                    word | (i as u32) << (slot * bits as usize)
+                });
+                out.extend_from_slice(&word.to_le_bytes());
+            }
+            out.extend_from_slice(&(layer.palette.len() as i32).to_le_bytes());
+            for state in &layer.palette {
+                nbt::encode_into(state, &mut out);
+            }
+        }
+        out
+    }
+
+    /// Return the block state at a block of the subchunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `x`, `y`, `z` - Block coordinates within the subchunk, each below 16
+    /// * `layer` - The layer to read, 0 for the blocks themselves
+    ///
+    /// # Returns
+    ///
+    /// The palette entry of the block, or `None` if the subchunk has no such layer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x`, `y` or `z` is not below 16.
+    pub fn block_at(&self, x: usize, y: usize, z: usize, layer: usize) -> Option<&Value> {
+        assert!(
+            x < 16 && y < 16 && z < 16,
+            "block ({}, {}, {}) is outside the subchunk",
+            x,
+            y,
+            z
+        );
+        let storage = self.layers.get(layer)?;
+        storage
+            .palette
+            .get(storage.indices[(x * 16 + z) * 16 + y] as usize)
+    }
+
+    /// Return the block states used in a layer.
+    ///
+    /// # Returns
+    ///
+    /// The palette of the layer, or `None` if the subchunk has no such layer.
+    pub fn palette(&self, layer: usize) -> Option<&[Value]> {
+        self.layers.get(layer).map(|storage| &storage.palette[..])
+    }
+}
+
+/// Cursor over a record that reports truncation as [`SubChunkError`].
+struct Reader<'a> {
+    record: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], SubChunkError> {
+        let bytes =
+            self.record
+                .get(self.offset..self.offset + len)
+                .ok_or(SubChunkError::Truncated {
+                    offset: self.offset,
+                })?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, SubChunkError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i32(&mut self) -> Result<i32, SubChunkError> {
+        Ok(i32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
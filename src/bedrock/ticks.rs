@@ -0,0 +1,269 @@
+use super::nbt::{self, Value};
+use super::{ChunkKey, Dimension, RecordTag};
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+
+/// Add `offset` to the block position of every compound in `value` that has
+/// integer `x`, `y` and `z` entries, like the entries of a `tickList`.
+fn offset_positions(value: &mut Value, (dx, dy, dz): (i32, i32, i32)) {
+    match value {
+        Value::Compound(entries) => {
+            let is_position = ["x", "y", "z"].iter().all(|axis| {
+                entries
+                    .iter()
+                    .any(|(name, value)| name == axis && matches!(value, Value::Int(_)))
+            });
+            for (name, value) in entries {
+                match (name.as_str(), value) {
+                    ("x", Value::Int(v)) if is_position => *v = v.wrapping_add(dx),
+                    ("y", Value::Int(v)) if is_position => *v = v.wrapping_add(dy),
+                    ("z", Value::Int(v)) if is_position => *v = v.wrapping_add(dz),
+                    (_, value) => offset_positions(value, (dx, dy, dz)),
+                }
+            }
+        }
+        Value::List { items, .. } => items
+            .iter_mut()
+            .for_each(|item| offset_positions(item, (dx, dy, dz))),
+        _ => {}
+    }
+}
+
+/// Encode NBT roots back to back, applying `offset` to their block positions.
+fn encode_ticks(roots: &[Value], offset: Option<(i32, i32, i32)>) -> Vec<u8> {
+    let mut record = Vec::new();
+    for root in roots {
+        match offset {
+            Some(offset) => {
+                let mut root = root.clone();
+                offset_positions(&mut root, offset);
+                nbt::encode_into(&root, &mut record);
+            }
+            None => nbt::encode_into(root, &mut record),
+        }
+    }
+    record
+}
+
+impl DB {
+    /// Read and decode an NBT record of a chunk; a missing record is empty.
+    fn chunk_nbt(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        tag: RecordTag,
+        options: &ReadOptions,
+    ) -> Result<Vec<Value>, String> {
+        match self.get(ChunkKey::new(x, z, dim, tag), options)? {
+            Some(record) => nbt::decode_all(&record)
+                .map_err(|e| format!("Invalid {} record in chunk ({}, {}): {}", tag, x, z, e)),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Read the scheduled block updates of a chunk.
+    ///
+    /// The [`RecordTag::PendingTicks`] record holds a compound with the
+    /// `currentTick` it was saved at and a `tickList` of entries, each with
+    /// the `blockState` to update, the `time` it is due and its absolute
+    /// `x`, `y` and `z` block position.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Value>)` - The root compounds of the record; empty if the chunk has none
+    /// * `Err(String)` - If the read failed or the record is not valid NBT
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// for root in db.pending_ticks(0, 0, Dimension::Overworld, &ReadOptions::new()).unwrap() {
+    ///     let ticks = root.get("tickList").and_then(|list| list.as_list()).unwrap_or_default();
+    ///     println!("{} pending ticks", ticks.len());
+    /// }
+    /// ```
+    pub fn pending_ticks(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Vec<Value>, String> {
+        self.chunk_nbt(x, z, dim, RecordTag::PendingTicks, options)
+    }
+
+    /// Replace the scheduled block updates of a chunk.
+    ///
+    /// When `offset` is given, it is added to the absolute block position of
+    /// every tick before writing, so that ticks copied along with a chunk
+    /// point at the blocks of the new location. An empty slice deletes the
+    /// record.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `ticks` - The root compounds to store, as returned by [`DB::pending_ticks`]
+    /// * `offset` - Optional `(x, y, z)` block offset to apply to tick positions
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written or deleted
+    /// * `Err(String)` - If an error occurred during the write operation
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions, WriteOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// // Move the ticks of chunk (0, 0) to chunk (2, 0)
+    /// let ticks = db.pending_ticks(0, 0, Dimension::Overworld, &ReadOptions::new()).unwrap();
+    /// db.set_pending_ticks(2, 0, Dimension::Overworld, &ticks, Some((32, 0, 0)), &WriteOptions::new())
+    ///     .unwrap();
+    /// ```
+    pub fn set_pending_ticks(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        ticks: &[Value],
+        offset: Option<(i32, i32, i32)>,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        let key = ChunkKey::new(x, z, dim, RecordTag::PendingTicks);
+        if ticks.is_empty() {
+            return self.delete(key, options);
+        }
+        self.put(key, encode_ticks(ticks, offset), options)
+    }
+
+    /// Read the random ticks of a chunk.
+    ///
+    /// The [`RecordTag::RandomTicks`] record has the same layout as the
+    /// pending ticks: a compound with a `tickList` of entries carrying
+    /// absolute block positions.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Value>)` - The root compounds of the record; empty if the chunk has none
+    /// * `Err(String)` - If the read failed or the record is not valid NBT
+    pub fn random_ticks(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Vec<Value>, String> {
+        self.chunk_nbt(x, z, dim, RecordTag::RandomTicks, options)
+    }
+
+    /// Replace the random ticks of a chunk.
+    ///
+    /// `offset` works as in [`DB::set_pending_ticks`]. An empty slice
+    /// deletes the record.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `ticks` - The root compounds to store, as returned by [`DB::random_ticks`]
+    /// * `offset` - Optional `(x, y, z)` block offset to apply to tick positions
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written or deleted
+    /// * `Err(String)` - If an error occurred during the write operation
+    pub fn set_random_ticks(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        ticks: &[Value],
+        offset: Option<(i32, i32, i32)>,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        let key = ChunkKey::new(x, z, dim, RecordTag::RandomTicks);
+        if ticks.is_empty() {
+            return self.delete(key, options);
+        }
+        self.put(key, encode_ticks(ticks, offset), options)
+    }
+
+    /// Read the biome state record of a chunk.
+    ///
+    /// The [`RecordTag::BiomeState`] record keeps per-biome state such as
+    /// snow accumulation in a small binary layout rather than NBT, and holds
+    /// no block positions, so it is returned as stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Vec<u8>))` - The raw record
+    /// * `Ok(None)` - If the chunk has no biome state
+    /// * `Err(String)` - If an error occurred during the read operation
+    pub fn biome_state(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Option<Vec<u8>>, String> {
+        self.get(ChunkKey::new(x, z, dim, RecordTag::BiomeState), options)
+    }
+
+    /// Write the biome state record of a chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `state` - The raw record
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written
+    /// * `Err(String)` - If an error occurred during the write operation
+    pub fn set_biome_state(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        state: impl AsRef<[u8]>,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        let key = ChunkKey::new(x, z, dim, RecordTag::BiomeState);
+        self.put(key, state, options)
+    }
+}
@@ -0,0 +1,243 @@
+use crate::bedrock::Dimension;
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+use crate::write_batch::WriteBatch;
+
+/// Prefix shared by every village record key.
+const VILLAGE_PREFIX: &str = "VILLAGE_";
+
+/// One of the four records the game stores per village.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum VillageRecord {
+    /// Bounds and state of the village (`_INFO`).
+    Info,
+    /// Villagers, golems and cats belonging to the village (`_DWELLERS`).
+    Dwellers,
+    /// Player reputation (`_PLAYERS`).
+    Players,
+    /// Claimed beds, bells and job sites (`_POI`).
+    Poi,
+}
+
+impl VillageRecord {
+    /// Every record kind, in the order of [`VillageRecords`]' fields.
+    pub const ALL: [VillageRecord; 4] = [
+        VillageRecord::Info,
+        VillageRecord::Dwellers,
+        VillageRecord::Players,
+        VillageRecord::Poi,
+    ];
+
+    /// Return the suffix the record kind has in keys, like `INFO`.
+    pub fn suffix(self) -> &'static str {
+        match self {
+            VillageRecord::Info => "INFO",
+            VillageRecord::Dwellers => "DWELLERS",
+            VillageRecord::Players => "PLAYERS",
+            VillageRecord::Poi => "POI",
+        }
+    }
+}
+
+/// The key of a village record.
+///
+/// Keys look like `VILLAGE_<dimension>_<uuid>_<record>`, with the
+/// dimension written as `Overworld`, `Nether` or `TheEnd`. Older worlds
+/// store `VILLAGE_<uuid>_<record>` instead; those keys parse with no
+/// dimension. Custom dimensions are written as their numeric id.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bleveldb::bedrock::{Dimension, VillageKey, VillageRecord};
+///
+/// let key = VillageKey::parse(b"VILLAGE_Nether_5b4b1c0e-7f0d-4b5c-a3c7-2d2e9f6c8a11_POI").unwrap();
+/// assert_eq!(key.dimension, Some(Dimension::Nether));
+/// assert_eq!(key.record, VillageRecord::Poi);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VillageKey {
+    /// The dimension segment, or `None` for keys written before it existed.
+    pub dimension: Option<Dimension>,
+    /// The village's UUID, as written in the key.
+    pub uuid: String,
+    /// Which of the village's records the key points at.
+    pub record: VillageRecord,
+}
+
+impl VillageKey {
+    /// Parse a village key, returning `None` for keys that are not village keys.
+    pub fn parse(key: &[u8]) -> Option<VillageKey> {
+        let key = std::str::from_utf8(key)
+            .ok()?
+            .strip_prefix(VILLAGE_PREFIX)?;
+        let (rest, suffix) = key.rsplit_once('_')?;
+        let record = VillageRecord::ALL
+            .into_iter()
+            .find(|record| record.suffix() == suffix)?;
+        let (dimension, uuid) = match rest.split_once('_') {
+            Some((name, uuid)) => (Some(parse_dimension_name(name)?), uuid),
+            None => (None, rest),
+        };
+        if uuid.is_empty() {
+            return None;
+        }
+        Some(VillageKey {
+            dimension,
+            uuid: uuid.to_string(),
+            record,
+        })
+    }
+
+    /// Return the encoded key.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut key = String::from(VILLAGE_PREFIX);
+        if let Some(dimension) = self.dimension {
+            key.push_str(&dimension_name(dimension));
+            key.push('_');
+        }
+        key.push_str(&self.uuid);
+        key.push('_');
+        key.push_str(self.record.suffix());
+        key.into_bytes()
+    }
+}
+
+/// Return the name a dimension has in village keys.
+fn dimension_name(dimension: Dimension) -> String {
+    match dimension {
+        Dimension::Overworld => "Overworld".to_string(),
+        Dimension::Nether => "Nether".to_string(),
+        Dimension::End => "TheEnd".to_string(),
+        Dimension::Custom(id) => id.to_string(),
+    }
+}
+
+/// Parse the dimension segment of a village key.
+fn parse_dimension_name(name: &str) -> Option<Dimension> {
+    match name {
+        "Overworld" => Some(Dimension::Overworld),
+        "Nether" => Some(Dimension::Nether),
+        "TheEnd" => Some(Dimension::End),
+        id => id.parse().ok().map(Dimension::parse),
+    }
+}
+
+/// The records of one village, as returned by [`DB::villages`].
+///
+/// Each record is `None` if the world does not store it; villages being
+/// created or abandoned commonly lack some of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VillageRecords {
+    /// The dimension segment of the keys, or `None` for old-style keys.
+    pub dimension: Option<Dimension>,
+    /// The village's UUID.
+    pub uuid: String,
+    /// The `_INFO` record.
+    pub info: Option<Vec<u8>>,
+    /// The `_DWELLERS` record.
+    pub dwellers: Option<Vec<u8>>,
+    /// The `_PLAYERS` record.
+    pub players: Option<Vec<u8>>,
+    /// The `_POI` record.
+    pub poi: Option<Vec<u8>>,
+}
+
+impl VillageRecords {
+    fn slot(&mut self, record: VillageRecord) -> &mut Option<Vec<u8>> {
+        match record {
+            VillageRecord::Info => &mut self.info,
+            VillageRecord::Dwellers => &mut self.dwellers,
+            VillageRecord::Players => &mut self.players,
+            VillageRecord::Poi => &mut self.poi,
+        }
+    }
+}
+
+impl DB {
+    /// Read every village, grouping the records of each.
+    ///
+    /// With `Some(dim)` only villages whose keys name that dimension are
+    /// returned; old-style keys without a dimension segment are only
+    /// included when `dim` is `None`. Villages come out in key order.
+    ///
+    /// # Arguments
+    ///
+    /// * `dim` - The dimension to list villages of, or `None` for all of them
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<VillageRecords>)` - The records of each village
+    /// * `Err(String)` - If an error occurred while reading the database
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// for village in db.villages(Some(Dimension::Overworld), &ReadOptions::new()).unwrap() {
+    ///     println!("{} has dwellers: {}", village.uuid, village.dwellers.is_some());
+    /// }
+    /// ```
+    pub fn villages(
+        &self,
+        dim: Option<Dimension>,
+        options: &ReadOptions,
+    ) -> Result<Vec<VillageRecords>, String> {
+        let mut villages: Vec<VillageRecords> = Vec::new();
+        let mut iter = self.prefix_iter(VILLAGE_PREFIX.as_bytes(), options);
+        for (key, value) in iter.by_ref() {
+            let Some(key) = VillageKey::parse(&key) else {
+                continue;
+            };
+            if dim.is_some() && key.dimension != dim {
+                continue;
+            }
+            // A village's records only differ in their suffix, so they are adjacent
+            let same_village = villages
+                .last()
+                .is_some_and(|last| last.dimension == key.dimension && last.uuid == key.uuid);
+            if !same_village {
+                villages.push(VillageRecords {
+                    dimension: key.dimension,
+                    uuid: key.uuid,
+                    ..Default::default()
+                });
+            }
+            *villages.last_mut().unwrap().slot(key.record) = Some(value);
+        }
+        iter.status()?;
+        Ok(villages)
+    }
+
+    /// Delete every record of a village in one batch.
+    ///
+    /// Records are matched by UUID alone, so both old-style keys and keys of
+    /// any dimension are removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - The UUID of the village
+    /// * `options` - Write options used for the batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of records deleted
+    /// * `Err(String)` - If the database is read-only, or reading or writing failed
+    pub fn delete_village(&self, uuid: &str, options: &WriteOptions) -> Result<usize, String> {
+        self.check_writable()?;
+        let mut batch = WriteBatch::new();
+        let mut iter = self.prefix_iter(VILLAGE_PREFIX.as_bytes(), &ReadOptions::new());
+        for (key, _) in iter.by_ref() {
+            if VillageKey::parse(&key).is_some_and(|parsed| parsed.uuid == uuid) {
+                batch.delete(key);
+            }
+        }
+        iter.status()?;
+        self.write(&batch, options)?;
+        Ok(batch.len())
+    }
+}
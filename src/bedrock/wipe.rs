@@ -0,0 +1,228 @@
+use std::collections::HashSet;
+
+use super::actors::parse_digest;
+use crate::bedrock::{Dimension, KeyDescription, describe_key};
+use crate::db::{DB, DEFAULT_BATCH_BYTES};
+use crate::options::{ReadOptions, WriteOptions};
+use crate::write_batch::WriteBatch;
+
+/// What [`DB::wipe_dimension`] removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WipeReport {
+    /// Number of chunk records deleted, subchunks included.
+    pub chunk_records: u64,
+    /// Number of `digp` actor digests deleted.
+    pub digests: u64,
+    /// Number of `actorprefix` records deleted.
+    pub actors: u64,
+    /// Number of village records deleted.
+    pub villages: u64,
+    /// Number of portals removed from the `portals` record. Always zero
+    /// without the `nbt` feature.
+    pub portals: u64,
+    /// Whether the deleted key range was compacted afterwards.
+    pub compacted: bool,
+}
+
+impl WipeReport {
+    /// Return the number of keys deleted. Portals are entries of a record
+    /// that is rewritten, so they are not counted.
+    pub fn total(&self) -> u64 {
+        self.chunk_records + self.digests + self.actors + self.villages
+    }
+}
+
+/// Deletes committed in batches of about [`DEFAULT_BATCH_BYTES`] of keys,
+/// tracking the smallest and largest key deleted.
+struct CappedDeletes<'a> {
+    db: &'a DB,
+    options: &'a WriteOptions,
+    batch: WriteBatch,
+    pending_bytes: usize,
+    range: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+impl CappedDeletes<'_> {
+    fn delete(&mut self, key: &[u8]) -> Result<(), String> {
+        match &mut self.range {
+            Some((first, last)) => {
+                if key < first.as_slice() {
+                    *first = key.to_vec();
+                }
+                if key > last.as_slice() {
+                    *last = key.to_vec();
+                }
+            }
+            None => self.range = Some((key.to_vec(), key.to_vec())),
+        }
+        self.batch.delete(key);
+        self.pending_bytes += key.len();
+        if self.pending_bytes >= DEFAULT_BATCH_BYTES {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        if self.pending_bytes > 0 {
+            self.db.write(&self.batch, self.options)?;
+            self.batch.clear();
+            self.pending_bytes = 0;
+        }
+        Ok(())
+    }
+}
+
+impl DB {
+    /// Delete every record that belongs to a dimension, such as to let the
+    /// game regenerate the Nether.
+    ///
+    /// One pass over a snapshot deletes the dimension's chunk records, its
+    /// `digp` digests and the actors they list, and the village records
+    /// whose keys name the dimension. Village keys written before they
+    /// carried a dimension are kept, as their dimension is unknown. With the
+    /// `nbt` feature, portals in the dimension are also dropped from the
+    /// `portals` record.
+    ///
+    /// Overworld keys have no dimension field, so every key is classified by
+    /// its full layout rather than by prefix: wiping another dimension never
+    /// touches Overworld records, and wiping the Overworld leaves the other
+    /// dimensions alone.
+    ///
+    /// Deletes are committed once a batch reaches about
+    /// [`DEFAULT_BATCH_BYTES`] of keys, so the wipe as a whole is not atomic;
+    /// the game must not have the world open. The wipe holds the same mutex
+    /// as [`DB::put_if`], so `put_if`, the actor and chunk helpers and, with
+    /// the `update-lock` feature, [`DB::update`] wait for it through this
+    /// handle and its clones. Plain `put`/`delete`/`write` calls, other `DB`
+    /// handles and other processes are not excluded and can interleave with
+    /// its batches.
+    ///
+    /// The `portals` record is rewritten before any chunk is deleted, so a
+    /// failure partway through leaves the portals already removed. With
+    /// `compact`, the compacted range runs from the smallest to the largest
+    /// deleted key; for the Overworld that spans nearly the whole keyspace.
+    ///
+    /// # Arguments
+    ///
+    /// * `dim` - The dimension to wipe
+    /// * `compact` - Whether to compact the range of deleted keys afterwards to reclaim space
+    /// * `options` - Write options used for every batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(WipeReport)` - What was deleted
+    /// * `Err(String)` - If the database is read-only, a digest or the `portals` record is malformed, or reading or writing failed; earlier batches stay applied
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, WriteOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let report = db.wipe_dimension(Dimension::Nether, true, &WriteOptions::new()).unwrap();
+    /// println!("Deleted {} keys", report.total());
+    /// ```
+    pub fn wipe_dimension(
+        &self,
+        dim: Dimension,
+        compact: bool,
+        options: &WriteOptions,
+    ) -> Result<WipeReport, String> {
+        self.check_writable()?;
+        let _guard = self.lock_writes();
+        let (_snapshot, read_options) = self.consistent_read_options(&ReadOptions::new());
+        read_options.fill_cache(false);
+
+        let dim = Dimension::parse(dim.id());
+        // First, so that a malformed `portals` record fails before anything is deleted
+        let mut report = WipeReport {
+            portals: self.remove_portals_in(dim, &read_options, options)?,
+            ..Default::default()
+        };
+        let mut deletes = CappedDeletes {
+            db: self,
+            options,
+            batch: WriteBatch::new(),
+            pending_bytes: 0,
+            range: None,
+        };
+        let mut stored_actors = HashSet::new();
+        let mut listed_actors = Vec::new();
+
+        let mut iter = self.iter(&read_options);
+        iter.seek_to_first();
+        while let (Some(key), Some(value)) = (iter.key_ref(), iter.value_ref()) {
+            match describe_key(key) {
+                KeyDescription::ChunkRecord { dim: key_dim, .. } if key_dim == dim => {
+                    deletes.delete(key)?;
+                    report.chunk_records += 1;
+                }
+                KeyDescription::ActorDigest { dim: key_dim, .. } if key_dim == dim => {
+                    listed_actors.extend(parse_digest(value)?);
+                    deletes.delete(key)?;
+                    report.digests += 1;
+                }
+                KeyDescription::ActorData(id) => {
+                    stored_actors.insert(id);
+                }
+                KeyDescription::Village(village) if village.dimension == Some(dim) => {
+                    deletes.delete(key)?;
+                    report.villages += 1;
+                }
+                _ => {}
+            }
+            iter.next_native();
+        }
+        iter.status()?;
+        drop(iter);
+
+        // `actorprefix` keys sort before `digp` keys, so actors are deleted last
+        for id in listed_actors {
+            if stored_actors.remove(&id) {
+                deletes.delete(&id.key())?;
+                report.actors += 1;
+            }
+        }
+        deletes.flush()?;
+
+        if compact && let Some((first, last)) = &deletes.range {
+            self.compact_range(Some(first), Some(last))?;
+            report.compacted = true;
+        }
+        Ok(report)
+    }
+
+    /// Drop the portals in `dim` from the `portals` record.
+    #[cfg(feature = "nbt")]
+    fn remove_portals_in(
+        &self,
+        dim: Dimension,
+        read_options: &ReadOptions,
+        options: &WriteOptions,
+    ) -> Result<u64, String> {
+        let Some(mut portals) = self.get_portals(read_options)? else {
+            return Ok(0);
+        };
+        let before = portals.portals.len();
+        portals
+            .portals
+            .retain(|portal| Dimension::parse(portal.dimension.id()) != dim);
+        let removed = before - portals.portals.len();
+        if removed > 0 {
+            self.set_portals(&portals, options)?;
+        }
+        Ok(removed as u64)
+    }
+
+    #[cfg(not(feature = "nbt"))]
+    fn remove_portals_in(
+        &self,
+        _dim: Dimension,
+        _read_options: &ReadOptions,
+        _options: &WriteOptions,
+    ) -> Result<u64, String> {
+        Ok(0)
+    }
+}
@@ -0,0 +1,541 @@
+use super::chunk_key::chunk_prefix;
+use super::key_description::parse_chunk_record_key;
+use crate::bedrock::{ChunkKey, Dimension, RecordTag};
+use crate::db::DB;
+use crate::iterator::DBIterator;
+use crate::options::{ReadOptions, WriteOptions};
+use crate::util::prefix_successor;
+use crate::write_batch::WriteBatch;
+
+impl DB {
+    /// Read the blob of a single subchunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `y_index` - Subchunk y index; negative below y=0 since 1.18
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Vec<u8>))` - The raw `SubChunkPrefix` record
+    /// * `Ok(None)` - If the subchunk is not stored
+    /// * `Err(String)` - If an error occurred during the read operation
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// // The lowest subchunk of the Overworld chunk at (0, 0)
+    /// let blob = db
+    ///     .get_subchunk(0, 0, Dimension::Overworld, -4, &ReadOptions::new())
+    ///     .unwrap();
+    /// ```
+    pub fn get_subchunk(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        y_index: i8,
+        options: &ReadOptions,
+    ) -> Result<Option<Vec<u8>>, String> {
+        self.get(ChunkKey::subchunk(x, z, dim, y_index), options)
+    }
+
+    /// Write the blob of a single subchunk.
+    ///
+    /// The blob is stored as is; it is up to the caller to encode it in a
+    /// subchunk format the game understands.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `y_index` - Subchunk y index; negative below y=0 since 1.18
+    /// * `blob` - The raw `SubChunkPrefix` record
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the subchunk was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub fn put_subchunk(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        y_index: i8,
+        blob: impl AsRef<[u8]>,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        self.put(ChunkKey::subchunk(x, z, dim, y_index), blob, options)
+    }
+
+    /// List the subchunk y indices stored for a chunk, in numeric order.
+    ///
+    /// The index is the last key byte, so leveldb orders negative indices
+    /// after all the positive ones (-4 is stored as 0xfc). The indices are
+    /// sorted after the scan, so they come out from the bottom of the chunk
+    /// up regardless.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<i8>)` - The stored y indices, lowest first; empty if there are none
+    /// * `Err(String)` - If an error occurred while reading the database
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let read_opts = ReadOptions::new();
+    /// for y in db.subchunks_of(0, 0, Dimension::Overworld, &read_opts).unwrap() {
+    ///     let blob = db.get_subchunk(0, 0, Dimension::Overworld, y, &read_opts).unwrap();
+    ///     println!("y={}: {:?} bytes", i32::from(y) * 16, blob.map(|b| b.len()));
+    /// }
+    /// ```
+    pub fn subchunks_of(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Vec<i8>, String> {
+        let dim = Dimension::parse(dim.id());
+        let mut prefix = chunk_prefix(x, z, dim);
+        prefix.push(RecordTag::SubChunkPrefix.to_byte());
+
+        let mut indices = Vec::new();
+        let mut iter = self.iter(options);
+        iter.seek(&prefix);
+        while let Some(key) = iter.key_ref() {
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            // Keys of other dimensions can share the Overworld prefix
+            if let Ok(key) = ChunkKey::parse(key)
+                && key.dimension() == dim
+                && let Some(y) = key.subchunk_index()
+            {
+                indices.push(y);
+            }
+            iter.next_native();
+        }
+        iter.status()?;
+        indices.sort_unstable();
+        Ok(indices)
+    }
+
+    /// Iterate over the positions of every chunk stored in a dimension.
+    ///
+    /// The whole keyspace is scanned and every key that parses as a
+    /// [`ChunkKey`] of `dim` contributes its `(x, z)` position; string keys
+    /// such as `BiomeData` or `~local_player` and records of other dimensions
+    /// are skipped, including string keys like `map_12345` that happen to
+    /// have the layout of a chunk key. All records of a chunk share the coordinate prefix and so
+    /// sort next to each other, which lets each position be reported once
+    /// without remembering the ones already seen.
+    ///
+    /// Positions come out in key order, which is not a spatial order since the
+    /// coordinates are stored little-endian. Read errors end the iteration
+    /// early; use [`DB::scan`] if they need to be told apart from the end of
+    /// the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `dim` - The dimension to list chunks of
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let nether_chunks = db.chunk_positions(Dimension::Nether, &ReadOptions::new()).count();
+    /// println!("The Nether has {} chunks", nether_chunks);
+    /// ```
+    pub fn chunk_positions(
+        &self,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> impl Iterator<Item = (i32, i32)> + '_ {
+        let dim = Dimension::parse(dim.id());
+        let mut iter = self.iter(options);
+        iter.seek_to_first();
+        let mut last = None;
+        std::iter::from_fn(move || {
+            while let Some(key) = iter.key_ref() {
+                let position = parse_chunk_record_key(key)
+                    .filter(|key| key.dimension() == dim)
+                    .map(|key| (key.x(), key.z()));
+                iter.next_native();
+                if position.is_some() && position != last {
+                    last = position;
+                    return position;
+                }
+            }
+            None
+        })
+    }
+
+    /// List the dimensions that have chunk data.
+    ///
+    /// The dimension field follows the chunk coordinates in the key, so no
+    /// key range holds a single dimension and there is nothing to probe
+    /// directly. This is a scan over the whole keyspace: it reads the first
+    /// record of each chunk outside the Overworld and seeks past the rest of
+    /// them, but Overworld records have to be read one by one, since their
+    /// keys can be prefixes of the keys of custom dimensions. In a typical
+    /// world, where nearly every key is an Overworld chunk record, it costs
+    /// about as much as iterating the database. Unlike a fixed list of
+    /// probes, this also finds dimensions added by add-ons.
+    ///
+    /// String keys that happen to have the layout of a chunk key, like
+    /// `map_12345`, are not counted as chunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Dimension>)` - The dimensions with data, in ascending order
+    /// * `Err(String)` - If an error occurred while reading the database
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let dimensions = db.dimensions(&ReadOptions::new()).unwrap();
+    /// if dimensions.contains(&Dimension::End) {
+    ///     println!("This world has been to the End");
+    /// }
+    /// ```
+    pub fn dimensions(&self, options: &ReadOptions) -> Result<Vec<Dimension>, String> {
+        let mut found = Vec::new();
+        let mut iter = self.iter(options);
+        iter.seek_to_first();
+        while let Some(key) = iter.key_ref() {
+            let Some(chunk_key) = parse_chunk_record_key(key) else {
+                iter.next_native();
+                continue;
+            };
+            if !found.contains(&chunk_key.dimension()) {
+                found.push(chunk_key.dimension());
+            }
+            if !chunk_key.dimension().has_key_field() {
+                // An Overworld key is a prefix of the keys of custom
+                // dimensions whose id starts with its tag, so nothing can
+                // be skipped safely
+                iter.next_native();
+                continue;
+            }
+            match prefix_successor(&key[..12]) {
+                Some(next) => iter.seek(&next),
+                None => break,
+            }
+        }
+        iter.status()?;
+        found.sort();
+        Ok(found)
+    }
+
+    /// Read the format version of a chunk.
+    ///
+    /// The version is stored under the `Version` tag (44) by current
+    /// versions of the game and under `LegacyVersion` (118) by older ones.
+    /// The current record wins when both exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the reads
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(u8))` - The chunk's version
+    /// * `Ok(None)` - If the chunk has neither record
+    /// * `Err(String)` - If a read failed or the record is empty
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// match db.chunk_version(0, 0, Dimension::Overworld, &ReadOptions::new()).unwrap() {
+    ///     Some(version) => println!("Chunk format {}", version),
+    ///     None => println!("Chunk not generated"),
+    /// }
+    /// ```
+    pub fn chunk_version(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Option<u8>, String> {
+        let (_snapshot, read_options) = self.consistent_read_options(options);
+        for tag in [RecordTag::Version, RecordTag::LegacyVersion] {
+            if let Some(value) = self.get(ChunkKey::new(x, z, dim, tag), &read_options)? {
+                return match value.first() {
+                    Some(&version) => Ok(Some(version)),
+                    None => Err(format!(
+                        "Empty {} record in chunk ({}, {}) of the {}",
+                        tag, x, z, dim
+                    )),
+                };
+            }
+        }
+        Ok(None)
+    }
+
+    /// Set the format version of a chunk.
+    ///
+    /// The version is written under the `Version` tag, and any
+    /// `LegacyVersion` record is deleted in the same batch so the two can
+    /// never disagree.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `version` - The new version
+    /// * `options` - Write options used for the batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the version was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub fn set_chunk_version(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        version: u8,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        let mut batch = WriteBatch::new();
+        batch.put(ChunkKey::new(x, z, dim, RecordTag::Version), [version]);
+        batch.delete(ChunkKey::new(x, z, dim, RecordTag::LegacyVersion));
+        self.write(&batch, options)
+    }
+
+    /// Check whether a chunk has been generated.
+    ///
+    /// Every generated chunk has a `Version` record, or a `LegacyVersion`
+    /// one if it was last saved before 1.16.100. The probe seeks an iterator
+    /// to those keys and compares them in place, so no value is copied;
+    /// that makes it cheap enough for map renderers to call for every
+    /// chunk on screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Chunk x coordinate
+    /// * `z` - Chunk z coordinate
+    /// * `dim` - The dimension of the chunk
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the chunk has a version record
+    /// * `Err(String)` - If an error occurred during the read operation
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// if !db.chunk_exists(10, -4, Dimension::Overworld, &ReadOptions::new()).unwrap() {
+    ///     println!("Chunk not generated");
+    /// }
+    /// ```
+    pub fn chunk_exists(
+        &self,
+        x: i32,
+        z: i32,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<bool, String> {
+        let mut iter = self.iter(options);
+        let exists = probe_version(&mut iter, x, z, dim);
+        iter.status()?;
+        Ok(exists)
+    }
+
+    /// Check whether several chunks of a dimension have been generated.
+    ///
+    /// The probes are sorted by key and answered with a single iterator, so
+    /// they read one consistent view and the seeks move forward through the
+    /// keyspace instead of starting over for every chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `positions` - The `(x, z)` chunk positions to check, in any order
+    /// * `dim` - The dimension of the chunks
+    /// * `options` - Read options controlling the behavior of the read operations
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<bool>)` - One entry per position, in input order
+    /// * `Err(String)` - If an error occurred during the reads
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let row: Vec<(i32, i32)> = (-16..16).map(|x| (x, 0)).collect();
+    /// let exists = db.chunks_exist(&row, Dimension::Overworld, &ReadOptions::new()).unwrap();
+    /// println!("{} of {} chunks generated", exists.iter().filter(|&&e| e).count(), row.len());
+    /// ```
+    pub fn chunks_exist(
+        &self,
+        positions: &[(i32, i32)],
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Vec<bool>, String> {
+        let mut order: Vec<usize> = (0..positions.len()).collect();
+        order.sort_by_cached_key(|&i| chunk_prefix(positions[i].0, positions[i].1, dim));
+
+        let mut iter = self.iter(options);
+        let mut exists = vec![false; positions.len()];
+        for i in order {
+            let (x, z) = positions[i];
+            exists[i] = probe_version(&mut iter, x, z, dim);
+        }
+        iter.status()?;
+        Ok(exists)
+    }
+
+    /// Iterate over the positions of the chunks inside a rectangle.
+    ///
+    /// Chunk keys start with the little-endian x and z coordinates, so a
+    /// region is not a contiguous key range and neighbouring z values are
+    /// scattered across the keyspace. Instead of scanning everything, this
+    /// does one seek per x value to skip columns with no chunks at all, and
+    /// then one seek per position in the columns that have some. That is far
+    /// cheaper than [`DB::chunk_positions`] for small regions of large
+    /// worlds; for regions covering most of the world a full scan wins.
+    ///
+    /// Positions come out ordered by x, then z. Read errors end the
+    /// iteration early.
+    ///
+    /// # Arguments
+    ///
+    /// * `dim` - The dimension to list chunks of
+    /// * `min` - The smallest `(x, z)` chunk coordinates to include
+    /// * `max` - The largest `(x, z)` chunk coordinates to include
+    /// * `options` - Read options used for the seeks
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// // The chunks within 8 chunks of the origin
+    /// for (x, z) in db.chunks_in_bounds(Dimension::Overworld, (-8, -8), (8, 8), &ReadOptions::new()) {
+    ///     println!("({}, {})", x, z);
+    /// }
+    /// ```
+    pub fn chunks_in_bounds(
+        &self,
+        dim: Dimension,
+        min: (i32, i32),
+        max: (i32, i32),
+        options: &ReadOptions,
+    ) -> impl Iterator<Item = (i32, i32)> + '_ {
+        let dim = Dimension::parse(dim.id());
+        let mut iter = self.iter(options);
+        let mut xs = min.0..=max.0;
+        let mut column: Option<(i32, std::ops::RangeInclusive<i32>)> = None;
+        std::iter::from_fn(move || {
+            loop {
+                if let Some((x, zs)) = &mut column {
+                    for z in zs.by_ref() {
+                        if seek_chunk(&mut iter, *x, z, dim) {
+                            return Some((*x, z));
+                        }
+                    }
+                    column = None;
+                }
+                let x = xs.next()?;
+                let x_prefix = x.to_le_bytes();
+                iter.seek(&x_prefix);
+                if iter.key_ref().is_some_and(|key| key.starts_with(&x_prefix)) {
+                    column = Some((x, min.1..=max.1));
+                }
+            }
+        })
+    }
+}
+
+/// Move `iter` to a record of the chunk at `(x, z)` in `dim`.
+///
+/// # Returns
+///
+/// `true` if the chunk has a record, with `iter` positioned on it.
+pub(crate) fn seek_chunk(iter: &mut DBIterator<'_>, x: i32, z: i32, dim: Dimension) -> bool {
+    let prefix = chunk_prefix(x, z, dim);
+    let mut target = prefix.clone();
+    if !dim.has_key_field() {
+        // Skip the Nether and End records that share the Overworld prefix
+        target.push(RecordTag::Data3D.to_byte());
+    }
+    iter.seek(&target);
+    while let Some(key) = iter.key_ref() {
+        if !key.starts_with(&prefix) {
+            return false;
+        }
+        if ChunkKey::parse(key).is_ok_and(|key| key.dimension() == dim) {
+            return true;
+        }
+        iter.next_native();
+    }
+    false
+}
+
+/// Seek `iter` to the version records of the chunk at `(x, z)` in `dim`.
+///
+/// # Returns
+///
+/// `true` if the chunk has a `Version` or `LegacyVersion` record.
+fn probe_version(iter: &mut DBIterator<'_>, x: i32, z: i32, dim: Dimension) -> bool {
+    [RecordTag::Version, RecordTag::LegacyVersion]
+        .into_iter()
+        .any(|tag| {
+            let key = ChunkKey::new(x, z, dim, tag);
+            iter.seek(key.as_bytes());
+            iter.key_ref() == Some(key.as_bytes())
+        })
+}
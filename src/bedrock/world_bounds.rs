@@ -0,0 +1,124 @@
+use super::key_description::parse_chunk_record_key;
+use crate::bedrock::Dimension;
+use crate::db::DB;
+use crate::options::ReadOptions;
+
+/// The smallest rectangle of chunks holding every chunk of a dimension, as
+/// returned by [`DB::world_bounds`]. Both corners are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChunkBounds {
+    /// Smallest chunk x coordinate.
+    pub min_x: i32,
+    /// Smallest chunk z coordinate.
+    pub min_z: i32,
+    /// Largest chunk x coordinate.
+    pub max_x: i32,
+    /// Largest chunk z coordinate.
+    pub max_z: i32,
+}
+
+impl ChunkBounds {
+    /// Return the number of chunks along the x axis.
+    pub fn width(&self) -> u64 {
+        (self.max_x as i64 - self.min_x as i64 + 1) as u64
+    }
+
+    /// Return the number of chunks along the z axis.
+    pub fn depth(&self) -> u64 {
+        (self.max_z as i64 - self.min_z as i64 + 1) as u64
+    }
+
+    /// Return the number of chunk positions in the rectangle, generated or not.
+    pub fn area(&self) -> u64 {
+        self.width() * self.depth()
+    }
+
+    /// Return whether the chunk at `(x, z)` lies within the bounds.
+    pub fn contains(&self, x: i32, z: i32) -> bool {
+        (self.min_x..=self.max_x).contains(&x) && (self.min_z..=self.max_z).contains(&z)
+    }
+
+    /// Return the block x and z coordinates of the corners, both inclusive.
+    ///
+    /// The coordinates are widened to `i64`, as chunk coordinates near the
+    /// ends of the `i32` range have block coordinates outside it.
+    pub fn to_block_coords(&self) -> ((i64, i64), (i64, i64)) {
+        (
+            (self.min_x as i64 * 16, self.min_z as i64 * 16),
+            (self.max_x as i64 * 16 + 15, self.max_z as i64 * 16 + 15),
+        )
+    }
+
+    fn extend(&mut self, x: i32, z: i32) {
+        self.min_x = self.min_x.min(x);
+        self.min_z = self.min_z.min(z);
+        self.max_x = self.max_x.max(x);
+        self.max_z = self.max_z.max(z);
+    }
+}
+
+impl DB {
+    /// Compute the extent of the chunks stored for a dimension.
+    ///
+    /// Chunk keys start with the coordinates in little-endian order, so the
+    /// lowest and highest keys belong to chunks whose low coordinate byte is
+    /// smallest and largest, not to the chunks at the edges of the world.
+    /// There is no shortcut through the key order, and every key is visited.
+    /// String keys with the layout of a chunk key, like `map_12345`, are
+    /// skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `dim` - The dimension to measure
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(ChunkBounds))` - The bounds of the stored chunks
+    /// * `Ok(None)` - If the dimension has no chunks
+    /// * `Err(String)` - If reading the database failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::Dimension;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// if let Some(bounds) = db.world_bounds(Dimension::Overworld, &ReadOptions::new()).unwrap() {
+    ///     let ((min_x, min_z), (max_x, max_z)) = bounds.to_block_coords();
+    ///     println!("Blocks ({}, {}) to ({}, {})", min_x, min_z, max_x, max_z);
+    /// }
+    /// ```
+    pub fn world_bounds(
+        &self,
+        dim: Dimension,
+        options: &ReadOptions,
+    ) -> Result<Option<ChunkBounds>, String> {
+        let dim = Dimension::parse(dim.id());
+        let mut bounds: Option<ChunkBounds> = None;
+        let mut iter = self.iter(options);
+        iter.seek_to_first();
+        while let Some(key) = iter.key_ref() {
+            if let Some(key) = parse_chunk_record_key(key)
+                && key.dimension() == dim
+            {
+                let (x, z) = (key.x(), key.z());
+                match &mut bounds {
+                    Some(bounds) => bounds.extend(x, z),
+                    None => {
+                        bounds = Some(ChunkBounds {
+                            min_x: x,
+                            min_z: z,
+                            max_x: x,
+                            max_z: z,
+                        })
+                    }
+                }
+            }
+            iter.next_native();
+        }
+        iter.status()?;
+        Ok(bounds)
+    }
+}
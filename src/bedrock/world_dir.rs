@@ -0,0 +1,200 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::nbt::{self, Value};
+use super::validate_db_dir;
+use crate::db::DB;
+use crate::options::Options;
+
+/// Size of the header in front of the NBT payload of `level.dat`.
+const LEVEL_DAT_HEADER: usize = 8;
+
+/// A Bedrock world directory: the world database together with its
+/// `level.dat` and `levelname.txt`.
+///
+/// `level.dat` starts with an 8-byte header, the storage version and the
+/// payload length as little-endian 32-bit integers, followed by a
+/// little-endian NBT compound with the world settings. The header's storage
+/// version is kept and written back by [`WorldDir::save_level_dat`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use bleveldb::bedrock::WorldDir;
+/// use bleveldb::options::ReadOptions;
+///
+/// let world = WorldDir::open("minecraftWorlds/abc123=").unwrap();
+/// println!("{:?} was last played at {:?}", world.name(), world.last_played());
+/// let players = world.db().players(&ReadOptions::new()).count();
+/// println!("{} players", players);
+/// ```
+pub struct WorldDir {
+    path: PathBuf,
+    db: DB,
+    storage_version: i32,
+    level_dat: Value,
+    level_name: Option<String>,
+    /// The directory a `.mcworld` archive was extracted to, removed on drop.
+    /// Declared after `db` so that the database is closed first.
+    #[cfg(feature = "mcworld")]
+    pub(super) extracted: Option<super::mcworld::ExtractedDir>,
+}
+
+/// Split `level.dat` into its storage version and NBT payload.
+fn parse_level_dat(data: &[u8]) -> Result<(i32, Value), String> {
+    if data.len() < LEVEL_DAT_HEADER {
+        return Err(format!(
+            "level.dat is {} bytes, too short for its header",
+            data.len()
+        ));
+    }
+    let storage_version = i32::from_le_bytes(data[..4].try_into().unwrap());
+    let len = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+    let payload = &data[LEVEL_DAT_HEADER..];
+    if payload.len() != len {
+        return Err(format!(
+            "level.dat header announces {} bytes of NBT but {} follow",
+            len,
+            payload.len()
+        ));
+    }
+    let level_dat = nbt::decode(payload).map_err(|e| format!("Invalid level.dat: {}", e))?;
+    if level_dat.as_compound().is_none() {
+        return Err("level.dat does not hold a compound".to_string());
+    }
+    Ok((storage_version, level_dat))
+}
+
+impl WorldDir {
+    /// Open a world directory with [`Options::bedrock`].
+    ///
+    /// The database directory is checked with [`validate_db_dir`] first, so
+    /// an incomplete copy is reported with a clear diagnosis.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The world directory, holding `db` and `level.dat`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(WorldDir)` - The opened world
+    /// * `Err(String)` - If the layout is wrong, `level.dat` is malformed or the database cannot be opened
+    pub fn open(path: impl AsRef<Path>) -> Result<WorldDir, String> {
+        WorldDir::open_with(path, &Options::bedrock())
+    }
+
+    /// Open a world directory with custom database options.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The world directory, holding `db` and `level.dat`
+    /// * `options` - Options used to open the database in `db`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(WorldDir)` - The opened world
+    /// * `Err(String)` - If the layout is wrong, `level.dat` is malformed or the database cannot be opened
+    pub fn open_with(path: impl AsRef<Path>, options: &Options) -> Result<WorldDir, String> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Err(format!("{} is not a directory", path.display()));
+        }
+        let level_dat_path = path.join("level.dat");
+        if !level_dat_path.is_file() {
+            return Err(format!("{} has no level.dat", path.display()));
+        }
+        let db_path = path.join("db");
+        if !db_path.is_dir() {
+            return Err(format!("{} has no db directory", path.display()));
+        }
+
+        let data = fs::read(&level_dat_path)
+            .map_err(|e| format!("Failed to read {}: {}", level_dat_path.display(), e))?;
+        let (storage_version, level_dat) = parse_level_dat(&data)?;
+        let level_name = match fs::read_to_string(path.join("levelname.txt")) {
+            Ok(name) => Some(name.trim_end_matches(['\r', '\n']).to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(format!("Failed to read levelname.txt: {}", e)),
+        };
+        validate_db_dir(&db_path).map_err(|issue| format!("{}: {}", db_path.display(), issue))?;
+        let db = DB::open(db_path, options)?;
+        Ok(WorldDir {
+            path: path.to_path_buf(),
+            db,
+            storage_version,
+            level_dat,
+            level_name,
+            #[cfg(feature = "mcworld")]
+            extracted: None,
+        })
+    }
+
+    /// Return the path of the world directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Return the world database.
+    pub fn db(&self) -> &DB {
+        &self.db
+    }
+
+    /// Return the NBT compound of `level.dat`.
+    pub fn level_dat(&self) -> &Value {
+        &self.level_dat
+    }
+
+    /// Return the NBT compound of `level.dat` for modification.
+    ///
+    /// Changes are kept in memory until [`WorldDir::save_level_dat`] is called.
+    pub fn level_dat_mut(&mut self) -> &mut Value {
+        &mut self.level_dat
+    }
+
+    /// Return the storage version from the `level.dat` header.
+    pub fn storage_version(&self) -> i32 {
+        self.storage_version
+    }
+
+    /// Return the name of the world.
+    ///
+    /// The name shown in the world list comes from `levelname.txt`; worlds
+    /// without one fall back to the `LevelName` entry of `level.dat`.
+    pub fn name(&self) -> Option<&str> {
+        self.level_name
+            .as_deref()
+            .or_else(|| self.level_dat.get("LevelName")?.as_str())
+    }
+
+    /// Return when the world was last played, in seconds since the Unix epoch.
+    pub fn last_played(&self) -> Option<i64> {
+        self.level_dat.get("LastPlayed")?.as_i64()
+    }
+
+    /// Write `level.dat` back with its header.
+    ///
+    /// The file is written next to the original and renamed over it, so a
+    /// failed save leaves the previous `level.dat` intact.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the file was written
+    /// * `Err(String)` - If the file could not be written
+    pub fn save_level_dat(&self) -> Result<(), String> {
+        let target = self.path.join("level.dat");
+        let temp = self.path.join("level.dat.tmp");
+        fs::write(&temp, self.level_dat_bytes())
+            .and_then(|()| fs::rename(&temp, &target))
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))
+    }
+
+    /// Encode `level.dat` with its header.
+    pub(super) fn level_dat_bytes(&self) -> Vec<u8> {
+        let payload = nbt::encode(&self.level_dat);
+        let mut data = Vec::with_capacity(LEVEL_DAT_HEADER + payload.len());
+        data.extend_from_slice(&self.storage_version.to_le_bytes());
+        data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        data.extend_from_slice(&payload);
+        data
+    }
+}
@@ -0,0 +1,96 @@
+use super::{ChunkKey, RecordTag};
+use crate::db::DB;
+use crate::options::ReadOptions;
+
+/// Keys read after each sampling seek.
+const KEYS_PER_SAMPLE: usize = 64;
+
+/// How the chunks of a world store their terrain, as reported by
+/// [`DB::detect_world_format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorldFormat {
+    /// A world saved before 1.0: terrain in one [`RecordTag::LegacyTerrain`]
+    /// record per chunk, alongside version records.
+    Legacy,
+    /// `LegacyTerrain` records without any version record, as left by the
+    /// earliest LevelDB-based releases or by tools that only copied terrain.
+    LegacyTerrainOnly,
+    /// Terrain in [`RecordTag::SubChunkPrefix`] records, as written since 1.0.
+    Modern,
+    /// Chunks in both layouts, as in a world that was opened in 1.0 or later
+    /// without visiting every chunk.
+    Mixed,
+}
+
+impl DB {
+    /// Guess how the chunks of the world store their terrain.
+    ///
+    /// Instead of reading every key, the scan seeks to 256 points spread
+    /// over the keyspace, one per possible first byte, and inspects up to 64
+    /// keys after each. The first key byte is the low byte of the chunk's x
+    /// coordinate, so the samples cover chunks all over the world; small
+    /// worlds are read completely.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(WorldFormat))` - The detected format
+    /// * `Ok(None)` - If no chunk records were found
+    /// * `Err(String)` - If an error occurred while reading the database
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::bedrock::WorldFormat;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// match db.detect_world_format(&ReadOptions::new()).unwrap() {
+    ///     Some(WorldFormat::Legacy | WorldFormat::LegacyTerrainOnly) => println!("Needs conversion"),
+    ///     Some(WorldFormat::Mixed) => println!("Partly converted"),
+    ///     Some(WorldFormat::Modern) => println!("Up to date"),
+    ///     None => println!("No chunks"),
+    /// }
+    /// ```
+    pub fn detect_world_format(
+        &self,
+        options: &ReadOptions,
+    ) -> Result<Option<WorldFormat>, String> {
+        let (mut legacy_terrain, mut subchunks, mut versions, mut chunks) =
+            (false, false, false, false);
+        let mut iter = self.iter(options);
+        for first in 0..=u8::MAX {
+            iter.seek(&[first]);
+            for _ in 0..KEYS_PER_SAMPLE {
+                let Some(key) = iter.key_ref() else { break };
+                if key[0] != first {
+                    break;
+                }
+                if let Ok(chunk) = ChunkKey::parse(key) {
+                    chunks = true;
+                    match chunk.tag() {
+                        RecordTag::LegacyTerrain => legacy_terrain = true,
+                        RecordTag::SubChunkPrefix => subchunks = true,
+                        RecordTag::Version | RecordTag::LegacyVersion => versions = true,
+                        _ => {}
+                    }
+                }
+                iter.next_native();
+            }
+        }
+        iter.status()?;
+
+        Ok(match (legacy_terrain, subchunks) {
+            (true, true) => Some(WorldFormat::Mixed),
+            (true, false) if versions => Some(WorldFormat::Legacy),
+            (true, false) => Some(WorldFormat::LegacyTerrainOnly),
+            // Chunks without terrain records are all air, which only
+            // current versions save
+            (false, _) if chunks => Some(WorldFormat::Modern),
+            (false, _) => None,
+        })
+    }
+}
@@ -0,0 +1,400 @@
+//! Typed forms of the small world-wide NBT records.
+//!
+//! Each struct keeps the fields it does not know in an `extra` list, in
+//! stored order, and writes them back after the known ones, so records saved
+//! by newer game versions survive a read-modify-write.
+
+use super::Dimension;
+use super::keys::{AUTONOMOUS_ENTITIES, BIOME_DATA, MOB_EVENTS, PORTALS};
+use super::nbt::{self, Fields, Value, compound, compound_list};
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+
+const ENDER_DRAGON_EVENT: &str = "minecraft:ender_dragon_event";
+const PILLAGER_PATROLS_EVENT: &str = "minecraft:pillager_patrols_event";
+const WANDERING_TRADER_EVENT: &str = "minecraft:wandering_trader_event";
+
+/// The snow level of one biome, from the `BiomeData` record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BiomeSnow {
+    /// The biome id.
+    pub biome: u8,
+    /// How much snow has built up in the biome.
+    pub snow_accumulation: f32,
+    /// Fields the parser does not know, in stored order.
+    pub extra: Vec<(String, Value)>,
+}
+
+/// The `BiomeData` record: per-biome state that is not tied to a chunk.
+///
+/// Not to be confused with the per-chunk `Data2D` and `Data3D` biome maps.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BiomeData {
+    /// The biomes with stored state.
+    pub biomes: Vec<BiomeSnow>,
+    /// Fields the parser does not know, in stored order.
+    pub extra: Vec<(String, Value)>,
+}
+
+/// The `mobevents` record: which mob events may happen.
+///
+/// Each toggle is `None` if the record does not store it, in which case it
+/// is not written back either.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MobEvents {
+    /// The switch for all mob events.
+    pub events_enabled: Option<bool>,
+    /// Whether the ender dragon fight can start.
+    pub ender_dragon: Option<bool>,
+    /// Whether pillager patrols spawn.
+    pub pillager_patrols: Option<bool>,
+    /// Whether the wandering trader spawns.
+    pub wandering_trader: Option<bool>,
+    /// Fields the parser does not know, in stored order.
+    pub extra: Vec<(String, Value)>,
+}
+
+/// A nether portal, from the `portals` record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Portal {
+    /// The dimension the portal is in.
+    pub dimension: Dimension,
+    /// The block position of the portal's corner.
+    pub position: (i32, i32, i32),
+    /// The width of the portal.
+    pub span: i8,
+    /// 1 if the portal extends along the x axis.
+    pub x_axis: i8,
+    /// 1 if the portal extends along the z axis.
+    pub z_axis: i8,
+    /// Fields the parser does not know, in stored order.
+    pub extra: Vec<(String, Value)>,
+}
+
+/// The `portals` record: every nether portal the game knows of.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Portals {
+    /// The portals, from the `PortalRecords` list.
+    pub portals: Vec<Portal>,
+    /// Unknown fields of the `data` compound, in stored order.
+    pub data_extra: Vec<(String, Value)>,
+    /// Unknown fields of the root compound, in stored order.
+    pub extra: Vec<(String, Value)>,
+}
+
+/// The `AutonomousEntities` record: entities not saved with any chunk.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AutonomousEntities {
+    /// The NBT compound of each entity.
+    pub entities: Vec<Value>,
+    /// Fields the parser does not know, in stored order.
+    pub extra: Vec<(String, Value)>,
+}
+
+impl BiomeSnow {
+    fn from_nbt(value: Value) -> Result<Self, String> {
+        let mut fields = Fields::new("BiomeData entry", value)?;
+        Ok(BiomeSnow {
+            biome: fields.byte("id")? as u8,
+            snow_accumulation: fields.float("snowAccumulation")?,
+            extra: fields.entries,
+        })
+    }
+
+    fn to_nbt(&self) -> Value {
+        compound(
+            vec![
+                ("id", Value::Byte(self.biome as i8)),
+                ("snowAccumulation", Value::Float(self.snow_accumulation)),
+            ],
+            &self.extra,
+        )
+    }
+}
+
+impl BiomeData {
+    /// Parse the root compound of a `BiomeData` record.
+    pub fn from_nbt(value: Value) -> Result<BiomeData, String> {
+        let mut fields = Fields::new("BiomeData", value)?;
+        Ok(BiomeData {
+            biomes: fields.list("list", BiomeSnow::from_nbt)?,
+            extra: fields.entries,
+        })
+    }
+
+    /// Build the root compound of a `BiomeData` record.
+    pub fn to_nbt(&self) -> Value {
+        let biomes = self.biomes.iter().map(BiomeSnow::to_nbt).collect();
+        compound(vec![("list", compound_list(biomes))], &self.extra)
+    }
+}
+
+impl MobEvents {
+    /// Parse the root compound of a `mobevents` record.
+    pub fn from_nbt(value: Value) -> Result<MobEvents, String> {
+        let mut fields = Fields::new("mobevents", value)?;
+        Ok(MobEvents {
+            events_enabled: fields.flag("events_enabled")?,
+            ender_dragon: fields.flag(ENDER_DRAGON_EVENT)?,
+            pillager_patrols: fields.flag(PILLAGER_PATROLS_EVENT)?,
+            wandering_trader: fields.flag(WANDERING_TRADER_EVENT)?,
+            extra: fields.entries,
+        })
+    }
+
+    /// Build the root compound of a `mobevents` record.
+    pub fn to_nbt(&self) -> Value {
+        let known = [
+            ("events_enabled", self.events_enabled),
+            (ENDER_DRAGON_EVENT, self.ender_dragon),
+            (PILLAGER_PATROLS_EVENT, self.pillager_patrols),
+            (WANDERING_TRADER_EVENT, self.wandering_trader),
+        ]
+        .into_iter()
+        .filter_map(|(name, flag)| Some((name, Value::Byte(flag? as i8))))
+        .collect();
+        compound(known, &self.extra)
+    }
+}
+
+impl Portal {
+    fn from_nbt(value: Value) -> Result<Self, String> {
+        let mut fields = Fields::new("Portal record", value)?;
+        Ok(Portal {
+            dimension: Dimension::parse(fields.int("DimId")?),
+            position: (fields.int("TpX")?, fields.int("TpY")?, fields.int("TpZ")?),
+            span: fields.byte("Span")?,
+            x_axis: fields.byte("Xa")?,
+            z_axis: fields.byte("Za")?,
+            extra: fields.entries,
+        })
+    }
+
+    fn to_nbt(&self) -> Value {
+        let (x, y, z) = self.position;
+        compound(
+            vec![
+                ("DimId", Value::Int(self.dimension.id())),
+                ("Span", Value::Byte(self.span)),
+                ("TpX", Value::Int(x)),
+                ("TpY", Value::Int(y)),
+                ("TpZ", Value::Int(z)),
+                ("Xa", Value::Byte(self.x_axis)),
+                ("Za", Value::Byte(self.z_axis)),
+            ],
+            &self.extra,
+        )
+    }
+}
+
+impl Portals {
+    /// Parse the root compound of a `portals` record.
+    pub fn from_nbt(value: Value) -> Result<Portals, String> {
+        let mut fields = Fields::new("portals", value)?;
+        let (portals, data_extra) = match fields.take("data") {
+            Some(data) => {
+                let mut data = Fields::new("portals data", data)?;
+                (data.list("PortalRecords", Portal::from_nbt)?, data.entries)
+            }
+            None => (Vec::new(), Vec::new()),
+        };
+        Ok(Portals {
+            portals,
+            data_extra,
+            extra: fields.entries,
+        })
+    }
+
+    /// Build the root compound of a `portals` record.
+    pub fn to_nbt(&self) -> Value {
+        let portals = self.portals.iter().map(Portal::to_nbt).collect();
+        let data = compound(
+            vec![("PortalRecords", compound_list(portals))],
+            &self.data_extra,
+        );
+        compound(vec![("data", data)], &self.extra)
+    }
+}
+
+impl AutonomousEntities {
+    /// Parse the root compound of an `AutonomousEntities` record.
+    pub fn from_nbt(value: Value) -> Result<AutonomousEntities, String> {
+        let mut fields = Fields::new("AutonomousEntities", value)?;
+        Ok(AutonomousEntities {
+            entities: fields.list("AutonomousEntityList", |entity| {
+                // Anything else could not be written back as a compound list
+                let entity = Fields::new("AutonomousEntityList entry", entity)?;
+                Ok(Value::Compound(entity.entries))
+            })?,
+            extra: fields.entries,
+        })
+    }
+
+    /// Build the root compound of an `AutonomousEntities` record.
+    pub fn to_nbt(&self) -> Value {
+        compound(
+            vec![("AutonomousEntityList", compound_list(self.entities.clone()))],
+            &self.extra,
+        )
+    }
+}
+
+impl DB {
+    /// Read and parse the NBT record stored under a fixed key.
+    fn world_record<T>(
+        &self,
+        key: &[u8],
+        parse: impl FnOnce(Value) -> Result<T, String>,
+        options: &ReadOptions,
+    ) -> Result<Option<T>, String> {
+        let Some(record) = self.get(key, options)? else {
+            return Ok(None);
+        };
+        let value = nbt::decode(&record)
+            .map_err(|e| format!("Invalid {} record: {}", String::from_utf8_lossy(key), e))?;
+        parse(value).map(Some)
+    }
+
+    /// Read the world's `BiomeData` record.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(BiomeData))` - The parsed record
+    /// * `Ok(None)` - If the world has no `BiomeData` record
+    /// * `Err(String)` - If the read failed or the record is malformed
+    pub fn get_biome_data(&self, options: &ReadOptions) -> Result<Option<BiomeData>, String> {
+        self.world_record(BIOME_DATA, BiomeData::from_nbt, options)
+    }
+
+    /// Write the world's `BiomeData` record.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The record to store
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub fn set_biome_data(&self, data: &BiomeData, options: &WriteOptions) -> Result<(), String> {
+        self.put(BIOME_DATA, nbt::encode(&data.to_nbt()), options)
+    }
+
+    /// Read the world's `mobevents` record.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(MobEvents))` - The parsed record
+    /// * `Ok(None)` - If the world has no `mobevents` record
+    /// * `Err(String)` - If the read failed or the record is malformed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions, WriteOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// // Stop pillager patrols from spawning
+    /// let mut events = db.get_mob_events(&ReadOptions::new()).unwrap().unwrap_or_default();
+    /// events.pillager_patrols = Some(false);
+    /// db.set_mob_events(&events, &WriteOptions::new()).unwrap();
+    /// ```
+    pub fn get_mob_events(&self, options: &ReadOptions) -> Result<Option<MobEvents>, String> {
+        self.world_record(MOB_EVENTS, MobEvents::from_nbt, options)
+    }
+
+    /// Write the world's `mobevents` record.
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - The record to store
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub fn set_mob_events(&self, events: &MobEvents, options: &WriteOptions) -> Result<(), String> {
+        self.put(MOB_EVENTS, nbt::encode(&events.to_nbt()), options)
+    }
+
+    /// Read the world's `portals` record.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Portals))` - The parsed record
+    /// * `Ok(None)` - If the world has no `portals` record
+    /// * `Err(String)` - If the read failed or the record is malformed
+    pub fn get_portals(&self, options: &ReadOptions) -> Result<Option<Portals>, String> {
+        self.world_record(PORTALS, Portals::from_nbt, options)
+    }
+
+    /// Write the world's `portals` record.
+    ///
+    /// # Arguments
+    ///
+    /// * `portals` - The record to store
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub fn set_portals(&self, portals: &Portals, options: &WriteOptions) -> Result<(), String> {
+        self.put(PORTALS, nbt::encode(&portals.to_nbt()), options)
+    }
+
+    /// Read the world's `AutonomousEntities` record.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(AutonomousEntities))` - The parsed record
+    /// * `Ok(None)` - If the world has no `AutonomousEntities` record
+    /// * `Err(String)` - If the read failed or the record is malformed
+    pub fn get_autonomous_entities(
+        &self,
+        options: &ReadOptions,
+    ) -> Result<Option<AutonomousEntities>, String> {
+        self.world_record(AUTONOMOUS_ENTITIES, AutonomousEntities::from_nbt, options)
+    }
+
+    /// Write the world's `AutonomousEntities` record.
+    ///
+    /// # Arguments
+    ///
+    /// * `entities` - The record to store
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the record was written
+    /// * `Err(String)` - If the database is read-only or the write failed
+    pub fn set_autonomous_entities(
+        &self,
+        entities: &AutonomousEntities,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        self.put(
+            AUTONOMOUS_ENTITIES,
+            nbt::encode(&entities.to_nbt()),
+            options,
+        )
+    }
+}
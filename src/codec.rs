@@ -0,0 +1,185 @@
+//! Storing serde values, for tool bookkeeping kept next to the data.
+//!
+//! Values are encoded with bincode unless another [`SerdeCodec`] is picked
+//! through the `_with` variants of the methods, such as a JSON codec so the
+//! records stay readable in a hex dump.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use bleveldb::DB;
+//! use bleveldb::options::{Options, ReadOptions, WriteOptions};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct LastRun {
+//!     chunks: u64,
+//!     finished: bool,
+//! }
+//!
+//! # let db = DB::open("world/db", &Options::default()).unwrap();
+//! let run = LastRun { chunks: 1200, finished: true };
+//! db.put_serde(b"mytool:last_run", &run, &WriteOptions::new()).unwrap();
+//! let run: Option<LastRun> = db.get_serde(b"mytool:last_run", &ReadOptions::new()).unwrap();
+//! ```
+
+use std::fmt;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::db::DB;
+use crate::options::{ReadOptions, WriteOptions};
+use crate::write_batch::WriteBatch;
+
+/// A format serde values are stored in.
+pub trait SerdeCodec {
+    /// Encode a value.
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, String>;
+
+    /// Decode a value.
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, String>;
+}
+
+/// The default codec: bincode's compact binary encoding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bincode;
+
+impl SerdeCodec for Bincode {
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, String> {
+        bincode::serialize(value).map_err(|e| e.to_string())
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, String> {
+        bincode::deserialize(bytes).map_err(|e| e.to_string())
+    }
+}
+
+/// Why storing or loading a serde value failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SerdeError {
+    /// Reading or writing the database failed.
+    Db(String),
+    /// The value could not be encoded.
+    Encode(String),
+    /// The key is present but its value could not be decoded as the
+    /// requested type.
+    Decode(String),
+}
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerdeError::Db(e) => write!(f, "{}", e),
+            SerdeError::Encode(e) => write!(f, "Failed to encode value: {}", e),
+            SerdeError::Decode(e) => write!(f, "Failed to decode value: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SerdeError {}
+
+impl DB {
+    /// Encode a value with bincode and store it.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to write
+    /// * `value` - The value to encode
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the value was written
+    /// * `Err(SerdeError)` - If encoding or the write failed
+    pub fn put_serde<T: Serialize + ?Sized>(
+        &self,
+        key: impl AsRef<[u8]>,
+        value: &T,
+        options: &WriteOptions,
+    ) -> Result<(), SerdeError> {
+        self.put_serde_with::<Bincode, T>(key, value, options)
+    }
+
+    /// Encode a value with the codec `C` and store it.
+    ///
+    /// See [`DB::put_serde`].
+    pub fn put_serde_with<C: SerdeCodec, T: Serialize + ?Sized>(
+        &self,
+        key: impl AsRef<[u8]>,
+        value: &T,
+        options: &WriteOptions,
+    ) -> Result<(), SerdeError> {
+        let bytes = C::encode(value).map_err(SerdeError::Encode)?;
+        self.put(key, bytes, options).map_err(SerdeError::Db)
+    }
+
+    /// Read a value stored with [`DB::put_serde`].
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to read
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(T))` - The decoded value
+    /// * `Ok(None)` - If the key does not exist
+    /// * `Err(SerdeError::Decode)` - If the key exists but its value is not a valid `T`
+    /// * `Err(SerdeError::Db)` - If the read failed
+    pub fn get_serde<T: DeserializeOwned>(
+        &self,
+        key: impl AsRef<[u8]>,
+        options: &ReadOptions,
+    ) -> Result<Option<T>, SerdeError> {
+        self.get_serde_with::<Bincode, T>(key, options)
+    }
+
+    /// Read a value stored with the codec `C`.
+    ///
+    /// See [`DB::get_serde`].
+    pub fn get_serde_with<C: SerdeCodec, T: DeserializeOwned>(
+        &self,
+        key: impl AsRef<[u8]>,
+        options: &ReadOptions,
+    ) -> Result<Option<T>, SerdeError> {
+        match self.get(key, options).map_err(SerdeError::Db)? {
+            Some(bytes) => C::decode(&bytes).map(Some).map_err(SerdeError::Decode),
+            None => Ok(None),
+        }
+    }
+}
+
+impl WriteBatch {
+    /// Encode a value with bincode and queue a put of it.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to write
+    /// * `value` - The value to encode
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the put was queued
+    /// * `Err(SerdeError::Encode)` - If the value could not be encoded; the batch is unchanged
+    pub fn put_serde<T: Serialize + ?Sized>(
+        &mut self,
+        key: impl AsRef<[u8]>,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.put_serde_with::<Bincode, T>(key, value)
+    }
+
+    /// Encode a value with the codec `C` and queue a put of it.
+    ///
+    /// See [`WriteBatch::put_serde`].
+    pub fn put_serde_with<C: SerdeCodec, T: Serialize + ?Sized>(
+        &mut self,
+        key: impl AsRef<[u8]>,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        let bytes = C::encode(value).map_err(SerdeError::Encode)?;
+        self.put(key, bytes);
+        Ok(())
+    }
+}
@@ -1,9 +1,23 @@
 use bleveldb_sys as sys;
-use std::path::Path;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::{ControlFlow, RangeBounds};
+use std::path::{Path, PathBuf};
 use std::ptr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
-use crate::options::{Options, ReadOptions, WriteOptions};
-use crate::util::{error_message, to_cstring};
+use crate::iterator::{DBIterator, PrefixIter, RangeIter, RevIter, SnapshotIter};
+use crate::metrics::Metrics;
+use crate::options::{
+    Compression, FilterPolicy, Options, OptionsSettings, ReadOptions, WriteOptions,
+};
+use crate::pinned::PinnedValue;
+use crate::small_value::SmallValue;
+use crate::snapshot::Snapshot;
+use crate::trace::op_span;
+use crate::util::{error_message, path_to_cstring, unique_temp_dir};
+use crate::write_batch::WriteBatch;
 
 /// A handle to a LevelDB database.
 ///
@@ -31,16 +45,110 @@ use crate::util::{error_message, to_cstring};
 /// `DB` implements `Send` and `Sync`, meaning it can be safely shared between threads.
 /// However, individual operations should be synchronized externally if needed.
 ///
+/// `DB` is also cheap to clone: every clone is a handle to the same open
+/// database, which is closed when the last handle is dropped. Moving a clone
+/// into a spawned thread lets that thread create its own iterators without
+/// threading lifetimes through `Arc<DB>`.
+///
+/// ```no_run
+/// # use bleveldb::DB;
+/// # use bleveldb::options::Options;
+/// let db = DB::open("path/to/database", &Options::default()).unwrap();
+///
+/// let reader = db.clone();
+/// let handle = std::thread::spawn(move || {
+///     reader.iter(&Default::default()).count()
+/// });
+/// println!("{} entries", handle.join().unwrap());
+/// ```
+///
 /// # SAFETY
 /// The underlying LevelDB implementation is thread-safe for concurrent reads,
 /// but writes should be synchronized. The Rust wrapper ensures proper synchronization
 /// through external synchronization requirements.
+#[derive(Clone)]
 pub struct DB {
+    pub(crate) inner: Arc<DbInner>,
+}
+
+/// The open database shared by every clone of a `DB` handle.
+pub(crate) struct DbInner {
     raw: *mut sys::leveldb_t,
+    path: PathBuf,
+    read_only: bool,
+    compression: Compression,
+    pub(crate) scratch_dir: Option<PathBuf>,
+    _filter_policy: Option<Arc<FilterPolicy>>,
+    /// Serializes conditional writes made through this handle and its clones.
+    write_lock: Mutex<()>,
+    pub(crate) metrics: Metrics,
+}
+
+unsafe impl Send for DbInner {}
+unsafe impl Sync for DbInner {}
+
+/// Default upper bound, in bytes, on the size of each batch written by bulk helpers
+/// such as [`DB::delete_range`].
+pub const DEFAULT_BATCH_BYTES: usize = 8 * 1024 * 1024;
+
+/// On-disk size of a database directory, broken down by file type.
+///
+/// Returned by [`DB::disk_usage`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    /// Total size of table files (`.ldb`, or `.sst` from older writers), in bytes.
+    pub table_bytes: u64,
+    /// Number of table files.
+    pub table_files: u64,
+    /// Total size of write-ahead log files (`.log`), in bytes.
+    pub log_bytes: u64,
+    /// Number of write-ahead log files.
+    pub log_files: u64,
+    /// Total size of `MANIFEST-*` files, in bytes.
+    pub manifest_bytes: u64,
+    /// Number of `MANIFEST-*` files.
+    pub manifest_files: u64,
+    /// Total size of every other file (`CURRENT`, `LOCK`, `LOG`, ...), in bytes.
+    pub other_bytes: u64,
+    /// Number of other files.
+    pub other_files: u64,
+}
+
+impl DiskUsage {
+    /// Total size of all files, in bytes.
+    pub fn total_bytes(&self) -> u64 {
+        self.table_bytes + self.log_bytes + self.manifest_bytes + self.other_bytes
+    }
+
+    /// Total number of files.
+    pub fn total_files(&self) -> u64 {
+        self.table_files + self.log_files + self.manifest_files + self.other_files
+    }
+
+    /// Add one file to the category its name belongs to.
+    fn record(&mut self, name: &str, len: u64) {
+        let (bytes, files) = if name.ends_with(".ldb") || name.ends_with(".sst") {
+            (&mut self.table_bytes, &mut self.table_files)
+        } else if name.ends_with(".log") {
+            (&mut self.log_bytes, &mut self.log_files)
+        } else if name.starts_with("MANIFEST-") {
+            (&mut self.manifest_bytes, &mut self.manifest_files)
+        } else {
+            (&mut self.other_bytes, &mut self.other_files)
+        };
+        *bytes += len;
+        *files += 1;
+    }
 }
 
-unsafe impl Send for DB {}
-unsafe impl Sync for DB {}
+/// Summary of a completed [`DB::scan`] call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScanStats {
+    /// Number of entries passed to the callback.
+    pub visited: u64,
+    /// Whether the callback stopped the scan with [`ControlFlow::Break`].
+    pub stopped_early: bool,
+}
 
 impl DB {
     /// Opens a database at the given path with the specified options.
@@ -59,6 +167,8 @@ impl DB {
     ///
     /// This function will return an error in the following situations:
     /// * The path contains null bytes
+    /// * The options fail [`Options::validate_for_path`] (unless disabled with
+    ///   [`Options::validate_on_open`])
     /// * The database cannot be created or opened (permissions, disk space, etc.)
     /// * The database is corrupted and cannot be repaired
     ///
@@ -77,8 +187,20 @@ impl DB {
     /// }
     /// ```
     pub fn open(path: impl AsRef<Path>, options: &Options) -> Result<Self, String> {
-        let cpath = to_cstring(path.as_ref().to_string_lossy().as_ref())
-            .ok_or("invalid path: contains null byte")?;
+        let path = path.as_ref();
+        let op = op_span!(INFO, "open", path = tracing::field::display(path.display()));
+        op.finish(Self::open_impl(path, options))
+    }
+
+    fn open_impl(path: &Path, options: &Options) -> Result<Self, String> {
+        if options.settings().validate_on_open {
+            options.validate_for_path(path).map_err(|issues| {
+                let messages: Vec<String> = issues.iter().map(ToString::to_string).collect();
+                format!("invalid options: {}", messages.join("; "))
+            })?;
+        }
+
+        let cpath = path_to_cstring(path)?;
 
         let mut err = ptr::null_mut();
 
@@ -87,17 +209,282 @@ impl DB {
         if !err.is_null() {
             Err(error_message(err as *mut _))
         } else if db.is_null() {
-            Err("failed to open database".to_string())
+            Err(format!("failed to open database at {}", path.display()))
+        } else {
+            Ok(Self {
+                inner: Arc::new(DbInner {
+                    raw: db,
+                    path: path.canonicalize().unwrap_or_else(|_| path.to_path_buf()),
+                    read_only: false,
+                    compression: options.settings().compression,
+                    scratch_dir: None,
+                    _filter_policy: options.filter_policy(),
+                    write_lock: Mutex::new(()),
+                    metrics: Metrics::new(),
+                }),
+            })
+        }
+    }
+
+    /// Opens an existing database without ever writing to its directory.
+    ///
+    /// This is meant for worlds on read-only media such as mounted backups or
+    /// Android content providers, where LevelDB cannot create its `LOCK` and
+    /// `LOG` files. The small metadata files (`CURRENT`, `MANIFEST-*` and the
+    /// write-ahead log) are copied into a private scratch directory, while the
+    /// table files are linked in place (copied on platforms without symlinks).
+    /// The scratch directory is removed when the handle is dropped.
+    ///
+    /// The handle sees the database as it was when opened. `put`, `delete`,
+    /// `write` and `compact_range` return an error instead of modifying anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The filesystem path of an existing database
+    /// * `options` - Configuration options; `create_if_missing` and `error_if_exists` are ignored
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DB)` - A read-only handle to the database
+    /// * `Err(String)` - If the database could not be opened, containing an error message
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::DB;
+    /// use bleveldb::options::{Options, ReadOptions};
+    ///
+    /// let db = DB::open_read_only("/mnt/backup/world/db", &Options::default()).unwrap();
+    /// let value = db.get(b"~local_player", &ReadOptions::new()).unwrap();
+    /// assert!(db.put(b"key", b"value", &Default::default()).is_err());
+    /// ```
+    pub fn open_read_only(path: impl AsRef<Path>, options: &Options) -> Result<Self, String> {
+        let source = path.as_ref();
+        if !source.join("CURRENT").is_file() {
+            return Err(format!("{} does not contain a database", source.display()));
+        }
+
+        let scratch = unique_temp_dir("bleveldb-ro")
+            .map_err(|e| format!("failed to create scratch directory: {}", e))?;
+
+        let opened = stage_read_only_files(source, &scratch)
+            .map_err(|e| format!("failed to stage {} for reading: {}", source.display(), e))
+            .and_then(|()| {
+                let scratch_options = options.duplicate();
+                scratch_options.create_if_missing(false);
+                scratch_options.error_if_exists(false);
+                scratch_options.disable_seek_autocompaction(true);
+                Self::open(&scratch, &scratch_options)
+            });
+
+        match opened {
+            Ok(mut db) => {
+                let inner = Arc::get_mut(&mut db.inner).expect("a freshly opened handle is unique");
+                inner.path = source
+                    .canonicalize()
+                    .unwrap_or_else(|_| source.to_path_buf());
+                inner.read_only = true;
+                inner.scratch_dir = Some(scratch);
+                Ok(db)
+            }
+            Err(e) => {
+                let _ = fs::remove_dir_all(&scratch);
+                Err(e)
+            }
+        }
+    }
+
+    /// Return the directory this database was opened from.
+    ///
+    /// The path is canonicalized when the database is opened, so it is absolute
+    /// even if a relative path was passed to [`DB::open`]. For handles opened
+    /// with [`DB::open_read_only`] this is the original directory, not the
+    /// scratch copy.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// let db = DB::open("test_db", &Options::default()).unwrap();
+    /// println!("Opened {}", db.path().display());
+    /// ```
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Check whether this handle was opened with [`DB::open_read_only`].
+    pub fn is_read_only(&self) -> bool {
+        self.inner.read_only
+    }
+
+    /// Measure how much space the database takes up on disk.
+    ///
+    /// Walks the database directory (see [`DB::path`]) and sums file sizes per
+    /// category. Symbolic links are neither followed nor counted, so nothing
+    /// outside the directory is measured. Entries that cannot be inspected,
+    /// for example because LevelDB deleted them mid-walk, are skipped.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DiskUsage)` - The sizes and counts per file type
+    /// * `Err(io::Error)` - If the database directory itself cannot be read
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let usage = db.disk_usage().unwrap();
+    /// println!("World size: {} bytes", usage.total_bytes());
+    /// ```
+    pub fn disk_usage(&self) -> io::Result<DiskUsage> {
+        let mut usage = DiskUsage::default();
+        let mut pending = vec![self.inner.path.clone()];
+        let mut root = true;
+
+        while let Some(dir) = pending.pop() {
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(e) if root => return Err(e),
+                Err(_) => continue,
+            };
+            root = false;
+
+            for entry in entries.flatten() {
+                let Ok(metadata) = fs::symlink_metadata(entry.path()) else {
+                    continue;
+                };
+                if metadata.is_dir() {
+                    pending.push(entry.path());
+                } else if metadata.is_file() {
+                    usage.record(&entry.file_name().to_string_lossy(), metadata.len());
+                }
+            }
+        }
+        Ok(usage)
+    }
+
+    /// Fail with a descriptive error if this handle may not modify the database.
+    pub(crate) fn check_writable(&self) -> Result<(), String> {
+        if self.inner.read_only {
+            Err(format!(
+                "database at {} is opened read-only",
+                self.inner.path.display()
+            ))
         } else {
-            Ok(Self { raw: db })
+            Ok(())
+        }
+    }
+
+    /// Delete the database stored at the given path.
+    ///
+    /// Only the files LevelDB owns are removed, so unrelated files that happen to
+    /// live in the directory are left alone. The directory itself is removed if
+    /// it ends up empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The filesystem path of the database to delete
+    /// * `options` - Options matching those the database was created with
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the database was deleted (or did not exist)
+    /// * `Err(String)` - If the database could not be deleted, containing an error message
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// * The path contains null bytes
+    /// * The database is currently open, in this process or another one
+    /// * An I/O error occurs while removing the files
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::DB;
+    /// use bleveldb::options::Options;
+    ///
+    /// DB::destroy("old_world/db", &Options::default())
+    ///     .expect("Failed to destroy database");
+    /// ```
+    pub fn destroy(path: impl AsRef<Path>, options: &Options) -> Result<(), String> {
+        let cpath = path_to_cstring(path.as_ref())?;
+
+        let mut err = ptr::null_mut();
+        unsafe { sys::leveldb_destroy_db(options.raw(), cpath.as_ptr(), &mut err) };
+
+        if !err.is_null() {
+            return Err(error_message(err as *mut _));
+        }
+        Ok(())
+    }
+
+    /// Attempt to recover a corrupted database at the given path.
+    ///
+    /// Repair rebuilds the `MANIFEST` and `CURRENT` files by scanning every table
+    /// and log file it can find in the directory. It is the standard recovery
+    /// path for worlds damaged by power loss or interrupted copies.
+    ///
+    /// Repair can recover:
+    /// * Databases whose `CURRENT` or `MANIFEST` files are missing or corrupt
+    /// * Records stored in intact table files and write-ahead logs
+    ///
+    /// Repair cannot recover:
+    /// * Records inside corrupted blocks of a table file (they are dropped)
+    /// * Writes that never reached the log on disk
+    /// * Deleted records that were compacted away
+    ///
+    /// Some data may therefore be lost; back up the directory before repairing.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The filesystem path of the database to repair
+    /// * `options` - Options matching those the database was created with
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the repair completed
+    /// * `Err(String)` - If the repair failed, containing an error message
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// * The path contains null bytes
+    /// * The database is currently open
+    /// * An I/O error occurs while rebuilding the metadata
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::DB;
+    /// use bleveldb::options::Options;
+    ///
+    /// let options = Options::default();
+    /// if DB::open("world/db", &options).is_err() {
+    ///     DB::repair("world/db", &options).expect("Failed to repair database");
+    /// }
+    /// ```
+    pub fn repair(path: impl AsRef<Path>, options: &Options) -> Result<(), String> {
+        let cpath = path_to_cstring(path.as_ref())?;
+
+        let mut err = ptr::null_mut();
+        unsafe { sys::leveldb_repair_db(options.raw(), cpath.as_ptr(), &mut err) };
+
+        if !err.is_null() {
+            return Err(error_message(err as *mut _));
         }
+        Ok(())
     }
 
     /// Retrieve a value for a given key.
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to look up in the database
+    /// * `key` - The key to look up in the database; anything viewable as bytes,
+    ///   such as `&[u8]`, `Vec<u8>`, `&str` or `String`
     /// * `options` - Read options controlling the behavior of the read operation
     ///
     /// # Returns
@@ -129,12 +516,30 @@ impl DB {
     ///     Err(e) => eprintln!("Error reading key: {}", e),
     /// }
     /// ```
-    pub fn get(&self, key: &[u8], options: &ReadOptions) -> Result<Option<Vec<u8>>, String> {
+    pub fn get(
+        &self,
+        key: impl AsRef<[u8]>,
+        options: &ReadOptions,
+    ) -> Result<Option<Vec<u8>>, String> {
+        let key = key.as_ref();
+        let op = op_span!(TRACE, "get", key_len = key.len());
+        op.key(key);
+        let result = self
+            .inner
+            .metrics
+            .get(|| self.get_impl(key, options), Vec::len);
+        if let Ok(Some(value)) = &result {
+            op.value_len(value.len());
+        }
+        op.finish(result)
+    }
+
+    fn get_impl(&self, key: &[u8], options: &ReadOptions) -> Result<Option<Vec<u8>>, String> {
         unsafe {
             let mut err = ptr::null_mut();
             let mut val_len: usize = 0;
             let val_ptr = sys::leveldb_get(
-                self.raw,
+                self.raw(),
                 options.raw(),
                 key.as_ptr() as *const _,
                 key.len(),
@@ -157,158 +562,834 @@ impl DB {
         }
     }
 
-    /// Insert or overwrite a key-value pair.
+    /// Retrieve a value into a caller-provided buffer.
     ///
-    /// If the key already exists in the database, its value will be overwritten.
+    /// `buf` is cleared and the value is copied straight into it, reusing its
+    /// allocation. Calling this in a loop with the same buffer avoids the
+    /// per-call `Vec` allocation of [`DB::get`]. On a miss `buf` is left empty.
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to insert or update
-    /// * `value` - The value to associate with the key
-    /// * `options` - Write options controlling the behavior of the write operation
+    /// * `key` - The key to look up in the database
+    /// * `buf` - Buffer that receives the value
+    /// * `options` - Read options controlling the behavior of the read operation
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - If the write operation completed successfully
-    /// * `Err(String)` - If an error occurred during the write operation
-    ///
-    /// # Errors
-    ///
-    /// This function will return an error if:
-    /// * The database is read-only
-    /// * An I/O error occurs during the write
-    /// * The write would exceed disk space limits
+    /// * `Ok(Some(usize))` - If the key exists, containing the length of the value
+    /// * `Ok(None)` - If the key does not exist
+    /// * `Err(String)` - If an error occurred during the read operation
     ///
     /// # Examples
     ///
     /// ```no_run
     /// # use bleveldb::DB;
-    /// # use bleveldb::options::{Options, WriteOptions};
-    /// # let options = Options::default();
-    /// # let db = DB::open("test_db", &options).unwrap();
-    /// // Write a key-value pair with sync enabled for durability
-    /// let write_options = WriteOptions::default();
-    /// write_options.sync(true);
-    ///
-    /// db.put(b"important_key", b"important_data", &write_options)
-    ///    .expect("Failed to write to database");
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let read_opts = ReadOptions::new();
+    /// let mut buf = Vec::new();
+    /// for key in [b"key1", b"key2"] {
+    ///     if let Some(len) = db.get_into(key, &mut buf, &read_opts).unwrap() {
+    ///         println!("{} bytes: {:?}", len, &buf[..]);
+    ///     }
+    /// }
     /// ```
-    pub fn put(&self, key: &[u8], value: &[u8], options: &WriteOptions) -> Result<(), String> {
+    pub fn get_into(
+        &self,
+        key: &[u8],
+        buf: &mut Vec<u8>,
+        options: &ReadOptions,
+    ) -> Result<Option<usize>, String> {
+        self.inner
+            .metrics
+            .get(|| self.get_into_impl(key, buf, options), |len| *len)
+    }
+
+    fn get_into_impl(
+        &self,
+        key: &[u8],
+        buf: &mut Vec<u8>,
+        options: &ReadOptions,
+    ) -> Result<Option<usize>, String> {
+        buf.clear();
         unsafe {
             let mut err = ptr::null_mut();
-            sys::leveldb_put(
-                self.raw,
+            let mut val_len: usize = 0;
+            let val_ptr = sys::leveldb_get(
+                self.raw(),
                 options.raw(),
                 key.as_ptr() as *const _,
                 key.len(),
-                value.as_ptr() as *const _,
-                value.len(),
+                &mut val_len,
                 &mut err,
             );
+
             if !err.is_null() {
                 return Err(error_message(err as *mut _));
             }
+
+            if val_ptr.is_null() {
+                return Ok(None);
+            }
+
+            let slice = std::slice::from_raw_parts(val_ptr as *const u8, val_len);
+            buf.extend_from_slice(slice);
+            sys::leveldb_free(val_ptr as *mut _);
+            Ok(Some(val_len))
         }
-        Ok(())
     }
 
-    /// Delete a key from the database.
+    /// Retrieve a value into an inline buffer when it is at most `N` bytes.
     ///
-    /// If the key does not exist in the database, this operation is a no-op and succeeds.
+    /// Most Bedrock records are small, and [`DB::get`] allocates a `Vec` for
+    /// each of them. This copies values of up to `N` bytes into a
+    /// [`SmallValue`] held on the stack instead and only allocates for longer
+    /// ones, so a loop reading chunk versions or finalized states makes no
+    /// allocations of its own. LevelDB's C API still copies the value into a
+    /// buffer of its own, which is freed before returning.
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to delete from the database
-    /// * `options` - Write options controlling the behavior of the delete operation
+    /// * `key` - The key to look up in the database
+    /// * `options` - Read options controlling the behavior of the read operation
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - If the delete operation completed successfully
-    /// * `Err(String)` - If an error occurred during the delete operation
-    ///
-    /// # Errors
-    ///
-    /// This function will return an error if:
-    /// * The database is read-only
-    /// * An I/O error occurs during the deletion
+    /// * `Ok(Some(SmallValue<N>))` - If the key exists, containing the value
+    /// * `Ok(None)` - If the key does not exist
+    /// * `Err(String)` - If an error occurred during the read operation
     ///
     /// # Examples
     ///
     /// ```no_run
     /// # use bleveldb::DB;
-    /// # use bleveldb::options::Options;
-    /// # let options = Options::default();
-    /// # let db = DB::open("test_db", &options).unwrap();
-    /// // Delete a key from the database
-    /// db.delete(b"unwanted_key", &Default::default())
-    ///    .expect("Failed to delete key");
+    /// # use bleveldb::bedrock::{ChunkKey, Dimension, RecordTag};
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("world/db", &Options::bedrock()).unwrap();
+    /// let read_opts = ReadOptions::new();
+    /// for x in 0..32 {
+    ///     let key = ChunkKey::new(x, 0, Dimension::Overworld, RecordTag::Version);
+    ///     if let Some(version) = db.get_small::<8>(key, &read_opts).unwrap() {
+    ///         println!("chunk {} has version {:?}", x, version.first());
+    ///     }
+    /// }
     /// ```
-    pub fn delete(&self, key: &[u8], options: &WriteOptions) -> Result<(), String> {
+    pub fn get_small<const N: usize>(
+        &self,
+        key: impl AsRef<[u8]>,
+        options: &ReadOptions,
+    ) -> Result<Option<SmallValue<N>>, String> {
+        let key = key.as_ref();
+        self.inner
+            .metrics
+            .get(|| self.get_small_impl(key, options), |value| value.len())
+    }
+
+    fn get_small_impl<const N: usize>(
+        &self,
+        key: &[u8],
+        options: &ReadOptions,
+    ) -> Result<Option<SmallValue<N>>, String> {
         unsafe {
             let mut err = ptr::null_mut();
-            sys::leveldb_delete(
-                self.raw,
+            let mut val_len: usize = 0;
+            let val_ptr = sys::leveldb_get(
+                self.raw(),
                 options.raw(),
                 key.as_ptr() as *const _,
                 key.len(),
+                &mut val_len,
                 &mut err,
             );
+
             if !err.is_null() {
                 return Err(error_message(err as *mut _));
             }
+
+            if val_ptr.is_null() {
+                return Ok(None);
+            }
+
+            let slice = std::slice::from_raw_parts(val_ptr as *const u8, val_len);
+            let value = SmallValue::from_slice(slice);
+            sys::leveldb_free(val_ptr as *mut _);
+            Ok(Some(value))
         }
-        Ok(())
     }
 
-    /// Compact the database over the given key range.
+    /// Retrieve a value for a given key without copying it.
     ///
-    /// Compaction reorganizes the database files to reduce disk space usage
-    /// and improve read performance. This operation is automatically performed
-    /// by LevelDB in the background, but can be manually triggered for specific
-    /// key ranges.
+    /// Unlike [`DB::get`], which copies every value into a new `Vec<u8>`, this
+    /// returns a [`PinnedValue`] guard that borrows the bytes straight from
+    /// LevelDB. This is useful in hot paths such as reading thousands of
+    /// subchunk records per second.
+    ///
+    /// The guard holds an open iterator, so keep it short-lived: as long as it
+    /// exists, the data it references cannot be compacted away.
     ///
     /// # Arguments
     ///
-    /// * `start` - The start key of the range to compact (inclusive). If `None`, starts from the beginning.
-    /// * `limit` - The limit key of the range to compact (exclusive). If `None`, continues to the end.
+    /// * `key` - The key to look up in the database
+    /// * `options` - Read options controlling the behavior of the read operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(PinnedValue))` - If the key was found
+    /// * `Ok(None)` - If the key was not found in the database
+    /// * `Err(String)` - If an error occurred during the read operation
     ///
     /// # Examples
     ///
     /// ```no_run
     /// # use bleveldb::DB;
-    /// # use bleveldb::options::Options;
+    /// # use bleveldb::options::{Options, ReadOptions};
     /// # let options = Options::default();
     /// # let db = DB::open("test_db", &options).unwrap();
-    /// // Compact the entire database
-    /// db.compact_range(None, None);
-    ///
-    /// // Compact only keys from "a" to "m"
-    /// db.compact_range(Some(b"a"), Some(b"m"));
+    /// if let Some(value) = db.get_pinned(b"my_key", &ReadOptions::default()).unwrap() {
+    ///     let first_byte = value[0];
+    ///     println!("Value has {} bytes, starting with {}", value.len(), first_byte);
+    /// }
     /// ```
-    pub fn compact_range(&self, start: Option<&[u8]>, limit: Option<&[u8]>) {
-        unsafe {
-            let (start_ptr, start_len) = match start {
-                Some(s) => (s.as_ptr() as *const _, s.len()),
-                None => (ptr::null(), 0),
-            };
-            let (limit_ptr, limit_len) = match limit {
-                Some(s) => (s.as_ptr() as *const _, s.len()),
-                None => (ptr::null(), 0),
+    pub fn get_pinned(
+        &self,
+        key: &[u8],
+        options: &ReadOptions,
+    ) -> Result<Option<PinnedValue<'_>>, String> {
+        self.inner.metrics.get(
+            || {
+                let mut iter = DBIterator::new(self, options);
+                iter.seek(key);
+
+                if iter.key_ref() == Some(key) {
+                    Ok(Some(PinnedValue::new(iter)))
+                } else {
+                    iter.status()?;
+                    Ok(None)
+                }
+            },
+            |value| value.len(),
+        )
+    }
+
+    /// Retrieve the values for several keys from one consistent view.
+    ///
+    /// All lookups read from a single snapshot (the one set on `options`, or a
+    /// fresh one), so the results are consistent with each other even while
+    /// other threads write. When `keys` are sorted in ascending order, a single
+    /// iterator is reused with successive seeks instead of independent gets.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys to look up, in any order
+    /// * `options` - Read options controlling the behavior of the read operations
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<Vec<u8>>>)` - One entry per key, in input order; `None` for missing keys
+    /// * `Err(String)` - If an error occurred during any of the reads
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// let values = db
+    ///     .multi_get(&[b"version".as_slice(), b"data3d", b"missing"], &ReadOptions::new())
+    ///     .unwrap();
+    /// assert_eq!(values.len(), 3);
+    /// ```
+    pub fn multi_get(
+        &self,
+        keys: &[impl AsRef<[u8]>],
+        options: &ReadOptions,
+    ) -> Result<Vec<Option<Vec<u8>>>, String> {
+        let (_snapshot, options) = self.consistent_read_options(options);
+
+        let sorted = keys.windows(2).all(|w| w[0].as_ref() <= w[1].as_ref());
+        if !sorted {
+            return keys
+                .iter()
+                .map(|key| self.get(key.as_ref(), &options))
+                .collect();
+        }
+
+        let mut iter = DBIterator::new(self, &options);
+        let mut values = Vec::with_capacity(keys.len());
+        for key in keys {
+            let key = key.as_ref();
+            let value = self.inner.metrics.get(
+                || {
+                    iter.seek(key);
+                    if iter.key_ref() == Some(key) {
+                        Ok(iter.value())
+                    } else {
+                        iter.status()?;
+                        Ok(None)
+                    }
+                },
+                Vec::len,
+            )?;
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    /// Insert or overwrite a key-value pair.
+    ///
+    /// If the key already exists in the database, its value will be overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to insert or update (any `AsRef<[u8]>`, like `&[u8]` or `String`)
+    /// * `value` - The value to associate with the key (any `AsRef<[u8]>`)
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the write operation completed successfully
+    /// * `Err(String)` - If an error occurred during the write operation
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// * The database is read-only
+    /// * An I/O error occurs during the write
+    /// * The write would exceed disk space limits
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, WriteOptions};
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// // Write a key-value pair with sync enabled for durability
+    /// let write_options = WriteOptions::default();
+    /// write_options.sync(true);
+    ///
+    /// db.put(b"important_key", b"important_data", &write_options)
+    ///    .expect("Failed to write to database");
+    /// ```
+    pub fn put(
+        &self,
+        key: impl AsRef<[u8]>,
+        value: impl AsRef<[u8]>,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        let (key, value) = (key.as_ref(), value.as_ref());
+        let op = op_span!(TRACE, "put", key_len = key.len());
+        op.key(key);
+        op.value_len(value.len());
+        op.finish(self.inner.metrics.put(key.len() + value.len(), || {
+            self.put_impl(key, value, options)
+        }))
+    }
+
+    fn put_impl(&self, key: &[u8], value: &[u8], options: &WriteOptions) -> Result<(), String> {
+        self.check_writable()?;
+        unsafe {
+            let mut err = ptr::null_mut();
+            sys::leveldb_put(
+                self.raw(),
+                options.raw(),
+                key.as_ptr() as *const _,
+                key.len(),
+                value.as_ptr() as *const _,
+                value.len(),
+                &mut err,
+            );
+            if !err.is_null() {
+                return Err(error_message(err as *mut _));
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete a key from the database.
+    ///
+    /// If the key does not exist in the database, this operation is a no-op and succeeds.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to delete from the database (any `AsRef<[u8]>`)
+    /// * `options` - Write options controlling the behavior of the delete operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the delete operation completed successfully
+    /// * `Err(String)` - If an error occurred during the delete operation
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// * The database is read-only
+    /// * An I/O error occurs during the deletion
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// // Delete a key from the database
+    /// db.delete(b"unwanted_key", &Default::default())
+    ///    .expect("Failed to delete key");
+    /// ```
+    pub fn delete(&self, key: impl AsRef<[u8]>, options: &WriteOptions) -> Result<(), String> {
+        let key = key.as_ref();
+        let op = op_span!(TRACE, "delete", key_len = key.len());
+        op.key(key);
+        op.finish(
+            self.inner
+                .metrics
+                .delete(key.len(), || self.delete_impl(key, options)),
+        )
+    }
+
+    fn delete_impl(&self, key: &[u8], options: &WriteOptions) -> Result<(), String> {
+        self.check_writable()?;
+        unsafe {
+            let mut err = ptr::null_mut();
+            sys::leveldb_delete(
+                self.raw(),
+                options.raw(),
+                key.as_ptr() as *const _,
+                key.len(),
+                &mut err,
+            );
+            if !err.is_null() {
+                return Err(error_message(err as *mut _));
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a value, transform it, and write the result back.
+    ///
+    /// `f` receives the current value (`None` if the key does not exist).
+    /// Returning `Some(value)` stores the new value, while returning `None`
+    /// deletes the key.
+    ///
+    /// LevelDB has no transactions, so the read and the write are two separate
+    /// operations: a concurrent writer can slip in between them and its change
+    /// will be overwritten. The update is only atomic if all writers to the key
+    /// are synchronized externally. With the `update-lock` feature, `update`
+    /// and [`DB::put_if`] calls through this handle and its clones are
+    /// serialized by an internal mutex; plain `put`/`delete`/`write` calls and
+    /// other processes are not.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to update
+    /// * `f` - Computes the new value from the current one
+    /// * `read_options` - Options used to read the current value
+    /// * `write_options` - Options used to write or delete the new value
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the new value was written or the key deleted
+    /// * `Err(String)` - If reading or writing failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions, WriteOptions};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// // Increment a one-byte counter, creating it if missing
+    /// db.update(
+    ///     b"counter",
+    ///     |current| {
+    ///         let n = current.and_then(|v| v.first().copied()).unwrap_or(0);
+    ///         Some(vec![n.wrapping_add(1)])
+    ///     },
+    ///     &ReadOptions::new(),
+    ///     &WriteOptions::new(),
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn update(
+        &self,
+        key: &[u8],
+        f: impl FnOnce(Option<Vec<u8>>) -> Option<Vec<u8>>,
+        read_options: &ReadOptions,
+        write_options: &WriteOptions,
+    ) -> Result<(), String> {
+        self.check_writable()?;
+
+        #[cfg(feature = "update-lock")]
+        let _guard = self.lock_writes();
+
+        let current = self.get(key, read_options)?;
+        match f(current) {
+            Some(value) => self.put(key, &value, write_options),
+            None => self.delete(key, write_options),
+        }
+    }
+
+    /// Write `new_value` only if the key currently holds `expected`.
+    ///
+    /// Passing `None` as `expected` means the key must not exist yet. The
+    /// comparison and the write happen under a mutex shared by every clone of
+    /// this handle, so concurrent `put_if` calls through it are linearizable.
+    /// The mutex does not cover plain `put`/`delete`/`write` calls, other `DB`
+    /// handles opened separately, or other processes; those can still change
+    /// the key between the check and the write.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to write
+    /// * `expected` - The value the key must currently hold, or `None` if it must be absent
+    /// * `new_value` - The value to store when the check succeeds
+    /// * `read_options` - Options used to read the current value
+    /// * `write_options` - Options used to write the new value
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - If the current value matched and `new_value` was written
+    /// * `Ok(false)` - If the current value did not match; nothing was written
+    /// * `Err(String)` - If reading or writing failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions, WriteOptions};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let (read, write) = (ReadOptions::new(), WriteOptions::new());
+    /// if db.put_if(b"owner", None, b"tool-a", &read, &write).unwrap() {
+    ///     println!("Claimed the world");
+    /// }
+    /// ```
+    pub fn put_if(
+        &self,
+        key: &[u8],
+        expected: Option<&[u8]>,
+        new_value: &[u8],
+        read_options: &ReadOptions,
+        write_options: &WriteOptions,
+    ) -> Result<bool, String> {
+        self.check_writable()?;
+        let _guard = self.lock_writes();
+
+        let current = self.get(key, read_options)?;
+        if current.as_deref() != expected {
+            return Ok(false);
+        }
+        self.put(key, new_value, write_options)?;
+        Ok(true)
+    }
+
+    /// Delete every key in the range `[start, end)`.
+    ///
+    /// The range is scanned under a snapshot, so keys inserted after the call
+    /// starts are never touched. Deletes are committed in batches of at most
+    /// [`DEFAULT_BATCH_BYTES`]; use [`DB::delete_range_with_batch_bytes`] to
+    /// choose a different cap. The deletion as a whole is therefore not atomic.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first key of the range (inclusive)
+    /// * `end` - The end of the range (exclusive)
+    /// * `options` - Write options used for every batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - The number of keys removed
+    /// * `Err(String)` - If scanning or writing failed; earlier batches stay applied
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, WriteOptions};
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// let removed = db.delete_range(b"user:", b"user;", &WriteOptions::new()).unwrap();
+    /// println!("Removed {} users", removed);
+    /// ```
+    pub fn delete_range(
+        &self,
+        start: &[u8],
+        end: &[u8],
+        options: &WriteOptions,
+    ) -> Result<u64, String> {
+        self.delete_range_with_batch_bytes(start, end, options, DEFAULT_BATCH_BYTES)
+    }
+
+    /// Delete every key in the range `[start, end)`, committing batches of at
+    /// most `batch_bytes` bytes of keys.
+    ///
+    /// See [`DB::delete_range`] for details.
+    pub fn delete_range_with_batch_bytes(
+        &self,
+        start: &[u8],
+        end: &[u8],
+        options: &WriteOptions,
+        batch_bytes: usize,
+    ) -> Result<u64, String> {
+        if start >= end {
+            return Ok(0);
+        }
+
+        let (_snapshot, read_options) = self.consistent_read_options(&ReadOptions::new());
+        read_options.fill_cache(false);
+
+        let mut iter = DBIterator::new(self, &read_options);
+        let mut batch = WriteBatch::new();
+        let mut pending_bytes = 0;
+        let mut pending = 0;
+        let mut removed = 0;
+
+        iter.seek(start);
+        while let Some(key) = iter.key_ref() {
+            if key >= end {
+                break;
+            }
+            batch.delete(key);
+            pending_bytes += key.len();
+            pending += 1;
+
+            if pending_bytes >= batch_bytes {
+                self.write(&batch, options)?;
+                batch.clear();
+                removed += pending;
+                pending_bytes = 0;
+                pending = 0;
+            }
+            iter.next_native();
+        }
+        iter.status()?;
+
+        if pending > 0 {
+            self.write(&batch, options)?;
+            removed += pending;
+        }
+        Ok(removed)
+    }
+
+    /// Insert every key/value pair produced by `items`, grouped into batches.
+    ///
+    /// Pairs are collected into a [`WriteBatch`] until their keys and values
+    /// add up to at least `batch_bytes` bytes, then the batch is committed and
+    /// a new one started; a final partial batch is committed at the end. This
+    /// is much faster than one [`DB::put`] per entry for large imports, but the
+    /// insert as a whole is not atomic.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The key/value pairs to insert
+    /// * `options` - Write options used for every batch
+    /// * `batch_bytes` - Size threshold, in bytes, at which a batch is committed
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - The number of entries written
+    /// * `Err(String)` - If a batch could not be written; the message includes
+    ///   how many entries were committed before the failure
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::db::DEFAULT_BATCH_BYTES;
+    /// # use bleveldb::options::{Options, WriteOptions};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let chunks = (0..1000u32).map(|i| (i.to_le_bytes(), vec![0u8; 512]));
+    /// let written = db.put_all(chunks, &WriteOptions::new(), DEFAULT_BATCH_BYTES).unwrap();
+    /// assert_eq!(written, 1000);
+    /// ```
+    pub fn put_all<I, K, V>(
+        &self,
+        items: I,
+        options: &WriteOptions,
+        batch_bytes: usize,
+    ) -> Result<u64, String>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.check_writable()?;
+
+        let mut batch = WriteBatch::new();
+        let mut pending_bytes = 0;
+        let mut pending = 0;
+        let mut written = 0;
+        let commit = |batch: &WriteBatch, written: u64| {
+            self.write(batch, options).map_err(|e| {
+                format!(
+                    "{} ({} entries were written before the failure)",
+                    e, written
+                )
+            })
+        };
+
+        for (key, value) in items {
+            let (key, value) = (key.as_ref(), value.as_ref());
+            batch.put(key, value);
+            pending_bytes += key.len() + value.len();
+            pending += 1;
+
+            if pending_bytes >= batch_bytes {
+                commit(&batch, written)?;
+                batch.clear();
+                written += pending;
+                pending_bytes = 0;
+                pending = 0;
+            }
+        }
+
+        if pending > 0 {
+            commit(&batch, written)?;
+            written += pending;
+        }
+        Ok(written)
+    }
+
+    /// Apply a batch of write operations atomically.
+    ///
+    /// Either every operation in the batch is applied, or none are.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - The batch of put/delete operations to commit
+    /// * `options` - Write options controlling the behavior of the write operation
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If all operations in the batch were committed successfully
+    /// * `Err(String)` - If an error occurred during the write operation
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// * The database is read-only
+    /// * Disk space is exhausted
+    /// * An I/O error occurs during the write
+    /// * The database is corrupted
+    ///
+    /// If an error is returned, no operations from the batch have been applied.
+    ///
+    /// An empty batch is not sent to LevelDB at all and returns `Ok(())` right
+    /// away, even with `sync` set; use [`DB::flush`] to force the log to disk.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, WriteBatch};
+    /// # use bleveldb::options::{Options, WriteOptions};
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"account:balance", b"1000");
+    /// batch.delete(b"account:pending");
+    ///
+    /// let write_options = WriteOptions::new();
+    /// write_options.sync(true);
+    ///
+    /// db.write(&batch, &write_options)
+    ///    .expect("Failed to commit batch");
+    /// ```
+    pub fn write(&self, batch: &WriteBatch, options: &WriteOptions) -> Result<(), String> {
+        let op = op_span!(
+            DEBUG,
+            "write",
+            ops = batch.len(),
+            bytes = batch.approximate_size()
+        );
+        let result = self.inner.metrics.batch(batch.approximate_size(), || {
+            self.check_writable()?;
+            if batch.is_empty() {
+                Ok(())
+            } else {
+                self.write_raw(batch, options)
+            }
+        });
+        op.finish(result)
+    }
+
+    /// Hand `batch` to LevelDB, even if it is empty.
+    fn write_raw(&self, batch: &WriteBatch, options: &WriteOptions) -> Result<(), String> {
+        unsafe {
+            let mut err = ptr::null_mut();
+            sys::leveldb_write(self.raw(), options.raw(), batch.raw(), &mut err);
+            if !err.is_null() {
+                return Err(error_message(err as *mut _));
+            }
+        }
+        Ok(())
+    }
+
+    /// Compact the database over the given key range.
+    ///
+    /// Compaction reorganizes the database files to reduce disk space usage
+    /// and improve read performance. This operation is automatically performed
+    /// by LevelDB in the background, but can be manually triggered for specific
+    /// key ranges.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The start key of the range to compact (inclusive). If `None`, starts from the beginning.
+    /// * `limit` - The limit key of the range to compact (exclusive). If `None`, continues to the end.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the compaction ran
+    /// * `Err(String)` - If the database is read-only
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// // Compact the entire database
+    /// db.compact_range(None, None).unwrap();
+    ///
+    /// // Compact only keys from "a" to "m"
+    /// db.compact_range(Some(b"a"), Some(b"m")).unwrap();
+    /// ```
+    pub fn compact_range(&self, start: Option<&[u8]>, limit: Option<&[u8]>) -> Result<(), String> {
+        let op = op_span!(INFO, "compact_range");
+        op.finish(self.compact_range_impl(start, limit))
+    }
+
+    fn compact_range_impl(&self, start: Option<&[u8]>, limit: Option<&[u8]>) -> Result<(), String> {
+        self.check_writable()?;
+        unsafe {
+            let (start_ptr, start_len) = match start {
+                Some(s) => (s.as_ptr() as *const _, s.len()),
+                None => (ptr::null(), 0),
+            };
+            let (limit_ptr, limit_len) = match limit {
+                Some(s) => (s.as_ptr() as *const _, s.len()),
+                None => (ptr::null(), 0),
             };
-            sys::leveldb_compact_range(self.raw, start_ptr, start_len, limit_ptr, limit_len);
+            sys::leveldb_compact_range(self.raw(), start_ptr, start_len, limit_ptr, limit_len);
         }
+        Ok(())
     }
 
     /// Synchronize the database to disk.
     ///
-    /// This method forces all pending writes to be flushed to disk.
-    /// In LevelDB, this is achieved by performing a full compaction,
-    /// which ensures all data is written to persistent storage.
+    /// Forces the write-ahead log to be synced, so every write made before the
+    /// call survives a crash or power loss even if it was issued without
+    /// [`WriteOptions::sync`]. The LevelDB version bundled with this crate has
+    /// no memtable flush, so this is done with an empty synchronous write; no
+    /// table files are rewritten and the call is cheap. Use
+    /// [`DB::compact_all`] to rewrite the whole database instead.
     ///
-    /// # Note
+    /// Flushing a read-only handle does nothing.
+    ///
+    /// # Returns
     ///
-    /// This operation may be expensive for large databases as it involves
-    /// rewriting the entire database contents.
+    /// * `Ok(())` - If the log was synced
+    /// * `Err(String)` - If the sync failed
     ///
     /// # Examples
     ///
@@ -317,19 +1398,49 @@ impl DB {
     /// # use bleveldb::options::Options;
     /// # let options = Options::default();
     /// # let db = DB::open("test_db", &options).unwrap();
-    /// // Force all data to disk
-    /// db.flush();
+    /// db.put(b"key", b"value", &Default::default()).unwrap();
+    /// // Make sure the write is durable
+    /// db.flush().unwrap();
+    /// ```
+    pub fn flush(&self) -> Result<(), String> {
+        if self.inner.read_only {
+            return Ok(());
+        }
+        let sync = WriteOptions::new();
+        sync.sync(true);
+        self.write_raw(&WriteBatch::new(), &sync)
+    }
+
+    /// Compact the entire database.
+    ///
+    /// Equivalent to `compact_range(None, None)`: every table file is read and
+    /// rewritten, dropping deleted and overwritten entries. On a large world
+    /// this can take minutes; use [`DB::flush`] if you only need durability.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the compaction ran
+    /// * `Err(String)` - If the database is read-only
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// // Reclaim space after deleting many chunks
+    /// db.compact_all().unwrap();
     /// ```
-    pub fn flush(&self) {
-        // In LevelDB, explicit flush isn't exposed, but compact_range(None, None)
-        // effectively forces all data to disk.
-        self.compact_range(None, None);
+    pub fn compact_all(&self) -> Result<(), String> {
+        self.compact_range(None, None)
     }
 
     /// Create a new iterator over the database contents.
     ///
     /// The iterator provides sequential access to all key-value pairs in the database.
-    /// The order of iteration is determined by the key comparator.
+    /// The order of iteration is determined by the key comparator. Used as an
+    /// `Iterator`, it starts at the first key; call one of the `seek*` methods
+    /// beforehand to start elsewhere.
     ///
     /// # Arguments
     ///
@@ -346,6 +1457,7 @@ impl DB {
     /// # use bleveldb::options::Options;
     /// # let options = Options::default();
     /// # let db = DB::open("test_db", &options).unwrap();
+    /// // Iteration starts at the first key unless the iterator is seeked first
     /// let iter = db.iter(&Default::default());
     ///
     /// for (key, value) in iter {
@@ -356,6 +1468,413 @@ impl DB {
         crate::iterator::DBIterator::new(self, options)
     }
 
+    /// Create an iterator that walks the database from the last key to the first.
+    ///
+    /// Unlike combining [`DBIterator::seek_to_last`] with the forward
+    /// `Iterator` implementation, every call to `next()` moves the cursor
+    /// backwards, so entries come out in descending key order. Use
+    /// [`RevIter::seek`] to start from a given key instead of the last one.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options controlling the behavior of the iterator
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// // The ten greatest keys, largest first
+    /// for (key, _) in db.iter_rev(&Default::default()).take(10) {
+    ///     println!("{:?}", key);
+    /// }
+    /// ```
+    pub fn iter_rev(&self, options: &ReadOptions) -> RevIter<'_> {
+        RevIter::new(self, options)
+    }
+
+    /// Create an iterator over a snapshot of the database taken right now.
+    ///
+    /// This is the one-call form of taking a [`Snapshot`], building read
+    /// options around it and creating an iterator: the returned iterator owns
+    /// its snapshot and releases it when dropped, so it can neither be leaked
+    /// nor released too early. It gives a stable view for long scans, such as
+    /// rendering a map, while the world keeps being written.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options controlling the behavior of the iterator; any
+    ///   snapshot already set on them is replaced by the new one
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let mut iter = db.snapshot_iter(&Default::default());
+    /// db.put(b"new", b"value", &Default::default()).unwrap();
+    ///
+    /// // Neither the iteration nor reads through its snapshot see "new"
+    /// let read_options = iter.snapshot().read_options(&Default::default());
+    /// assert!(db.get(b"new", &read_options).unwrap().is_none());
+    /// assert!(iter.all(|(key, _)| key != b"new"));
+    /// ```
+    pub fn snapshot_iter(&self, options: &ReadOptions) -> SnapshotIter<'_> {
+        SnapshotIter::new(self, options)
+    }
+
+    /// Create an iterator over the entries whose keys start with `prefix`.
+    ///
+    /// The iterator is already positioned at the first matching key and returns
+    /// `None` as soon as a key no longer starts with `prefix`, so callers cannot
+    /// accidentally run into the next chunk's records. An empty prefix iterates
+    /// over the whole database.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The key prefix to iterate over
+    /// * `options` - Read options controlling the behavior of the iterator
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// // All records of the Overworld chunk at (0, 0)
+    /// let chunk_prefix = [0u8; 8];
+    /// for (key, value) in db.prefix_iter(&chunk_prefix, &Default::default()) {
+    ///     println!("Record tag {} ({} bytes)", key[8], value.len());
+    /// }
+    /// ```
+    pub fn prefix_iter(&self, prefix: &[u8], options: &ReadOptions) -> PrefixIter<'_> {
+        PrefixIter::new(self, prefix, options)
+    }
+
+    /// Create an iterator over the entries whose keys fall within `range`.
+    ///
+    /// Included, excluded and unbounded ends are all supported: an excluded
+    /// start skips an exact match, and an included end yields the boundary key.
+    /// A reversed range yields nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The key range to iterate over
+    /// * `options` - Read options controlling the behavior of the iterator
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// // Keys from "a" (inclusive) to "m" (exclusive)
+    /// let count = db.range(b"a".to_vec()..b"m".to_vec(), &Default::default()).count();
+    ///
+    /// // Every key from "user:" onwards
+    /// for (key, value) in db.range(b"user:".to_vec().., &Default::default()) {
+    ///     println!("Key: {:?}", key);
+    /// }
+    /// ```
+    pub fn range<R: RangeBounds<Vec<u8>>>(&self, range: R, options: &ReadOptions) -> RangeIter<'_> {
+        RangeIter::new(self, range, options)
+    }
+
+    /// Check whether the database contains no keys at all.
+    ///
+    /// This only positions an iterator at the first key, so it is cheap even
+    /// for very large databases.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options controlling the behavior of the check
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the database has no keys
+    /// * `Err(String)` - If the iterator reported an error
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// if db.is_empty(&Default::default()).unwrap() {
+    ///     println!("This world has no data");
+    /// }
+    /// ```
+    pub fn is_empty(&self, options: &ReadOptions) -> Result<bool, String> {
+        let mut iter = DBIterator::new(self, options);
+        iter.seek_to_first();
+        let empty = !iter.valid();
+        iter.status()?;
+        Ok(empty)
+    }
+
+    /// Count every key in the database.
+    ///
+    /// # Note
+    ///
+    /// This is an O(n) full scan: every entry is visited, although keys and
+    /// values are never copied. Avoid calling it on hot paths for large worlds.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Read options controlling the behavior of the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - The number of keys
+    /// * `Err(String)` - If the scan stopped because of an error
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// println!("{} records", db.count_keys(&Default::default()).unwrap());
+    /// ```
+    pub fn count_keys(&self, options: &ReadOptions) -> Result<u64, String> {
+        self.count_prefix(b"", options)
+    }
+
+    /// Count the keys that start with `prefix`.
+    ///
+    /// Like [`DB::count_keys`] this is a scan, but it is bounded to the keys
+    /// under the prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The key prefix to count
+    /// * `options` - Read options controlling the behavior of the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - The number of keys starting with `prefix`
+    /// * `Err(String)` - If the scan stopped because of an error
+    pub fn count_prefix(&self, prefix: &[u8], options: &ReadOptions) -> Result<u64, String> {
+        let mut iter = DBIterator::new(self, options);
+        iter.seek(prefix);
+
+        let mut count = 0;
+        while let Some(key) = iter.key_ref() {
+            if !key.starts_with(prefix) {
+                break;
+            }
+            count += 1;
+            iter.next_native();
+        }
+        iter.status()?;
+        Ok(count)
+    }
+
+    /// Call `f` with every entry from `start` onwards, without allocating.
+    ///
+    /// The callback receives borrowed key and value slices that are only valid
+    /// for the duration of the call. Returning [`ControlFlow::Break`] stops the
+    /// scan early. This is the fastest way to run an analytics pass over a
+    /// large world, since no `Vec` is created per entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first key to visit (inclusive), or `None` to start at the first key
+    /// * `f` - Callback invoked with each key and value
+    /// * `options` - Read options controlling the behavior of the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ScanStats)` - How many entries were visited and whether the callback stopped early
+    /// * `Err(String)` - If the scan stopped because of an error
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # use std::ops::ControlFlow;
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let mut largest = 0;
+    /// db.scan(
+    ///     None,
+    ///     |_key, value| {
+    ///         largest = largest.max(value.len());
+    ///         ControlFlow::Continue(())
+    ///     },
+    ///     &ReadOptions::new(),
+    /// )
+    /// .unwrap();
+    /// println!("Largest record: {} bytes", largest);
+    /// ```
+    pub fn scan(
+        &self,
+        start: Option<&[u8]>,
+        mut f: impl FnMut(&[u8], &[u8]) -> ControlFlow<()>,
+        options: &ReadOptions,
+    ) -> Result<ScanStats, String> {
+        let mut iter = DBIterator::new(self, options);
+        match start {
+            Some(start) => iter.seek(start),
+            None => iter.seek_to_first(),
+        }
+
+        let mut stats = ScanStats::default();
+        while let (Some(key), Some(value)) = (iter.key_ref(), iter.value_ref()) {
+            stats.visited += 1;
+            if f(key, value).is_break() {
+                stats.stopped_early = true;
+                break;
+            }
+            iter.next_native();
+        }
+        iter.status()?;
+        Ok(stats)
+    }
+
+    /// Take a snapshot of the current state of the database.
+    ///
+    /// See [`Snapshot`] for details. The snapshot is released when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// let snapshot = db.snapshot();
+    /// let read_options = snapshot.read_options(&ReadOptions::new());
+    /// let value = db.get(b"key", &read_options).unwrap();
+    /// ```
+    pub fn snapshot(&self) -> Snapshot<'_> {
+        Snapshot::new(self)
+    }
+
+    /// Return read options guaranteed to read from a single snapshot.
+    ///
+    /// If `options` already carries a snapshot it is reused and no new snapshot
+    /// is taken; otherwise a fresh snapshot is returned alongside the options
+    /// and must be kept alive while they are in use.
+    pub(crate) fn consistent_read_options(
+        &self,
+        options: &ReadOptions,
+    ) -> (Option<Snapshot<'_>>, ReadOptions) {
+        if options.has_snapshot() {
+            (None, options.duplicate())
+        } else {
+            let snapshot = self.snapshot();
+            let options = snapshot.read_options(options);
+            (Some(snapshot), options)
+        }
+    }
+
+    /// Close the database, reporting any problem instead of ignoring it.
+    ///
+    /// Dropping a `DB` also closes it, but silently. `close` first forces the
+    /// write-ahead log to disk with a synchronous write, so once it returns `Ok`
+    /// every acknowledged write is durable and the directory can safely be
+    /// copied or archived. The handle is consumed, so it cannot be used again.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the database was synced and closed cleanly
+    /// * `Err(String)` - If syncing the log or cleaning up failed (the database is closed
+    ///   regardless), or if other clones of this handle are still alive (the database
+    ///   then stays open until the last clone is dropped)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// let db = DB::open("world/db", &Options::default()).unwrap();
+    /// db.put(b"key", b"value", &Default::default()).unwrap();
+    ///
+    /// db.close().expect("Database was not closed cleanly");
+    /// // Safe to archive the directory now
+    /// ```
+    pub fn close(self) -> Result<(), String> {
+        let synced = self.flush();
+
+        let mut inner = Arc::try_unwrap(self.inner).map_err(|shared| {
+            format!(
+                "database at {} is still used by {} other handle(s)",
+                shared.path.display(),
+                Arc::strong_count(&shared) - 1
+            )
+        })?;
+
+        unsafe { sys::leveldb_close(inner.raw) };
+        inner.raw = ptr::null_mut();
+
+        let cleaned = match inner.scratch_dir.take() {
+            Some(scratch) => fs::remove_dir_all(&scratch).map_err(|e| {
+                format!(
+                    "failed to remove scratch directory {}: {}",
+                    scratch.display(),
+                    e
+                )
+            }),
+            None => Ok(()),
+        };
+
+        synced.and(cleaned)
+    }
+
+    /// Return the compression this database was opened with.
+    pub(crate) fn compression(&self) -> Compression {
+        self.inner.compression
+    }
+
+    /// Acquire the mutex serializing conditional writes on this database.
+    ///
+    /// A panic while the lock was held leaves no partial state behind, so a
+    /// poisoned lock is simply taken over.
+    pub(crate) fn lock_writes(&self) -> MutexGuard<'_, ()> {
+        self.inner
+            .write_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Estimate the on-disk size of each `[start, limit)` key range.
+    ///
+    /// Only data already written to table files is counted; entries still in
+    /// the memtable contribute nothing.
+    pub(crate) fn approximate_sizes(&self, ranges: &[(&[u8], &[u8])]) -> Vec<u64> {
+        let start_ptrs: Vec<*const libc::c_char> = ranges
+            .iter()
+            .map(|(start, _)| start.as_ptr() as *const _)
+            .collect();
+        let start_lens: Vec<usize> = ranges.iter().map(|(start, _)| start.len()).collect();
+        let limit_ptrs: Vec<*const libc::c_char> = ranges
+            .iter()
+            .map(|(_, limit)| limit.as_ptr() as *const _)
+            .collect();
+        let limit_lens: Vec<usize> = ranges.iter().map(|(_, limit)| limit.len()).collect();
+        let mut sizes = vec![0u64; ranges.len()];
+
+        unsafe {
+            sys::leveldb_approximate_sizes(
+                self.raw(),
+                ranges.len() as libc::c_int,
+                start_ptrs.as_ptr(),
+                start_lens.as_ptr(),
+                limit_ptrs.as_ptr(),
+                limit_lens.as_ptr(),
+                sizes.as_mut_ptr(),
+            );
+        }
+        sizes
+    }
+
     /// Return the raw pointer to the underlying LevelDB database.
     ///
     /// # Safety
@@ -368,17 +1887,142 @@ impl DB {
     ///
     /// A raw pointer to the underlying `leveldb_t` database handle.
     pub(crate) fn raw(&self) -> *mut sys::leveldb_t {
-        self.raw
+        self.inner.raw
+    }
+
+    /// Return the raw LevelDB handle, for calling [`sys`](crate::sys)
+    /// functions this crate does not wrap.
+    ///
+    /// The handle stays owned by this `DB` and is closed when the last clone
+    /// is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The caller must not close the handle, and must not use it once this
+    /// `DB` and every clone of it are gone. Writes made through it bypass the
+    /// read-only check and the write lock of [`DB::update`].
+    ///
+    /// # Returns
+    ///
+    /// The `leveldb_t` handle, valid as long as this `DB` or any of its
+    /// clones is alive
+    pub unsafe fn as_raw(&self) -> *mut sys::leveldb_t {
+        self.inner.raw
+    }
+
+    /// Wrap a raw LevelDB handle, taking ownership of it.
+    ///
+    /// The returned `DB` closes the handle when its last clone is dropped. It
+    /// is treated as writable and as using the default compression; `path`
+    /// is what [`DB::path`] reports and what helpers that work on the
+    /// database files, such as [`DB::checkpoint`], read.
+    ///
+    /// To move a handle out of a `DB` and back, take it with
+    /// [`DB::as_raw`] and [`std::mem::forget`] the `DB`, which must have no
+    /// other clones.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid open handle, such as one returned by
+    /// `leveldb_open`, that nothing else closes or wraps, and `path` must be
+    /// the directory it was opened at.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The handle to take ownership of
+    /// * `path` - The directory of the database
+    ///
+    /// # Returns
+    ///
+    /// A `DB` owning the handle
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let raw = unsafe { db.as_raw() };
+    /// std::mem::forget(db);
+    ///
+    /// let db = unsafe { DB::from_raw(raw, "world/db") };
+    /// ```
+    pub unsafe fn from_raw(raw: *mut sys::leveldb_t, path: impl AsRef<Path>) -> DB {
+        let path = path.as_ref();
+        DB {
+            inner: Arc::new(DbInner {
+                raw,
+                path: path.canonicalize().unwrap_or_else(|_| path.to_path_buf()),
+                read_only: false,
+                compression: OptionsSettings::default().compression,
+                scratch_dir: None,
+                _filter_policy: None,
+                write_lock: Mutex::new(()),
+                metrics: Metrics::new(),
+            }),
+        }
     }
 }
 
-impl Drop for DB {
+impl fmt::Debug for DB {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DB")
+            .field("path", &self.inner.path)
+            .field("read_only", &self.inner.read_only)
+            .field("leveldb_version", &crate::leveldb_version())
+            .finish()
+    }
+}
+
+impl Drop for DbInner {
     /// Close the database and release all associated resources.
     ///
-    /// This method is automatically called when the `DB` instance goes out of scope.
+    /// This method is automatically called when the last `DB` handle goes out of scope.
     /// It ensures that all database files are properly closed and any pending
-    /// operations are completed.
+    /// operations are completed. It does nothing if [`DB::close`] already ran.
     fn drop(&mut self) {
+        if self.raw.is_null() {
+            return;
+        }
         unsafe { sys::leveldb_close(self.raw) };
+        if let Some(scratch) = self.scratch_dir.take() {
+            let _ = fs::remove_dir_all(scratch);
+        }
+    }
+}
+
+/// Populate a scratch directory from which a read-only handle can be opened.
+///
+/// Metadata and log files are copied because LevelDB rewrites them on open;
+/// table files are immutable and are linked instead of copied where possible.
+fn stage_read_only_files(source: &Path, scratch: &Path) -> io::Result<()> {
+    let source = source.canonicalize()?;
+    for entry in fs::read_dir(&source)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let name = entry.file_name();
+        let name_str = name.to_string_lossy();
+        let from = entry.path();
+        let to = scratch.join(&name);
+
+        if name_str == "CURRENT" || name_str.starts_with("MANIFEST-") || name_str.ends_with(".log")
+        {
+            fs::copy(&from, &to)?;
+        } else if name_str.ends_with(".ldb") || name_str.ends_with(".sst") {
+            link_or_copy(&from, &to)?;
+        }
     }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn link_or_copy(from: &Path, to: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(from, to)
+}
+
+#[cfg(not(unix))]
+fn link_or_copy(from: &Path, to: &Path) -> io::Result<()> {
+    fs::copy(from, to).map(|_| ())
 }
@@ -0,0 +1,363 @@
+//! Streaming export and import of a database in a portable binary format.
+//!
+//! The format is described by [`FORMAT_SPEC`]. It is written by
+//! [`DB::export_to_writer`] and read back by [`DB::import_from_reader`], and
+//! can be sent over a pipe or socket instead of copying the database directory.
+
+use std::io::{self, Read, Write};
+
+use crate::db::{DB, DEFAULT_BATCH_BYTES};
+use crate::iterator::DBIterator;
+use crate::options::{ReadOptions, WriteOptions};
+use crate::util::{Crc32, encode_varint, read_varint};
+use crate::write_batch::WriteBatch;
+
+/// Magic bytes every export stream starts with.
+pub const MAGIC: [u8; 8] = *b"BLDBDUMP";
+
+/// Version of the export format written by this crate.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Tag byte preceding every key/value record.
+pub(crate) const RECORD_TAG: u8 = 0x01;
+
+/// Tag byte preceding the trailer.
+pub(crate) const END_TAG: u8 = 0x00;
+
+/// Description of the export stream format.
+pub const FORMAT_SPEC: &str = "\
+bleveldb export format, version 1
+
+All integers are unsigned. `varint` is LEB128 (7 bits per byte, least
+significant group first, high bit set on every byte but the last).
+
+stream  := header record* trailer
+header  := magic version
+magic   := \"BLDBDUMP\"                       (8 bytes)
+version := u8                               (currently 1)
+record  := 0x01 varint(key_len) key varint(value_len) value
+trailer := 0x00 varint(record_count) crc32
+
+Records appear in ascending key order. `crc32` is the CRC-32 (IEEE 802.3)
+of every byte from the start of the magic through `record_count`, stored as
+a little-endian u32. A stream without a valid trailer is truncated or
+corrupt and must be rejected as a whole.
+";
+
+/// Summary of a completed [`DB::export_to_writer`] call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExportStats {
+    /// Number of key/value records written.
+    pub records: u64,
+    /// Total size of the exported keys, in bytes.
+    pub key_bytes: u64,
+    /// Total size of the exported values, in bytes.
+    pub value_bytes: u64,
+    /// Total size of the stream, including header and trailer, in bytes.
+    pub bytes_written: u64,
+}
+
+/// How [`DB::import_from_reader`] treats keys that already exist in the database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Replace existing values with the imported ones.
+    #[default]
+    Overwrite,
+    /// Keep existing values and skip the imported ones.
+    SkipExisting,
+    /// Stop the import at the first key that already exists.
+    ErrorOnConflict,
+}
+
+/// Summary of a completed [`DB::import_from_reader`] call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportStats {
+    /// Number of records read from the stream.
+    pub records: u64,
+    /// Number of records written to the database.
+    pub written: u64,
+    /// Number of records skipped because the key already existed.
+    pub skipped: u64,
+    /// Number of write batches committed.
+    pub batches: u64,
+}
+
+/// Writer adapter that checksums and counts everything passing through it.
+struct ChecksumWriter<W: Write> {
+    inner: W,
+    crc: Crc32,
+    written: u64,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    fn put(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.inner.write_all(bytes)?;
+        self.crc.update(bytes);
+        self.written += bytes.len() as u64;
+        Ok(())
+    }
+}
+
+/// Reader adapter that checksums everything passing through it.
+struct ChecksumReader<R: Read> {
+    inner: R,
+    crc: Crc32,
+}
+
+impl<R: Read> Read for ChecksumReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.crc.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Read exactly `len` bytes into `buf` without trusting `len` for allocation.
+fn read_field(reader: &mut impl Read, len: u64, buf: &mut Vec<u8>) -> io::Result<()> {
+    buf.clear();
+    reader.take(len).read_to_end(buf)?;
+    if (buf.len() as u64) < len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(())
+}
+
+/// Describe a stream read failure.
+fn stream_error(e: io::Error) -> String {
+    if e.kind() == io::ErrorKind::UnexpectedEof {
+        "truncated export stream".to_string()
+    } else {
+        format!("failed to read export stream: {}", e)
+    }
+}
+
+impl DB {
+    /// Write every entry of the database to `writer` in the export format.
+    ///
+    /// The entries are read from a snapshot, so writes made while the export
+    /// runs are not included. If `options` already carries a snapshot, that
+    /// one is used. See [`FORMAT_SPEC`] for the layout of the stream.
+    ///
+    /// Output is buffered internally; there is no need to wrap `writer` in a
+    /// `BufWriter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Destination of the export stream
+    /// * `options` - Read options used for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ExportStats)` - How many records and bytes were written
+    /// * `Err(String)` - If reading the database or writing the stream failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let mut out = std::io::stdout().lock();
+    /// let stats = db.export_to_writer(&mut out, &ReadOptions::new()).unwrap();
+    /// eprintln!("Exported {} records", stats.records);
+    /// ```
+    pub fn export_to_writer(
+        &self,
+        writer: &mut impl Write,
+        options: &ReadOptions,
+    ) -> Result<ExportStats, String> {
+        let (_snapshot, read_options) = self.consistent_read_options(options);
+        read_options.fill_cache(false);
+
+        let mut out = ChecksumWriter {
+            inner: io::BufWriter::new(writer),
+            crc: Crc32::new(),
+            written: 0,
+        };
+        let io_error = |e: io::Error| format!("failed to write export stream: {}", e);
+
+        let mut stats = ExportStats::default();
+        let mut scratch = Vec::with_capacity(10);
+
+        out.put(&MAGIC).map_err(io_error)?;
+        out.put(&[FORMAT_VERSION]).map_err(io_error)?;
+
+        let mut iter = DBIterator::new(self, &read_options);
+        iter.seek_to_first();
+        while let (Some(key), Some(value)) = (iter.key_ref(), iter.value_ref()) {
+            out.put(&[RECORD_TAG]).map_err(io_error)?;
+            for field in [key, value] {
+                scratch.clear();
+                encode_varint(field.len() as u64, &mut scratch);
+                out.put(&scratch).map_err(io_error)?;
+                out.put(field).map_err(io_error)?;
+            }
+            stats.records += 1;
+            stats.key_bytes += key.len() as u64;
+            stats.value_bytes += value.len() as u64;
+            iter.next_native();
+        }
+        iter.status()?;
+
+        scratch.clear();
+        scratch.push(END_TAG);
+        encode_varint(stats.records, &mut scratch);
+        out.put(&scratch).map_err(io_error)?;
+        let checksum = out.crc.finish().to_le_bytes();
+        out.put(&checksum).map_err(io_error)?;
+        out.inner.flush().map_err(io_error)?;
+
+        stats.bytes_written = out.written;
+        Ok(stats)
+    }
+
+    /// Load records produced by [`DB::export_to_writer`] into the database.
+    ///
+    /// Records are applied in batches of at most [`DEFAULT_BATCH_BYTES`]. The
+    /// last batch is only committed after the stream's trailer and checksum
+    /// have been verified, but earlier batches are already applied by then:
+    /// when the stream turns out to be corrupt or truncated, or a conflict
+    /// stops the import, the error message says how many records were
+    /// committed before the failure.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the export stream
+    /// * `options` - Write options used for every batch
+    /// * `mode` - How to handle keys that already exist
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ImportStats)` - How many records were read, written and skipped
+    /// * `Err(String)` - If the stream is invalid, a conflict was found, or writing failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::export::ImportMode;
+    /// # use bleveldb::options::{Options, WriteOptions};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let mut input = std::io::stdin().lock();
+    /// let stats = db
+    ///     .import_from_reader(&mut input, &WriteOptions::new(), ImportMode::SkipExisting)
+    ///     .unwrap();
+    /// eprintln!("Imported {}, skipped {}", stats.written, stats.skipped);
+    /// ```
+    pub fn import_from_reader(
+        &self,
+        reader: &mut impl Read,
+        options: &WriteOptions,
+        mode: ImportMode,
+    ) -> Result<ImportStats, String> {
+        let mut stats = ImportStats::default();
+        self.import_records(reader, options, mode, &mut stats)
+            .map_err(|e| {
+                if stats.written > 0 {
+                    format!("{} ({} records were already imported)", e, stats.written)
+                } else {
+                    e
+                }
+            })?;
+        Ok(stats)
+    }
+
+    /// Apply the records of an export stream, committing full batches as they fill up.
+    ///
+    /// `stats.written` only counts records whose batch has been committed.
+    fn import_records(
+        &self,
+        reader: &mut impl Read,
+        options: &WriteOptions,
+        mode: ImportMode,
+        stats: &mut ImportStats,
+    ) -> Result<(), String> {
+        let mut input = ChecksumReader {
+            inner: io::BufReader::new(reader),
+            crc: Crc32::new(),
+        };
+
+        let mut header = [0u8; 9];
+        input.read_exact(&mut header).map_err(stream_error)?;
+        if header[..8] != MAGIC {
+            return Err("not a bleveldb export stream".to_string());
+        }
+        if header[8] != FORMAT_VERSION {
+            return Err(format!("unsupported export format version {}", header[8]));
+        }
+
+        let read_options = ReadOptions::new();
+        let mut batch = WriteBatch::new();
+        let mut pending_bytes = 0;
+        let mut pending = 0;
+        let (mut key, mut value) = (Vec::new(), Vec::new());
+
+        loop {
+            let mut tag = [0u8];
+            input.read_exact(&mut tag).map_err(stream_error)?;
+            match tag[0] {
+                RECORD_TAG => {}
+                END_TAG => break,
+                other => {
+                    return Err(format!(
+                        "corrupt export stream: unexpected tag 0x{:02x}",
+                        other
+                    ));
+                }
+            }
+
+            let key_len = read_varint(&mut input).map_err(stream_error)?;
+            read_field(&mut input, key_len, &mut key).map_err(stream_error)?;
+            let value_len = read_varint(&mut input).map_err(stream_error)?;
+            read_field(&mut input, value_len, &mut value).map_err(stream_error)?;
+            stats.records += 1;
+
+            if mode != ImportMode::Overwrite && self.get(&key, &read_options)?.is_some() {
+                if mode == ImportMode::ErrorOnConflict {
+                    return Err(format!(
+                        "key {:?} already exists",
+                        String::from_utf8_lossy(&key)
+                    ));
+                }
+                stats.skipped += 1;
+                continue;
+            }
+
+            batch.put(&key, &value);
+            pending_bytes += key.len() + value.len();
+            pending += 1;
+            if pending_bytes >= DEFAULT_BATCH_BYTES {
+                self.write(&batch, options)?;
+                batch.clear();
+                stats.written += pending;
+                stats.batches += 1;
+                pending_bytes = 0;
+                pending = 0;
+            }
+        }
+
+        let count = read_varint(&mut input).map_err(stream_error)?;
+        let expected = input.crc.finish();
+        let mut checksum = [0u8; 4];
+        input
+            .inner
+            .read_exact(&mut checksum)
+            .map_err(stream_error)?;
+        if u32::from_le_bytes(checksum) != expected {
+            return Err("corrupt export stream: checksum mismatch".to_string());
+        }
+        if count != stats.records {
+            return Err(format!(
+                "corrupt export stream: trailer counts {} records but {} were read",
+                count, stats.records
+            ));
+        }
+
+        if pending > 0 {
+            self.write(&batch, options)?;
+            stats.written += pending;
+            stats.batches += 1;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+//! Loading large amounts of presorted data, such as a converted world.
+
+use crate::bedrock::describe_key;
+use crate::db::{DB, DEFAULT_BATCH_BYTES};
+use crate::jsonl::KeyEncoding;
+use crate::options::WriteOptions;
+use crate::write_batch::WriteBatch;
+
+/// Summary of a completed [`DB::ingest_sorted`] call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IngestStats {
+    /// Number of records written.
+    pub records: u64,
+    /// Total size of the written keys, in bytes.
+    pub key_bytes: u64,
+    /// Total size of the written values, in bytes.
+    pub value_bytes: u64,
+    /// Number of write batches committed.
+    pub batches: u64,
+}
+
+impl DB {
+    /// Write a large, presorted set of records and compact the result.
+    ///
+    /// Keys must be strictly ascending, which is checked as they arrive.
+    /// Records are committed in batches of about [`DEFAULT_BATCH_BYTES`],
+    /// the largest the bulk helpers use, and a single compaction of the whole
+    /// database follows the last one, so the tables end up sorted into
+    /// levels once instead of being rewritten over and over while loading.
+    /// Open the database with [`Options::bulk_load`](crate::Options::bulk_load)
+    /// for best results.
+    ///
+    /// Each batch is atomic, but the ingest as a whole is not: when a key is
+    /// out of order, or a commit fails, the batches committed before stay
+    /// in the database and the error says how many records they hold. The
+    /// records of the pending batch are discarded and nothing is compacted.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The keys and values to write, in ascending key order
+    /// * `options` - Write options used for every batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(IngestStats)` - How many records, bytes and batches were written
+    /// * `Err(String)` - If a key is not greater than the one before it, or writing failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, WriteOptions};
+    /// let db = DB::open("converted/db", &Options::bulk_load()).unwrap();
+    /// let records = (0..1_000_000u32).map(|i| (i.to_be_bytes(), b"value"));
+    /// let stats = db.ingest_sorted(records, &WriteOptions::new()).unwrap();
+    /// println!("Loaded {} records in {} batches", stats.records, stats.batches);
+    /// ```
+    pub fn ingest_sorted<I, K, V>(
+        &self,
+        items: I,
+        options: &WriteOptions,
+    ) -> Result<IngestStats, String>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.check_writable()?;
+        let mut stats = IngestStats::default();
+        let mut batch = WriteBatch::new();
+        let mut pending = IngestStats::default();
+        let mut previous: Option<Vec<u8>> = None;
+
+        for (index, (key, value)) in items.into_iter().enumerate() {
+            let (key, value) = (key.as_ref(), value.as_ref());
+            if let Some(previous) = &previous
+                && key <= previous.as_slice()
+            {
+                return Err(format!(
+                    "key {} ({}) at index {} is not greater than the key before it, {} ({}); {} records were written",
+                    KeyEncoding::Hex.encode(key),
+                    describe_key(key),
+                    index,
+                    KeyEncoding::Hex.encode(previous),
+                    describe_key(previous),
+                    stats.records
+                ));
+            }
+            match &mut previous {
+                Some(previous) => {
+                    previous.clear();
+                    previous.extend_from_slice(key);
+                }
+                None => previous = Some(key.to_vec()),
+            }
+
+            batch.put(key, value);
+            pending.records += 1;
+            pending.key_bytes += key.len() as u64;
+            pending.value_bytes += value.len() as u64;
+            if batch.approximate_size() >= DEFAULT_BATCH_BYTES {
+                self.commit_ingest_batch(&mut batch, &mut pending, &mut stats, options)?;
+            }
+        }
+
+        if !batch.is_empty() {
+            self.commit_ingest_batch(&mut batch, &mut pending, &mut stats, options)?;
+        }
+        if stats.records > 0 {
+            self.compact_all()?;
+        }
+        Ok(stats)
+    }
+
+    /// Commit the pending batch of [`DB::ingest_sorted`] and move its counts
+    /// into `stats`.
+    fn commit_ingest_batch(
+        &self,
+        batch: &mut WriteBatch,
+        pending: &mut IngestStats,
+        stats: &mut IngestStats,
+        options: &WriteOptions,
+    ) -> Result<(), String> {
+        self.write(batch, options).map_err(|e| {
+            format!(
+                "failed to commit batch after {} records: {}",
+                stats.records, e
+            )
+        })?;
+        batch.clear();
+        stats.records += pending.records;
+        stats.key_bytes += pending.key_bytes;
+        stats.value_bytes += pending.value_bytes;
+        stats.batches += 1;
+        *pending = IngestStats::default();
+        Ok(())
+    }
+}
@@ -1,9 +1,14 @@
 use bleveldb_sys as sys;
+use std::iter::FusedIterator;
 use std::marker::PhantomData;
+use std::ops::{Bound, Deref, DerefMut, RangeBounds};
 use std::slice;
 
 use crate::DB;
 use crate::options::ReadOptions;
+use crate::snapshot::Snapshot;
+use crate::trace::op_span;
+use crate::util::error_message;
 
 /// A safe iterator over key-value pairs in a LevelDB database.
 ///
@@ -12,6 +17,14 @@ use crate::options::ReadOptions;
 ///
 /// The iterator yields `(Vec<u8>, Vec<u8>)` pairs representing (key, value).
 ///
+/// Used as an `Iterator`, a fresh `DBIterator` starts at the first key; it
+/// also implements [`DoubleEndedIterator`], so `.rev()` walks from the last
+/// key down. Once
+/// `next()` has returned `None` the iterator stays exhausted, as required by
+/// [`FusedIterator`]; calling [`seek`](DBIterator::seek),
+/// [`seek_to_first`](DBIterator::seek_to_first) or
+/// [`seek_to_last`](DBIterator::seek_to_last) resets it.
+///
 /// # Examples
 ///
 /// ## Basic iteration
@@ -52,6 +65,18 @@ use crate::options::ReadOptions;
 /// - The iterator maintains internal resources that are automatically cleaned up when dropped
 pub struct DBIterator<'db> {
     raw: *mut sys::leveldb_iterator_t,
+    /// Whether a seek has placed the cursor; until then `next()` starts at the first key.
+    positioned: bool,
+    /// Set once `next()` has returned `None`; cleared by every `seek*` call.
+    exhausted: bool,
+    /// Whether the cursor currently serves `next_back()` rather than `next()`.
+    reversed: bool,
+    /// Next key `next()` would yield, saved while the cursor serves the back end
+    /// (`None` if `next()` has not started).
+    front_at: Option<Vec<u8>>,
+    /// Next key `next_back()` would yield, saved while the cursor serves the
+    /// front end (`None` if `next_back()` has not started).
+    back_at: Option<Vec<u8>>,
     _db: PhantomData<&'db DB>,
 }
 
@@ -70,9 +95,67 @@ impl<'db> DBIterator<'db> {
     ///
     /// A new iterator positioned before the first key in the database.
     pub(crate) fn new(db: &'db DB, options: &ReadOptions) -> Self {
+        let op = op_span!(TRACE, "iter", snapshot = options.has_snapshot());
         let iter = unsafe { sys::leveldb_create_iterator(db.raw(), options.raw()) };
+        db.inner.metrics.iterator();
+        let _ = op.finish(Ok(()));
         Self {
             raw: iter,
+            positioned: false,
+            exhausted: false,
+            reversed: false,
+            front_at: None,
+            back_at: None,
+            _db: PhantomData,
+        }
+    }
+
+    /// Return the raw LevelDB iterator, for calling [`sys`](crate::sys)
+    /// functions this crate does not wrap.
+    ///
+    /// # Safety
+    ///
+    /// The pointer stays owned by this iterator: the caller must not destroy
+    /// it or use it after the iterator is dropped. Moving the cursor through
+    /// it while `next()` or `next_back()` are under way confuses the
+    /// position they track; call a `seek*` method afterwards to reset it.
+    ///
+    /// # Returns
+    ///
+    /// The `leveldb_iterator_t` pointer
+    pub unsafe fn as_raw(&self) -> *mut sys::leveldb_iterator_t {
+        self.raw
+    }
+
+    /// Wrap a raw LevelDB iterator over `db`, taking ownership of it.
+    ///
+    /// The pointer is destroyed when the returned iterator is dropped. If
+    /// the cursor is on an entry, iteration with `next()` continues from
+    /// there; otherwise it starts at the first key.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must come from `leveldb_create_iterator` on the handle of `db`
+    /// and must not be destroyed or wrapped by anything else. A snapshot it
+    /// reads from must outlive the returned iterator.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database the iterator reads, which it borrows
+    /// * `raw` - The iterator to take ownership of
+    ///
+    /// # Returns
+    ///
+    /// A `DBIterator` owning the pointer
+    pub unsafe fn from_raw(_db: &'db DB, raw: *mut sys::leveldb_iterator_t) -> Self {
+        let positioned = unsafe { sys::leveldb_iter_valid(raw) } != 0;
+        Self {
+            raw,
+            positioned,
+            exhausted: false,
+            reversed: false,
+            front_at: None,
+            back_at: None,
             _db: PhantomData,
         }
     }
@@ -96,6 +179,7 @@ impl<'db> DBIterator<'db> {
     /// }
     /// ```
     pub fn seek_to_first(&mut self) {
+        self.reset();
         unsafe { sys::leveldb_iter_seek_to_first(self.raw) };
     }
 
@@ -118,6 +202,7 @@ impl<'db> DBIterator<'db> {
     /// }
     /// ```
     pub fn seek_to_last(&mut self) {
+        self.reset();
         unsafe { sys::leveldb_iter_seek_to_last(self.raw) };
     }
 
@@ -128,7 +213,7 @@ impl<'db> DBIterator<'db> {
     /// # Arguments
     ///
     /// * `key` - The key to seek to. The iterator will position itself at the first key
-    ///           that is greater than or equal to this key according to the comparator.
+    ///   that is greater than or equal to this key according to the comparator.
     ///
     /// # Examples
     ///
@@ -151,6 +236,55 @@ impl<'db> DBIterator<'db> {
     /// }
     /// ```
     pub fn seek(&mut self, key: &[u8]) {
+        self.reset();
+        self.seek_raw(key);
+    }
+
+    /// Move iterator to the last key less than or equal to the given key.
+    ///
+    /// This is the backward counterpart of [`seek`](DBIterator::seek), useful
+    /// to find the record covering a position. If `key` is after the last key
+    /// the iterator lands on the last key; if it is before the first key (or
+    /// the database is empty) the iterator becomes invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to seek to
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, options::Options};
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// let mut iter = db.iter(&Default::default());
+    ///
+    /// // Find the checkpoint written at or before tick 1500
+    /// iter.seek_for_prev(b"checkpoint_000001500");
+    /// if let Some(key) = iter.key() {
+    ///     println!("Covering checkpoint: {:?}", key);
+    /// }
+    /// ```
+    pub fn seek_for_prev(&mut self, key: &[u8]) {
+        self.seek(key);
+        if !self.valid() {
+            self.seek_to_last();
+        } else if self.key_ref().is_some_and(|found| found > key) {
+            self.prev_native();
+        }
+    }
+
+    /// Forget all traversal state before an explicit seek.
+    fn reset(&mut self) {
+        self.positioned = true;
+        self.exhausted = false;
+        self.reversed = false;
+        self.front_at = None;
+        self.back_at = None;
+    }
+
+    /// Move the cursor without touching the traversal state.
+    fn seek_raw(&mut self, key: &[u8]) {
         unsafe {
             sys::leveldb_iter_seek(self.raw, key.as_ptr() as *const _, key.len());
         }
@@ -215,14 +349,21 @@ impl<'db> DBIterator<'db> {
         unsafe { sys::leveldb_iter_prev(self.raw) };
     }
 
-    /// Move to the previous key and return the current key-value pair.
+    /// Return the current key-value pair and move to the previous key.
     ///
-    /// This is a convenience method that combines moving backward with data retrieval.
+    /// This is the backward counterpart of [`Iterator::next`]: the entry the
+    /// cursor sits on is returned first, so `seek_to_last` followed by repeated
+    /// `prev()` calls visits every entry, the last one included. On an
+    /// iterator that has not been positioned yet, it starts at the last key.
+    ///
+    /// `prev()` only moves the cursor; it does not take part in the
+    /// bookkeeping that keeps `next()` and `next_back()` from yielding an entry
+    /// twice. Use [`DB::iter_rev`] or `.rev()` for plain backward iteration.
     ///
     /// # Returns
     ///
-    /// * `Some((key, value))` - If the iterator was valid before moving and is still valid after moving
-    /// * `None` - If the iterator was not valid or reached the beginning
+    /// * `Some((key, value))` - The entry the iterator was positioned at
+    /// * `None` - If the iterator is not positioned at a valid entry
     ///
     /// # Examples
     ///
@@ -233,21 +374,18 @@ impl<'db> DBIterator<'db> {
     /// let mut iter = db.iter(&Default::default());
     /// iter.seek_to_last();
     ///
-    /// // Get the last item and move backward
+    /// // Visit every entry, from the last key to the first
     /// while let Some((key, value)) = iter.prev() {
-    ///     println!("Previous key: {:?}", key);
+    ///     println!("Key: {:?}", key);
     /// }
     /// ```
     pub fn prev(&mut self) -> Option<(Vec<u8>, Vec<u8>)> {
-        if !self.valid() {
-            None
-        } else {
-            self.prev_native();
-
-            let key = self.key()?;
-            let value = self.value()?;
-            Some((key, value))
+        if !self.positioned {
+            self.seek_to_last();
         }
+        let item = (self.key()?, self.value()?);
+        self.prev_native();
+        Some(item)
     }
 
     /// Check if the iterator is currently positioned at a valid key-value pair.
@@ -288,7 +426,7 @@ impl<'db> DBIterator<'db> {
     /// # Note
     ///
     /// The returned vector is a copy of the key data. For performance-sensitive code,
-    /// consider whether you need to copy the data or can work with references.
+    /// use [`key_ref`](DBIterator::key_ref) to borrow it instead.
     ///
     /// # Examples
     ///
@@ -304,15 +442,7 @@ impl<'db> DBIterator<'db> {
     /// }
     /// ```
     pub fn key(&self) -> Option<Vec<u8>> {
-        unsafe {
-            if self.valid() {
-                let mut klen: usize = 0;
-                let ptr = sys::leveldb_iter_key(self.raw, &mut klen);
-                Some(slice::from_raw_parts(ptr as *const u8, klen).to_vec())
-            } else {
-                None
-            }
-        }
+        self.key_ref().map(<[u8]>::to_vec)
     }
 
     /// Get the current value at the iterator position.
@@ -325,7 +455,7 @@ impl<'db> DBIterator<'db> {
     /// # Note
     ///
     /// The returned vector is a copy of the value data. For performance-sensitive code,
-    /// consider whether you need to copy the data or can work with references.
+    /// use [`value_ref`](DBIterator::value_ref) to borrow it instead.
     ///
     /// # Examples
     ///
@@ -341,14 +471,374 @@ impl<'db> DBIterator<'db> {
     /// }
     /// ```
     pub fn value(&self) -> Option<Vec<u8>> {
+        self.value_ref().map(<[u8]>::to_vec)
+    }
+
+    /// Visit entries by seeking ahead instead of stepping through every key.
+    ///
+    /// The returned iterator yields the current entry (the first one if the
+    /// iterator has not been positioned yet), then calls `make_next_target`
+    /// with its key and seeks to the returned key. This skips dense parts of
+    /// the key space, for example jumping straight to the next x/z stripe of
+    /// chunk keys when building an overview map. A target at or before the
+    /// current key just advances by one entry, so the scan always makes
+    /// progress; a target past the last key ends it.
+    ///
+    /// # Arguments
+    ///
+    /// * `make_next_target` - Computes the key to seek to after visiting a key
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, options::Options};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let mut iter = db.iter(&Default::default());
+    /// // Visit one key per distinct first byte
+    /// let samples: Vec<_> = iter
+    ///     .step_by_seek(|key| vec![key[0].saturating_add(1)])
+    ///     .map(|(key, _)| key)
+    ///     .collect();
+    /// ```
+    pub fn step_by_seek<F>(&mut self, make_next_target: F) -> SeekStep<'_, 'db, F>
+    where
+        F: FnMut(&[u8]) -> Vec<u8>,
+    {
+        SeekStep {
+            iter: self,
+            make_next_target,
+            done: false,
+        }
+    }
+
+    /// Copy the next key-value pair into caller-provided buffers.
+    ///
+    /// This is the allocation-free counterpart of [`Iterator::next`]: it follows
+    /// exactly the same rules (a fresh iterator starts at the first key, explicit
+    /// `seek*` calls set the starting point, and once the end is reached it
+    /// keeps returning `false` until the next seek), but overwrites `key` and
+    /// `value` instead of returning new `Vec`s. Once the buffers have grown to
+    /// the largest entry seen, scanning a whole world allocates nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Buffer that receives the key; its previous contents are replaced
+    /// * `value` - Buffer that receives the value; its previous contents are replaced
+    ///
+    /// # Returns
+    ///
+    /// * `true` - If an entry was copied into the buffers
+    /// * `false` - If the iterator is exhausted; the buffers are left untouched
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, options::Options};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let mut iter = db.iter(&Default::default());
+    /// let (mut key, mut value) = (Vec::new(), Vec::new());
+    ///
+    /// let mut largest = 0;
+    /// while iter.next_into(&mut key, &mut value) {
+    ///     largest = largest.max(value.len());
+    /// }
+    /// iter.status().expect("Iteration stopped early");
+    /// ```
+    pub fn next_into(&mut self, key: &mut Vec<u8>, value: &mut Vec<u8>) -> bool {
+        if !self.prepare_front() {
+            return false;
+        }
+        match (self.key_ref(), self.value_ref()) {
+            (Some(k), Some(v)) => {
+                key.clear();
+                key.extend_from_slice(k);
+                value.clear();
+                value.extend_from_slice(v);
+            }
+            _ => return false,
+        }
+        self.next_native();
+        true
+    }
+
+    /// Return the entry the next call to `next()` would yield, without consuming it.
+    ///
+    /// The LevelDB cursor already sits on that entry, so peeking is cheap and
+    /// makes look-ahead logic, such as grouping the records of one chunk, easy
+    /// to write without buffering. It follows the same positioning rules as
+    /// [`Iterator::next`]: on a fresh iterator it moves to the first key, which
+    /// is why it takes `&mut self`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((key, value))` - The entry `next()` would return
+    /// * `None` - If the iterator is exhausted
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, options::Options};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let mut iter = db.iter(&Default::default());
+    /// // Group consecutive keys sharing their first 8 bytes
+    /// while let Some((first, _)) = iter.next() {
+    ///     let prefix = first[..first.len().min(8)].to_vec();
+    ///     let mut records = 1;
+    ///     while iter.peek_key().is_some_and(|key| key.starts_with(&prefix)) {
+    ///         iter.next();
+    ///         records += 1;
+    ///     }
+    ///     println!("{:?}: {} records", prefix, records);
+    /// }
+    /// ```
+    pub fn peek(&mut self) -> Option<(Vec<u8>, Vec<u8>)> {
+        if !self.prepare_front() {
+            return None;
+        }
+        Some((self.key_ref()?.to_vec(), self.value_ref()?.to_vec()))
+    }
+
+    /// Borrow the key the next call to `next()` would yield, without consuming it.
+    ///
+    /// Like [`peek`](DBIterator::peek), but returns only the key and does not
+    /// copy it.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&[u8])` - The key `next()` would return
+    /// * `None` - If the iterator is exhausted
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, options::Options};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let mut iter = db.iter(&Default::default());
+    /// if let Some(key) = iter.peek_key() {
+    ///     println!("First key: {:?}", key);
+    /// }
+    /// ```
+    pub fn peek_key(&mut self) -> Option<&[u8]> {
+        if !self.prepare_front() {
+            return None;
+        }
+        self.key_ref()
+    }
+
+    /// Place the cursor on the entry `next()` should yield.
+    ///
+    /// Positions a fresh iterator at the first key and takes the cursor back
+    /// from the back end if needed. Returns `false`, marking the iterator as
+    /// exhausted, if no entry is left before the back end's position.
+    fn prepare_front(&mut self) -> bool {
+        if self.exhausted {
+            return false;
+        }
+        if self.reversed {
+            // Hand the cursor back to the front end, remembering where the back end stands
+            let Some(back) = self.key_ref().map(<[u8]>::to_vec) else {
+                self.exhausted = true;
+                return false;
+            };
+            self.back_at = Some(back);
+            self.reversed = false;
+            match self.front_at.take() {
+                Some(front) => self.seek_raw(&front),
+                None => unsafe { sys::leveldb_iter_seek_to_first(self.raw) },
+            }
+        } else if !self.positioned {
+            self.positioned = true;
+            unsafe { sys::leveldb_iter_seek_to_first(self.raw) };
+        }
+
+        let in_bounds = self
+            .key_ref()
+            .is_some_and(|key| self.back_at.as_deref().is_none_or(|back| key <= back));
+        if !in_bounds {
+            self.exhausted = true;
+        }
+        in_bounds
+    }
+
+    /// Check whether the iterator encountered an error.
+    ///
+    /// An iterator that hits a corrupted block or an I/O error becomes invalid;
+    /// this method distinguishes that case from simply reaching the end.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If no error has occurred
+    /// * `Err(String)` - The error reported by LevelDB
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, options::Options};
+    /// # let options = Options::default();
+    /// # let db = DB::open("test_db", &options).unwrap();
+    /// let mut iter = db.iter(&Default::default());
+    /// iter.seek_to_first();
+    /// while iter.valid() {
+    ///     iter.next_native();
+    /// }
+    /// iter.status().expect("Iteration stopped early");
+    /// ```
+    pub fn status(&self) -> Result<(), String> {
+        let mut err = std::ptr::null_mut();
+        unsafe { sys::leveldb_iter_get_error(self.raw, &mut err) };
+        if !err.is_null() {
+            return Err(error_message(err as *mut _));
+        }
+        Ok(())
+    }
+
+    /// Borrow the current key without copying it.
+    ///
+    /// Unlike [`key`](DBIterator::key), this does not allocate, which matters
+    /// when scanning a whole world just to classify its keys. LevelDB only
+    /// guarantees the slice until the iterator is next modified, so it borrows
+    /// the iterator: the compiler rejects any `seek*`, `next*` or `prev*` call
+    /// while the slice is alive.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&[u8])` - The current key if the iterator is valid
+    /// * `None` - If the iterator is not positioned at a valid entry
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, options::Options};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let mut iter = db.iter(&Default::default());
+    /// iter.seek_to_first();
+    ///
+    /// let mut chunk_keys = 0;
+    /// while let Some(key) = iter.key_ref() {
+    ///     if key.len() == 9 || key.len() == 13 {
+    ///         chunk_keys += 1;
+    ///     }
+    ///     iter.next_native();
+    /// }
+    /// ```
+    ///
+    /// Moving the iterator while a borrowed key is still in use does not compile:
+    ///
+    /// ```compile_fail
+    /// # use bleveldb::{DB, options::Options};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let mut iter = db.iter(&Default::default());
+    /// iter.seek_to_first();
+    /// let key = iter.key_ref().unwrap();
+    /// iter.next_native();
+    /// println!("{:?}", key);
+    /// ```
+    pub fn key_ref(&self) -> Option<&[u8]> {
+        if !self.valid() {
+            return None;
+        }
         unsafe {
-            if self.valid() {
-                let mut vlen: usize = 0;
-                let ptr = sys::leveldb_iter_value(self.raw, &mut vlen);
-                Some(slice::from_raw_parts(ptr as *const u8, vlen).to_vec())
-            } else {
-                None
+            let mut klen: usize = 0;
+            let ptr = sys::leveldb_iter_key(self.raw, &mut klen);
+            Some(slice::from_raw_parts(ptr as *const u8, klen))
+        }
+    }
+
+    /// Borrow the current value without copying it.
+    ///
+    /// See [`key_ref`](DBIterator::key_ref) for the lifetime guarantee: the
+    /// slice is valid until the iterator is next modified.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&[u8])` - The current value if the iterator is valid
+    /// * `None` - If the iterator is not positioned at a valid entry
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, options::Options};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let mut iter = db.iter(&Default::default());
+    /// iter.seek_to_first();
+    ///
+    /// let mut total = 0;
+    /// while let Some(value) = iter.value_ref() {
+    ///     total += value.len();
+    ///     iter.next_native();
+    /// }
+    /// ```
+    pub fn value_ref(&self) -> Option<&[u8]> {
+        if !self.valid() {
+            return None;
+        }
+        unsafe {
+            let mut vlen: usize = 0;
+            let ptr = sys::leveldb_iter_value(self.raw, &mut vlen);
+            Some(slice::from_raw_parts(ptr as *const u8, vlen))
+        }
+    }
+
+    /// Copy the current key into a caller-provided buffer.
+    ///
+    /// `buf` is cleared and the key is copied into it, reusing its
+    /// allocation. Together with [`value_into`](DBIterator::value_into) and
+    /// [`next_native`](DBIterator::next_native) this scans without allocating
+    /// once the buffers have grown to the largest entry, while keeping the
+    /// copies usable after the iterator moves on.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - Buffer that receives the key; left empty if the iterator is not valid
+    ///
+    /// # Returns
+    ///
+    /// `true` if the iterator is positioned at a valid entry, `false` otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, options::Options};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let mut iter = db.iter(&Default::default());
+    /// iter.seek_to_first();
+    ///
+    /// let (mut key, mut value) = (Vec::new(), Vec::new());
+    /// while iter.key_into(&mut key) && iter.value_into(&mut value) {
+    ///     println!("{:?} = {} bytes", key, value.len());
+    ///     iter.next_native();
+    /// }
+    /// ```
+    pub fn key_into(&self, buf: &mut Vec<u8>) -> bool {
+        buf.clear();
+        match self.key_ref() {
+            Some(key) => {
+                buf.extend_from_slice(key);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Copy the current value into a caller-provided buffer.
+    ///
+    /// See [`key_into`](DBIterator::key_into).
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - Buffer that receives the value; left empty if the iterator is not valid
+    ///
+    /// # Returns
+    ///
+    /// `true` if the iterator is positioned at a valid entry, `false` otherwise
+    pub fn value_into(&self, buf: &mut Vec<u8>) -> bool {
+        buf.clear();
+        match self.value_ref() {
+            Some(value) => {
+                buf.extend_from_slice(value);
+                true
             }
+            None => false,
         }
     }
 }
@@ -368,10 +858,13 @@ impl<'db> Iterator for DBIterator<'db> {
     ///
     /// # Behavior
     ///
-    /// - The iterator starts before the first element
-    /// - The first call to `next()` returns the first key-value pair
+    /// - A fresh iterator is not positioned yet; the first call to `next()`
+    ///   seeks to the first key and returns the first key-value pair
+    /// - If a `seek*` method was called first, iteration starts from that position instead
     /// - Subsequent calls advance through the database in order
-    /// - Returns `None` when all elements have been visited
+    /// - Returns `None` when all elements have been visited, and keeps returning
+    ///   `None` afterwards (the iterator is fused) until a `seek*` method
+    ///   repositions it
     ///
     /// # Examples
     ///
@@ -391,17 +884,86 @@ impl<'db> Iterator for DBIterator<'db> {
     /// let all_data: Vec<(Vec<u8>, Vec<u8>)> = iter2.collect();
     /// ```
     fn next(&mut self) -> Option<Self::Item> {
-        if !self.valid() {
+        if !self.prepare_front() {
             return None;
         }
-
-        let key = self.key()?;
-        let value = self.value()?;
+        let item = match (self.key_ref(), self.value_ref()) {
+            (Some(key), Some(value)) => (key.to_vec(), value.to_vec()),
+            _ => return None,
+        };
         self.next_native();
-        Some((key, value))
+        Some(item)
+    }
+}
+
+impl<'db> DoubleEndedIterator for DBIterator<'db> {
+    /// Return the entry with the greatest key not yet yielded from either end.
+    ///
+    /// On a fresh iterator this starts at the last key. `next()` and
+    /// `next_back()` can be interleaved freely: both ends share one LevelDB
+    /// cursor, which is re-seeked whenever the direction changes, and once the
+    /// two ends meet both return `None`. After an explicit `seek*` call, the
+    /// back end only yields keys at or after the seek position.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, options::Options};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// // Newest-first listing of keys
+    /// for (key, _) in db.iter(&Default::default()).rev().take(10) {
+    ///     println!("{:?}", key);
+    /// }
+    /// ```
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+        if !self.reversed {
+            // Take over the cursor, remembering where the front end stands
+            if self.positioned {
+                let Some(front) = self.key_ref().map(<[u8]>::to_vec) else {
+                    self.exhausted = true;
+                    return None;
+                };
+                self.front_at = Some(front);
+            }
+            self.positioned = true;
+            self.reversed = true;
+            match self.back_at.take() {
+                Some(back) => {
+                    // The saved key may have been deleted meanwhile; never step past it
+                    self.seek_raw(&back);
+                    if !self.valid() {
+                        unsafe { sys::leveldb_iter_seek_to_last(self.raw) };
+                    } else if self.key_ref().is_some_and(|key| key > back.as_slice()) {
+                        self.prev_native();
+                    }
+                }
+                None => unsafe { sys::leveldb_iter_seek_to_last(self.raw) },
+            }
+        }
+
+        let item = match (self.key_ref(), self.value_ref()) {
+            (Some(key), Some(value))
+                if self.front_at.as_deref().is_none_or(|front| key >= front) =>
+            {
+                (key.to_vec(), value.to_vec())
+            }
+            _ => {
+                self.exhausted = true;
+                return None;
+            }
+        };
+        self.prev_native();
+        Some(item)
     }
 }
 
+/// Once `next()` returns `None` it keeps doing so; only an explicit `seek*`
+/// call makes the iterator yield entries again.
+impl<'db> FusedIterator for DBIterator<'db> {}
+
 impl<'db> Drop for DBIterator<'db> {
     /// Clean up the iterator resources.
     ///
@@ -413,3 +975,348 @@ impl<'db> Drop for DBIterator<'db> {
         }
     }
 }
+
+/// An iterator that jumps between entries with seeks.
+///
+/// Created by [`DBIterator::step_by_seek`].
+pub struct SeekStep<'a, 'db, F> {
+    iter: &'a mut DBIterator<'db>,
+    make_next_target: F,
+    done: bool,
+}
+
+impl<'a, 'db, F> Iterator for SeekStep<'a, 'db, F>
+where
+    F: FnMut(&[u8]) -> Vec<u8>,
+{
+    type Item = (Vec<u8>, Vec<u8>);
+
+    /// Return the current entry and seek to the target computed from its key.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if !self.iter.positioned {
+            self.iter.seek_to_first();
+        }
+
+        let Some((key, value)) = self.iter.key().zip(self.iter.value()) else {
+            self.done = true;
+            return None;
+        };
+
+        let target = (self.make_next_target)(&key);
+        if target.as_slice() > key.as_slice() {
+            self.iter.seek(&target);
+        } else {
+            self.iter.next_native();
+        }
+        Some((key, value))
+    }
+}
+
+impl<'a, 'db, F> FusedIterator for SeekStep<'a, 'db, F> where F: FnMut(&[u8]) -> Vec<u8> {}
+
+/// A [`DBIterator`] that owns the snapshot it reads from.
+///
+/// Created by [`DB::snapshot_iter`]. Every entry comes from the state of the
+/// database when the iterator was created, no matter how long the iteration
+/// takes or what is written meanwhile, and the snapshot is released together
+/// with the iterator. It dereferences to [`DBIterator`], so seeks, `peek` and
+/// the borrowed accessors are all available.
+pub struct SnapshotIter<'db> {
+    // Declared before the snapshot so the iterator is destroyed first
+    iter: DBIterator<'db>,
+    snapshot: Snapshot<'db>,
+}
+
+impl<'db> SnapshotIter<'db> {
+    /// Take a snapshot and create an iterator reading through it.
+    pub(crate) fn new(db: &'db DB, options: &ReadOptions) -> Self {
+        let snapshot = db.snapshot();
+        let iter = DBIterator::new(db, &snapshot.read_options(options));
+        Self { iter, snapshot }
+    }
+
+    /// Return the snapshot this iterator reads from.
+    ///
+    /// Use it to make other reads, such as point lookups with
+    /// [`DB::get`], see the same state as the iterator.
+    pub fn snapshot(&self) -> &Snapshot<'db> {
+        &self.snapshot
+    }
+}
+
+impl<'db> Deref for SnapshotIter<'db> {
+    type Target = DBIterator<'db>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.iter
+    }
+}
+
+impl<'db> DerefMut for SnapshotIter<'db> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.iter
+    }
+}
+
+impl<'db> Iterator for SnapshotIter<'db> {
+    type Item = (Vec<u8>, Vec<u8>);
+
+    /// Return the next key-value pair, as [`DBIterator`] does.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+impl<'db> DoubleEndedIterator for SnapshotIter<'db> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<'db> FusedIterator for SnapshotIter<'db> {}
+
+/// An iterator over the database in descending key order.
+///
+/// Created by [`DB::iter_rev`]. It starts at the last key; every call to
+/// `next()` returns the current entry and steps the cursor backwards.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::{DB, options::Options};
+/// # let db = DB::open("test_db", &Options::default()).unwrap();
+/// let mut iter = db.iter_rev(&Default::default());
+/// // Walk backwards from "m" (or the greatest key before it)
+/// iter.seek(b"m");
+/// for (key, value) in iter {
+///     println!("Key: {:?}, Value: {:?}", key, value);
+/// }
+/// ```
+pub struct RevIter<'db> {
+    inner: DBIterator<'db>,
+    done: bool,
+}
+
+impl<'db> RevIter<'db> {
+    /// Create a reverse iterator positioned at the last key.
+    ///
+    /// This is marked as `pub(crate)` because reverse iterators should be
+    /// created through the `DB::iter_rev()` method rather than directly.
+    pub(crate) fn new(db: &'db DB, options: &ReadOptions) -> Self {
+        let mut inner = DBIterator::new(db, options);
+        inner.seek_to_last();
+        Self { inner, done: false }
+    }
+
+    /// Position the iterator at the greatest key less than or equal to `key`.
+    ///
+    /// If every key is greater than `key`, the iterator yields nothing.
+    pub fn seek(&mut self, key: &[u8]) {
+        self.done = false;
+        self.inner.seek_for_prev(key);
+    }
+
+    /// Position the iterator at the last key again.
+    pub fn seek_to_last(&mut self) {
+        self.done = false;
+        self.inner.seek_to_last();
+    }
+
+    /// Check whether the underlying iterator encountered an error.
+    ///
+    /// See [`DBIterator::status`].
+    pub fn status(&self) -> Result<(), String> {
+        self.inner.status()
+    }
+}
+
+impl<'db> Iterator for RevIter<'db> {
+    type Item = (Vec<u8>, Vec<u8>);
+
+    /// Return the current entry and move to the previous key.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let Some(item) = self.inner.key().zip(self.inner.value()) else {
+            self.done = true;
+            return None;
+        };
+        self.inner.prev_native();
+        Some(item)
+    }
+}
+
+impl<'db> FusedIterator for RevIter<'db> {}
+
+/// An iterator over the entries whose keys start with a given prefix.
+///
+/// Created by [`DB::prefix_iter`]. The iterator seeks to the prefix on creation
+/// and stops as soon as it reaches a key that no longer starts with it, so it
+/// never runs into the records that follow.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::{DB, options::Options};
+/// # let options = Options::default();
+/// # let db = DB::open("test_db", &options).unwrap();
+/// for (key, value) in db.prefix_iter(b"player_", &Default::default()) {
+///     println!("Player record: {:?} ({} bytes)", key, value.len());
+/// }
+/// ```
+pub struct PrefixIter<'db> {
+    inner: DBIterator<'db>,
+    prefix: Vec<u8>,
+    done: bool,
+}
+
+impl<'db> PrefixIter<'db> {
+    /// Create an iterator positioned at the first key starting with `prefix`.
+    ///
+    /// This is marked as `pub(crate)` because prefix iterators should be created
+    /// through the `DB::prefix_iter()` method rather than directly.
+    pub(crate) fn new(db: &'db DB, prefix: &[u8], options: &ReadOptions) -> Self {
+        let mut inner = DBIterator::new(db, options);
+        inner.seek(prefix);
+        Self {
+            inner,
+            prefix: prefix.to_vec(),
+            done: false,
+        }
+    }
+
+    /// Return the prefix this iterator is bounded by.
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    /// Check whether the underlying iterator encountered an error.
+    ///
+    /// See [`DBIterator::status`].
+    pub fn status(&self) -> Result<(), String> {
+        self.inner.status()
+    }
+}
+
+impl<'db> Iterator for PrefixIter<'db> {
+    type Item = (Vec<u8>, Vec<u8>);
+
+    /// Return the next entry under the prefix, or `None` once the prefix is left.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = match (self.inner.key_ref(), self.inner.value_ref()) {
+            (Some(key), Some(value)) if key.starts_with(&self.prefix) => {
+                (key.to_vec(), value.to_vec())
+            }
+            _ => {
+                self.done = true;
+                return None;
+            }
+        };
+        self.inner.next_native();
+        Some(item)
+    }
+}
+
+impl<'db> FusedIterator for PrefixIter<'db> {}
+
+/// An iterator over the entries whose keys fall within a range.
+///
+/// Created by [`DB::range`]. Both ends of the range may be included, excluded
+/// or unbounded, exactly like the standard library's range types.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::{DB, options::Options};
+/// # let options = Options::default();
+/// # let db = DB::open("test_db", &options).unwrap();
+/// for (key, value) in db.range(b"a".to_vec()..b"m".to_vec(), &Default::default()) {
+///     println!("Key: {:?}, Value: {:?}", key, value);
+/// }
+/// ```
+pub struct RangeIter<'db> {
+    inner: DBIterator<'db>,
+    end: Bound<Vec<u8>>,
+    done: bool,
+}
+
+impl<'db> RangeIter<'db> {
+    /// Create an iterator positioned at the first key within `range`.
+    ///
+    /// This is marked as `pub(crate)` because range iterators should be created
+    /// through the `DB::range()` method rather than directly.
+    pub(crate) fn new<R: RangeBounds<Vec<u8>>>(
+        db: &'db DB,
+        range: R,
+        options: &ReadOptions,
+    ) -> Self {
+        let mut inner = DBIterator::new(db, options);
+        match range.start_bound() {
+            Bound::Included(start) => inner.seek(start),
+            Bound::Excluded(start) => {
+                inner.seek(start);
+                if inner.key_ref() == Some(start.as_slice()) {
+                    inner.next_native();
+                }
+            }
+            Bound::Unbounded => inner.seek_to_first(),
+        }
+
+        Self {
+            inner,
+            end: range.end_bound().cloned(),
+            done: false,
+        }
+    }
+
+    /// Check whether the underlying iterator encountered an error.
+    ///
+    /// See [`DBIterator::status`].
+    pub fn status(&self) -> Result<(), String> {
+        self.inner.status()
+    }
+}
+
+impl<'db> Iterator for RangeIter<'db> {
+    type Item = (Vec<u8>, Vec<u8>);
+
+    /// Return the next entry within the range, or `None` once the end is passed.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = match (self.inner.key_ref(), self.inner.value_ref()) {
+            (Some(key), Some(value)) => {
+                let in_range = match &self.end {
+                    Bound::Included(end) => key <= end.as_slice(),
+                    Bound::Excluded(end) => key < end.as_slice(),
+                    Bound::Unbounded => true,
+                };
+                in_range.then(|| (key.to_vec(), value.to_vec()))
+            }
+            _ => None,
+        };
+
+        match item {
+            Some(item) => {
+                self.inner.next_native();
+                Some(item)
+            }
+            None => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+impl<'db> FusedIterator for RangeIter<'db> {}
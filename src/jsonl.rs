@@ -0,0 +1,547 @@
+//! Export and import of a database as JSON lines, for use from scripts.
+//!
+//! Every entry becomes one line holding a JSON object:
+//!
+//! ```text
+//! {"key":"7e6c6f63616c5f706c61796572","value":"0a00...","description":"player ~local_player"}
+//! ```
+//!
+//! `key` and `value` hold the raw bytes in the [`KeyEncoding`] picked for the
+//! export. `description` is the key as classified by
+//! [`describe_key`](crate::bedrock::describe_key), for readers only: the
+//! import ignores it, along with any other field.
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::bedrock::describe_key;
+use crate::db::{DB, DEFAULT_BATCH_BYTES};
+use crate::export::ExportStats;
+use crate::iterator::DBIterator;
+use crate::options::{ReadOptions, WriteOptions};
+use crate::write_batch::WriteBatch;
+
+/// Number of malformed lines [`DB::import_jsonl`] keeps the details of.
+pub const MAX_REPORTED_LINES: usize = 100;
+
+/// Deepest nesting of arrays and objects accepted in skipped fields.
+const MAX_DEPTH: usize = 512;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// How keys and values are written as JSON strings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyEncoding {
+    /// Lowercase hexadecimal, two digits per byte. Uppercase digits are
+    /// accepted on import.
+    #[default]
+    Hex,
+    /// Standard base64 (RFC 4648) with padding.
+    Base64,
+}
+
+impl KeyEncoding {
+    /// Encode bytes as a string in this encoding.
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            KeyEncoding::Hex => {
+                let mut out = String::with_capacity(bytes.len() * 2);
+                for byte in bytes {
+                    out.push_str(&format!("{:02x}", byte));
+                }
+                out
+            }
+            KeyEncoding::Base64 => {
+                let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+                for chunk in bytes.chunks(3) {
+                    let group = chunk
+                        .iter()
+                        .enumerate()
+                        .fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+                    for i in 0..4 {
+                        if i <= chunk.len() {
+                            let index = (group >> (18 - 6 * i)) & 0x3f;
+                            out.push(BASE64_ALPHABET[index as usize] as char);
+                        } else {
+                            out.push('=');
+                        }
+                    }
+                }
+                out
+            }
+        }
+    }
+
+    /// Decode a string in this encoding.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<u8>)` - The decoded bytes
+    /// * `Err(String)` - If `text` is not valid in this encoding
+    pub fn decode(self, text: &str) -> Result<Vec<u8>, String> {
+        match self {
+            KeyEncoding::Hex => {
+                if !text.len().is_multiple_of(2) {
+                    return Err("hex string has an odd number of digits".to_string());
+                }
+                text.as_bytes()
+                    .chunks(2)
+                    .map(|pair| {
+                        std::str::from_utf8(pair)
+                            .ok()
+                            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                            .ok_or_else(|| format!("invalid hex digits {:?}", pair))
+                    })
+                    .collect()
+            }
+            KeyEncoding::Base64 => {
+                let bytes = text.as_bytes();
+                if !bytes.len().is_multiple_of(4) {
+                    return Err("base64 string length is not a multiple of 4".to_string());
+                }
+                let mut out = Vec::with_capacity(bytes.len() / 4 * 3);
+                let groups = bytes.len() / 4;
+                for (n, group) in bytes.chunks(4).enumerate() {
+                    let padding = group.iter().rev().take_while(|&&b| b == b'=').count();
+                    if padding > 2 || (padding > 0 && n + 1 < groups) {
+                        return Err("misplaced base64 padding".to_string());
+                    }
+                    let mut value = 0u32;
+                    for &b in &group[..4 - padding] {
+                        let digit = BASE64_ALPHABET
+                            .iter()
+                            .position(|&a| a == b)
+                            .ok_or_else(|| format!("invalid base64 character {:?}", b as char))?;
+                        value = value << 6 | digit as u32;
+                    }
+                    value <<= 6 * padding as u32;
+                    out.extend_from_slice(&value.to_be_bytes()[1..4 - padding]);
+                }
+                Ok(out)
+            }
+        }
+    }
+}
+
+impl fmt::Display for KeyEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            KeyEncoding::Hex => "hex",
+            KeyEncoding::Base64 => "base64",
+        })
+    }
+}
+
+/// A line [`DB::import_jsonl`] could not use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedLine {
+    /// The line number, starting at 1.
+    pub line: u64,
+    /// Why the line was rejected.
+    pub reason: String,
+}
+
+impl fmt::Display for MalformedLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+/// Summary of a completed [`DB::import_jsonl`] call.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonlImportStats {
+    /// Number of entries written to the database.
+    pub written: u64,
+    /// Number of malformed lines skipped.
+    pub malformed: u64,
+    /// The first [`MAX_REPORTED_LINES`] malformed lines.
+    pub malformed_lines: Vec<MalformedLine>,
+    /// Number of write batches committed.
+    pub batches: u64,
+}
+
+impl DB {
+    /// Write every entry of the database to `writer` as JSON lines.
+    ///
+    /// The entries are read from a snapshot in key order, one object per
+    /// line; see the [module documentation](crate::jsonl) for the fields.
+    /// Output is buffered internally.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Destination of the lines
+    /// * `key_encoding` - How keys and values are encoded
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ExportStats)` - How many entries and bytes were written
+    /// * `Err(String)` - If reading the database or writing failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::jsonl::KeyEncoding;
+    /// # use bleveldb::options::Options;
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let mut out = std::fs::File::create("world.jsonl").unwrap();
+    /// db.export_jsonl(&mut out, KeyEncoding::Base64).unwrap();
+    /// ```
+    pub fn export_jsonl(
+        &self,
+        writer: &mut impl Write,
+        key_encoding: KeyEncoding,
+    ) -> Result<ExportStats, String> {
+        let (_snapshot, read_options) = self.consistent_read_options(&ReadOptions::new());
+        read_options.fill_cache(false);
+        let io_error = |e: io::Error| format!("failed to write JSON lines: {}", e);
+
+        let mut out = io::BufWriter::new(writer);
+        let mut stats = ExportStats::default();
+        let mut line = String::new();
+        let mut iter = DBIterator::new(self, &read_options);
+        iter.seek_to_first();
+        while let (Some(key), Some(value)) = (iter.key_ref(), iter.value_ref()) {
+            line.clear();
+            line.push_str("{\"key\":\"");
+            line.push_str(&key_encoding.encode(key));
+            line.push_str("\",\"value\":\"");
+            line.push_str(&key_encoding.encode(value));
+            line.push_str("\",\"description\":");
+            write_json_string(&describe_key(key).to_string(), &mut line);
+            line.push_str("}\n");
+            out.write_all(line.as_bytes()).map_err(io_error)?;
+
+            stats.records += 1;
+            stats.key_bytes += key.len() as u64;
+            stats.value_bytes += value.len() as u64;
+            stats.bytes_written += line.len() as u64;
+            iter.next_native();
+        }
+        iter.status()?;
+        out.flush().map_err(io_error)?;
+        Ok(stats)
+    }
+
+    /// Load entries written by [`DB::export_jsonl`] into the database.
+    ///
+    /// Each line needs string fields `key` and `value` in `key_encoding`;
+    /// other fields are ignored unless nested more than 512 levels deep, and
+    /// so are blank lines. Existing keys are
+    /// overwritten. Entries are applied in batches of at most
+    /// [`DEFAULT_BATCH_BYTES`], so an import that fails part way leaves the
+    /// earlier batches applied.
+    ///
+    /// A line that is not such an object stops the import with an error
+    /// naming its line number, unless `skip_malformed` is set: then it is
+    /// counted and reported in the returned stats instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the lines
+    /// * `key_encoding` - How keys and values are encoded
+    /// * `skip_malformed` - Whether to skip malformed lines rather than fail
+    /// * `options` - Write options used for every batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonlImportStats)` - How many entries were written and lines skipped
+    /// * `Err(String)` - If reading failed, writing failed, or a line is malformed and `skip_malformed` is not set
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::jsonl::KeyEncoding;
+    /// # use bleveldb::options::{Options, WriteOptions};
+    /// # let db = DB::open("world/db", &Options::default()).unwrap();
+    /// let input = std::io::BufReader::new(std::fs::File::open("world.jsonl").unwrap());
+    /// let stats = db
+    ///     .import_jsonl(input, KeyEncoding::Base64, true, &WriteOptions::new())
+    ///     .unwrap();
+    /// for line in &stats.malformed_lines {
+    ///     eprintln!("Skipped {}", line);
+    /// }
+    /// ```
+    pub fn import_jsonl(
+        &self,
+        reader: impl BufRead,
+        key_encoding: KeyEncoding,
+        skip_malformed: bool,
+        options: &WriteOptions,
+    ) -> Result<JsonlImportStats, String> {
+        let mut stats = JsonlImportStats::default();
+        let mut batch = WriteBatch::new();
+        let mut pending_bytes = 0;
+        let mut pending = 0;
+        for (index, line) in reader.lines().enumerate() {
+            let number = index as u64 + 1;
+            let line =
+                line.map_err(|e| format!("failed to read JSON lines at line {}: {}", number, e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (key, value) = match parse_line(&line, key_encoding) {
+                Ok(entry) => entry,
+                Err(reason) => {
+                    let malformed = MalformedLine {
+                        line: number,
+                        reason,
+                    };
+                    if !skip_malformed {
+                        return Err(malformed.to_string());
+                    }
+                    stats.malformed += 1;
+                    if stats.malformed_lines.len() < MAX_REPORTED_LINES {
+                        stats.malformed_lines.push(malformed);
+                    }
+                    continue;
+                }
+            };
+
+            batch.put(&key, &value);
+            pending_bytes += key.len() + value.len();
+            pending += 1;
+            if pending_bytes >= DEFAULT_BATCH_BYTES {
+                self.write(&batch, options)?;
+                batch.clear();
+                stats.written += pending;
+                stats.batches += 1;
+                pending_bytes = 0;
+                pending = 0;
+            }
+        }
+        if pending > 0 {
+            self.write(&batch, options)?;
+            stats.written += pending;
+            stats.batches += 1;
+        }
+        Ok(stats)
+    }
+}
+
+/// Append `text` to `out` as a quoted JSON string.
+fn write_json_string(text: &str, out: &mut String) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Read the key and value of one line.
+fn parse_line(line: &str, key_encoding: KeyEncoding) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let mut parser = Parser {
+        input: line.as_bytes(),
+        pos: 0,
+    };
+    let (mut key, mut value) = (None, None);
+    parser.expect(b'{')?;
+    if !parser.eat(b'}') {
+        loop {
+            let name = parser.string()?;
+            parser.expect(b':')?;
+            match name.as_str() {
+                "key" => key = Some(parser.string()?),
+                "value" => value = Some(parser.string()?),
+                _ => parser.skip_value(0)?,
+            }
+            if parser.eat(b'}') {
+                break;
+            }
+            parser.expect(b',')?;
+        }
+    }
+    parser.skip_whitespace();
+    if parser.pos < parser.input.len() {
+        return Err("unexpected text after the object".to_string());
+    }
+
+    let decode = |field: &str, text: Option<String>| {
+        let text = text.ok_or_else(|| format!("missing \"{}\" field", field))?;
+        key_encoding
+            .decode(&text)
+            .map_err(|e| format!("invalid {} in \"{}\": {}", key_encoding, field, e))
+    };
+    Ok((decode("key", key)?, decode("value", value)?))
+}
+
+/// A minimal JSON reader, enough to pick string fields out of an object.
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .input
+            .get(self.pos)
+            .is_some_and(|b| b" \t\r\n".contains(b))
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let found = self.peek() == Some(byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{}'", byte as char)))
+        }
+    }
+
+    fn unexpected(&mut self, expected: &str) -> String {
+        match self.peek() {
+            Some(_) => format!("expected {} at column {}", expected, self.pos + 1),
+            None => format!("expected {} but the line ended", expected),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+        loop {
+            let start = self.pos;
+            while self
+                .input
+                .get(self.pos)
+                .is_some_and(|&b| b != b'"' && b != b'\\')
+            {
+                self.pos += 1;
+            }
+            // The input came from a `str` and the run ends at an ASCII byte
+            out.push_str(std::str::from_utf8(&self.input[start..self.pos]).unwrap());
+            match self.input.get(self.pos) {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(_) => {
+                    self.pos += 1;
+                    out.push(self.escape()?);
+                }
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, String> {
+        let byte = self.input.get(self.pos).copied();
+        self.pos += 1;
+        Ok(match byte {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                let high = self.hex4()?;
+                let code = if (0xD800..0xDC00).contains(&high) {
+                    if self.input.get(self.pos..self.pos + 2) != Some(b"\\u") {
+                        return Err("unpaired surrogate in string".to_string());
+                    }
+                    self.pos += 2;
+                    let low = self.hex4()?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err("unpaired surrogate in string".to_string());
+                    }
+                    0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                } else {
+                    high
+                };
+                char::from_u32(code).ok_or("unpaired surrogate in string")?
+            }
+            _ => return Err(format!("invalid escape at column {}", self.pos)),
+        })
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let digits = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| format!("invalid \\u escape at column {}", self.pos + 1))?;
+        self.pos += 4;
+        Ok(digits)
+    }
+
+    /// Skip a value of any type, nested `depth` containers deep.
+    fn skip_value(&mut self, depth: usize) -> Result<(), String> {
+        match self.peek() {
+            Some(b'"') => self.string().map(drop),
+            Some(open @ (b'{' | b'[')) => {
+                if depth >= MAX_DEPTH {
+                    return Err(format!(
+                        "nested deeper than {} levels at column {}",
+                        MAX_DEPTH,
+                        self.pos + 1
+                    ));
+                }
+                self.pos += 1;
+                let close = if open == b'{' { b'}' } else { b']' };
+                if self.eat(close) {
+                    return Ok(());
+                }
+                loop {
+                    if open == b'{' {
+                        self.string()?;
+                        self.expect(b':')?;
+                    }
+                    self.skip_value(depth + 1)?;
+                    if self.eat(close) {
+                        return Ok(());
+                    }
+                    self.expect(b',')?;
+                }
+            }
+            Some(_) => {
+                let start = self.pos;
+                while self
+                    .input
+                    .get(self.pos)
+                    .is_some_and(|b| b.is_ascii_alphanumeric() || b"+-.".contains(b))
+                {
+                    self.pos += 1;
+                }
+                let literal = &self.input[start..self.pos];
+                let valid = matches!(literal, b"true" | b"false" | b"null")
+                    || std::str::from_utf8(literal)
+                        .is_ok_and(|number| number.parse::<f64>().is_ok());
+                if valid {
+                    Ok(())
+                } else {
+                    self.pos = start;
+                    Err(self.unexpected("a value"))
+                }
+            }
+            None => Err(self.unexpected("a value")),
+        }
+    }
+}
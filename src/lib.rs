@@ -17,9 +17,27 @@
 //! assert_eq!(value.unwrap(), b"value");
 //! ```
 
+#[cfg(feature = "async")]
+pub mod async_db;
+pub mod backup;
+pub mod batch_writer;
+pub mod bedrock;
+#[cfg(feature = "serde")]
+pub mod codec;
 pub mod db;
+pub mod export;
+pub mod ingest;
+pub mod integrity;
 pub mod iterator;
+pub mod jsonl;
+pub mod merge;
+mod metrics;
 pub mod options;
+mod parallel;
+pub mod pinned;
+pub mod small_value;
+pub mod snapshot;
+mod trace;
 pub mod write_batch;
 
 #[cfg(test)]
@@ -28,18 +46,61 @@ mod tests;
 #[cfg(feature = "error")]
 mod error;
 
-pub use db::DB;
+#[cfg(feature = "async")]
+pub use async_db::AsyncDB;
+pub use backup::BackupReport;
+pub use batch_writer::BatchWriter;
+#[cfg(feature = "serde")]
+pub use codec::{Bincode, SerdeCodec, SerdeError};
+pub use db::{DB, DiskUsage, ScanStats};
+pub use export::{ExportStats, ImportMode, ImportStats};
+pub use ingest::IngestStats;
+pub use integrity::{CorruptRange, IntegrityReport};
+pub use jsonl::{JsonlImportStats, KeyEncoding, MalformedLine};
+pub use merge::{MergePolicy, MergeReport, MergeResolver};
+#[cfg(feature = "metrics")]
+pub use metrics::{ErrorKind, LATENCY_BUCKETS_US, LatencyHistogram, Metrics, MetricsSnapshot};
 pub use iterator::DBIterator;
+pub use iterator::PrefixIter;
+pub use iterator::RangeIter;
+pub use iterator::RevIter;
+pub use iterator::SeekStep;
+pub use iterator::SnapshotIter;
 pub use options::Options;
 pub use options::ReadOptions;
 pub use options::WriteOptions;
-pub use write_batch::WriteBatch;
+pub use parallel::CompactRange;
+pub use pinned::PinnedValue;
+pub use small_value::SmallValue;
+pub use snapshot::Snapshot;
+pub use write_batch::{BatchOp, WriteBatch};
 
 #[cfg(feature = "error")]
 pub use error::Error;
 
+/// The raw LevelDB bindings, for use with the `as_raw` and `from_raw`
+/// methods of the handle types.
+pub use bleveldb_sys as sys;
+
 /// Internal utility functions (not public API)
 pub(crate) mod util;
 
 /// Version of the crate at runtime
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Version of the LevelDB library linked into this crate, as `(major, minor)`.
+///
+/// # Examples
+///
+/// ```no_run
+/// let (major, minor) = bleveldb::leveldb_version();
+/// println!("bleveldb {} (leveldb {}.{})", bleveldb::VERSION, major, minor);
+/// ```
+pub fn leveldb_version() -> (i32, i32) {
+    unsafe {
+        (
+            bleveldb_sys::leveldb_major_version(),
+            bleveldb_sys::leveldb_minor_version(),
+        )
+    }
+}
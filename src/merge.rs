@@ -0,0 +1,153 @@
+//! Copying the entries of one database into another, such as to combine a
+//! backup with a live world.
+
+use std::fmt;
+
+use crate::db::{DB, DEFAULT_BATCH_BYTES};
+use crate::options::{ReadOptions, WriteOptions};
+use crate::write_batch::WriteBatch;
+
+/// A resolver for [`MergePolicy::Custom`].
+pub type MergeResolver<'a> = Box<dyn FnMut(&[u8], &[u8], &[u8]) -> Vec<u8> + 'a>;
+
+/// Which value [`DB::merge_from`] keeps for a key both databases hold.
+pub enum MergePolicy<'a> {
+    /// Keep the value of the database merged into.
+    PreferSelf,
+    /// Replace it with the value of the database merged from.
+    PreferOther,
+    /// Call the resolver with the key, the value merged into and the value
+    /// merged from, and store what it returns.
+    Custom(MergeResolver<'a>),
+}
+
+impl fmt::Debug for MergePolicy<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergePolicy::PreferSelf => f.write_str("PreferSelf"),
+            MergePolicy::PreferOther => f.write_str("PreferOther"),
+            MergePolicy::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// What [`DB::merge_from`] did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    /// Number of keys only the other database held, copied over.
+    pub added: u64,
+    /// Number of keys both held whose value was replaced.
+    pub overwritten: u64,
+    /// Number of keys both held whose value was kept, either by the policy
+    /// or because the chosen value was the same.
+    pub skipped: u64,
+    /// Number of write batches committed.
+    pub batches: u64,
+}
+
+impl DB {
+    /// Copy every entry of `other` into this database, resolving keys both
+    /// hold with `policy`.
+    ///
+    /// Both databases are read from snapshots taken at the start, walking
+    /// their keys side by side, so writes made while the merge runs do not
+    /// change its outcome. Changes are committed in batches of at most
+    /// [`DEFAULT_BATCH_BYTES`], so an error part way leaves the earlier
+    /// batches applied. Keys that only this database holds are left alone.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The database to copy from; it is only read
+    /// * `policy` - Which value to keep for keys both databases hold
+    /// * `options` - Write options used for every batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MergeReport)` - How many keys were added, overwritten and skipped
+    /// * `Err(String)` - If this database is read-only, or reading or writing failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::merge::MergePolicy;
+    /// # use bleveldb::options::{Options, WriteOptions};
+    /// let live = DB::open("world/db", &Options::default()).unwrap();
+    /// let backup = DB::open_read_only("backup/db", &Options::default()).unwrap();
+    ///
+    /// // Restore what was lost since the backup, keeping everything newer
+    /// let report = live
+    ///     .merge_from(&backup, MergePolicy::PreferSelf, &WriteOptions::new())
+    ///     .unwrap();
+    /// println!("Restored {} keys", report.added);
+    /// ```
+    pub fn merge_from(
+        &self,
+        other: &DB,
+        mut policy: MergePolicy<'_>,
+        options: &WriteOptions,
+    ) -> Result<MergeReport, String> {
+        self.check_writable()?;
+        let (_ours_snapshot, ours_options) = self.consistent_read_options(&ReadOptions::new());
+        let (_theirs_snapshot, theirs_options) = other.consistent_read_options(&ReadOptions::new());
+        ours_options.fill_cache(false);
+        theirs_options.fill_cache(false);
+
+        let mut report = MergeReport::default();
+        let mut batch = WriteBatch::new();
+        let mut pending_bytes = 0;
+        let mut ours = self.iter(&ours_options);
+        let mut theirs = other.iter(&theirs_options);
+        ours.seek_to_first();
+        theirs.seek_to_first();
+
+        while let (Some(key), Some(value)) = (theirs.key_ref(), theirs.value_ref()) {
+            while ours.key_ref().is_some_and(|ours_key| ours_key < key) {
+                ours.next_native();
+            }
+            let merged = match ours.key_ref().zip(ours.value_ref()) {
+                Some((ours_key, ours_value)) if ours_key == key => {
+                    let merged = match &mut policy {
+                        MergePolicy::PreferSelf => None,
+                        MergePolicy::PreferOther => Some(value.to_vec()),
+                        MergePolicy::Custom(resolve) => Some(resolve(key, ours_value, value)),
+                    };
+                    match merged {
+                        Some(merged) if merged != ours_value => {
+                            report.overwritten += 1;
+                            Some(merged)
+                        }
+                        _ => {
+                            report.skipped += 1;
+                            None
+                        }
+                    }
+                }
+                _ => {
+                    report.added += 1;
+                    Some(value.to_vec())
+                }
+            };
+
+            if let Some(merged) = merged {
+                pending_bytes += key.len() + merged.len();
+                batch.put(key, merged);
+                if pending_bytes >= DEFAULT_BATCH_BYTES {
+                    self.write(&batch, options)?;
+                    batch.clear();
+                    report.batches += 1;
+                    pending_bytes = 0;
+                }
+            }
+            theirs.next_native();
+        }
+        ours.status()?;
+        theirs.status()?;
+
+        if !batch.is_empty() {
+            self.write(&batch, options)?;
+            report.batches += 1;
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,398 @@
+//! Operation counters and latency histograms, behind the `metrics` feature.
+//!
+//! Every [`DB`](crate::DB) handle owns a [`Metrics`] shared by its clones,
+//! updated with relaxed atomic adds on each operation and read with
+//! [`DB::metrics`](crate::DB::metrics). The counters only ever grow, so they
+//! map directly onto Prometheus counters; the histograms use fixed bucket
+//! bounds for the same reason.
+//!
+//! Without the feature, [`Metrics`] is a unit struct whose methods do
+//! nothing and compile away.
+
+#[cfg(feature = "metrics")]
+pub use enabled::{ErrorKind, LATENCY_BUCKETS_US, LatencyHistogram, Metrics, MetricsSnapshot};
+
+#[cfg(feature = "metrics")]
+mod enabled {
+    use std::fmt;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Instant;
+
+    use crate::db::DB;
+
+    /// Upper bounds, in microseconds, of the buckets of a
+    /// [`LatencyHistogram`]. A last bucket holds everything slower.
+    pub const LATENCY_BUCKETS_US: [u64; 9] = [1, 4, 16, 64, 256, 1024, 4096, 16384, 65536];
+
+    const BUCKETS: usize = LATENCY_BUCKETS_US.len() + 1;
+
+    /// What kind of failure an operation reported, derived from the message
+    /// LevelDB or this crate returned.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ErrorKind {
+        /// A write to a database opened read-only.
+        ReadOnly,
+        /// LevelDB reported a corrupted file or record.
+        Corruption,
+        /// LevelDB reported a failed file system operation.
+        Io,
+        /// LevelDB rejected an argument or option.
+        InvalidArgument,
+        /// Any other error.
+        Other,
+    }
+
+    impl ErrorKind {
+        /// Every kind, in the order of [`MetricsSnapshot::errors`].
+        pub const ALL: [ErrorKind; 5] = [
+            ErrorKind::ReadOnly,
+            ErrorKind::Corruption,
+            ErrorKind::Io,
+            ErrorKind::InvalidArgument,
+            ErrorKind::Other,
+        ];
+
+        /// Classify an error message.
+        ///
+        /// # Arguments
+        ///
+        /// * `message` - An error returned by a database operation
+        ///
+        /// # Returns
+        ///
+        /// The kind the message describes, or [`ErrorKind::Other`]
+        pub fn classify(message: &str) -> ErrorKind {
+            if message.ends_with("is opened read-only") {
+                ErrorKind::ReadOnly
+            } else if message.starts_with("Corruption:") {
+                ErrorKind::Corruption
+            } else if message.starts_with("IO error:") {
+                ErrorKind::Io
+            } else if message.starts_with("Invalid argument:") {
+                ErrorKind::InvalidArgument
+            } else {
+                ErrorKind::Other
+            }
+        }
+
+        /// Return a short lowercase name, suitable as a metric label.
+        pub fn as_str(self) -> &'static str {
+            match self {
+                ErrorKind::ReadOnly => "read_only",
+                ErrorKind::Corruption => "corruption",
+                ErrorKind::Io => "io",
+                ErrorKind::InvalidArgument => "invalid_argument",
+                ErrorKind::Other => "other",
+            }
+        }
+    }
+
+    impl fmt::Display for ErrorKind {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.as_str())
+        }
+    }
+
+    /// Latencies of one class of operations.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct LatencyHistogram {
+        /// Number of operations per bucket; `counts[i]` took at most
+        /// `LATENCY_BUCKETS_US[i]` microseconds and more than the bound
+        /// before it, the last entry counts the rest.
+        pub counts: [u64; BUCKETS],
+        /// Total time of all operations, in microseconds.
+        pub sum_us: u64,
+    }
+
+    impl LatencyHistogram {
+        /// Return the number of operations recorded.
+        pub fn count(&self) -> u64 {
+            self.counts.iter().sum()
+        }
+
+        /// Return the number of operations that took at most each bound of
+        /// [`LATENCY_BUCKETS_US`], as Prometheus `le` buckets expect.
+        pub fn cumulative(&self) -> [u64; BUCKETS] {
+            let mut total = 0;
+            self.counts.map(|count| {
+                total += count;
+                total
+            })
+        }
+    }
+
+    /// The counters of a [`Metrics`] at one point in time.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct MetricsSnapshot {
+        /// Point lookups, counting each key of a `multi_get`.
+        pub gets: u64,
+        /// Point lookups that found their key.
+        pub get_hits: u64,
+        /// Single-key puts.
+        pub puts: u64,
+        /// Single-key deletes.
+        pub deletes: u64,
+        /// Write batches committed.
+        pub batch_commits: u64,
+        /// Bytes of values returned by point lookups.
+        pub bytes_read: u64,
+        /// Bytes of keys and values passed to puts and deletes, plus the
+        /// encoded size of committed batches.
+        pub bytes_written: u64,
+        /// Iterators created, including those used internally by helpers.
+        pub iterators: u64,
+        /// Failed operations, indexed like [`ErrorKind::ALL`].
+        pub errors: [u64; ErrorKind::ALL.len()],
+        /// Latency of point lookups.
+        pub read_latency: LatencyHistogram,
+        /// Latency of puts, deletes and batch commits.
+        pub write_latency: LatencyHistogram,
+    }
+
+    impl MetricsSnapshot {
+        /// Return the number of failed operations of one kind.
+        pub fn errors(&self, kind: ErrorKind) -> u64 {
+            self.errors[kind as usize]
+        }
+    }
+
+    #[derive(Default)]
+    struct Histogram {
+        counts: [AtomicU64; BUCKETS],
+        sum_us: AtomicU64,
+    }
+
+    impl Histogram {
+        fn record(&self, start: Instant) {
+            let us = start.elapsed().as_micros() as u64;
+            let bucket = LATENCY_BUCKETS_US
+                .iter()
+                .position(|&bound| us <= bound)
+                .unwrap_or(BUCKETS - 1);
+            self.counts[bucket].fetch_add(1, Ordering::Relaxed);
+            self.sum_us.fetch_add(us, Ordering::Relaxed);
+        }
+
+        fn snapshot(&self) -> LatencyHistogram {
+            LatencyHistogram {
+                counts: std::array::from_fn(|i| self.counts[i].load(Ordering::Relaxed)),
+                sum_us: self.sum_us.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Live operation counters of a database, shared by all clones of its
+    /// handle.
+    #[derive(Default)]
+    pub struct Metrics {
+        gets: AtomicU64,
+        get_hits: AtomicU64,
+        puts: AtomicU64,
+        deletes: AtomicU64,
+        batch_commits: AtomicU64,
+        bytes_read: AtomicU64,
+        bytes_written: AtomicU64,
+        iterators: AtomicU64,
+        errors: [AtomicU64; ErrorKind::ALL.len()],
+        read_latency: Histogram,
+        write_latency: Histogram,
+    }
+
+    impl fmt::Debug for Metrics {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(&self.snapshot(), f)
+        }
+    }
+
+    fn add(counter: &AtomicU64, n: u64) {
+        counter.fetch_add(n, Ordering::Relaxed);
+    }
+
+    impl DB {
+        /// Return the operation counters of this database.
+        ///
+        /// The counters are shared by every clone of the handle and start at
+        /// zero when the database is opened.
+        ///
+        /// # Returns
+        ///
+        /// The live counters; call [`Metrics::snapshot`] to read them
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// # use bleveldb::DB;
+        /// # use bleveldb::options::{Options, ReadOptions};
+        /// # let db = DB::open("world/db", &Options::default()).unwrap();
+        /// db.get(b"~local_player", &ReadOptions::new()).unwrap();
+        ///
+        /// let metrics = db.metrics().snapshot();
+        /// println!("{} gets, {} hits", metrics.gets, metrics.get_hits);
+        /// ```
+        pub fn metrics(&self) -> &Metrics {
+            &self.inner.metrics
+        }
+    }
+
+    impl Metrics {
+        /// Read every counter.
+        ///
+        /// Counters are read one after the other while other threads may
+        /// keep updating them, so the snapshot is only exact once no
+        /// operations are in flight.
+        ///
+        /// # Returns
+        ///
+        /// The current value of every counter
+        pub fn snapshot(&self) -> MetricsSnapshot {
+            let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+            MetricsSnapshot {
+                gets: load(&self.gets),
+                get_hits: load(&self.get_hits),
+                puts: load(&self.puts),
+                deletes: load(&self.deletes),
+                batch_commits: load(&self.batch_commits),
+                bytes_read: load(&self.bytes_read),
+                bytes_written: load(&self.bytes_written),
+                iterators: load(&self.iterators),
+                errors: std::array::from_fn(|i| load(&self.errors[i])),
+                read_latency: self.read_latency.snapshot(),
+                write_latency: self.write_latency.snapshot(),
+            }
+        }
+
+        pub(crate) fn new() -> Self {
+            Self::default()
+        }
+
+        fn error(&self, message: &str) {
+            add(&self.errors[ErrorKind::classify(message) as usize], 1);
+        }
+
+        /// Run a point lookup and record it, with `len` giving the size of
+        /// the value found.
+        pub(crate) fn get<T>(
+            &self,
+            read: impl FnOnce() -> Result<Option<T>, String>,
+            len: impl FnOnce(&T) -> usize,
+        ) -> Result<Option<T>, String> {
+            let start = Instant::now();
+            let result = read();
+            add(&self.gets, 1);
+            match &result {
+                Ok(Some(value)) => {
+                    add(&self.get_hits, 1);
+                    add(&self.bytes_read, len(value) as u64);
+                }
+                Ok(None) => {}
+                Err(e) => self.error(e),
+            }
+            self.read_latency.record(start);
+            result
+        }
+
+        /// Run a single-key put of `bytes` bytes and record it.
+        pub(crate) fn put(
+            &self,
+            bytes: usize,
+            write: impl FnOnce() -> Result<(), String>,
+        ) -> Result<(), String> {
+            add(&self.puts, 1);
+            self.write(bytes, write)
+        }
+
+        /// Run a single-key delete of `bytes` bytes and record it.
+        pub(crate) fn delete(
+            &self,
+            bytes: usize,
+            write: impl FnOnce() -> Result<(), String>,
+        ) -> Result<(), String> {
+            add(&self.deletes, 1);
+            self.write(bytes, write)
+        }
+
+        /// Run a batch commit of `bytes` encoded bytes and record it.
+        pub(crate) fn batch(
+            &self,
+            bytes: usize,
+            write: impl FnOnce() -> Result<(), String>,
+        ) -> Result<(), String> {
+            let result = self.write(bytes, write);
+            if result.is_ok() {
+                add(&self.batch_commits, 1);
+            }
+            result
+        }
+
+        fn write(
+            &self,
+            bytes: usize,
+            write: impl FnOnce() -> Result<(), String>,
+        ) -> Result<(), String> {
+            let start = Instant::now();
+            let result = write();
+            match &result {
+                Ok(()) => add(&self.bytes_written, bytes as u64),
+                Err(e) => self.error(e),
+            }
+            self.write_latency.record(start);
+            result
+        }
+
+        pub(crate) fn iterator(&self) {
+            add(&self.iterators, 1);
+        }
+    }
+}
+
+/// Operation counters of a database; does nothing without the `metrics`
+/// feature.
+#[cfg(not(feature = "metrics"))]
+pub(crate) struct Metrics;
+
+#[cfg(not(feature = "metrics"))]
+impl Metrics {
+    #[inline(always)]
+    pub(crate) fn new() -> Self {
+        Metrics
+    }
+
+    #[inline(always)]
+    pub(crate) fn get<T>(
+        &self,
+        read: impl FnOnce() -> Result<Option<T>, String>,
+        _len: impl FnOnce(&T) -> usize,
+    ) -> Result<Option<T>, String> {
+        read()
+    }
+
+    #[inline(always)]
+    pub(crate) fn put(
+        &self,
+        _bytes: usize,
+        write: impl FnOnce() -> Result<(), String>,
+    ) -> Result<(), String> {
+        write()
+    }
+
+    #[inline(always)]
+    pub(crate) fn delete(
+        &self,
+        _bytes: usize,
+        write: impl FnOnce() -> Result<(), String>,
+    ) -> Result<(), String> {
+        write()
+    }
+
+    #[inline(always)]
+    pub(crate) fn batch(
+        &self,
+        _bytes: usize,
+        write: impl FnOnce() -> Result<(), String>,
+    ) -> Result<(), String> {
+        write()
+    }
+
+    #[inline(always)]
+    pub(crate) fn iterator(&self) {}
+}
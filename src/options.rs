@@ -1,7 +1,141 @@
 use bleveldb_sys::{self as leveldb_sys};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
 
 pub type Compression = leveldb_sys::Compression;
 
+/// A record of every setting applied to an [`Options`] instance.
+///
+/// LevelDB's C API has no getters for options, so `Options` keeps this shadow
+/// copy in sync with the native handle. It starts out with LevelDB's own
+/// defaults and is what [`Options::validate`] inspects.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptionsSettings {
+    /// Whether a missing database is created on open.
+    pub create_if_missing: bool,
+    /// Whether opening an existing database is an error.
+    pub error_if_exists: bool,
+    /// Whether aggressive corruption checks are enabled.
+    pub paranoid_checks: bool,
+    /// Bytes buffered in the memtable before it is written to a table file.
+    pub write_buffer_size: usize,
+    /// Maximum number of files LevelDB keeps open.
+    pub max_open_files: i32,
+    /// Approximate size of uncompressed user data per block.
+    pub block_size: usize,
+    /// Number of keys between restart points for delta encoding.
+    pub block_restart_interval: i32,
+    /// Bytes written to a table file before switching to a new one.
+    pub max_file_size: usize,
+    /// Compression applied to table blocks.
+    pub compression: Compression,
+    /// Bits per key of the bloom filter, if one is configured.
+    pub bloom_filter_bits: Option<i32>,
+    /// Whether `DB::open` runs [`Options::validate_for_path`] first.
+    pub validate_on_open: bool,
+}
+
+impl Default for OptionsSettings {
+    fn default() -> Self {
+        Self {
+            create_if_missing: false,
+            error_if_exists: false,
+            paranoid_checks: false,
+            write_buffer_size: 4 * 1024 * 1024,
+            max_open_files: 1000,
+            block_size: 4 * 1024,
+            block_restart_interval: 16,
+            max_file_size: 2 * 1024 * 1024,
+            compression: Compression::Snappy,
+            bloom_filter_bits: None,
+            validate_on_open: true,
+        }
+    }
+}
+
+/// The rule an [`OptionsIssue`] was raised for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptionsIssueKind {
+    /// `error_if_exists` is set but the path already holds a database.
+    ErrorIfExistsOnExistingDatabase,
+    /// `write_buffer_size` is zero.
+    ZeroWriteBufferSize,
+    /// `block_size` is zero.
+    ZeroBlockSize,
+    /// `block_size` is larger than `max_file_size`.
+    BlockSizeExceedsMaxFileSize,
+    /// A bloom filter was configured with zero bits per key.
+    ZeroBloomFilterBits,
+}
+
+/// A single problem found by [`Options::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionsIssue {
+    /// Machine-readable rule that was violated.
+    pub kind: OptionsIssueKind,
+    /// Human-readable explanation, including how to fix it.
+    pub message: String,
+}
+
+impl OptionsIssue {
+    fn new(kind: OptionsIssueKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for OptionsIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Error returned by an [`Options`] setter the linked LevelDB cannot apply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionsError {
+    /// The option exists in some LevelDB builds but not in the one
+    /// `bleveldb-sys` links.
+    Unsupported {
+        /// Name of the setter.
+        option: &'static str,
+        /// Why the option is unavailable.
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for OptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionsError::Unsupported { option, reason } => {
+                write!(f, "{} is not supported: {}", option, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OptionsError {}
+
+/// Owned bloom filter policy shared between `Options` and every `DB` opened with it.
+///
+/// LevelDB only stores the policy pointer, so it must outlive all databases using it.
+pub(crate) struct FilterPolicy {
+    raw: *mut leveldb_sys::leveldb_filterpolicy_t,
+}
+
+// SAFETY: the policy is immutable once created and LevelDB uses it from any thread.
+unsafe impl Send for FilterPolicy {}
+unsafe impl Sync for FilterPolicy {}
+
+impl Drop for FilterPolicy {
+    fn drop(&mut self) {
+        unsafe { leveldb_sys::leveldb_filterpolicy_destroy(self.raw) }
+    }
+}
+
 /// Configuration options for opening or creating a LevelDB database.
 ///
 /// This struct allows you to customize various aspects of database behavior
@@ -26,6 +160,8 @@ pub type Compression = leveldb_sys::Compression;
 /// ```
 pub struct Options {
     raw: *mut leveldb_sys::leveldb_options_t,
+    settings: Cell<OptionsSettings>,
+    filter_policy: RefCell<Option<Arc<FilterPolicy>>>,
 }
 
 impl Options {
@@ -35,7 +171,7 @@ impl Options {
     /// - `create_if_missing`: false
     /// - `error_if_exists`: false
     /// - `paranoid_checks`: false
-    /// - `compression`: No compression
+    /// - `compression`: Snappy (LevelDB's default)
     ///
     /// # Returns
     ///
@@ -44,18 +180,90 @@ impl Options {
     pub fn new() -> Self {
         Self {
             raw: unsafe { leveldb_sys::leveldb_options_create() },
+            settings: Cell::new(OptionsSettings::default()),
+            filter_policy: RefCell::new(None),
         }
     }
 
+    /// Create `Options` matching the settings Minecraft Bedrock opens its
+    /// world databases with.
+    ///
+    /// Tables are compressed with `Compression::ZlibRaw`, the format the game
+    /// reads and writes, blocks are 160KB, and a 10-bit bloom filter is
+    /// attached. `create_if_missing` stays off, so opening a path without a
+    /// world fails instead of creating an empty database.
+    ///
+    /// # Returns
+    ///
+    /// A new `Options` instance with the Bedrock settings.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::DB;
+    /// use bleveldb::options::Options;
+    ///
+    /// let db = DB::open("world/db", &Options::bedrock()).unwrap();
+    /// ```
+    pub fn bedrock() -> Self {
+        let options = Self::new();
+        options.compression(Compression::ZlibRaw);
+        options.write_buffer_size(4 * 1024 * 1024);
+        options.block_size(160 * 1024);
+        options.bloom_filter(10);
+        options
+    }
+
+    /// Create `Options` suited to loading a large amount of data at once,
+    /// such as when converting a Java edition world.
+    ///
+    /// Starts from [`Options::bedrock`], so tables use the format the game
+    /// reads, and enables `create_if_missing`. A 128MB write buffer and 64MB
+    /// table files keep the number of tables, and so the background
+    /// compaction work while loading, low. The write buffer is held in
+    /// memory, up to twice over while it is flushed, and replaying a full
+    /// log after a crash takes correspondingly long, so reopen the
+    /// database with regular options once the load is done.
+    ///
+    /// # Returns
+    ///
+    /// A new `Options` instance with the bulk load settings.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::DB;
+    /// use bleveldb::options::{Options, WriteOptions};
+    ///
+    /// let db = DB::open("converted/db", &Options::bulk_load()).unwrap();
+    /// # let records: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
+    /// db.ingest_sorted(records, &WriteOptions::new()).unwrap();
+    /// ```
+    pub fn bulk_load() -> Self {
+        let options = Self::bedrock();
+        options.create_if_missing(true);
+        options.write_buffer_size(128 * 1024 * 1024);
+        options.max_file_size(64 * 1024 * 1024);
+        options
+    }
+
+    /// Update the shadow settings record.
+    fn record(&self, update: impl FnOnce(&mut OptionsSettings)) {
+        let mut settings = self.settings.get();
+        update(&mut settings);
+        self.settings.set(settings);
+    }
+
     /// Configure whether to create the database if it doesn't exist.
     ///
     /// # Arguments
     ///
     /// * `value` - If `true`, the database will be created if it is missing.
-    ///             If `false`, attempting to open a non-existent database will fail.
+    ///   If `false`, attempting to open a non-existent database will fail.
     ///
     pub fn create_if_missing(&self, value: bool) {
         unsafe { leveldb_sys::leveldb_options_set_create_if_missing(self.raw, value as u8) };
+        self.record(|s| s.create_if_missing = value);
     }
 
     /// Configure whether to raise an error if the database already exists.
@@ -63,10 +271,11 @@ impl Options {
     /// # Arguments
     ///
     /// * `value` - If `true`, an error is raised if the database already exists.
-    ///             If `false`, existing databases are opened normally.
+    ///   If `false`, existing databases are opened normally.
     ///
     pub fn error_if_exists(&self, value: bool) {
         unsafe { leveldb_sys::leveldb_options_set_error_if_exists(self.raw, value as u8) };
+        self.record(|s| s.error_if_exists = value);
     }
 
     /// Enable or disable paranoid checks for data integrity.
@@ -78,10 +287,11 @@ impl Options {
     /// # Arguments
     ///
     /// * `value` - If `true`, enable paranoid data checks.
-    ///             If `false`, use normal checking level.
+    ///   If `false`, use normal checking level.
     ///
     pub fn paranoid_checks(&self, value: bool) {
         unsafe { leveldb_sys::leveldb_options_set_paranoid_checks(self.raw, value as u8) };
+        self.record(|s| s.paranoid_checks = value);
     }
 
     /// Set the compression algorithm for stored data.
@@ -101,6 +311,235 @@ impl Options {
         unsafe {
             leveldb_sys::leveldb_options_set_compression(self.raw, compression_type);
         }
+        self.record(|s| s.compression = compression_type);
+    }
+
+    /// Set the zstd compression level used with `Compression::Zstd`.
+    ///
+    /// The LevelDB bundled with `bleveldb-sys` is built without zstd and its
+    /// C API has no setter for the level, so this always fails and leaves
+    /// the options unchanged; tables written with `Compression::Zstd` fall
+    /// back to being stored uncompressed.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The zstd level, from -5 to 22
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the level was set
+    /// * `Err(OptionsError::Unsupported)` - If the linked LevelDB cannot set it
+    pub fn zstd_level(&self, level: i32) -> Result<(), OptionsError> {
+        let _ = level;
+        Err(OptionsError::Unsupported {
+            option: "zstd_level",
+            reason: "bleveldb-sys builds LevelDB without zstd and exposes no level setter",
+        })
+    }
+
+    /// Set a trained zstd dictionary used with `Compression::Zstd`.
+    ///
+    /// Small, repetitive values such as NBT records compress much better with
+    /// a dictionary, but LevelDB has no dictionary support, so this always
+    /// fails and leaves the options unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `dictionary` - The dictionary, as produced by `zstd --train`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the dictionary was set
+    /// * `Err(OptionsError::Unsupported)` - If the linked LevelDB cannot use it
+    pub fn zstd_dictionary(&self, dictionary: &[u8]) -> Result<(), OptionsError> {
+        let _ = dictionary;
+        Err(OptionsError::Unsupported {
+            option: "zstd_dictionary",
+            reason: "LevelDB has no support for compression dictionaries",
+        })
+    }
+
+    /// Set the amount of data to build up in memory before writing a table file.
+    ///
+    /// Larger values increase performance, especially during bulk loads, at the
+    /// cost of memory and a longer recovery when the database is reopened.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Write buffer size in bytes (LevelDB default: 4MB)
+    ///
+    pub fn write_buffer_size(&self, size: usize) {
+        unsafe { leveldb_sys::leveldb_options_set_write_buffer_size(self.raw, size) };
+        self.record(|s| s.write_buffer_size = size);
+    }
+
+    /// Set the number of open files that can be used by the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `num` - Maximum number of open files (LevelDB default: 1000)
+    ///
+    pub fn max_open_files(&self, num: i32) {
+        unsafe { leveldb_sys::leveldb_options_set_max_open_files(self.raw, num) };
+        self.record(|s| s.max_open_files = num);
+    }
+
+    /// Set the approximate size of user data packed per block.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Uncompressed block size in bytes (LevelDB default: 4KB)
+    ///
+    pub fn block_size(&self, size: usize) {
+        unsafe { leveldb_sys::leveldb_options_set_block_size(self.raw, size) };
+        self.record(|s| s.block_size = size);
+    }
+
+    /// Set the number of keys between restart points for delta encoding of keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Restart interval (LevelDB default: 16)
+    ///
+    pub fn block_restart_interval(&self, interval: i32) {
+        unsafe { leveldb_sys::leveldb_options_set_block_restart_interval(self.raw, interval) };
+        self.record(|s| s.block_restart_interval = interval);
+    }
+
+    /// Set the number of bytes written to a table file before switching to a new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Maximum table file size in bytes (LevelDB default: 2MB)
+    ///
+    pub fn max_file_size(&self, size: usize) {
+        unsafe { leveldb_sys::leveldb_options_set_max_file_size(self.raw, size) };
+        self.record(|s| s.max_file_size = size);
+    }
+
+    /// Attach a bloom filter policy to reduce disk reads for missing keys.
+    ///
+    /// The policy is kept alive by every `DB` opened with these options, so the
+    /// `Options` instance may be dropped right after `DB::open`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits_per_key` - Bits of filter per key; 10 yields a ~1% false positive rate
+    ///
+    pub fn bloom_filter(&self, bits_per_key: i32) {
+        let policy = Arc::new(FilterPolicy {
+            raw: unsafe { leveldb_sys::leveldb_filterpolicy_create_bloom(bits_per_key) },
+        });
+        unsafe { leveldb_sys::leveldb_options_set_filter_policy(self.raw, policy.raw) };
+        *self.filter_policy.borrow_mut() = Some(policy);
+        self.record(|s| s.bloom_filter_bits = Some(bits_per_key));
+    }
+
+    /// Configure whether `DB::open` validates these options before opening.
+    ///
+    /// Validation is enabled by default. Disable it to hand the settings to
+    /// LevelDB untouched, which silently clamps or ignores nonsensical values.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - If `true`, `DB::open` fails when [`Options::validate_for_path`] reports issues.
+    ///
+    pub fn validate_on_open(&self, value: bool) {
+        self.record(|s| s.validate_on_open = value);
+    }
+
+    /// Return a copy of every setting applied so far.
+    pub fn settings(&self) -> OptionsSettings {
+        self.settings.get()
+    }
+
+    /// Check the settings for contradictory or nonsensical values.
+    ///
+    /// Only rules that do not depend on the database location are checked;
+    /// use [`Options::validate_for_path`] to include those as well.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If no issues were found
+    /// * `Err(Vec<OptionsIssue>)` - Every issue found, in a stable order
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::options::{Options, OptionsIssueKind};
+    ///
+    /// let options = Options::new();
+    /// options.write_buffer_size(0);
+    ///
+    /// let issues = options.validate().unwrap_err();
+    /// assert_eq!(issues[0].kind, OptionsIssueKind::ZeroWriteBufferSize);
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<OptionsIssue>> {
+        let settings = self.settings.get();
+        let mut issues = Vec::new();
+
+        if settings.write_buffer_size == 0 {
+            issues.push(OptionsIssue::new(
+                OptionsIssueKind::ZeroWriteBufferSize,
+                "write_buffer_size is 0; use at least a few megabytes (LevelDB default: 4MB)",
+            ));
+        }
+        if settings.block_size == 0 {
+            issues.push(OptionsIssue::new(
+                OptionsIssueKind::ZeroBlockSize,
+                "block_size is 0; use a positive size such as 4096",
+            ));
+        } else if settings.block_size > settings.max_file_size {
+            issues.push(OptionsIssue::new(
+                OptionsIssueKind::BlockSizeExceedsMaxFileSize,
+                format!(
+                    "block_size ({}) is larger than max_file_size ({}); lower block_size or raise max_file_size",
+                    settings.block_size, settings.max_file_size
+                ),
+            ));
+        }
+        if settings.bloom_filter_bits == Some(0) {
+            issues.push(OptionsIssue::new(
+                OptionsIssueKind::ZeroBloomFilterBits,
+                "bloom_filter was given 0 bits per key, which filters nothing; use around 10",
+            ));
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Check the settings against the database location they will be used for.
+    ///
+    /// Runs every rule of [`Options::validate`] plus the rules that need to look
+    /// at `path`, such as `error_if_exists` on an existing database.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The database directory the options will be used to open
+    ///
+    pub fn validate_for_path(&self, path: impl AsRef<Path>) -> Result<(), Vec<OptionsIssue>> {
+        let mut issues = self.validate().err().unwrap_or_default();
+
+        let path = path.as_ref();
+        if self.settings.get().error_if_exists && path.join("CURRENT").exists() {
+            issues.push(OptionsIssue::new(
+                OptionsIssueKind::ErrorIfExistsOnExistingDatabase,
+                format!(
+                    "error_if_exists is set but {} already contains a database",
+                    path.display()
+                ),
+            ));
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
     }
 
     /// Get the raw pointer to the underlying LevelDB options.
@@ -116,6 +555,90 @@ impl Options {
     pub(crate) fn raw(&self) -> *mut leveldb_sys::leveldb_options_t {
         self.raw
     }
+
+    /// Return the raw LevelDB options, for calling [`sys`](crate::sys)
+    /// functions this crate does not wrap.
+    ///
+    /// # Safety
+    ///
+    /// The pointer stays owned by these `Options`: the caller must not
+    /// destroy it or use it after they are dropped. Settings changed through
+    /// it are not reflected by [`Options::settings`] or carried over by
+    /// [`Options::duplicate`].
+    ///
+    /// # Returns
+    ///
+    /// The `leveldb_options_t` pointer
+    pub unsafe fn as_raw(&self) -> *mut leveldb_sys::leveldb_options_t {
+        self.raw
+    }
+
+    /// Wrap raw LevelDB options, taking ownership of them.
+    ///
+    /// The pointer is destroyed when the returned `Options` are dropped.
+    /// Whatever was set on it before, [`Options::settings`] starts out at
+    /// the defaults and only tracks changes made through the setters.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must come from `leveldb_options_create` and must not be
+    /// destroyed or wrapped by anything else. A filter policy, cache or
+    /// environment already set on it must outlive the returned `Options`
+    /// and every database opened with them.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The options to take ownership of
+    ///
+    /// # Returns
+    ///
+    /// `Options` owning the pointer
+    pub unsafe fn from_raw(raw: *mut leveldb_sys::leveldb_options_t) -> Self {
+        Self {
+            raw,
+            settings: Cell::new(OptionsSettings::default()),
+            filter_policy: RefCell::new(None),
+        }
+    }
+
+    /// Create a new `Options` instance carrying the same settings as this one.
+    ///
+    /// Settings are replayed from the shadow record, and the bloom filter policy
+    /// (if any) is shared rather than recreated.
+    pub(crate) fn duplicate(&self) -> Options {
+        let settings = self.settings.get();
+        let copy = Options::new();
+        copy.create_if_missing(settings.create_if_missing);
+        copy.error_if_exists(settings.error_if_exists);
+        copy.paranoid_checks(settings.paranoid_checks);
+        copy.write_buffer_size(settings.write_buffer_size);
+        copy.max_open_files(settings.max_open_files);
+        copy.block_size(settings.block_size);
+        copy.block_restart_interval(settings.block_restart_interval);
+        copy.max_file_size(settings.max_file_size);
+        copy.compression(settings.compression);
+        copy.validate_on_open(settings.validate_on_open);
+        if let Some(policy) = self.filter_policy() {
+            unsafe { leveldb_sys::leveldb_options_set_filter_policy(copy.raw, policy.raw) };
+            *copy.filter_policy.borrow_mut() = Some(policy);
+            copy.record(|s| s.bloom_filter_bits = settings.bloom_filter_bits);
+        }
+        copy
+    }
+
+    /// Stop LevelDB from scheduling compactions in response to read patterns.
+    pub(crate) fn disable_seek_autocompaction(&self, value: bool) {
+        unsafe {
+            leveldb_sys::leveldb_options_set_disable_seek_autocompaction(self.raw, value as u8)
+        };
+    }
+
+    /// Get a shared handle to the configured bloom filter policy, if any.
+    ///
+    /// Databases hold on to this so the policy outlives them.
+    pub(crate) fn filter_policy(&self) -> Option<Arc<FilterPolicy>> {
+        self.filter_policy.borrow().clone()
+    }
 }
 
 impl Default for Options {
@@ -199,8 +722,15 @@ impl AsSnapshot for *const leveldb_sys::leveldb_snapshot_t {
 /// ```
 pub struct ReadOptions {
     raw: *mut leveldb_sys::leveldb_readoptions_t,
+    verify_checksums: Cell<bool>,
+    fill_cache: Cell<bool>,
+    snapshot: Cell<*const leveldb_sys::leveldb_snapshot_t>,
 }
 
+// SAFETY: the options object is exclusively owned and LevelDB only reads it
+// during a call; the snapshot it may point to can be used from any thread.
+unsafe impl Send for ReadOptions {}
+
 impl ReadOptions {
     /// Create a new `ReadOptions` instance with default values.
     ///
@@ -216,6 +746,9 @@ impl ReadOptions {
     pub fn new() -> Self {
         Self {
             raw: unsafe { leveldb_sys::leveldb_readoptions_create() },
+            verify_checksums: Cell::new(false),
+            fill_cache: Cell::new(true),
+            snapshot: Cell::new(std::ptr::null()),
         }
     }
 
@@ -228,10 +761,11 @@ impl ReadOptions {
     /// # Arguments
     ///
     /// * `value` - If `true`, verify checksums on all reads.
-    ///             If `false`, skip checksum verification.
+    ///   If `false`, skip checksum verification.
     ///
     pub fn verify_checksums(&self, value: bool) {
         unsafe { leveldb_sys::leveldb_readoptions_set_verify_checksums(self.raw, value as u8) };
+        self.verify_checksums.set(value);
     }
 
     /// Configure whether reads should populate the cache.
@@ -243,10 +777,11 @@ impl ReadOptions {
     /// # Arguments
     ///
     /// * `value` - If `true`, reads will fill the cache.
-    ///             If `false`, reads will not affect the cache.
+    ///   If `false`, reads will not affect the cache.
     ///
     pub fn fill_cache(&self, value: bool) {
         unsafe { leveldb_sys::leveldb_readoptions_set_fill_cache(self.raw, value as u8) };
+        self.fill_cache.set(value);
     }
 
     /// Set a snapshot for consistent reads.
@@ -273,10 +808,30 @@ impl ReadOptions {
     /// let value2 = db.get(b"key2", &read_options).unwrap();
     /// ```
     pub fn snapshot<Snapshot: AsSnapshot>(&self, snapshot: &Snapshot) {
-        let snapshot_ptr = snapshot.as_snapshot_ptr();
+        self.set_snapshot_ptr(snapshot.as_snapshot_ptr());
+    }
+
+    fn set_snapshot_ptr(&self, snapshot_ptr: *const leveldb_sys::leveldb_snapshot_t) {
         unsafe {
             leveldb_sys::leveldb_readoptions_set_snapshot(self.raw, snapshot_ptr);
         }
+        self.snapshot.set(snapshot_ptr);
+    }
+
+    /// Check whether a snapshot has been set on these options.
+    pub(crate) fn has_snapshot(&self) -> bool {
+        !self.snapshot.get().is_null()
+    }
+
+    /// Create a copy of these options with the same flags and snapshot.
+    pub(crate) fn duplicate(&self) -> ReadOptions {
+        let copy = ReadOptions::new();
+        copy.verify_checksums(self.verify_checksums.get());
+        copy.fill_cache(self.fill_cache.get());
+        if self.has_snapshot() {
+            copy.set_snapshot_ptr(self.snapshot.get());
+        }
+        copy
     }
 
     /// Get the raw pointer to the underlying LevelDB read options.
@@ -292,6 +847,51 @@ impl ReadOptions {
     pub(crate) fn raw(&self) -> *mut leveldb_sys::leveldb_readoptions_t {
         self.raw
     }
+
+    /// Return the raw LevelDB read options, for calling
+    /// [`sys`](crate::sys) functions this crate does not wrap.
+    ///
+    /// # Safety
+    ///
+    /// The pointer stays owned by these `ReadOptions`: the caller must not
+    /// destroy it or use it after they are dropped. Flags and snapshots set
+    /// through it are used by direct reads, but not by helpers that copy the
+    /// options, such as [`DB::multi_get`](crate::DB::multi_get).
+    ///
+    /// # Returns
+    ///
+    /// The `leveldb_readoptions_t` pointer
+    pub unsafe fn as_raw(&self) -> *mut leveldb_sys::leveldb_readoptions_t {
+        self.raw
+    }
+
+    /// Wrap raw LevelDB read options, taking ownership of them.
+    ///
+    /// The pointer is destroyed when the returned `ReadOptions` are dropped.
+    /// Whatever was set on it before, they are treated as having the default
+    /// flags and no snapshot, as [`ReadOptions::as_raw`] describes.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must come from `leveldb_readoptions_create` and must not be
+    /// destroyed or wrapped by anything else. A snapshot already set on it
+    /// must outlive every read made with the returned `ReadOptions`.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The read options to take ownership of
+    ///
+    /// # Returns
+    ///
+    /// `ReadOptions` owning the pointer
+    pub unsafe fn from_raw(raw: *mut leveldb_sys::leveldb_readoptions_t) -> Self {
+        Self {
+            raw,
+            verify_checksums: Cell::new(false),
+            fill_cache: Cell::new(true),
+            snapshot: Cell::new(std::ptr::null()),
+        }
+    }
 }
 
 impl Default for ReadOptions {
@@ -370,7 +970,7 @@ impl WriteOptions {
     /// # Arguments
     ///
     /// * `value` - If `true`, writes are synchronized to disk.
-    ///             If `false`, writes are asynchronous (faster but less durable).
+    ///   If `false`, writes are asynchronous (faster but less durable).
     /// ```
     pub fn sync(&self, value: bool) {
         unsafe { leveldb_sys::leveldb_writeoptions_set_sync(self.raw, value as u8) };
@@ -389,6 +989,41 @@ impl WriteOptions {
     pub(crate) fn raw(&self) -> *mut leveldb_sys::leveldb_writeoptions_t {
         self.raw
     }
+
+    /// Return the raw LevelDB write options, for calling
+    /// [`sys`](crate::sys) functions this crate does not wrap.
+    ///
+    /// # Safety
+    ///
+    /// The pointer stays owned by these `WriteOptions`: the caller must not
+    /// destroy it or use it after they are dropped.
+    ///
+    /// # Returns
+    ///
+    /// The `leveldb_writeoptions_t` pointer
+    pub unsafe fn as_raw(&self) -> *mut leveldb_sys::leveldb_writeoptions_t {
+        self.raw
+    }
+
+    /// Wrap raw LevelDB write options, taking ownership of them.
+    ///
+    /// The pointer is destroyed when the returned `WriteOptions` are dropped.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must come from `leveldb_writeoptions_create` and must not be
+    /// destroyed or wrapped by anything else.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The write options to take ownership of
+    ///
+    /// # Returns
+    ///
+    /// `WriteOptions` owning the pointer
+    pub unsafe fn from_raw(raw: *mut leveldb_sys::leveldb_writeoptions_t) -> Self {
+        Self { raw }
+    }
 }
 
 impl Default for WriteOptions {
@@ -0,0 +1,485 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::db::DB;
+use crate::options::ReadOptions;
+
+#[cfg(feature = "rayon")]
+use crate::db::ScanStats;
+#[cfg(feature = "rayon")]
+use crate::iterator::DBIterator;
+#[cfg(feature = "rayon")]
+use std::ops::ControlFlow;
+#[cfg(feature = "rayon")]
+use std::sync::atomic::{AtomicBool, AtomicU64};
+
+/// A `[start, end)` key range for [`DB::compact_parallel`]; `None` stands
+/// for the start or end of the key space.
+pub type CompactRange<'a> = (Option<&'a [u8]>, Option<&'a [u8]>);
+
+/// Number of candidate split points sampled per requested range.
+const SAMPLES_PER_RANGE: usize = 16;
+
+/// Most candidate split points sampled by [`DB::split_ranges`].
+const MAX_SAMPLES: usize = 4096;
+
+/// Most ranges returned by [`DB::split_ranges`].
+const MAX_RANGES: usize = MAX_SAMPLES / SAMPLES_PER_RANGE;
+
+impl DB {
+    /// Split the key space into `n` roughly balanced `[start, end)` ranges.
+    ///
+    /// Candidate boundaries are placed at evenly spaced points between the
+    /// first and last key and snapped to real keys with seeks. They are then
+    /// grouped so that every range holds about the same amount of table data
+    /// according to LevelDB's size estimates, or the same number of candidate
+    /// intervals if nothing has been written to table files yet.
+    ///
+    /// The ranges are sorted, disjoint and together cover every key: the first
+    /// starts at the empty key and the last has an empty `end`, which stands
+    /// for "no upper bound". Fewer than `n` ranges are returned when the
+    /// database is too small to split further; an empty database yields a
+    /// single range covering everything. `n` is capped at 256, which bounds
+    /// the number of seeks spent sampling.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The desired number of ranges
+    /// * `options` - Read options used to sample keys
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// for (start, end) in db.split_ranges(8, &ReadOptions::new()) {
+    ///     println!("{:?}..{:?}", start, end);
+    /// }
+    /// ```
+    pub fn split_ranges(&self, n: usize, options: &ReadOptions) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let n = n.clamp(1, MAX_RANGES);
+        let mut iter = self.iter(options);
+        iter.seek_to_first();
+        let Some(first) = iter.key() else {
+            return vec![(Vec::new(), Vec::new())];
+        };
+        iter.seek_to_last();
+        let Some(last) = iter.key() else {
+            return vec![(Vec::new(), Vec::new())];
+        };
+
+        // Snap evenly spaced synthetic keys to real keys; these become the
+        // candidate boundaries between fine-grained intervals.
+        let samples = n * SAMPLES_PER_RANGE;
+        let mut boundaries: Vec<Vec<u8>> = Vec::new();
+        for i in 1..samples {
+            iter.seek(&interpolate_key(&first, &last, i, samples));
+            if let Some(key) = iter.key_ref()
+                && key > first.as_slice()
+                && boundaries.last().is_none_or(|prev| key > prev.as_slice())
+            {
+                boundaries.push(key.to_vec());
+            }
+        }
+
+        let mut past_last = last;
+        past_last.push(0);
+        let mut edges = Vec::with_capacity(boundaries.len() + 2);
+        edges.push(first);
+        edges.extend(boundaries);
+        edges.push(past_last);
+
+        let intervals: Vec<(&[u8], &[u8])> = edges
+            .windows(2)
+            .map(|pair| (pair[0].as_slice(), pair[1].as_slice()))
+            .collect();
+        let mut weights = self.approximate_sizes(&intervals);
+        if weights.iter().all(|&w| w == 0) {
+            weights.iter_mut().for_each(|w| *w = 1);
+        }
+        let total: u64 = weights.iter().sum();
+
+        // Cut after the interval where the running weight crosses each quota
+        let mut ranges = Vec::with_capacity(n);
+        let mut start = Vec::new();
+        let mut running = 0u64;
+        for (i, weight) in weights.iter().enumerate().take(weights.len() - 1) {
+            running += weight;
+            let quota = total * (ranges.len() as u64 + 1) / n as u64;
+            if running >= quota && ranges.len() + 1 < n {
+                let end = edges[i + 1].clone();
+                ranges.push((std::mem::replace(&mut start, end.clone()), end));
+            }
+        }
+        ranges.push((start, Vec::new()));
+        ranges
+    }
+
+    /// Compact several disjoint key ranges on a pool of `threads` threads.
+    ///
+    /// Each range is passed to [`DB::compact_range`], with `None` standing
+    /// for the start or end of the key space. Ranges are treated as
+    /// half-open `[start, end)`, like those of [`DB::split_ranges`], so
+    /// ranges sharing a boundary do not overlap. Overlapping ranges would
+    /// rewrite the same tables twice and are rejected before anything is
+    /// compacted, as are ranges whose start is past their end.
+    ///
+    /// LevelDB accepts manual compactions from several threads, but the
+    /// version bundled with this crate serializes them internally and runs
+    /// them one at a time on its single background thread. The threads take
+    /// turns, so the total time is close to compacting the ranges in
+    /// sequence. The first error is returned once every thread has finished;
+    /// ranges compacted before it stay compacted.
+    ///
+    /// # Arguments
+    ///
+    /// * `ranges` - Disjoint `(start, end)` ranges to compact
+    /// * `threads` - The number of threads to use; `0` is treated as `1`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If every range was compacted
+    /// * `Err(String)` - If the database is read-only, the ranges overlap, or a compaction failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{CompactRange, DB};
+    /// # use bleveldb::options::Options;
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let ranges: [CompactRange; 2] = [(None, Some(b"m")), (Some(b"m"), None)];
+    /// db.compact_parallel(&ranges, 2).unwrap();
+    /// ```
+    pub fn compact_parallel(
+        &self,
+        ranges: &[CompactRange<'_>],
+        threads: usize,
+    ) -> Result<(), String> {
+        self.check_writable()?;
+        check_disjoint(ranges)?;
+
+        let next = AtomicUsize::new(0);
+        let threads = threads.max(1).min(ranges.len());
+        let results: Vec<Result<(), String>> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        loop {
+                            let Some(&(start, end)) =
+                                ranges.get(next.fetch_add(1, Ordering::Relaxed))
+                            else {
+                                return Ok(());
+                            };
+                            self.compact_range(start, end)?;
+                        }
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|_| Err("compaction thread panicked".to_string()))
+                })
+                .collect()
+        });
+        results.into_iter().collect()
+    }
+
+    /// Compact the whole database, split with [`DB::split_ranges`] into `n`
+    /// ranges compacted by [`DB::compact_parallel`] on `threads` threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of ranges to split the key space into
+    /// * `threads` - The number of threads to use; `0` is treated as `1`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If every range was compacted
+    /// * `Err(String)` - If the database is read-only or a compaction failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::Options;
+    /// let db = DB::open("world/db", &Options::bedrock()).unwrap();
+    /// db.compact_parallel_split(8, 4).unwrap();
+    /// ```
+    pub fn compact_parallel_split(&self, n: usize, threads: usize) -> Result<(), String> {
+        self.check_writable()?;
+        let read_options = ReadOptions::new();
+        read_options.fill_cache(false);
+        let split = self.split_ranges(n, &read_options);
+        let ranges: Vec<CompactRange<'_>> = split
+            .iter()
+            .map(|(start, end)| {
+                // Empty keys stand for the ends of the key space
+                let start = (!start.is_empty()).then_some(start.as_slice());
+                let end = (!end.is_empty()).then_some(end.as_slice());
+                (start, end)
+            })
+            .collect();
+        self.compact_parallel(&ranges, threads)
+    }
+
+    /// Run `f` over every entry, scanning the ranges from [`DB::split_ranges`] in parallel.
+    ///
+    /// All ranges are read from one shared snapshot (or the snapshot already
+    /// set on `options`), so the scan sees a single consistent state of the
+    /// database. The callback runs on rayon's thread pool and therefore has to
+    /// be `Sync`; entries arrive in key order within a range but ranges are
+    /// processed concurrently. Returning [`ControlFlow::Break`] from any call
+    /// stops the whole scan as soon as every worker notices.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of ranges to split the key space into
+    /// * `f` - Callback invoked with each key and value
+    /// * `options` - Read options controlling the behavior of the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ScanStats)` - How many entries were visited in total and whether the scan was stopped
+    /// * `Err(String)` - If any range stopped because of an error
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # use std::ops::ControlFlow;
+    /// # use std::sync::atomic::{AtomicU64, Ordering};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let bytes = AtomicU64::new(0);
+    /// db.par_scan(
+    ///     rayon::current_num_threads(),
+    ///     |_key, value| {
+    ///         bytes.fetch_add(value.len() as u64, Ordering::Relaxed);
+    ///         ControlFlow::Continue(())
+    ///     },
+    ///     &ReadOptions::new(),
+    /// )
+    /// .unwrap();
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_scan<F>(&self, n: usize, f: F, options: &ReadOptions) -> Result<ScanStats, String>
+    where
+        F: Fn(&[u8], &[u8]) -> ControlFlow<()> + Sync,
+    {
+        use rayon::prelude::*;
+
+        let (_snapshot, read_options) = self.consistent_read_options(options);
+        let tasks: Vec<(Vec<u8>, Vec<u8>, ReadOptions)> = self
+            .split_ranges(n, &read_options)
+            .into_iter()
+            .map(|(start, end)| (start, end, read_options.duplicate()))
+            .collect();
+
+        let stop = AtomicBool::new(false);
+        let visited = AtomicU64::new(0);
+        tasks
+            .into_par_iter()
+            .try_for_each(|(start, end, range_options)| {
+                let mut iter = DBIterator::new(self, &range_options);
+                iter.seek(&start);
+                let mut count = 0;
+                while let (Some(key), Some(value)) = (iter.key_ref(), iter.value_ref()) {
+                    if (!end.is_empty() && key >= end.as_slice()) || stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    count += 1;
+                    if f(key, value).is_break() {
+                        stop.store(true, Ordering::Relaxed);
+                        break;
+                    }
+                    iter.next_native();
+                }
+                visited.fetch_add(count, Ordering::Relaxed);
+                iter.status()
+            })?;
+
+        Ok(ScanStats {
+            visited: visited.into_inner(),
+            stopped_early: stop.into_inner(),
+        })
+    }
+
+    /// Fold every entry into one value per range in parallel and combine the
+    /// results, such as to total up statistics over the whole database.
+    ///
+    /// The key space is split with [`DB::split_ranges`] and all ranges are
+    /// read from one shared snapshot (or the snapshot already set on
+    /// `options`), each by its own iterator on rayon's thread pool. Every
+    /// range starts from `identity()`, `fold` adds each entry of the range in
+    /// key order, and `reduce` combines the results of the ranges. `reduce`
+    /// must be associative; with an identity that is neutral for it, the
+    /// result is the same as folding the whole database serially. A panic in
+    /// any callback is resumed on the calling thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of ranges to split the key space into
+    /// * `identity` - Creates the starting value of each range
+    /// * `fold` - Adds an entry, given as key and value, to a range's value
+    /// * `reduce` - Combines the values of two ranges
+    /// * `options` - Read options controlling the behavior of the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(T)` - The combined value
+    /// * `Err(String)` - If reading any range failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::DB;
+    /// # use bleveldb::options::{Options, ReadOptions};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// // Count the entries and their value bytes
+    /// let (entries, bytes) = db
+    ///     .par_map_reduce(
+    ///         rayon::current_num_threads(),
+    ///         || (0u64, 0u64),
+    ///         |(entries, bytes), _key, value| {
+    ///             *entries += 1;
+    ///             *bytes += value.len() as u64;
+    ///         },
+    ///         |a, b| (a.0 + b.0, a.1 + b.1),
+    ///         &ReadOptions::new(),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_map_reduce<T, I, F, R>(
+        &self,
+        n: usize,
+        identity: I,
+        fold: F,
+        reduce: R,
+        options: &ReadOptions,
+    ) -> Result<T, String>
+    where
+        T: Send,
+        I: Fn() -> T + Sync + Send,
+        F: Fn(&mut T, &[u8], &[u8]) + Sync,
+        R: Fn(T, T) -> T + Sync + Send,
+    {
+        let (_snapshot, read_options) = self.consistent_read_options(options);
+        let ranges = self.split_ranges(n, &read_options);
+        self.par_reduce_ranges(
+            ranges,
+            &read_options,
+            &identity,
+            |iter, end| {
+                let mut value = identity();
+                while let (Some(key), Some(entry)) = (iter.key_ref(), iter.value_ref()) {
+                    if !end.is_empty() && key >= end {
+                        break;
+                    }
+                    fold(&mut value, key, entry);
+                    iter.next_native();
+                }
+                iter.status().map(|()| value)
+            },
+            reduce,
+        )
+    }
+
+    /// Run `fold_range` over each `[start, end)` range on rayon's thread pool
+    /// and combine the results with `reduce`.
+    ///
+    /// Each range gets its own iterator, positioned at `start`, reading with
+    /// a copy of `read_options`; the caller keeps any snapshot they refer to
+    /// alive. An empty `end` stands for "no upper bound".
+    #[cfg(feature = "rayon")]
+    pub(crate) fn par_reduce_ranges<T, I, F, R>(
+        &self,
+        ranges: Vec<(Vec<u8>, Vec<u8>)>,
+        read_options: &ReadOptions,
+        identity: I,
+        fold_range: F,
+        reduce: R,
+    ) -> Result<T, String>
+    where
+        T: Send,
+        I: Fn() -> T + Sync + Send,
+        F: Fn(&mut DBIterator<'_>, &[u8]) -> Result<T, String> + Sync,
+        R: Fn(T, T) -> T + Sync + Send,
+    {
+        use rayon::prelude::*;
+
+        let tasks: Vec<(Vec<u8>, Vec<u8>, ReadOptions)> = ranges
+            .into_iter()
+            .map(|(start, end)| (start, end, read_options.duplicate()))
+            .collect();
+        tasks
+            .into_par_iter()
+            .map(|(start, end, range_options)| {
+                let mut iter = DBIterator::new(self, &range_options);
+                iter.seek(&start);
+                fold_range(&mut iter, &end)
+            })
+            .try_reduce(identity, |a, b| Ok(reduce(a, b)))
+    }
+}
+
+/// Check that no two `[start, end)` ranges overlap and none is reversed.
+fn check_disjoint(ranges: &[CompactRange<'_>]) -> Result<(), String> {
+    for (i, range) in ranges.iter().enumerate() {
+        if let (Some(start), Some(end)) = range
+            && start > end
+        {
+            return Err(format!("compaction range {} starts after it ends", i));
+        }
+    }
+    // Empty ranges hold no keys and overlap nothing; of the rest, sorted by
+    // start (`None` sorts first), each only needs checking against the next
+    let mut order: Vec<usize> = (0..ranges.len())
+        .filter(|&i| ranges[i].0.is_none() || ranges[i].0 != ranges[i].1)
+        .collect();
+    order.sort_by_key(|&i| ranges[i].0);
+    for pair in order.windows(2) {
+        let overlaps = match (ranges[pair[0]].1, ranges[pair[1]].0) {
+            (None, _) | (_, None) => true,
+            (Some(end), Some(start)) => end > start,
+        };
+        if overlaps {
+            return Err(format!(
+                "compaction ranges {} and {} overlap",
+                pair[0].min(pair[1]),
+                pair[0].max(pair[1])
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Return the key `i / n` of the way from `first` to `last`.
+///
+/// Keys are compared after their common prefix, reading the next 16 bytes as
+/// a big-endian integer (shorter keys are padded with zeros).
+fn interpolate_key(first: &[u8], last: &[u8], i: usize, n: usize) -> Vec<u8> {
+    let common = first.iter().zip(last).take_while(|(a, b)| a == b).count();
+    let as_number = |key: &[u8]| {
+        let mut bytes = [0u8; 16];
+        let tail = &key[common..];
+        let len = tail.len().min(16);
+        bytes[..len].copy_from_slice(&tail[..len]);
+        u128::from_be_bytes(bytes)
+    };
+
+    let (low, high) = (as_number(first), as_number(last));
+    let span = high.saturating_sub(low);
+    // Divide before multiplying so the product cannot overflow
+    let offset = (span / n as u128) * i as u128 + (span % n as u128) * i as u128 / n as u128;
+    let point = (low + offset).to_be_bytes();
+
+    let significant = point.iter().rposition(|&b| b != 0).map_or(0, |pos| pos + 1);
+    let mut key = first[..common].to_vec();
+    key.extend_from_slice(&point[..significant]);
+    key
+}
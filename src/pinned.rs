@@ -0,0 +1,79 @@
+use std::fmt;
+use std::ops::Deref;
+
+use crate::iterator::DBIterator;
+
+/// A value read from the database without copying it into a `Vec`.
+///
+/// LevelDB's C `get` API always copies the value into a fresh allocation, so
+/// `PinnedValue` instead holds an iterator positioned at the key and exposes the
+/// iterator's value slice directly. The guard borrows the `DB` it was read from
+/// and releases the iterator when dropped.
+///
+/// `PinnedValue` dereferences to `&[u8]`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::{DB, options::{Options, ReadOptions}};
+/// # let options = Options::default();
+/// # let db = DB::open("test_db", &options).unwrap();
+/// if let Some(value) = db.get_pinned(b"key", &ReadOptions::new()).unwrap() {
+///     println!("{} bytes, first byte {:?}", value.len(), value.first());
+/// }
+/// ```
+///
+/// The guard cannot outlive the database it borrows from:
+///
+/// ```compile_fail
+/// # use bleveldb::{DB, options::{Options, ReadOptions}};
+/// # let options = Options::default();
+/// let db = DB::open("test_db", &options).unwrap();
+/// let value = db.get_pinned(b"key", &ReadOptions::new()).unwrap().unwrap();
+/// drop(db);
+/// println!("{}", value.len());
+/// ```
+pub struct PinnedValue<'db> {
+    iter: DBIterator<'db>,
+}
+
+impl<'db> PinnedValue<'db> {
+    /// Wrap an iterator that is positioned at the requested key.
+    pub(crate) fn new(iter: DBIterator<'db>) -> Self {
+        debug_assert!(iter.valid());
+        Self { iter }
+    }
+
+    /// Borrow the pinned bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        self.iter.value_ref().unwrap_or_default()
+    }
+}
+
+impl Deref for PinnedValue<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for PinnedValue<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl PartialEq<[u8]> for PinnedValue<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl fmt::Debug for PinnedValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PinnedValue")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+use std::ops::Deref;
+
+/// A value read by [`DB::get_small`](crate::DB::get_small), stored inline
+/// when it fits in `N` bytes.
+///
+/// Values of at most `N` bytes live in a fixed-size array inside the
+/// `SmallValue`, so reading them allocates nothing on the Rust side; longer
+/// values spill into a `Vec`. Pick `N` to cover the records read in hot
+/// loops, such as the one-byte chunk versions or the four-byte
+/// finalized states.
+///
+/// `SmallValue` dereferences to `&[u8]`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::{DB, options::{Options, ReadOptions}};
+/// # let db = DB::open("test_db", &Options::default()).unwrap();
+/// if let Some(value) = db.get_small::<16>(b"key", &ReadOptions::new()).unwrap() {
+///     println!("{} bytes, inline: {}", value.len(), value.is_inline());
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SmallValue<const N: usize>(Repr<N>);
+
+/// The storage of a [`SmallValue`]; kept private so that `Inline` always
+/// holds at most `N` bytes and `Heap` always more.
+#[derive(Clone)]
+enum Repr<const N: usize> {
+    /// A value of at most `N` bytes, in the first `len` bytes of `buf`.
+    Inline { buf: [u8; N], len: usize },
+    /// A value longer than `N` bytes.
+    Heap(Vec<u8>),
+}
+
+impl<const N: usize> SmallValue<N> {
+    /// Copy `value` into a new `SmallValue`, inline if it fits.
+    pub fn from_slice(value: &[u8]) -> Self {
+        if value.len() <= N {
+            let mut buf = [0; N];
+            buf[..value.len()].copy_from_slice(value);
+            SmallValue(Repr::Inline {
+                buf,
+                len: value.len(),
+            })
+        } else {
+            SmallValue(Repr::Heap(value.to_vec()))
+        }
+    }
+
+    /// Borrow the value's bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        match &self.0 {
+            Repr::Inline { buf, len } => &buf[..*len],
+            Repr::Heap(value) => value,
+        }
+    }
+
+    /// Return whether the value is stored inline.
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+
+    /// Convert the value into a `Vec`, allocating if it was inline.
+    pub fn into_vec(self) -> Vec<u8> {
+        match self.0 {
+            Repr::Inline { buf, len } => buf[..len].to_vec(),
+            Repr::Heap(value) => value,
+        }
+    }
+}
+
+impl<const N: usize> Deref for SmallValue<N> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for SmallValue<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> PartialEq for SmallValue<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const N: usize> Eq for SmallValue<N> {}
+
+impl<const N: usize> PartialEq<[u8]> for SmallValue<N> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<const N: usize> fmt::Debug for SmallValue<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SmallValue").field(&self.as_slice()).finish()
+    }
+}
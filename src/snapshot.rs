@@ -0,0 +1,68 @@
+use bleveldb_sys as sys;
+
+use crate::DB;
+use crate::options::{AsSnapshot, ReadOptions};
+
+/// A consistent, read-only view of the database at a point in time.
+///
+/// Reads performed through `ReadOptions` carrying this snapshot see the
+/// database exactly as it was when the snapshot was taken, regardless of
+/// later writes. The snapshot is released when dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bleveldb::{DB, options::{Options, ReadOptions}};
+/// # let options = Options::default();
+/// # let db = DB::open("test_db", &options).unwrap();
+/// let snapshot = db.snapshot();
+/// let read_options = ReadOptions::new();
+/// read_options.snapshot(&snapshot);
+///
+/// db.put(b"key", b"new value", &Default::default()).unwrap();
+///
+/// // Still sees the value from before the put
+/// let old_value = db.get(b"key", &read_options).unwrap();
+/// ```
+pub struct Snapshot<'db> {
+    db: &'db DB,
+    raw: *const sys::leveldb_snapshot_t,
+}
+
+impl<'db> Snapshot<'db> {
+    /// Take a new snapshot of the database.
+    ///
+    /// This is marked as `pub(crate)` because snapshots should be created through
+    /// the `DB::snapshot()` method rather than directly.
+    pub(crate) fn new(db: &'db DB) -> Self {
+        Self {
+            db,
+            raw: unsafe { sys::leveldb_create_snapshot(db.raw()) },
+        }
+    }
+
+    /// Create read options that read through this snapshot.
+    ///
+    /// The checksum and cache flags are copied from `options`.
+    pub fn read_options(&self, options: &ReadOptions) -> ReadOptions {
+        let pinned = options.duplicate();
+        pinned.snapshot(self);
+        pinned
+    }
+}
+
+impl AsSnapshot for Snapshot<'_> {
+    fn as_snapshot_ptr(&self) -> *const sys::leveldb_snapshot_t {
+        self.raw
+    }
+}
+
+impl Drop for Snapshot<'_> {
+    /// Release the snapshot.
+    ///
+    /// This method is automatically called when the snapshot goes out of scope.
+    /// Any `ReadOptions` still referring to it must not be used afterwards.
+    fn drop(&mut self) {
+        unsafe { sys::leveldb_release_snapshot(self.db.raw(), self.raw) };
+    }
+}
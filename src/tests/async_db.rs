@@ -0,0 +1,100 @@
+use crate::options::Options;
+use crate::{AsyncDB, DB, WriteBatch};
+use tempfile::TempDir;
+
+fn setup_test_db(name: &str) -> (AsyncDB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join(name);
+
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(&db_path, &options).expect("Failed to open database");
+    (AsyncDB::new(db), temp_dir)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_gets() {
+    let (db, _temp_dir) = setup_test_db("concurrent_gets");
+    let mut batch = WriteBatch::new();
+    for i in 0..100u32 {
+        batch.put(i.to_be_bytes(), (i * 2).to_le_bytes());
+    }
+    db.write(batch).await.unwrap();
+
+    let tasks: Vec<_> = (0..100u32)
+        .map(|i| {
+            let db = db.clone();
+            tokio::spawn(async move { (i, db.get(i.to_be_bytes().to_vec()).await) })
+        })
+        .collect();
+    for task in tasks {
+        let (i, value) = task.await.unwrap();
+        assert_eq!(value.unwrap(), Some((i * 2).to_le_bytes().to_vec()));
+    }
+
+    assert_eq!(db.get(b"missing".to_vec()).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_put_delete_and_compact() {
+    let (db, _temp_dir) = setup_test_db("put_delete");
+    let db = db.sync_writes(true);
+    db.put(b"key".to_vec(), b"value".to_vec()).await.unwrap();
+    assert_eq!(
+        db.get(b"key".to_vec()).await.unwrap(),
+        Some(b"value".to_vec())
+    );
+
+    db.delete(b"key".to_vec()).await.unwrap();
+    db.compact_range(None, Some(b"z".to_vec())).await.unwrap();
+    assert_eq!(db.get(b"key".to_vec()).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_streamed_scan() {
+    let (db, _temp_dir) = setup_test_db("streamed_scan");
+    for i in 0..25u8 {
+        db.put(vec![b'k', i], vec![i]).await.unwrap();
+    }
+    db.put(b"other".to_vec(), b"x".to_vec()).await.unwrap();
+
+    let mut receiver = db.scan(Some(vec![b'k']), Some(vec![b'k' + 1]), 10);
+    let mut sizes = Vec::new();
+    let mut entries = Vec::new();
+    while let Some(batch) = receiver.recv().await {
+        let batch = batch.unwrap();
+        sizes.push(batch.len());
+        entries.extend(batch);
+    }
+    assert_eq!(sizes, vec![10, 10, 5]);
+    let expected: Vec<_> = (0..25u8).map(|i| (vec![b'k', i], vec![i])).collect();
+    assert_eq!(entries, expected);
+
+    // The whole database in one batch
+    let mut receiver = db.scan(None, None, 100);
+    assert_eq!(receiver.recv().await.unwrap().unwrap().len(), 26);
+    assert!(receiver.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn test_scan_stops_when_receiver_dropped() {
+    let (db, _temp_dir) = setup_test_db("scan_dropped");
+    for i in 0..50u8 {
+        db.put(vec![i], vec![i]).await.unwrap();
+    }
+
+    let mut receiver = db.scan(None, None, 1);
+    assert_eq!(
+        receiver.recv().await.unwrap().unwrap(),
+        vec![(vec![0], vec![0])]
+    );
+    drop(receiver);
+
+    // The database stays usable once the scan gives up
+    db.put(b"after".to_vec(), b"scan".to_vec()).await.unwrap();
+    assert_eq!(
+        db.get(b"after".to_vec()).await.unwrap(),
+        Some(b"scan".to_vec())
+    );
+}
@@ -0,0 +1,144 @@
+use crate::DB;
+use crate::options::{Options, ReadOptions, WriteOptions};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tempfile::TempDir;
+
+fn setup_source_db(entries: usize) -> (DB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(temp_dir.path().join("source"), &options).expect("Failed to open database");
+    let write_opts = WriteOptions::new();
+    for i in 0..entries {
+        let key = format!("base_{:05}", i);
+        db.put(key.as_bytes(), &[i as u8; 64], &write_opts)
+            .expect("Put failed");
+    }
+    (db, temp_dir)
+}
+
+#[test]
+fn test_backup_to_copies_everything() {
+    let (db, temp_dir) = setup_source_db(1000);
+    let dest = temp_dir.path().join("backup");
+
+    let report = db.backup_to(&dest, &Options::new()).expect("Backup failed");
+    assert_eq!(report.keys, 1000);
+    assert_eq!(report.bytes, 1000 * (10 + 64));
+    assert!(report.batches >= 1);
+
+    let backup = DB::open(&dest, &Options::new()).expect("Failed to open backup");
+    let read_opts = ReadOptions::new();
+    assert!(db.iter(&read_opts).eq(backup.iter(&read_opts)));
+}
+
+#[test]
+fn test_backup_to_existing_destination_fails() {
+    let (db, temp_dir) = setup_source_db(10);
+    let dest = temp_dir.path().join("backup");
+    db.backup_to(&dest, &Options::new()).expect("Backup failed");
+
+    // A second backup must not clobber the first one
+    assert!(db.backup_to(&dest, &Options::new()).is_err());
+    let backup = DB::open(&dest, &Options::new()).expect("Failed to open backup");
+    assert_eq!(backup.count_keys(&ReadOptions::new()).unwrap(), 10);
+}
+
+#[test]
+fn test_backup_to_during_writes() {
+    let (db, temp_dir) = setup_source_db(20_000);
+    let dest = temp_dir.path().join("backup");
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let written = Arc::new(AtomicU64::new(0));
+    let writer = {
+        let (db, stop, written) = (db.clone(), stop.clone(), written.clone());
+        std::thread::spawn(move || {
+            let write_opts = WriteOptions::new();
+            let mut i = 0u64;
+            while !stop.load(Ordering::SeqCst) {
+                let key = format!("late_{:08}", i);
+                db.put(key.as_bytes(), b"late", &write_opts).unwrap();
+                i += 1;
+                written.store(i, Ordering::SeqCst);
+            }
+        })
+    };
+
+    while written.load(Ordering::SeqCst) == 0 {
+        std::thread::yield_now();
+    }
+    let report = db.backup_to(&dest, &Options::new());
+    stop.store(true, Ordering::SeqCst);
+    writer.join().expect("Writer thread panicked");
+    let report = report.expect("Backup failed");
+
+    // The writer adds keys in order, so a snapshot holds some prefix of them
+    let backup = DB::open(&dest, &Options::new()).expect("Failed to open backup");
+    let read_opts = ReadOptions::new();
+    let late_in_backup = backup.count_prefix(b"late_", &read_opts).unwrap();
+    assert!(late_in_backup >= 1);
+    assert_eq!(report.keys, 20_000 + late_in_backup);
+
+    let expected_late = db
+        .prefix_iter(b"late_", &read_opts)
+        .take(late_in_backup as usize);
+    assert!(expected_late.eq(backup.prefix_iter(b"late_", &read_opts)));
+    assert!(
+        db.prefix_iter(b"base_", &read_opts)
+            .eq(backup.prefix_iter(b"base_", &read_opts))
+    );
+}
+
+#[test]
+fn test_checkpoint() {
+    let (db, temp_dir) = setup_source_db(500);
+    db.compact_all().expect("Compaction failed");
+    let write_opts = WriteOptions::new();
+    // Still only in the log when the checkpoint is taken
+    db.put(b"logged", b"before", &write_opts).unwrap();
+
+    let dest = temp_dir.path().join("checkpoint");
+    db.checkpoint(&dest).expect("Checkpoint failed");
+
+    db.put(b"logged", b"after", &write_opts).unwrap();
+    db.put(b"late", b"after", &write_opts).unwrap();
+    db.delete(b"base_00000", &write_opts).unwrap();
+
+    let checkpoint = DB::open(&dest, &Options::new()).expect("Failed to open checkpoint");
+    let read_opts = ReadOptions::new();
+    assert_eq!(checkpoint.count_prefix(b"base_", &read_opts).unwrap(), 500);
+    assert_eq!(
+        checkpoint.get(b"logged", &read_opts).unwrap(),
+        Some(b"before".to_vec())
+    );
+    assert_eq!(checkpoint.get(b"late", &read_opts).unwrap(), None);
+
+    // The two databases are independent
+    checkpoint
+        .put(b"only_in_checkpoint", b"x", &write_opts)
+        .unwrap();
+    checkpoint.compact_all().unwrap();
+    assert_eq!(db.get(b"only_in_checkpoint", &read_opts).unwrap(), None);
+    assert_eq!(db.count_prefix(b"base_", &read_opts).unwrap(), 499);
+}
+
+#[test]
+fn test_checkpoint_requires_empty_destination() {
+    let (db, temp_dir) = setup_source_db(10);
+    let dest = temp_dir.path().join("occupied");
+    std::fs::create_dir(&dest).unwrap();
+    std::fs::write(dest.join("keep.txt"), b"data").unwrap();
+
+    assert!(db.checkpoint(&dest).is_err());
+    assert_eq!(std::fs::read(dest.join("keep.txt")).unwrap(), b"data");
+
+    // An existing but empty directory is fine
+    let empty = temp_dir.path().join("empty");
+    std::fs::create_dir(&empty).unwrap();
+    db.checkpoint(&empty).expect("Checkpoint failed");
+    let checkpoint = DB::open(&empty, &Options::new()).unwrap();
+    assert_eq!(checkpoint.count_keys(&ReadOptions::new()).unwrap(), 10);
+}
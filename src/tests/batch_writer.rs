@@ -0,0 +1,95 @@
+use crate::options::{Options, ReadOptions, WriteOptions};
+use crate::{BatchWriter, DB};
+use tempfile::TempDir;
+
+fn setup_test_db(name: &str) -> (DB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join(name);
+
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(&db_path, &options).expect("Failed to open database");
+    (db, temp_dir)
+}
+
+#[test]
+fn test_batch_writer_commits_by_count() {
+    let (db, _temp_dir) = setup_test_db("test_batch_writer_count");
+    let read_opts = ReadOptions::new();
+    let mut writer = BatchWriter::new(&db, WriteOptions::new(), 10, usize::MAX);
+
+    for i in 0..9u8 {
+        writer.put(&[i], b"value").unwrap();
+    }
+    assert_eq!((writer.batches(), writer.pending()), (0, 9));
+    assert_eq!(db.get(&[0], &read_opts).unwrap(), None);
+
+    // The tenth operation reaches the threshold
+    writer.delete(b"missing").unwrap();
+    assert_eq!(
+        (writer.batches(), writer.written(), writer.pending()),
+        (1, 10, 0)
+    );
+    assert_eq!(db.get(&[0], &read_opts).unwrap(), Some(b"value".to_vec()));
+
+    for i in 9..25u8 {
+        writer.put(&[i], b"value").unwrap();
+    }
+    assert_eq!(writer.batches(), 2);
+    assert_eq!(writer.finish().unwrap(), 26);
+    assert_eq!(db.iter(&read_opts).count(), 25);
+}
+
+#[test]
+fn test_batch_writer_commits_by_size() {
+    let (db, _temp_dir) = setup_test_db("test_batch_writer_size");
+    let mut writer = BatchWriter::new(&db, WriteOptions::new(), usize::MAX, 10_000);
+
+    writer.put(b"small", b"value").unwrap();
+    assert_eq!(writer.batches(), 0);
+    writer.put(b"big", &[0u8; 10_000]).unwrap();
+    assert_eq!((writer.batches(), writer.written()), (1, 2));
+
+    writer.put(b"after", b"value").unwrap();
+    writer.flush().unwrap();
+    assert_eq!((writer.batches(), writer.written()), (2, 3));
+    assert_eq!(writer.finish().unwrap(), 3);
+}
+
+#[test]
+fn test_batch_writer_drop_commits_pending() {
+    let (db, _temp_dir) = setup_test_db("test_batch_writer_drop");
+    {
+        let mut writer = BatchWriter::new(&db, WriteOptions::new(), 100, usize::MAX);
+        writer.put(b"key", b"value").unwrap();
+    }
+    assert_eq!(
+        db.get(b"key", &ReadOptions::new()).unwrap(),
+        Some(b"value".to_vec())
+    );
+}
+
+#[test]
+fn test_batch_writer_error_propagation() {
+    let (db, temp_dir) = setup_test_db("test_batch_writer_error");
+    db.put(b"existing", b"value", &WriteOptions::new()).unwrap();
+    drop(db);
+    let read_only = DB::open_read_only(
+        temp_dir.path().join("test_batch_writer_error"),
+        &Options::new(),
+    )
+    .unwrap();
+
+    let mut writer = BatchWriter::new(&read_only, WriteOptions::new(), 2, usize::MAX);
+    writer.put(b"a", b"1").unwrap();
+    let error = writer.put(b"b", b"2").unwrap_err();
+    assert!(error.contains("read-only"), "{}", error);
+    assert!(error.contains("0 operations"), "{}", error);
+
+    // The failure sticks: later calls and finish report it without writing
+    assert_eq!(writer.delete(b"c").unwrap_err(), error);
+    assert_eq!(writer.flush().unwrap_err(), error);
+    assert_eq!(writer.pending(), 0);
+    assert_eq!(writer.finish().unwrap_err(), error);
+}
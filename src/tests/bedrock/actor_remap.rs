@@ -0,0 +1,168 @@
+use super::setup_world_db;
+use crate::DB;
+use crate::bedrock::{
+    ActorId, ActorIdRemapper, ChunkKey, CopyOptions, Dimension, RecordTag, copy_chunk_with,
+};
+use crate::options::{ReadOptions, WriteOptions};
+use crate::write_batch::WriteBatch;
+use std::collections::HashSet;
+
+/// Store chunk `(x, 0)` in the Overworld with `ids` as its actors.
+fn write_actors(db: &DB, x: i32, ids: &[u64]) {
+    db.put(
+        ChunkKey::new(x, 0, Dimension::Overworld, RecordTag::Version),
+        [40],
+        &WriteOptions::new(),
+    )
+    .unwrap();
+    for &id in ids {
+        db.put_actor(
+            x,
+            0,
+            Dimension::Overworld,
+            ActorId(id.to_be_bytes()),
+            actor_record(id as i64),
+            &WriteOptions::new(),
+        )
+        .unwrap();
+    }
+}
+
+#[cfg(feature = "nbt")]
+fn actor_record(unique_id: i64) -> Vec<u8> {
+    use crate::bedrock::nbt::{self, Value};
+
+    nbt::encode(&Value::Compound(vec![
+        (
+            "identifier".to_string(),
+            Value::String("minecraft:cow".to_string()),
+        ),
+        ("UniqueID".to_string(), Value::Long(unique_id)),
+    ]))
+}
+
+#[cfg(not(feature = "nbt"))]
+fn actor_record(unique_id: i64) -> Vec<u8> {
+    unique_id.to_le_bytes().to_vec()
+}
+
+#[test]
+fn test_remapper_allocates_unused_ids() {
+    let (db, _temp_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+
+    let mut remapper = ActorIdRemapper::new(&db, &read_opts).unwrap();
+    assert_eq!(remapper.allocate(), ActorId(1u64.to_be_bytes()));
+    assert_eq!(remapper.allocate(), ActorId(2u64.to_be_bytes()));
+
+    write_actors(&db, 0, &[5, 9]);
+    let mut remapper = ActorIdRemapper::new(&db, &read_opts).unwrap();
+    assert!(remapper.is_used(ActorId(5u64.to_be_bytes())));
+    assert!(!remapper.is_used(ActorId(6u64.to_be_bytes())));
+    assert_eq!(remapper.allocate(), ActorId(10u64.to_be_bytes()));
+
+    // Wrapping around skips 0 and every taken id
+    write_actors(&db, 1, &[u64::MAX, 1]);
+    let mut remapper = ActorIdRemapper::new(&db, &read_opts).unwrap();
+    assert_eq!(remapper.allocate(), ActorId(2u64.to_be_bytes()));
+    assert_eq!(remapper.allocate(), ActorId(3u64.to_be_bytes()));
+}
+
+#[test]
+fn test_merge_overlapping_actor_ids() {
+    let (src, _src_dir) = setup_world_db();
+    let (dst, _dst_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+    // Both worlds count their actors from 1
+    write_actors(&src, 0, &[1, 2]);
+    write_actors(&src, 1, &[3]);
+    write_actors(&dst, 0, &[1, 2, 3]);
+    let dst_ids: HashSet<ActorId> = dst
+        .actor_digest(0, 0, Dimension::Overworld, &read_opts)
+        .unwrap()
+        .into_iter()
+        .collect();
+
+    let mut remapper = ActorIdRemapper::new(&dst, &read_opts).unwrap();
+    for x in 0..2 {
+        let report = copy_chunk_with(
+            &src,
+            &dst,
+            (x, 0, Dimension::Overworld),
+            (x + 10, 0, Dimension::Overworld),
+            CopyOptions::default(),
+            &mut remapper,
+        )
+        .unwrap();
+        assert_eq!(report.actors, if x == 0 { 2 } else { 1 });
+    }
+
+    let mut seen = dst_ids.clone();
+    for x in [10, 11] {
+        for id in dst
+            .actor_digest(x, 0, Dimension::Overworld, &read_opts)
+            .unwrap()
+        {
+            assert!(seen.insert(id), "{} is listed twice", id);
+            let data = dst.actor_data(id, &read_opts).unwrap().unwrap();
+            #[cfg(feature = "nbt")]
+            {
+                let actor = crate::bedrock::nbt::decode(&data).unwrap();
+                assert_eq!(
+                    actor.get("UniqueID").and_then(|v| v.as_i64()),
+                    Some(i64::from_be_bytes(id.0))
+                );
+            }
+            #[cfg(not(feature = "nbt"))]
+            assert_eq!(data.len(), 8);
+        }
+    }
+    assert_eq!(seen.len(), 6);
+
+    // The original actors of the destination are untouched
+    let digest: HashSet<ActorId> = dst
+        .actor_digest(0, 0, Dimension::Overworld, &read_opts)
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(digest, dst_ids);
+}
+
+#[test]
+fn test_queue_actors_drops_dangling_ids() {
+    let (src, _src_dir) = setup_world_db();
+    let (dst, _dst_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+    write_actors(&src, 0, &[1, 2]);
+    src.delete(ActorId(1u64.to_be_bytes()).key(), &WriteOptions::new())
+        .unwrap();
+
+    let mut remapper = ActorIdRemapper::new(&dst, &read_opts).unwrap();
+    let mut batch = WriteBatch::new();
+    let chunk = (0, 0, Dimension::Overworld);
+    let remapped = remapper
+        .queue_actors(&src, &read_opts, chunk, chunk, &mut batch)
+        .unwrap();
+    assert_eq!(
+        remapped,
+        vec![(ActorId(2u64.to_be_bytes()), ActorId(1u64.to_be_bytes()))]
+    );
+    assert!(
+        remapper
+            .queue_actors(
+                &src,
+                &read_opts,
+                (5, 5, Dimension::Nether),
+                chunk,
+                &mut batch
+            )
+            .unwrap()
+            .is_empty()
+    );
+    dst.write(&batch, &WriteOptions::new()).unwrap();
+    assert_eq!(
+        dst.actor_digest(0, 0, Dimension::Overworld, &read_opts)
+            .unwrap(),
+        vec![ActorId(1u64.to_be_bytes())]
+    );
+}
@@ -0,0 +1,140 @@
+use super::setup_world_db;
+use crate::bedrock::{Actor, ActorId, Dimension};
+use crate::options::{ReadOptions, WriteOptions};
+
+fn digest_key(x: i32, z: i32, dim: Option<i32>) -> Vec<u8> {
+    let mut key = b"digp".to_vec();
+    key.extend_from_slice(&x.to_le_bytes());
+    key.extend_from_slice(&z.to_le_bytes());
+    if let Some(dim) = dim {
+        key.extend_from_slice(&dim.to_le_bytes());
+    }
+    key
+}
+
+fn actor_key(id: u64) -> Vec<u8> {
+    let mut key = b"actorprefix".to_vec();
+    key.extend_from_slice(&id.to_le_bytes());
+    key
+}
+
+#[test]
+fn test_actor_digest_layouts() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    // Missing and empty digests both mean no actors
+    assert!(
+        db.actor_digest(0, 0, Dimension::Overworld, &read_opts)
+            .unwrap()
+            .is_empty()
+    );
+    db.put(digest_key(0, 0, None), b"", &write_opts).unwrap();
+    assert!(
+        db.actor_digest(0, 0, Dimension::Overworld, &read_opts)
+            .unwrap()
+            .is_empty()
+    );
+
+    let mut digest = Vec::new();
+    digest.extend_from_slice(&1u64.to_le_bytes());
+    digest.extend_from_slice(&2u64.to_le_bytes());
+    digest.extend_from_slice(&3u64.to_le_bytes());
+    db.put(digest_key(-4, 5, Some(1)), &digest, &write_opts)
+        .unwrap();
+    db.put(actor_key(1), b"one", &write_opts).unwrap();
+    db.put(actor_key(3), b"three", &write_opts).unwrap();
+
+    let ids = db
+        .actor_digest(-4, 5, Dimension::Nether, &read_opts)
+        .unwrap();
+    assert_eq!(ids, [1u64, 2, 3].map(|id| ActorId(id.to_le_bytes())));
+    assert!(
+        db.actor_digest(-4, 5, Dimension::Overworld, &read_opts)
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        db.actor_data(ids[0], &read_opts).unwrap().as_deref(),
+        Some(&b"one"[..])
+    );
+
+    // Dangling ids are reported, not dropped
+    let actors = db
+        .actors_in_chunk(-4, 5, Dimension::Nether, &read_opts)
+        .unwrap();
+    assert_eq!(
+        actors,
+        [
+            Actor {
+                id: ids[0],
+                data: Some(b"one".to_vec())
+            },
+            Actor {
+                id: ids[1],
+                data: None
+            },
+            Actor {
+                id: ids[2],
+                data: Some(b"three".to_vec())
+            },
+        ]
+    );
+
+    db.put(digest_key(9, 9, Some(2)), [0u8; 12], &write_opts)
+        .unwrap();
+    assert!(db.actor_digest(9, 9, Dimension::End, &read_opts).is_err());
+    assert_eq!(ids[1].to_string(), "0200000000000000");
+}
+
+#[test]
+fn test_put_and_remove_actor() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    let (a, b) = (ActorId(7u64.to_le_bytes()), ActorId(8u64.to_le_bytes()));
+
+    db.put_actor(2, 3, Dimension::End, a, b"a", &write_opts)
+        .unwrap();
+    db.put_actor(2, 3, Dimension::End, b, b"b", &write_opts)
+        .unwrap();
+    db.put_actor(2, 3, Dimension::End, a, b"a2", &write_opts)
+        .unwrap();
+    assert_eq!(
+        db.actor_digest(2, 3, Dimension::End, &read_opts).unwrap(),
+        [a, b]
+    );
+    assert_eq!(
+        db.get(actor_key(7), &read_opts).unwrap(),
+        Some(b"a2".to_vec())
+    );
+
+    let mut raw_digest = 7u64.to_le_bytes().to_vec();
+    raw_digest.extend_from_slice(&8u64.to_le_bytes());
+    assert_eq!(
+        db.get(digest_key(2, 3, Some(2)), &read_opts).unwrap(),
+        Some(raw_digest)
+    );
+
+    assert!(
+        db.remove_actor(2, 3, Dimension::End, a, &write_opts)
+            .unwrap()
+    );
+    assert!(
+        !db.remove_actor(2, 3, Dimension::End, a, &write_opts)
+            .unwrap()
+    );
+    assert_eq!(
+        db.actor_digest(2, 3, Dimension::End, &read_opts).unwrap(),
+        [b]
+    );
+    assert_eq!(db.actor_data(a, &read_opts).unwrap(), None);
+
+    assert!(
+        db.remove_actor(2, 3, Dimension::End, b, &write_opts)
+            .unwrap()
+    );
+    assert_eq!(
+        db.get(digest_key(2, 3, Some(2)), &read_opts).unwrap(),
+        Some(Vec::new())
+    );
+}
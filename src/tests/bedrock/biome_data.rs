@@ -0,0 +1,184 @@
+use super::{Rng, setup_world_db};
+use crate::bedrock::{
+    BiomeDataError, BiomeSection, ChunkKey, Data2D, Data3D, Dimension, RecordTag,
+};
+use crate::options::{ReadOptions, WriteOptions};
+
+fn heightmap_bytes() -> Vec<u8> {
+    (0..256u16).flat_map(|i| (i + 60).to_le_bytes()).collect()
+}
+
+#[test]
+fn test_data2d_round_trip() {
+    let mut record = heightmap_bytes();
+    record.extend((0..=255u8).rev());
+
+    let data = Data2D::parse(&record).unwrap();
+    assert_eq!(data.height_at(0, 0), 60);
+    assert_eq!(data.height_at(3, 1), 60 + 16 + 3);
+    assert_eq!(data.biome_at(0, 0), 255);
+    assert_eq!(data.biome_at(15, 15), 0);
+    assert_eq!(data.encode(), record);
+
+    assert_eq!(
+        Data2D::parse(&record[..767]),
+        Err(BiomeDataError::InvalidLength(767))
+    );
+    record.push(0);
+    assert_eq!(
+        Data2D::parse(&record),
+        Err(BiomeDataError::InvalidLength(769))
+    );
+}
+
+#[test]
+fn test_data3d_parse_synthetic() {
+    let mut record = heightmap_bytes();
+    // Section 0: a single biome
+    record.push(0);
+    record.extend_from_slice(&7u32.to_le_bytes());
+    // Section 1: 1 bit per value, the upper half (y >= 8) of every column is biome 4
+    record.push(1 << 1);
+    for _ in 0..128 {
+        record.extend_from_slice(&0xFF00FF00u32.to_le_bytes());
+    }
+    record.extend_from_slice(&2u32.to_le_bytes());
+    record.extend_from_slice(&1u32.to_le_bytes());
+    record.extend_from_slice(&4u32.to_le_bytes());
+    // Section 2: same as section 1
+    record.push(0xFF);
+
+    let data = Data3D::parse(&record).unwrap();
+    assert_eq!(data.sections.len(), 3);
+    assert_eq!(data.height_at(15, 0), 75);
+    assert_eq!(data.biome_at(5, 0, 9), Some(7));
+    assert_eq!(data.biome_at(5, 15, 9), Some(7));
+    assert_eq!(data.biome_at(5, 16, 9), Some(1));
+    assert_eq!(data.biome_at(5, 24, 9), Some(4));
+    assert_eq!(data.biome_at(0, 32 + 3, 0), Some(1));
+    assert_eq!(data.biome_at(0, 32 + 12, 0), Some(4));
+    assert_eq!(data.biome_at(0, 48, 0), None);
+    assert_eq!(data.encode(), record);
+
+    // Only a heightmap is still a valid record
+    let data = Data3D::parse(&heightmap_bytes()).unwrap();
+    assert!(data.sections.is_empty());
+    assert_eq!(data.biome_at(0, 0, 0), None);
+}
+
+#[test]
+fn test_data3d_round_trip_random() {
+    let mut rng = Rng::new(0xB10E);
+    for palette_len in [1usize, 2, 3, 5, 16, 17, 33, 64, 65, 300] {
+        let palette: Vec<u32> = (0..palette_len)
+            .map(|_| rng.next_u64() as u32 % 200)
+            .collect();
+        let indices: Vec<u16> = (0..4096)
+            .map(|_| (rng.next_u64() % palette_len as u64) as u16)
+            .collect();
+        let data = Data3D {
+            heights: [rng.next_u64() as u16; 256],
+            sections: vec![
+                BiomeSection::Paletted {
+                    palette: palette.clone(),
+                    indices: indices.clone(),
+                },
+                BiomeSection::SameAsBelow,
+                BiomeSection::Single(12),
+            ],
+        };
+        let parsed = Data3D::parse(&data.encode()).unwrap();
+        assert_eq!(parsed, data, "palette of {}", palette_len);
+
+        let (x, y, z) = (3, 9, 14);
+        let expected = palette[indices[(x * 16 + z) * 16 + y] as usize];
+        assert_eq!(parsed.biome_at(x, y, z), Some(expected));
+        assert_eq!(parsed.biome_at(x, 16 + y, z), Some(expected));
+        assert_eq!(parsed.biome_at(x, 32 + y, z), Some(12));
+    }
+}
+
+#[test]
+fn test_data3d_errors() {
+    assert_eq!(
+        Data3D::parse(&[0u8; 100]),
+        Err(BiomeDataError::Truncated { offset: 100 })
+    );
+
+    let mut record = heightmap_bytes();
+    record.push(0xFF);
+    assert_eq!(
+        Data3D::parse(&record),
+        Err(BiomeDataError::CopyWithoutPrevious)
+    );
+
+    let mut record = heightmap_bytes();
+    record.push(7 << 1);
+    assert_eq!(
+        Data3D::parse(&record),
+        Err(BiomeDataError::InvalidBitsPerValue {
+            section: 0,
+            bits: 7
+        })
+    );
+
+    // A single-biome section cut short
+    let mut record = heightmap_bytes();
+    record.extend_from_slice(&[0, 1, 0]);
+    assert_eq!(
+        Data3D::parse(&record),
+        Err(BiomeDataError::Truncated { offset: 513 })
+    );
+
+    // A paletted section without its palette
+    let mut record = heightmap_bytes();
+    record.push(0);
+    record.extend_from_slice(&1u32.to_le_bytes());
+    record.push(2 << 1);
+    // 2 bits per value: 16 values per word, 256 words
+    record.extend(std::iter::repeat_n(0u8, 256 * 4));
+    assert_eq!(
+        Data3D::parse(&record),
+        Err(BiomeDataError::Truncated {
+            offset: 517 + 1 + 1024
+        })
+    );
+
+    // Indices past the end of the palette
+    record.extend_from_slice(&0u32.to_le_bytes());
+    assert_eq!(
+        Data3D::parse(&record),
+        Err(BiomeDataError::PaletteIndexOutOfRange {
+            section: 1,
+            index: 0
+        })
+    );
+}
+
+#[test]
+fn test_get_data3d() {
+    let (db, _temp_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+    let data = Data3D {
+        heights: [70; 256],
+        sections: vec![BiomeSection::Single(1); 24],
+    };
+    let key = ChunkKey::new(-1, 2, Dimension::Overworld, RecordTag::Data3D);
+    db.put(key, data.encode(), &WriteOptions::new()).unwrap();
+
+    assert_eq!(
+        db.get_data3d(-1, 2, Dimension::Overworld, &read_opts)
+            .unwrap(),
+        Some(data)
+    );
+    assert_eq!(
+        db.get_data3d(-1, 2, Dimension::Nether, &read_opts).unwrap(),
+        None
+    );
+
+    db.put(key, [0u8; 10], &WriteOptions::new()).unwrap();
+    assert!(
+        db.get_data3d(-1, 2, Dimension::Overworld, &read_opts)
+            .is_err()
+    );
+}
@@ -0,0 +1,105 @@
+use super::setup_world_db;
+use crate::bedrock::{BiomeSection, ChunkKey, Data2D, Data3D, Dimension, RecordTag, biome_name};
+use crate::options::{ReadOptions, WriteOptions};
+
+fn synthetic_data3d() -> Data3D {
+    // The lower half of every column is biome 4, the upper half biome 24
+    let indices = (0..4096).map(|i| (i % 16 >= 8) as u16).collect();
+    Data3D {
+        heights: [64; 256],
+        sections: vec![
+            BiomeSection::Single(1),
+            BiomeSection::SameAsBelow,
+            BiomeSection::Paletted {
+                palette: vec![4, 24, 7],
+                indices,
+            },
+            BiomeSection::SameAsBelow,
+        ],
+    }
+}
+
+#[test]
+fn test_biome_name() {
+    assert_eq!(biome_name(0), Some("ocean"));
+    assert_eq!(biome_name(179), Some("crimson_forest"));
+    assert_eq!(biome_name(192), Some("cherry_grove"));
+    assert_eq!(biome_name(50), None);
+    assert_eq!(biome_name(u32::MAX), None);
+}
+
+#[test]
+fn test_biome_histogram() {
+    let (db, _temp_dir) = setup_world_db();
+    let write_opts = WriteOptions::new();
+    let put = |x, z, dim, tag, value: Vec<u8>| {
+        db.put(ChunkKey::new(x, z, dim, tag), value, &write_opts)
+            .unwrap();
+    };
+
+    let data3d = synthetic_data3d().encode();
+    let data2d = Data2D {
+        heights: [64; 256],
+        biomes: [21; 256],
+    }
+    .encode();
+    // Two 1.18 chunks; one also keeps its old Data2D, which is ignored
+    put(
+        0,
+        0,
+        Dimension::Overworld,
+        RecordTag::Data3D,
+        data3d.clone(),
+    );
+    put(
+        1,
+        0,
+        Dimension::Overworld,
+        RecordTag::Data3D,
+        data3d.clone(),
+    );
+    put(
+        1,
+        0,
+        Dimension::Overworld,
+        RecordTag::Data2D,
+        data2d.clone(),
+    );
+    // An older chunk with column biomes only
+    put(-5, 2, Dimension::Overworld, RecordTag::Data2D, data2d);
+    // A chunk without biomes and one with a broken record
+    put(3, 3, Dimension::Overworld, RecordTag::Version, vec![40]);
+    put(4, 4, Dimension::Overworld, RecordTag::Data3D, vec![0; 10]);
+    // Another dimension is not counted
+    put(0, 0, Dimension::Nether, RecordTag::Data3D, data3d);
+
+    let histogram = db
+        .biome_histogram(Dimension::Overworld, &ReadOptions::new())
+        .unwrap();
+    assert_eq!(histogram.chunks, 5);
+    assert_eq!(histogram.missing, 1);
+    assert_eq!(histogram.invalid, 1);
+    assert_eq!(
+        histogram
+            .blocks
+            .iter()
+            .map(|(&k, &v)| (k, v))
+            .collect::<Vec<_>>(),
+        vec![(1, 2 * 8192), (4, 2 * 4096), (24, 2 * 4096)]
+    );
+    assert_eq!(
+        histogram
+            .columns
+            .iter()
+            .map(|(&k, &v)| (k, v))
+            .collect::<Vec<_>>(),
+        vec![(21, 256)]
+    );
+    let ranked = histogram.ranked_blocks();
+    assert_eq!(ranked[0], (1, Some("plains"), 16384));
+
+    let empty = db
+        .biome_histogram(Dimension::End, &ReadOptions::new())
+        .unwrap();
+    assert_eq!(empty, Default::default());
+}
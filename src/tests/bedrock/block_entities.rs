@@ -0,0 +1,155 @@
+use super::setup_world_db;
+use crate::bedrock::nbt::{self, Value};
+use crate::bedrock::{ChunkKey, Dimension, RecordTag};
+use crate::options::{ReadOptions, WriteOptions};
+
+fn block_entity(id: &str, x: i32, y: i32, z: i32) -> Value {
+    Value::Compound(vec![
+        ("id".to_string(), Value::String(id.to_string())),
+        ("isMovable".to_string(), Value::Byte(1)),
+        ("x".to_string(), Value::Int(x)),
+        ("y".to_string(), Value::Int(y)),
+        ("z".to_string(), Value::Int(z)),
+    ])
+}
+
+/// A chest with an empty item list, which keeps the `End` element type.
+fn empty_chest(x: i32, y: i32, z: i32) -> Value {
+    let mut chest = block_entity("Chest", x, y, z);
+    chest.insert("Items", Value::list(Vec::new()));
+    chest
+}
+
+#[test]
+fn test_block_entities_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+    let write_opts = WriteOptions::new();
+
+    assert_eq!(
+        db.block_entities(2, -3, Dimension::Nether, &read_opts)
+            .unwrap(),
+        Vec::new()
+    );
+
+    let entities = vec![
+        empty_chest(32, 40, -48),
+        block_entity("Sign", 33, 41, -47),
+        block_entity("MobSpawner", 40, 12, -40),
+    ];
+    db.set_block_entities(2, -3, Dimension::Nether, &entities, &write_opts)
+        .unwrap();
+    assert_eq!(
+        db.block_entities(2, -3, Dimension::Nether, &read_opts)
+            .unwrap(),
+        entities
+    );
+    // The record holds the compounds back to back
+    let record = db
+        .get(
+            ChunkKey::new(2, -3, Dimension::Nether, RecordTag::BlockEntity),
+            &read_opts,
+        )
+        .unwrap()
+        .unwrap();
+    let expected: Vec<u8> = entities.iter().flat_map(nbt::encode).collect();
+    assert_eq!(record, expected);
+    assert_eq!(
+        db.block_entities(2, -3, Dimension::Overworld, &read_opts)
+            .unwrap(),
+        Vec::new()
+    );
+
+    db.set_block_entities(2, -3, Dimension::Nether, &[], &write_opts)
+        .unwrap();
+    assert_eq!(
+        db.get(
+            ChunkKey::new(2, -3, Dimension::Nether, RecordTag::BlockEntity),
+            &read_opts
+        )
+        .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_block_entities_invalid_record() {
+    let (db, _temp_dir) = setup_world_db();
+    db.put(
+        ChunkKey::new(1, 1, Dimension::Overworld, RecordTag::BlockEntity),
+        [10, 0, 0, 1],
+        &WriteOptions::new(),
+    )
+    .unwrap();
+    let err = db
+        .block_entities(1, 1, Dimension::Overworld, &ReadOptions::new())
+        .unwrap_err();
+    assert!(err.contains("(1, 1)"), "{}", err);
+}
+
+#[test]
+fn test_all_block_entities() {
+    let (db, _temp_dir) = setup_world_db();
+    let write_opts = WriteOptions::new();
+    super::write_synthetic_world(
+        &db,
+        &[(0, 0, Dimension::Overworld), (1, 0, Dimension::Overworld)],
+    );
+    let chests = vec![empty_chest(0, 64, 0), empty_chest(1, 64, 0)];
+    db.set_block_entities(0, 0, Dimension::Overworld, &chests, &write_opts)
+        .unwrap();
+    db.set_block_entities(
+        5,
+        -1,
+        Dimension::Overworld,
+        &[block_entity("Sign", 80, 70, -10)],
+        &write_opts,
+    )
+    .unwrap();
+    db.set_block_entities(
+        0,
+        0,
+        Dimension::End,
+        &[block_entity("EndGateway", 0, 60, 0)],
+        &write_opts,
+    )
+    .unwrap();
+    // A broken record is reported and skipped
+    db.put(
+        ChunkKey::new(-1, 0, Dimension::Overworld, RecordTag::BlockEntity),
+        [8, 0],
+        &write_opts,
+    )
+    .unwrap();
+
+    let read_opts = ReadOptions::new();
+    let mut found = Vec::new();
+    let mut errors = Vec::new();
+    for entry in db.all_block_entities(Dimension::Overworld, &read_opts) {
+        match entry {
+            Ok((position, entity)) => found.push((position, entity)),
+            Err(e) => errors.push(e),
+        }
+    }
+    found.sort_by_key(|(position, _)| *position);
+    assert_eq!(
+        found,
+        vec![
+            ((0, 0), chests[0].clone()),
+            ((0, 0), chests[1].clone()),
+            ((5, -1), block_entity("Sign", 80, 70, -10)),
+        ]
+    );
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("(-1, 0)"), "{}", errors[0]);
+
+    let end: Vec<_> = db
+        .all_block_entities(Dimension::End, &read_opts)
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(end, vec![((0, 0), block_entity("EndGateway", 0, 60, 0))]);
+    assert_eq!(
+        db.all_block_entities(Dimension::Nether, &read_opts).count(),
+        0
+    );
+}
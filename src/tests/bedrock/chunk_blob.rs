@@ -0,0 +1,195 @@
+use super::setup_world_db;
+use crate::DB;
+use crate::bedrock::{ActorId, ChunkBlob, ChunkKey, Dimension, RecordTag};
+use crate::options::{ReadOptions, WriteOptions};
+
+/// Write a chunk with a few records, two subchunks and two actors.
+fn write_chunk(db: &DB, x: i32, z: i32, dim: Dimension) {
+    let write_opts = WriteOptions::new();
+    db.put(
+        ChunkKey::new(x, z, dim, RecordTag::Version),
+        [40],
+        &write_opts,
+    )
+    .unwrap();
+    db.put(
+        ChunkKey::new(x, z, dim, RecordTag::BlockEntity),
+        b"block entities",
+        &write_opts,
+    )
+    .unwrap();
+    for y in [-4, 7] {
+        db.put_subchunk(x, z, dim, y, [y as u8; 10], &write_opts)
+            .unwrap();
+    }
+    for id in [1u64, 2] {
+        db.put_actor(
+            x,
+            z,
+            dim,
+            ActorId(id.to_be_bytes()),
+            [id as u8; 3],
+            &write_opts,
+        )
+        .unwrap();
+    }
+}
+
+type ChunkContents = (Vec<(RecordTag, Option<i8>, Vec<u8>)>, Vec<Vec<u8>>);
+
+/// Return the records of a chunk by tag and subchunk index, and the data of its actors.
+fn chunk_contents(db: &DB, x: i32, z: i32, dim: Dimension) -> ChunkContents {
+    let read_opts = ReadOptions::new();
+    let blob = db.export_chunk(x, z, dim, &read_opts).unwrap();
+    let records = blob
+        .records
+        .iter()
+        .map(|(suffix, value)| {
+            let mut key = ChunkKey::new(x, z, dim, RecordTag::Version)
+                .as_bytes()
+                .to_vec();
+            key.pop();
+            key.extend_from_slice(suffix);
+            let key = ChunkKey::parse(&key).unwrap();
+            (key.tag(), key.subchunk_index(), value.clone())
+        })
+        .collect();
+    let actors = db
+        .actors_in_chunk(x, z, dim, &read_opts)
+        .unwrap()
+        .into_iter()
+        .map(|actor| actor.data.unwrap())
+        .collect();
+    (records, actors)
+}
+
+#[test]
+fn test_chunk_blob_bytes_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    write_chunk(&db, -3, 8, Dimension::Nether);
+
+    let blob = db
+        .export_chunk(-3, 8, Dimension::Nether, &ReadOptions::new())
+        .unwrap();
+    assert_eq!((blob.x, blob.z, blob.dimension), (-3, 8, Dimension::Nether));
+    assert_eq!(blob.records.len(), 4);
+    // Records are in key order, so the subchunk at y = -4 (0xfc) comes after y = 7
+    assert_eq!(
+        blob.records[0],
+        (vec![RecordTag::Version.to_byte()], vec![40])
+    );
+    assert_eq!(
+        blob.records[2],
+        (
+            vec![RecordTag::SubChunkPrefix.to_byte(), 0xfc],
+            vec![0xfc; 10]
+        )
+    );
+    assert_eq!(
+        blob.actors,
+        vec![
+            (ActorId(1u64.to_be_bytes()), vec![1; 3]),
+            (ActorId(2u64.to_be_bytes()), vec![2; 3])
+        ]
+    );
+
+    let bytes = blob.to_bytes();
+    assert_eq!(&bytes[..8], b"BLDBCHNK");
+    assert_eq!(ChunkBlob::from_bytes(&bytes).unwrap(), blob);
+
+    let mut corrupt = bytes.clone();
+    corrupt[20] ^= 1;
+    assert!(
+        ChunkBlob::from_bytes(&corrupt)
+            .unwrap_err()
+            .contains("checksum")
+    );
+    assert!(ChunkBlob::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(ChunkBlob::from_bytes(b"BLDBDUMP\x01").is_err());
+    let mut version = bytes;
+    version[8] = 9;
+    assert!(
+        ChunkBlob::from_bytes(&version)
+            .unwrap_err()
+            .contains("version 9")
+    );
+
+    assert!(
+        db.export_chunk(0, 0, Dimension::Nether, &ReadOptions::new())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_import_chunk_same_position() {
+    let (src, _src_dir) = setup_world_db();
+    let (dst, _dst_dir) = setup_world_db();
+    write_chunk(&src, 5, 5, Dimension::Overworld);
+    let blob = src
+        .export_chunk(5, 5, Dimension::Overworld, &ReadOptions::new())
+        .unwrap();
+    let blob = ChunkBlob::from_bytes(&blob.to_bytes()).unwrap();
+
+    let report = dst.import_chunk(&blob, None, &WriteOptions::new()).unwrap();
+    assert_eq!(report.records, 4);
+    assert_eq!(report.actors, 2);
+    assert_eq!(report.replaced.total(), 0);
+    assert_eq!(
+        chunk_contents(&dst, 5, 5, Dimension::Overworld),
+        chunk_contents(&src, 5, 5, Dimension::Overworld)
+    );
+
+    // Importing again replaces the chunk, including its actors
+    let report = dst.import_chunk(&blob, None, &WriteOptions::new()).unwrap();
+    assert_eq!(report.replaced.actors, 2);
+    assert_eq!(
+        dst.actor_digest(5, 5, Dimension::Overworld, &ReadOptions::new())
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(
+        chunk_contents(&dst, 5, 5, Dimension::Overworld),
+        chunk_contents(&src, 5, 5, Dimension::Overworld)
+    );
+}
+
+#[test]
+fn test_import_chunk_shifted() {
+    let (db, _temp_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+    write_chunk(&db, 0, 0, Dimension::Overworld);
+    let blob = db
+        .export_chunk(0, 0, Dimension::Overworld, &read_opts)
+        .unwrap();
+
+    let report = db
+        .import_chunk(&blob, Some((-20, 31, Dimension::End)), &WriteOptions::new())
+        .unwrap();
+    assert_eq!(report.records, 4);
+    assert_eq!(
+        chunk_contents(&db, -20, 31, Dimension::End),
+        chunk_contents(&db, 0, 0, Dimension::Overworld)
+    );
+
+    // The imported actors do not share ids with the originals
+    let original = db
+        .actor_digest(0, 0, Dimension::Overworld, &read_opts)
+        .unwrap();
+    let imported = db
+        .actor_digest(-20, 31, Dimension::End, &read_opts)
+        .unwrap();
+    assert_eq!(imported.len(), 2);
+    assert!(imported.iter().all(|id| !original.contains(id)));
+
+    let mut bad = blob.clone();
+    bad.records.push((vec![0xee], vec![]));
+    assert!(
+        db.import_chunk(
+            &bad,
+            Some((1, 1, Dimension::Overworld)),
+            &WriteOptions::new()
+        )
+        .is_err()
+    );
+}
@@ -0,0 +1,221 @@
+use super::Rng;
+use crate::bedrock::{ChunkKey, Dimension, KeyParseError, RecordTag};
+use std::cmp::Ordering;
+
+#[test]
+fn test_record_tag_bytes() {
+    for tag in RecordTag::ALL {
+        assert_eq!(RecordTag::from_byte(tag.to_byte()), Some(tag));
+    }
+    assert_eq!(RecordTag::Data3D.to_byte(), 43);
+    assert_eq!(RecordTag::Version.to_byte(), 44);
+    assert_eq!(RecordTag::SubChunkPrefix.to_byte(), 47);
+    assert_eq!(RecordTag::BlockEntity.to_byte(), 49);
+    assert_eq!(RecordTag::FinalizedState.to_byte(), 54);
+    assert_eq!(RecordTag::ActorDigestVersion.to_byte(), 65);
+    assert_eq!(RecordTag::LegacyVersion.to_byte(), 118);
+    assert_eq!(RecordTag::from_byte(42), None);
+    assert_eq!(RecordTag::from_byte(66), None);
+}
+
+#[test]
+fn test_chunk_key_layouts() {
+    let overworld = ChunkKey::new(1, -1, Dimension::Overworld, RecordTag::Version);
+    assert_eq!(
+        overworld.as_bytes(),
+        [1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 44]
+    );
+
+    let nether = ChunkKey::new(-2, 3, Dimension::Nether, RecordTag::Data3D);
+    assert_eq!(
+        nether.encode(),
+        vec![0xfe, 0xff, 0xff, 0xff, 3, 0, 0, 0, 1, 0, 0, 0, 43]
+    );
+
+    let subchunk = ChunkKey::subchunk(0, 0, Dimension::Overworld, -4);
+    assert_eq!(subchunk.as_bytes(), [0, 0, 0, 0, 0, 0, 0, 0, 47, 0xfc]);
+    assert_eq!(subchunk.subchunk_index(), Some(-4));
+
+    let end_subchunk = ChunkKey::subchunk(7, 8, Dimension::End, 19);
+    assert_eq!(end_subchunk.as_bytes().len(), 14);
+    assert_eq!(end_subchunk.as_bytes()[12..], [47, 19]);
+}
+
+/// Return a key with random coordinates, dimension, tag and subchunk index.
+fn random_key(rng: &mut Rng) -> ChunkKey {
+    let (x, z) = (rng.next_i32(), rng.next_i32());
+    let dimension = match rng.next_u64() % 3 {
+        0 => Dimension::Overworld,
+        1 => Dimension::parse(rng.next_i32() % 4),
+        _ => Dimension::parse(rng.next_i32()),
+    };
+    let tag = RecordTag::ALL[(rng.next_u64() % RecordTag::ALL.len() as u64) as usize];
+    if tag == RecordTag::SubChunkPrefix {
+        ChunkKey::subchunk(x, z, dimension, rng.next_u64() as i8)
+    } else {
+        ChunkKey::new(x, z, dimension, tag)
+    }
+}
+
+#[test]
+fn test_chunk_key_round_trip_random() {
+    let mut rng = Rng::new(0x5eed);
+    for _ in 0..10_000 {
+        let key = random_key(&mut rng);
+        let (x, z, dimension, tag) = (key.x(), key.z(), key.dimension(), key.tag());
+
+        let parsed = ChunkKey::parse(&key.encode()).unwrap();
+        assert_eq!(parsed, key);
+        assert_eq!((parsed.x(), parsed.z()), (x, z));
+        assert_eq!(parsed.dimension(), dimension);
+        assert_eq!(parsed.tag(), tag);
+    }
+}
+
+#[test]
+fn test_chunk_key_order_matches_bytes() {
+    let mut rng = Rng::new(0x0bd3);
+    let mut keys = Vec::new();
+    for _ in 0..10_000 {
+        let (a, b) = (random_key(&mut rng), random_key(&mut rng));
+        assert_eq!(a.cmp(&b), a.encode().cmp(&b.encode()));
+        assert_eq!(a.cmp(&a), Ordering::Equal);
+        keys.push(a);
+    }
+    // Small coordinates share most bytes, which exercises the later fields
+    for _ in 0..10_000 {
+        let small = |rng: &mut Rng| (rng.next_u64() % 5) as i32 - 2;
+        let dimension = Dimension::parse(small(&mut rng).rem_euclid(3));
+        let (x, z) = (small(&mut rng), small(&mut rng));
+        let a = ChunkKey::subchunk(x, z, dimension, small(&mut rng) as i8);
+        let b = ChunkKey::new(
+            x,
+            z,
+            dimension,
+            RecordTag::ALL[(rng.next_u64() % 8) as usize],
+        );
+        assert_eq!(a.cmp(&b), a.encode().cmp(&b.encode()));
+        assert_eq!(b.cmp(&a), b.encode().cmp(&a.encode()));
+    }
+
+    keys.sort();
+    assert!(keys.windows(2).all(|w| w[0].as_bytes() <= w[1].as_bytes()));
+}
+
+#[test]
+fn test_chunk_key_order_is_not_numeric() {
+    let overworld = |x, z| ChunkKey::new(x, z, Dimension::Overworld, RecordTag::Version);
+    assert!(overworld(256, 0) < overworld(1, 0));
+    assert!(overworld(1, 0) < overworld(-1, 0));
+    assert!(overworld(0, 5) < overworld(1, 0));
+    let subchunk = |y| ChunkKey::subchunk(0, 0, Dimension::Overworld, y);
+    assert!(subchunk(19) < subchunk(-4));
+    assert!(subchunk(-4) < subchunk(-1));
+}
+
+#[test]
+fn test_chunk_key_successor() {
+    let mut rng = Rng::new(0x50cc);
+    for _ in 0..1_000 {
+        let key = random_key(&mut rng);
+        let successor = key.successor();
+        assert!(key.as_bytes() < successor.as_slice());
+        assert!(successor.starts_with(key.as_bytes()));
+        // No key lies strictly between a key and its successor
+        let other = random_key(&mut rng);
+        assert!(!(key.as_bytes() < other.as_bytes() && other.as_bytes() < successor.as_slice()));
+    }
+}
+
+#[test]
+fn test_chunk_key_every_subchunk_index() {
+    for y in i8::MIN..=i8::MAX {
+        for dimension in [Dimension::Overworld, Dimension::Nether] {
+            let key = ChunkKey::subchunk(-1, 1, dimension, y);
+            assert_eq!(ChunkKey::parse(key.as_ref()), Ok(key));
+        }
+    }
+}
+
+#[test]
+fn test_chunk_key_parse_rejects() {
+    for len in [0, 1, 8, 11, 12, 15, 40] {
+        assert_eq!(
+            ChunkKey::parse(&vec![44; len]),
+            Err(KeyParseError::InvalidLength(len))
+        );
+    }
+
+    // Flat keys of chunk-key length have no known tag in the tag position
+    for key in [
+        &b"BiomeData"[..],
+        b"Overworld",
+        b"mobevents",
+        b"~local_player",
+    ] {
+        assert!(matches!(
+            ChunkKey::parse(key),
+            Err(KeyParseError::UnknownTag(_))
+        ));
+    }
+    assert!(ChunkKey::parse(b"scoreboard").is_err());
+
+    let mut key = ChunkKey::new(0, 0, Dimension::Overworld, RecordTag::SubChunkPrefix).encode();
+    assert_eq!(
+        ChunkKey::parse(&key),
+        Err(KeyParseError::MissingSubChunkIndex)
+    );
+    key[8] = RecordTag::Version.to_byte();
+    key.push(0);
+    assert_eq!(
+        ChunkKey::parse(&key),
+        Err(KeyParseError::UnexpectedSubChunkIndex(RecordTag::Version))
+    );
+
+    let mut dimensioned = ChunkKey::new(0, 0, Dimension::Nether, RecordTag::Data2D).encode();
+    dimensioned.push(3);
+    assert_eq!(
+        ChunkKey::parse(&dimensioned),
+        Err(KeyParseError::UnexpectedSubChunkIndex(RecordTag::Data2D))
+    );
+}
+
+#[test]
+fn test_chunk_key_dimensions() {
+    for (dimension, id) in [
+        (Dimension::Overworld, None),
+        (Dimension::Nether, Some(1)),
+        (Dimension::End, Some(2)),
+        (Dimension::Custom(-7), Some(-7)),
+    ] {
+        let key = ChunkKey::new(5, 6, dimension, RecordTag::Version);
+        match id {
+            None => assert_eq!(key.as_bytes().len(), 9),
+            Some(id) => assert_eq!(key.as_bytes()[8..12], i32::to_le_bytes(id)),
+        }
+        assert_eq!(
+            ChunkKey::parse(key.as_bytes()).unwrap().dimension(),
+            dimension
+        );
+    }
+
+    // Non-canonical custom ids are normalized
+    let nether = ChunkKey::new(5, 6, Dimension::Custom(1), RecordTag::Version);
+    assert_eq!(
+        nether,
+        ChunkKey::new(5, 6, Dimension::Nether, RecordTag::Version)
+    );
+    assert_eq!(
+        ChunkKey::new(5, 6, Dimension::Custom(0), RecordTag::Version)
+            .as_bytes()
+            .len(),
+        9
+    );
+
+    let mut explicit = vec![0u8; 12];
+    explicit.push(RecordTag::Version.to_byte());
+    assert_eq!(
+        ChunkKey::parse(&explicit),
+        Err(KeyParseError::ExplicitOverworld)
+    );
+}
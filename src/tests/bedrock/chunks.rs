@@ -0,0 +1,265 @@
+use super::{setup_world_db, write_synthetic_world};
+use crate::DB;
+use crate::bedrock::{ActorId, ChunkKey, CopyOptions, Dimension, RecordTag, copy_chunk};
+use crate::options::{ReadOptions, WriteOptions};
+use std::collections::BTreeSet;
+
+fn all_keys(db: &DB) -> BTreeSet<Vec<u8>> {
+    db.iter(&ReadOptions::new()).map(|(key, _)| key).collect()
+}
+
+/// Write one record of every tag, a few subchunks and two actors.
+fn write_full_chunk(db: &DB, x: i32, z: i32, dim: Dimension, first_actor: u64) {
+    let write_opts = WriteOptions::new();
+    for tag in RecordTag::ALL {
+        if tag != RecordTag::SubChunkPrefix {
+            db.put(ChunkKey::new(x, z, dim, tag), [tag.to_byte()], &write_opts)
+                .unwrap();
+        }
+    }
+    for y in -4..20 {
+        db.put_subchunk(x, z, dim, y, [y as u8], &write_opts)
+            .unwrap();
+    }
+    for id in [first_actor, first_actor + 1] {
+        db.put_actor(x, z, dim, ActorId(id.to_le_bytes()), b"actor", &write_opts)
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_delete_chunk() {
+    let (db, _temp_dir) = setup_world_db();
+    let write_opts = WriteOptions::new();
+
+    // Neighbours that must survive: the same position in other dimensions,
+    // adjacent chunks and the usual string keys
+    write_full_chunk(&db, 1, 2, Dimension::Nether, 100);
+    write_full_chunk(&db, 1, 3, Dimension::Overworld, 200);
+    write_full_chunk(&db, 1, 2, Dimension::Custom(0x2c), 300);
+    write_synthetic_world(&db, &[(2, 2, Dimension::Overworld), (1, 1, Dimension::End)]);
+    let survivors = all_keys(&db);
+
+    write_full_chunk(&db, 1, 2, Dimension::Overworld, 1);
+    // Leave a dangling id in the digest
+    db.delete(ActorId(2u64.to_le_bytes()).key(), &write_opts)
+        .unwrap();
+
+    let report = db
+        .delete_chunk(1, 2, Dimension::Overworld, &write_opts)
+        .unwrap();
+    assert_eq!(report.count(RecordTag::SubChunkPrefix), 24);
+    assert_eq!(report.count(RecordTag::Version), 1);
+    assert_eq!(report.count(RecordTag::LegacyVersion), 1);
+    assert_eq!(report.records.len(), RecordTag::ALL.len());
+    assert!(report.digest);
+    assert_eq!(report.actors, 1);
+    assert_eq!(report.total(), 23 + 24 + 1 + 1);
+    assert_eq!(all_keys(&db), survivors);
+
+    let again = db
+        .delete_chunk(1, 2, Dimension::Overworld, &write_opts)
+        .unwrap();
+    assert_eq!(again.total(), 0);
+
+    let nether = db
+        .delete_chunk(1, 2, Dimension::Nether, &write_opts)
+        .unwrap();
+    assert_eq!(nether.total(), 23 + 24 + 1 + 2);
+    assert!(
+        db.chunk_positions(Dimension::Nether, &ReadOptions::new())
+            .next()
+            .is_none()
+    );
+}
+
+/// Return the chunk's records with the coordinates stripped from the keys.
+fn chunk_records(db: &DB, x: i32, z: i32, dim: Dimension) -> Vec<(RecordTag, Option<i8>, Vec<u8>)> {
+    let mut records = Vec::new();
+    db.scan_chunk(x, z, dim, &ReadOptions::new(), |key, value| {
+        records.push((key.tag(), key.subchunk_index(), value.to_vec()));
+    })
+    .unwrap();
+    records
+}
+
+#[test]
+fn test_copy_chunk_same_db() {
+    let (db, _temp_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+    write_full_chunk(&db, 0, 0, Dimension::Overworld, 1);
+
+    let report = copy_chunk(
+        &db,
+        &db,
+        (0, 0, Dimension::Overworld),
+        (-7, 4, Dimension::Nether),
+        CopyOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(report.records, 23 + 24);
+    assert_eq!(report.actors, 2);
+    assert_eq!(report.replaced.total(), 0);
+    assert_eq!(
+        chunk_records(&db, -7, 4, Dimension::Nether),
+        chunk_records(&db, 0, 0, Dimension::Overworld)
+    );
+
+    // The copies got fresh ids, and the originals are untouched
+    let original = db
+        .actor_digest(0, 0, Dimension::Overworld, &read_opts)
+        .unwrap();
+    let copied = db
+        .actor_digest(-7, 4, Dimension::Nether, &read_opts)
+        .unwrap();
+    assert_eq!(copied.len(), 2);
+    assert!(copied.iter().all(|id| !original.contains(id)));
+    for id in copied {
+        assert_eq!(
+            db.actor_data(id, &read_opts).unwrap(),
+            Some(b"actor".to_vec())
+        );
+    }
+
+    assert!(
+        copy_chunk(
+            &db,
+            &db,
+            (0, 0, Dimension::Overworld),
+            (0, 0, Dimension::Overworld),
+            CopyOptions::default()
+        )
+        .is_err()
+    );
+    assert!(
+        copy_chunk(
+            &db,
+            &db,
+            (9, 9, Dimension::Overworld),
+            (10, 9, Dimension::Overworld),
+            CopyOptions::default()
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_copy_chunk_cross_db() {
+    let (src, _src_dir) = setup_world_db();
+    let (dst, _dst_dir) = setup_world_db();
+    write_full_chunk(&src, 3, 3, Dimension::End, 1);
+    // An existing actor in the destination with the id the copy would otherwise reuse
+    write_full_chunk(&dst, 50, 50, Dimension::End, 1);
+
+    let opts = CopyOptions {
+        entities: false,
+        block_entities: false,
+        ..Default::default()
+    };
+    let report = copy_chunk(
+        &src,
+        &dst,
+        (3, 3, Dimension::End),
+        (3, 3, Dimension::End),
+        opts,
+    )
+    .unwrap();
+    assert_eq!(report.records, 23 + 24 - 2);
+    assert_eq!(report.actors, 0);
+    let records = chunk_records(&dst, 3, 3, Dimension::End);
+    assert!(
+        records
+            .iter()
+            .all(|(tag, _, _)| *tag != RecordTag::Entity && *tag != RecordTag::BlockEntity)
+    );
+    assert!(
+        dst.actor_digest(3, 3, Dimension::End, &ReadOptions::new())
+            .unwrap()
+            .is_empty()
+    );
+
+    let report = copy_chunk(
+        &src,
+        &dst,
+        (3, 3, Dimension::End),
+        (4, 3, Dimension::End),
+        CopyOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(report.actors, 2);
+    let existing = dst
+        .actor_digest(50, 50, Dimension::End, &ReadOptions::new())
+        .unwrap();
+    let copied = dst
+        .actor_digest(4, 3, Dimension::End, &ReadOptions::new())
+        .unwrap();
+    assert!(copied.iter().all(|id| !existing.contains(id)));
+    assert_eq!(
+        chunk_records(&dst, 4, 3, Dimension::End),
+        chunk_records(&src, 3, 3, Dimension::End)
+    );
+}
+
+#[test]
+fn test_copy_chunk_overwrite() {
+    let (db, _temp_dir) = setup_world_db();
+    let write_opts = WriteOptions::new();
+    write_full_chunk(&db, 0, 0, Dimension::Overworld, 1);
+    db.put(
+        ChunkKey::new(1, 0, Dimension::Overworld, RecordTag::Version),
+        [1u8],
+        &write_opts,
+    )
+    .unwrap();
+    db.put_subchunk(1, 0, Dimension::Overworld, 100, b"stale", &write_opts)
+        .unwrap();
+    db.put_actor(
+        1,
+        0,
+        Dimension::Overworld,
+        ActorId([7; 8]),
+        b"old actor",
+        &write_opts,
+    )
+    .unwrap();
+    let before = all_keys(&db);
+
+    let err = copy_chunk(
+        &db,
+        &db,
+        (0, 0, Dimension::Overworld),
+        (1, 0, Dimension::Overworld),
+        CopyOptions::default(),
+    );
+    assert!(err.is_err());
+    assert_eq!(all_keys(&db), before);
+
+    let opts = CopyOptions {
+        overwrite: true,
+        ..Default::default()
+    };
+    let report = copy_chunk(
+        &db,
+        &db,
+        (0, 0, Dimension::Overworld),
+        (1, 0, Dimension::Overworld),
+        opts,
+    )
+    .unwrap();
+    assert_eq!(report.replaced.count(RecordTag::Version), 1);
+    assert_eq!(report.replaced.count(RecordTag::SubChunkPrefix), 1);
+    assert_eq!(report.replaced.actors, 1);
+    assert_eq!(
+        chunk_records(&db, 1, 0, Dimension::Overworld),
+        chunk_records(&db, 0, 0, Dimension::Overworld)
+    );
+    assert_eq!(
+        db.actor_data(ActorId([7; 8]), &ReadOptions::new()).unwrap(),
+        None
+    );
+    assert_eq!(
+        db.get_subchunk(1, 0, Dimension::Overworld, 100, &ReadOptions::new())
+            .unwrap(),
+        None
+    );
+}
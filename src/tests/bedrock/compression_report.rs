@@ -0,0 +1,118 @@
+use super::setup_world_db;
+use crate::DB;
+use crate::bedrock::{ChunkKey, Dimension, RecordTag};
+use crate::options::{Compression, Options, WriteOptions};
+
+/// Bytes that no compressor can shrink, from a xorshift generator.
+fn random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed | 1;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+#[test]
+fn test_compression_report_compressible_and_random() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = Options::new();
+    options.create_if_missing(true);
+    options.compression(Compression::No);
+    let db = DB::open(temp_dir.path().join("db"), &options).unwrap();
+
+    let write_opts = WriteOptions::new();
+    let mut data3d_bytes = 0;
+    for i in 0..300 {
+        let key = ChunkKey::new(i, 0, Dimension::Overworld, RecordTag::Data3D);
+        let value: Vec<u8> = b"biome".iter().copied().cycle().take(2048).collect();
+        data3d_bytes += (key.as_bytes().len() + value.len()) as u64;
+        db.put(key, value, &write_opts).unwrap();
+        db.put(
+            ChunkKey::subchunk(i, 0, Dimension::Overworld, 0),
+            random_bytes(i as u64, 2048),
+            &write_opts,
+        )
+        .unwrap();
+    }
+    db.put(b"~local_player", random_bytes(7, 100), &write_opts)
+        .unwrap();
+    db.compact_all().unwrap();
+
+    let report = db.compression_report().unwrap();
+    assert_eq!(report.compression, Compression::No);
+    assert_eq!(
+        report
+            .classes
+            .iter()
+            .map(|class| class.tag)
+            .collect::<Vec<_>>(),
+        vec![
+            Some(RecordTag::Data3D),
+            Some(RecordTag::SubChunkPrefix),
+            None
+        ]
+    );
+    assert_eq!(report.entries(), 601);
+
+    let data3d = report.class(Some(RecordTag::Data3D)).unwrap();
+    assert_eq!(data3d.entries, 300);
+    assert_eq!(data3d.logical_bytes, data3d_bytes);
+    assert_eq!(data3d.sampled_bytes, data3d_bytes);
+    let subchunks = report.class(Some(RecordTag::SubChunkPrefix)).unwrap();
+    assert_eq!(subchunks.entries, 300);
+
+    // Uncompressed tables take about as much as the data itself
+    for class in [data3d, subchunks] {
+        let ratio = class.ratio(Compression::No).unwrap();
+        assert!((0.9..1.2).contains(&ratio), "{:?}: {}", class.tag, ratio);
+    }
+    // "biome" repeated uses five byte values equally often
+    assert!(
+        (data3d.entropy - 5f64.log2()).abs() < 0.01,
+        "{}",
+        data3d.entropy
+    );
+    assert!(subchunks.entropy > 7.9, "{}", subchunks.entropy);
+
+    // The files on disk are split between the kinds of records
+    let attributed: u64 = report.classes.iter().map(|class| class.on_disk_bytes).sum();
+    assert!(report.on_disk_bytes - attributed <= report.classes.len() as u64);
+    assert!(subchunks.on_disk_bytes > data3d.on_disk_bytes / 2);
+
+    // Switching to zlib saves most of the compressible half, if the linked
+    // LevelDB was built with zlib at all
+    let compressible = data3d.ratio(Compression::ZlibRaw).unwrap();
+    let random = subchunks.ratio(Compression::ZlibRaw).unwrap();
+    assert!(random > 0.9, "{}", random);
+    if compressible < 0.5 {
+        let savings = report.estimated_savings(Compression::ZlibRaw);
+        assert!(savings > (data3d_bytes / 2) as i64, "{}", savings);
+        assert!(savings > report.estimated_savings(Compression::No));
+    } else {
+        assert!(compressible > 0.9, "{}", compressible);
+    }
+    assert_eq!(
+        report.estimated_bytes(Compression::ZlibRaw),
+        report
+            .classes
+            .iter()
+            .map(|class| class.estimated(Compression::ZlibRaw))
+            .sum::<u64>()
+    );
+}
+
+#[test]
+fn test_compression_report_empty_db() {
+    let (db, _temp_dir) = setup_world_db();
+
+    let report = db.compression_report().unwrap();
+    assert!(report.classes.is_empty());
+    assert_eq!(report.entries(), 0);
+    assert_eq!(report.logical_bytes(), 0);
+    assert_eq!(report.estimated_bytes(Compression::ZlibRaw), 0);
+    assert!(report.class(None).is_none());
+}
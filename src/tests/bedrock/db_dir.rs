@@ -0,0 +1,131 @@
+use std::fs;
+use std::path::Path;
+
+use crate::DB;
+use crate::bedrock::{DbDirIssue, validate_db_dir};
+use crate::options::{Options, WriteOptions};
+
+/// Create a real database at `path` and close it again.
+fn create_db(path: &Path) {
+    let options = Options::new();
+    options.create_if_missing(true);
+    let db = DB::open(path, &options).unwrap();
+    db.put(b"key", b"value", &WriteOptions::new()).unwrap();
+}
+
+fn manifest_name(path: &Path) -> String {
+    fs::read_to_string(path.join("CURRENT"))
+        .unwrap()
+        .trim_end()
+        .to_string()
+}
+
+#[test]
+fn test_validate_db_dir_accepts_database() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("db");
+    create_db(&db_path);
+    assert_eq!(validate_db_dir(&db_path), Ok(()));
+}
+
+#[test]
+fn test_validate_db_dir_not_a_directory() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let missing = temp_dir.path().join("missing");
+    assert_eq!(
+        validate_db_dir(&missing),
+        Err(DbDirIssue::NotADirectory(missing.clone()))
+    );
+    fs::write(&missing, b"").unwrap();
+    assert_eq!(
+        validate_db_dir(&missing),
+        Err(DbDirIssue::NotADirectory(missing))
+    );
+}
+
+#[test]
+fn test_validate_db_dir_world_root() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("db");
+    create_db(&db_path);
+    let issue = validate_db_dir(temp_dir.path()).unwrap_err();
+    assert_eq!(
+        issue,
+        DbDirIssue::WorldRoot {
+            db_path: db_path.clone()
+        }
+    );
+    assert!(
+        issue
+            .to_string()
+            .contains(&format!("did you mean {}", db_path.display())),
+        "{}",
+        issue
+    );
+
+    // A world root whose database is missing is still recognized by level.dat
+    let other = tempfile::tempdir().unwrap();
+    fs::write(other.path().join("level.dat"), b"").unwrap();
+    assert_eq!(
+        validate_db_dir(other.path()),
+        Err(DbDirIssue::WorldRoot {
+            db_path: other.path().join("db")
+        })
+    );
+}
+
+#[test]
+fn test_validate_db_dir_missing_current() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    assert_eq!(
+        validate_db_dir(temp_dir.path()),
+        Err(DbDirIssue::MissingCurrent)
+    );
+}
+
+#[test]
+fn test_validate_db_dir_invalid_current() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    create_db(temp_dir.path());
+    fs::write(temp_dir.path().join("CURRENT"), "../MANIFEST-000001\n").unwrap();
+    assert_eq!(
+        validate_db_dir(temp_dir.path()),
+        Err(DbDirIssue::InvalidCurrent(
+            "../MANIFEST-000001\n".to_string()
+        ))
+    );
+    fs::write(temp_dir.path().join("CURRENT"), "").unwrap();
+    assert!(matches!(
+        validate_db_dir(temp_dir.path()),
+        Err(DbDirIssue::InvalidCurrent(_))
+    ));
+}
+
+#[test]
+fn test_validate_db_dir_missing_manifest() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    create_db(temp_dir.path());
+    let manifest = manifest_name(temp_dir.path());
+    fs::remove_file(temp_dir.path().join(&manifest)).unwrap();
+    assert_eq!(
+        validate_db_dir(temp_dir.path()),
+        Err(DbDirIssue::MissingManifest(manifest))
+    );
+}
+
+#[test]
+fn test_validate_db_dir_no_data_files() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    create_db(temp_dir.path());
+    for entry in fs::read_dir(temp_dir.path()).unwrap() {
+        let path = entry.unwrap().path();
+        let ext = path.extension().and_then(|ext| ext.to_str());
+        if matches!(ext, Some("ldb" | "sst" | "log")) {
+            fs::remove_file(path).unwrap();
+        }
+    }
+    assert_eq!(
+        validate_db_dir(temp_dir.path()),
+        Err(DbDirIssue::NoDataFiles)
+    );
+}
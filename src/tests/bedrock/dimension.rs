@@ -0,0 +1,29 @@
+use crate::bedrock::Dimension;
+
+#[test]
+fn test_dimension_ids() {
+    for (dimension, id, name) in [
+        (Dimension::Overworld, 0, "overworld"),
+        (Dimension::Nether, 1, "nether"),
+        (Dimension::End, 2, "the_end"),
+        (Dimension::Custom(1000), 1000, "dimension_1000"),
+        (Dimension::Custom(-3), -3, "dimension_-3"),
+    ] {
+        assert_eq!(Dimension::parse(id), dimension);
+        assert_eq!(dimension.id(), id);
+        assert_eq!(dimension.to_string(), name);
+    }
+    assert_eq!(Dimension::default(), Dimension::Overworld);
+}
+
+#[test]
+fn test_dimension_encode_into() {
+    let mut buf = vec![0xAA];
+    Dimension::Overworld.encode_into(&mut buf);
+    assert_eq!(buf, [0xAA]);
+
+    Dimension::Nether.encode_into(&mut buf);
+    Dimension::End.encode_into(&mut buf);
+    Dimension::Custom(-2).encode_into(&mut buf);
+    assert_eq!(buf, [0xAA, 1, 0, 0, 0, 2, 0, 0, 0, 0xfe, 0xff, 0xff, 0xff]);
+}
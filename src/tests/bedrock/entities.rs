@@ -0,0 +1,201 @@
+use super::setup_world_db;
+use crate::DB;
+use crate::bedrock::nbt::{self, Value};
+use crate::bedrock::{ActorId, ChunkKey, Dimension, Entity, EntityStorage, RecordTag};
+use crate::options::{ReadOptions, WriteOptions};
+
+fn entity_nbt(identifier: &str, unique_id: i64) -> Value {
+    Value::Compound(vec![
+        (
+            "identifier".to_string(),
+            Value::String(identifier.to_string()),
+        ),
+        ("UniqueID".to_string(), Value::Long(unique_id)),
+        (
+            "Pos".to_string(),
+            Value::list(vec![
+                Value::Float(1.5),
+                Value::Float(64.0),
+                Value::Float(-3.5),
+            ]),
+        ),
+        ("Tags".to_string(), Value::list(Vec::new())),
+    ])
+}
+
+/// Store entities the way worlds before 1.18.30 did, in one tag 50 record.
+fn write_legacy(db: &DB, x: i32, z: i32, dim: Dimension, entities: &[Value]) {
+    let record: Vec<u8> = entities.iter().flat_map(nbt::encode).collect();
+    db.put(
+        ChunkKey::new(x, z, dim, RecordTag::Entity),
+        record,
+        &WriteOptions::new(),
+    )
+    .unwrap();
+}
+
+/// Store an entity the way newer worlds do, as an actor listed in the digest.
+fn write_actor(db: &DB, x: i32, z: i32, dim: Dimension, id: u64, entity: &Value) -> ActorId {
+    let id = ActorId(id.to_be_bytes());
+    db.put_actor(x, z, dim, id, nbt::encode(entity), &WriteOptions::new())
+        .unwrap();
+    id
+}
+
+#[test]
+fn test_entities_in_chunk_legacy() {
+    let (db, _temp_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+    let entities = [
+        entity_nbt("minecraft:cow", 1),
+        entity_nbt("minecraft:pig", 2),
+    ];
+    write_legacy(&db, 3, 4, Dimension::Overworld, &entities);
+
+    let found = db
+        .entities_in_chunk(3, 4, Dimension::Overworld, &read_opts)
+        .unwrap();
+    assert_eq!(
+        found,
+        entities
+            .iter()
+            .map(|nbt| Entity {
+                nbt: nbt.clone(),
+                format: EntityStorage::Legacy,
+            })
+            .collect::<Vec<_>>()
+    );
+    assert!(
+        db.entities_in_chunk(3, 4, Dimension::Nether, &read_opts)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_entities_in_chunk_actors_and_merged() {
+    let (db, _temp_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+    let zombie = entity_nbt("minecraft:zombie", 10);
+    let skeleton = entity_nbt("minecraft:skeleton", 11);
+    let zombie_id = write_actor(&db, -1, 2, Dimension::Nether, 7, &zombie);
+    let skeleton_id = write_actor(&db, -1, 2, Dimension::Nether, 8, &skeleton);
+
+    let found = db
+        .entities_in_chunk(-1, 2, Dimension::Nether, &read_opts)
+        .unwrap();
+    assert_eq!(
+        found,
+        vec![
+            Entity {
+                nbt: zombie.clone(),
+                format: EntityStorage::Actor(zombie_id),
+            },
+            Entity {
+                nbt: skeleton.clone(),
+                format: EntityStorage::Actor(skeleton_id),
+            },
+        ]
+    );
+
+    // A chunk caught between formats has both; a dangling digest entry is skipped
+    let strider = entity_nbt("minecraft:strider", 12);
+    write_legacy(
+        &db,
+        -1,
+        2,
+        Dimension::Nether,
+        std::slice::from_ref(&strider),
+    );
+    db.delete(skeleton_id.key(), &WriteOptions::new()).unwrap();
+    let found = db
+        .entities_in_chunk(-1, 2, Dimension::Nether, &read_opts)
+        .unwrap();
+    assert_eq!(
+        found,
+        vec![
+            Entity {
+                nbt: strider,
+                format: EntityStorage::Legacy,
+            },
+            Entity {
+                nbt: zombie,
+                format: EntityStorage::Actor(zombie_id),
+            },
+        ]
+    );
+
+    db.put(zombie_id.key(), [10, 0], &WriteOptions::new())
+        .unwrap();
+    let err = db
+        .entities_in_chunk(-1, 2, Dimension::Nether, &read_opts)
+        .unwrap_err();
+    assert!(err.contains(&zombie_id.to_string()), "{}", err);
+}
+
+#[test]
+fn test_all_entities() {
+    let (db, _temp_dir) = setup_world_db();
+    super::write_synthetic_world(&db, &[(0, 0, Dimension::Overworld)]);
+    let cow = entity_nbt("minecraft:cow", 1);
+    let sheep = entity_nbt("minecraft:sheep", 2);
+    let bee = entity_nbt("minecraft:bee", 3);
+    let blaze = entity_nbt("minecraft:blaze", 4);
+    write_legacy(
+        &db,
+        0,
+        0,
+        Dimension::Overworld,
+        &[cow.clone(), sheep.clone()],
+    );
+    let bee_id = write_actor(&db, 5, -5, Dimension::Overworld, 1, &bee);
+    write_actor(&db, 5, -5, Dimension::Nether, 2, &blaze);
+    // A malformed legacy record is reported and skipped
+    db.put(
+        ChunkKey::new(9, 9, Dimension::Overworld, RecordTag::Entity),
+        [1, 0],
+        &WriteOptions::new(),
+    )
+    .unwrap();
+
+    let read_opts = ReadOptions::new();
+    let (found, errors): (Vec<_>, Vec<_>) = db
+        .all_entities(Dimension::Overworld, &read_opts)
+        .partition(Result::is_ok);
+    let found: Vec<_> = found.into_iter().map(Result::unwrap).collect();
+    assert_eq!(
+        found,
+        vec![
+            (
+                (0, 0),
+                Entity {
+                    nbt: cow,
+                    format: EntityStorage::Legacy,
+                }
+            ),
+            (
+                (0, 0),
+                Entity {
+                    nbt: sheep,
+                    format: EntityStorage::Legacy,
+                }
+            ),
+            (
+                (5, -5),
+                Entity {
+                    nbt: bee,
+                    format: EntityStorage::Actor(bee_id),
+                }
+            ),
+        ]
+    );
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].as_ref().unwrap_err().contains("(9, 9)"));
+
+    let nether: Vec<_> = db
+        .all_entities(Dimension::Nether, &read_opts)
+        .map(|entry| entry.unwrap().1.nbt)
+        .collect();
+    assert_eq!(nether, vec![blaze]);
+    assert_eq!(db.all_entities(Dimension::End, &read_opts).count(), 0);
+}
@@ -0,0 +1,62 @@
+use super::setup_world_db;
+use crate::bedrock::{ChunkKey, Dimension, FinalizedState, RecordTag};
+use crate::options::{ReadOptions, WriteOptions};
+
+#[test]
+fn test_finalized_state_values() {
+    let known = [
+        (0, FinalizedState::NeedsInstaticking),
+        (1, FinalizedState::NeedsPopulation),
+        (2, FinalizedState::Done),
+    ];
+    for (value, state) in known {
+        assert_eq!(FinalizedState::from_u32(value), state);
+        assert_eq!(state.to_u32(), value);
+    }
+    assert_eq!(FinalizedState::from_u32(3), FinalizedState::Unknown(3));
+    assert_eq!(FinalizedState::Unknown(u32::MAX).to_u32(), u32::MAX);
+}
+
+#[test]
+fn test_finalized_state_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    let dim = Dimension::Nether;
+    assert_eq!(db.finalized_state(4, -9, dim, &read_opts).unwrap(), None);
+
+    for state in [
+        FinalizedState::NeedsInstaticking,
+        FinalizedState::NeedsPopulation,
+        FinalizedState::Done,
+        FinalizedState::Unknown(0x0102_0304),
+    ] {
+        db.set_finalized_state(4, -9, dim, state, &write_opts)
+            .unwrap();
+        assert_eq!(
+            db.finalized_state(4, -9, dim, &read_opts).unwrap(),
+            Some(state)
+        );
+    }
+    // Stored as a little-endian u32
+    assert_eq!(
+        db.get(
+            ChunkKey::new(4, -9, dim, RecordTag::FinalizedState),
+            &read_opts
+        )
+        .unwrap(),
+        Some(vec![4, 3, 2, 1])
+    );
+    assert_eq!(
+        db.finalized_state(4, -9, Dimension::Overworld, &read_opts)
+            .unwrap(),
+        None
+    );
+
+    db.put(
+        ChunkKey::new(0, 0, dim, RecordTag::FinalizedState),
+        [2u8],
+        &write_opts,
+    )
+    .unwrap();
+    assert!(db.finalized_state(0, 0, dim, &read_opts).is_err());
+}
@@ -0,0 +1,108 @@
+use crate::bedrock::{
+    ActorId, ChunkKey, Dimension, KeyDescription, KnownKey, PlayerKey, RecordTag, describe_key,
+};
+
+fn describe(key: impl AsRef<[u8]>) -> String {
+    describe_key(key.as_ref()).to_string()
+}
+
+#[test]
+fn test_describe_chunk_keys() {
+    assert_eq!(
+        describe(ChunkKey::subchunk(12, -7, Dimension::Overworld, 3)),
+        "chunk(12,-7) overworld SubChunkPrefix y=3"
+    );
+    assert_eq!(
+        describe(ChunkKey::subchunk(0, 0, Dimension::Nether, -4)),
+        "chunk(0,0) nether SubChunkPrefix y=-4"
+    );
+    assert_eq!(
+        describe(ChunkKey::new(-1, 5, Dimension::End, RecordTag::Version)),
+        "chunk(-1,5) the_end Version"
+    );
+    assert_eq!(
+        describe(ChunkKey::new(2, 2, Dimension::Custom(7), RecordTag::Data3D)),
+        "chunk(2,2) dimension_7 Data3D"
+    );
+    assert_eq!(
+        describe_key(
+            ChunkKey::new(3, 4, Dimension::Overworld, RecordTag::LegacyTerrain).as_bytes()
+        ),
+        KeyDescription::ChunkRecord {
+            pos: (3, 4),
+            dim: Dimension::Overworld,
+            tag: RecordTag::LegacyTerrain,
+            subchunk: None
+        }
+    );
+}
+
+#[test]
+fn test_describe_string_keys() {
+    assert_eq!(describe(b"~local_player"), "player ~local_player");
+    assert_eq!(
+        describe_key(b"player_server_0a1b"),
+        KeyDescription::Player(PlayerKey::Server("0a1b".to_string()))
+    );
+    assert_eq!(describe(b"player_0a1b"), "player player_0a1b");
+    // 9 bytes ending in a tag byte, so it would also parse as a chunk key
+    assert_eq!(describe(b"map_12345"), "map 12345");
+    assert_eq!(describe(b"map_-3"), "map -3");
+    assert_eq!(
+        describe(b"VILLAGE_Overworld_5b4b1c0e_POI"),
+        "village 5b4b1c0e overworld POI"
+    );
+    assert_eq!(describe(b"VILLAGE_5b4b1c0e_INFO"), "village 5b4b1c0e INFO");
+    assert_eq!(
+        describe(b"structuretemplate_mystructure:house"),
+        "structure mystructure:house"
+    );
+    assert_eq!(describe(b"mobevents"), "known mobevents");
+    assert_eq!(
+        describe_key(b"LevelChunkMetaDataDictionary"),
+        KeyDescription::KnownFlat(KnownKey::LevelChunkMetaDataDictionary)
+    );
+}
+
+#[test]
+fn test_describe_actor_keys() {
+    let mut digest = b"digp".to_vec();
+    digest.extend_from_slice(&1i32.to_le_bytes());
+    digest.extend_from_slice(&(-2i32).to_le_bytes());
+    assert_eq!(describe(&digest), "actor digest chunk(1,-2) overworld");
+    digest.extend_from_slice(&1i32.to_le_bytes());
+    assert_eq!(describe(&digest), "actor digest chunk(1,-2) nether");
+
+    let id = ActorId([0, 0, 0, 1, 0, 0, 0, 5]);
+    assert_eq!(describe(id.key()), "actor 0000000100000005");
+    assert_eq!(describe_key(&id.key()), KeyDescription::ActorData(id));
+}
+
+#[test]
+fn test_describe_unknown_keys() {
+    assert_eq!(describe(b""), "unknown (0 bytes) ");
+    assert_eq!(
+        describe([0xde, 0xad, 0xbe, 0xef]),
+        "unknown (4 bytes) deadbeef"
+    );
+    // Prefixes of known families without a valid rest
+    assert_eq!(describe(b"map_01"), "unknown (6 bytes) 6d61705f3031");
+    for key in [
+        &b"actorprefix\x01"[..],
+        b"structuretemplate_:x",
+        b"VILLAGE__INFO",
+    ] {
+        assert!(matches!(describe_key(key), KeyDescription::Unknown(_)));
+    }
+    // A 9-byte key with an unknown tag byte
+    assert_eq!(
+        describe([1, 0, 0, 0, 2, 0, 0, 0, 0xff]),
+        "unknown (9 bytes) 0100000002000000ff"
+    );
+
+    let long = vec![0xabu8; 40];
+    assert_eq!(
+        describe(&long),
+        format!("unknown (40 bytes) {}...", "ab".repeat(32))
+    );
+}
@@ -0,0 +1,34 @@
+use super::setup_world_db;
+use crate::bedrock::{KnownKey, PlayerKey, keys};
+use crate::options::{ReadOptions, WriteOptions};
+use std::collections::HashSet;
+
+#[test]
+fn test_known_keys_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    let raw: HashSet<&[u8]> = KnownKey::all().map(KnownKey::key).collect();
+    assert_eq!(raw.len(), KnownKey::all().count());
+
+    for key in KnownKey::all() {
+        assert_eq!(db.get_known(key, &read_opts).unwrap(), None);
+        db.put_known(key, key.to_string(), &write_opts).unwrap();
+        assert_eq!(KnownKey::parse(key.key()), Some(key));
+    }
+    for key in KnownKey::all() {
+        let expected = key.to_string().into_bytes();
+        assert_eq!(
+            db.get_known(key, &read_opts).unwrap(),
+            Some(expected.clone())
+        );
+        assert_eq!(db.get(key.key(), &read_opts).unwrap(), Some(expected));
+    }
+
+    assert_eq!(
+        db.get(keys::MOB_EVENTS, &read_opts).unwrap(),
+        Some(b"mobevents".to_vec())
+    );
+    assert_eq!(KnownKey::LocalPlayer.key(), PlayerKey::Local.encode());
+    assert_eq!(KnownKey::parse(b"Mobevents"), None);
+}
@@ -0,0 +1,102 @@
+use super::setup_world_db;
+use crate::bedrock::{ChunkKey, Dimension, LegacyTerrain, LegacyTerrainError, RecordTag};
+use crate::options::{ReadOptions, WriteOptions};
+
+fn synthetic_record() -> Vec<u8> {
+    let mut record = vec![0u8; 83200];
+    // Block (1, 5, 2) has index (1 * 16 + 2) * 128 + 5 = 2309
+    record[2309] = 17;
+    // Odd index: high nibbles
+    record[32768 + 2309 / 2] = 0x30;
+    record[49152 + 2309 / 2] = 0xF0;
+    record[65536 + 2309 / 2] = 0x70;
+    // Block (0, 0, 0), index 0: low nibbles
+    record[0] = 7;
+    record[32768] = 0x02;
+    record[49152] = 0x0A;
+    // Column (3, 4) has index 4 * 16 + 3 = 67
+    record[81920 + 67] = 64;
+    record[82176 + 67 * 4..82176 + 68 * 4].copy_from_slice(&[21, 0x10, 0x80, 0x20]);
+    record
+}
+
+#[test]
+fn test_legacy_terrain_parse_synthetic() {
+    let record = synthetic_record();
+    let terrain = LegacyTerrain::parse(&record).unwrap();
+
+    assert_eq!(terrain.block_id_at(1, 5, 2), 17);
+    assert_eq!(terrain.block_data_at(1, 5, 2), 3);
+    assert_eq!(terrain.sky_light_at(1, 5, 2), 15);
+    assert_eq!(terrain.block_light_at(1, 5, 2), 7);
+
+    assert_eq!(terrain.block_id_at(0, 0, 0), 7);
+    assert_eq!(terrain.block_data_at(0, 0, 0), 2);
+    assert_eq!(terrain.sky_light_at(0, 0, 0), 10);
+    assert_eq!(terrain.block_light_at(0, 0, 0), 0);
+    // The neighbour sharing the nibble bytes of (0, 0, 0)
+    assert_eq!(terrain.block_data_at(0, 1, 0), 0);
+
+    assert_eq!(terrain.height_at(3, 4), 64);
+    assert_eq!(terrain.height_at(4, 3), 0);
+    assert_eq!(terrain.biome_at(3, 4), 21);
+    assert_eq!(terrain.grass_color_at(3, 4), [0x10, 0x80, 0x20]);
+    assert_eq!(terrain.as_bytes(), &record[..]);
+}
+
+#[test]
+fn test_legacy_terrain_invalid_length() {
+    let mut record = synthetic_record();
+    assert_eq!(
+        LegacyTerrain::parse(&record[..83199]),
+        Err(LegacyTerrainError::InvalidLength(83199))
+    );
+    record.push(0);
+    assert_eq!(
+        LegacyTerrain::parse(&record),
+        Err(LegacyTerrainError::InvalidLength(83201))
+    );
+}
+
+#[test]
+#[should_panic(expected = "outside the chunk")]
+fn test_legacy_terrain_out_of_range() {
+    let terrain = LegacyTerrain::parse(&synthetic_record()).unwrap();
+    terrain.block_id_at(0, 128, 0);
+}
+
+#[test]
+fn test_db_legacy_terrain() {
+    let (db, _temp_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+    let write_opts = WriteOptions::new();
+
+    assert_eq!(
+        db.legacy_terrain(0, 0, Dimension::Overworld, &read_opts)
+            .unwrap(),
+        None
+    );
+
+    db.put(
+        ChunkKey::new(-2, 9, Dimension::Overworld, RecordTag::LegacyTerrain),
+        synthetic_record(),
+        &write_opts,
+    )
+    .unwrap();
+    let terrain = db
+        .legacy_terrain(-2, 9, Dimension::Overworld, &read_opts)
+        .unwrap()
+        .unwrap();
+    assert_eq!(terrain.block_id_at(1, 5, 2), 17);
+
+    db.put(
+        ChunkKey::new(1, 1, Dimension::Overworld, RecordTag::LegacyTerrain),
+        [0u8; 100],
+        &write_opts,
+    )
+    .unwrap();
+    let err = db
+        .legacy_terrain(1, 1, Dimension::Overworld, &read_opts)
+        .unwrap_err();
+    assert!(err.contains("100 bytes"), "{}", err);
+}
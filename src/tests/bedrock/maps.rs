@@ -0,0 +1,42 @@
+use super::setup_world_db;
+use crate::options::{ReadOptions, WriteOptions};
+
+#[test]
+fn test_map_ids_and_max() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    assert_eq!(db.map_ids(&read_opts).count(), 0);
+    assert_eq!(db.max_map_id(&read_opts).unwrap(), None);
+
+    for key in [
+        "map_abc",
+        "map_",
+        "map_12x",
+        "map_+5",
+        "map_007",
+        "map_-0",
+        "map_99999999999999999999",
+        "mapx_5",
+    ] {
+        db.put(key, b"bogus", &write_opts).unwrap();
+    }
+    assert_eq!(db.map_ids(&read_opts).count(), 0);
+    assert_eq!(db.max_map_id(&read_opts).unwrap(), None);
+
+    for id in [-4294967295i64, -1, 0, 9, 10, i64::MAX, i64::MIN] {
+        db.put_map(id, id.to_string(), &write_opts).unwrap();
+    }
+    let mut ids: Vec<i64> = db.map_ids(&read_opts).collect();
+    ids.sort();
+    assert_eq!(ids, [i64::MIN, -4294967295, -1, 0, 9, 10, i64::MAX]);
+    assert_eq!(db.max_map_id(&read_opts).unwrap(), Some(i64::MAX));
+
+    assert_eq!(db.get(b"map_-1", &read_opts).unwrap(), Some(b"-1".to_vec()));
+    assert_eq!(db.get_map(10, &read_opts).unwrap(), Some(b"10".to_vec()));
+    assert_eq!(db.get_map(11, &read_opts).unwrap(), None);
+
+    db.delete(format!("map_{}", i64::MAX), &write_opts).unwrap();
+    // "map_9" sorts after "map_10", but 10 is the larger id
+    assert_eq!(db.max_map_id(&read_opts).unwrap(), Some(10));
+}
@@ -0,0 +1,197 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use super::world_dir::write_world;
+use crate::bedrock::nbt::Value;
+use crate::bedrock::{MCWORLD_COMPRESSION, WorldDir};
+use crate::options::{ReadOptions, WriteOptions};
+
+/// Zip every file under `dir` into `archive`, with `prefix` in front of
+/// every entry name.
+fn zip_dir(dir: &Path, archive: &Path, prefix: &str) {
+    fn add(zip: &mut ZipWriter<File>, base: &Path, dir: &Path, prefix: &str) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                add(zip, base, &path, prefix);
+            } else {
+                let name = path.strip_prefix(base).unwrap().to_str().unwrap();
+                zip.start_file(format!("{}{}", prefix, name), SimpleFileOptions::default())
+                    .unwrap();
+                zip.write_all(&fs::read(&path).unwrap()).unwrap();
+            }
+        }
+    }
+
+    let mut zip = ZipWriter::new(File::create(archive).unwrap());
+    add(&mut zip, dir, dir, prefix);
+    zip.finish().unwrap();
+}
+
+/// Build a `.mcworld` of a minimal world and return its path.
+fn build_mcworld(temp_dir: &Path, prefix: &str) -> std::path::PathBuf {
+    let world = temp_dir.join("world");
+    fs::create_dir(&world).unwrap();
+    write_world(&world, Some("Zipped World"));
+    let archive = temp_dir.join("world.mcworld");
+    zip_dir(&world, &archive, prefix);
+    fs::remove_dir_all(&world).unwrap();
+    archive
+}
+
+#[test]
+fn test_open_mcworld_flat() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let archive = build_mcworld(temp_dir.path(), "");
+
+    let world = WorldDir::open_mcworld(&archive).unwrap();
+    assert_eq!(world.name(), Some("Zipped World"));
+    assert_eq!(
+        world
+            .db()
+            .get(b"~local_player", &ReadOptions::new())
+            .unwrap(),
+        Some(b"player".to_vec())
+    );
+    world
+        .db()
+        .put(b"new", b"value", &WriteOptions::new())
+        .unwrap();
+
+    // The extraction is removed with the world
+    let extracted = world.path().to_path_buf();
+    assert!(extracted.join("db").join("CURRENT").is_file());
+    drop(world);
+    assert!(!extracted.exists());
+}
+
+#[test]
+fn test_open_mcworld_single_folder() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let archive = build_mcworld(temp_dir.path(), "My World/");
+
+    let target = temp_dir.path().join("extracted");
+    let world = WorldDir::open_mcworld_in(&archive, &target).unwrap();
+    assert_eq!(world.path(), target.join("My World"));
+    assert_eq!(world.name(), Some("Zipped World"));
+    drop(world);
+
+    // A caller-provided directory is kept, and must be empty to be reused
+    assert!(target.join("My World").join("level.dat").is_file());
+    assert!(WorldDir::open_mcworld_in(&archive, &target).is_err());
+}
+
+#[test]
+fn test_open_mcworld_rejects_archive_without_db() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let archive = temp_dir.path().join("empty.mcworld");
+    let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+    zip.start_file("level.dat", SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(b"not a world").unwrap();
+    zip.start_file("db/000003.log", SimpleFileOptions::default())
+        .unwrap();
+    zip.finish().unwrap();
+    let err = WorldDir::open_mcworld(&archive).err().unwrap();
+    assert!(err.contains("has no db/CURRENT"), "{}", err);
+
+    // Two top-level folders are not a world either
+    let archive = temp_dir.path().join("two.mcworld");
+    let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+    zip.start_file("a/db/CURRENT", SimpleFileOptions::default())
+        .unwrap();
+    zip.start_file("b/level.dat", SimpleFileOptions::default())
+        .unwrap();
+    zip.finish().unwrap();
+    assert!(WorldDir::open_mcworld(&archive).is_err());
+
+    let not_zip = temp_dir.path().join("plain.mcworld");
+    fs::write(&not_zip, b"plain text").unwrap();
+    let err = WorldDir::open_mcworld(&not_zip).err().unwrap();
+    assert!(err.contains("not a zip archive"), "{}", err);
+}
+
+/// Read every entry of a database in key order.
+fn entries(world: &WorldDir) -> Vec<(Vec<u8>, Vec<u8>)> {
+    world.db().iter(&ReadOptions::new()).collect()
+}
+
+#[test]
+fn test_save_mcworld_round_trip() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("world");
+    fs::create_dir(&path).unwrap();
+    write_world(&path, Some("Saved World"));
+    fs::write(path.join("world_icon.jpeg"), b"\xff\xd8icon").unwrap();
+
+    let mut world = WorldDir::open(&path).unwrap();
+    for i in 0..1000u32 {
+        world
+            .db()
+            .put(&i.to_be_bytes(), &[i as u8; 100], &WriteOptions::new())
+            .unwrap();
+    }
+    // Unsaved level.dat edits are included
+    if let Value::Compound(fields) = world.level_dat_mut() {
+        fields.push(("Saved".to_string(), Value::Byte(1)));
+    }
+    let archive = temp_dir.path().join("saved.mcworld");
+    world.save_mcworld(&archive).unwrap();
+    assert!(!temp_dir.path().join("saved.mcworld.tmp").exists());
+
+    // The world sits at the archive root, every file deflated
+    let mut zip = ZipArchive::new(File::open(&archive).unwrap()).unwrap();
+    let names: Vec<String> = zip.file_names().map(str::to_string).collect();
+    assert_eq!(
+        &names[..4],
+        ["level.dat", "levelname.txt", "world_icon.jpeg", "db/"]
+    );
+    assert!(names.contains(&"db/CURRENT".to_string()));
+    assert!(names[4..].iter().all(|name| name.starts_with("db/")));
+    for i in 0..zip.len() {
+        let entry = zip.by_index(i).unwrap();
+        if entry.is_file() {
+            assert_eq!(entry.compression(), MCWORLD_COMPRESSION, "{}", entry.name());
+        }
+    }
+
+    let reopened = WorldDir::open_mcworld(&archive).unwrap();
+    assert_eq!(reopened.name(), Some("Saved World"));
+    assert_eq!(reopened.level_dat(), world.level_dat());
+    assert_eq!(entries(&reopened), entries(&world));
+    assert_eq!(
+        fs::read(reopened.path().join("world_icon.jpeg")).unwrap(),
+        b"\xff\xd8icon"
+    );
+}
+
+#[test]
+fn test_save_mcworld_without_optional_files() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("world");
+    fs::create_dir(&path).unwrap();
+    write_world(&path, None);
+
+    let world = WorldDir::open(&path).unwrap();
+    let archive = temp_dir.path().join("saved.mcworld");
+    fs::write(&archive, b"previous").unwrap();
+    world.save_mcworld(&archive).unwrap();
+
+    let zip = ZipArchive::new(File::open(&archive).unwrap()).unwrap();
+    let names: Vec<&str> = zip.file_names().collect();
+    assert!(!names.contains(&"levelname.txt"));
+    assert!(!names.contains(&"world_icon.jpeg"));
+
+    let reopened = WorldDir::open_mcworld(&archive).unwrap();
+    assert_eq!(entries(&reopened), entries(&world));
+
+    // A failed save leaves the existing archive alone
+    let missing = temp_dir.path().join("missing").join("saved.mcworld");
+    assert!(world.save_mcworld(&missing).is_err());
+    assert!(!missing.exists());
+}
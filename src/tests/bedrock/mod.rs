@@ -0,0 +1,109 @@
+mod actor_remap;
+mod actors;
+mod biome_data;
+mod biome_histogram;
+#[cfg(feature = "nbt")]
+mod block_entities;
+mod chunk_blob;
+mod chunk_key;
+mod chunks;
+mod compression_report;
+mod db_dir;
+mod dimension;
+#[cfg(feature = "nbt")]
+mod entities;
+mod finalized_state;
+mod key_description;
+mod keys;
+mod legacy_terrain;
+mod maps;
+#[cfg(feature = "mcworld")]
+mod mcworld;
+#[cfg(feature = "nbt")]
+mod nbt;
+mod orphans;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod players;
+mod record;
+#[cfg(feature = "nbt")]
+mod scoreboard;
+mod structures;
+#[cfg(feature = "subchunk")]
+mod subchunk;
+#[cfg(feature = "nbt")]
+mod ticks;
+mod villages;
+mod wipe;
+mod world;
+mod world_bounds;
+#[cfg(feature = "nbt")]
+mod world_dir;
+mod world_format;
+#[cfg(feature = "nbt")]
+mod world_records;
+
+use crate::DB;
+use crate::bedrock::{ChunkKey, Dimension, RecordTag};
+use crate::options::{Options, WriteOptions};
+use tempfile::TempDir;
+
+/// Open an empty database to stand in for a world's `db` directory.
+pub(crate) fn setup_world_db() -> (DB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = Options::new();
+    options.create_if_missing(true);
+    let db = DB::open(temp_dir.path().join("db"), &options).unwrap();
+    (db, temp_dir)
+}
+
+/// Write a few records for every chunk in `chunks`, plus the string keys a
+/// real world keeps next to them.
+pub(crate) fn write_synthetic_world(db: &DB, chunks: &[(i32, i32, Dimension)]) {
+    let write_opts = WriteOptions::new();
+    for &(x, z, dim) in chunks {
+        for tag in [
+            RecordTag::Version,
+            RecordTag::Data3D,
+            RecordTag::FinalizedState,
+        ] {
+            db.put(ChunkKey::new(x, z, dim, tag), [0u8], &write_opts)
+                .unwrap();
+        }
+        for y in [-4, 0, 3] {
+            db.put_subchunk(x, z, dim, y, [9u8], &write_opts).unwrap();
+        }
+    }
+    for key in [
+        &b"BiomeData"[..],
+        b"~local_player",
+        b"player_server_0a1b2c3d",
+        b"map_-12345",
+        b"map_5",
+        b"portals",
+        b"Overworld",
+        b"mobevents",
+    ] {
+        db.put(key, b"{}", &write_opts).unwrap();
+    }
+}
+
+/// Small deterministic xorshift generator for property-style tests.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng(seed | 1)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub(crate) fn next_i32(&mut self) -> i32 {
+        self.next_u64() as i32
+    }
+}
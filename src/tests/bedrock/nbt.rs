@@ -0,0 +1,154 @@
+use crate::bedrock::nbt::{self, NbtError, Value};
+
+/// A block state as the game writes it, assembled by hand.
+fn stone_bytes() -> Vec<u8> {
+    let mut bytes = vec![10, 0, 0];
+    bytes.extend_from_slice(&[8, 4, 0]);
+    bytes.extend_from_slice(b"name");
+    bytes.extend_from_slice(&[15, 0]);
+    bytes.extend_from_slice(b"minecraft:stone");
+    bytes.extend_from_slice(&[10, 6, 0]);
+    bytes.extend_from_slice(b"states");
+    bytes.push(0);
+    bytes.extend_from_slice(&[3, 7, 0]);
+    bytes.extend_from_slice(b"version");
+    bytes.extend_from_slice(&18_100_737i32.to_le_bytes());
+    bytes.push(0);
+    bytes
+}
+
+fn every_tag() -> Value {
+    Value::Compound(vec![
+        ("byte".to_string(), Value::Byte(-3)),
+        ("short".to_string(), Value::Short(-300)),
+        ("int".to_string(), Value::Int(70_000)),
+        ("long".to_string(), Value::Long(-1 << 40)),
+        ("float".to_string(), Value::Float(0.5)),
+        ("double".to_string(), Value::Double(-2.25)),
+        ("bytes".to_string(), Value::ByteArray(vec![0, 255, 7])),
+        ("string".to_string(), Value::String("héllo".to_string())),
+        (
+            "list".to_string(),
+            Value::list(vec![Value::Short(1), Value::Short(2)]),
+        ),
+        ("empty".to_string(), Value::list(Vec::new())),
+        (
+            "nested".to_string(),
+            Value::Compound(vec![("inner".to_string(), Value::Byte(1))]),
+        ),
+        ("ints".to_string(), Value::IntArray(vec![-1, 0, 1])),
+        (
+            "longs".to_string(),
+            Value::LongArray(vec![i64::MIN, i64::MAX]),
+        ),
+    ])
+}
+
+#[test]
+fn test_decode_block_state() {
+    let value = nbt::decode(&stone_bytes()).unwrap();
+    assert_eq!(
+        value.get("name").and_then(Value::as_str),
+        Some("minecraft:stone")
+    );
+    assert_eq!(value.get("states"), Some(&Value::Compound(Vec::new())));
+    assert_eq!(
+        value.get("version").and_then(Value::as_i64),
+        Some(18_100_737)
+    );
+    assert_eq!(value.get("missing"), None);
+    assert_eq!(nbt::encode(&value), stone_bytes());
+}
+
+#[test]
+fn test_round_trip_every_tag() {
+    let value = every_tag();
+    let bytes = nbt::encode(&value);
+    assert_eq!(nbt::decode(&bytes).unwrap(), value);
+
+    // Concatenated roots, as in block entity records
+    let mut both = bytes.clone();
+    nbt::encode_into(&Value::Int(5), &mut both);
+    assert_eq!(
+        nbt::decode_all(&both).unwrap(),
+        vec![value.clone(), Value::Int(5)]
+    );
+    assert_eq!(nbt::decode_prefix(&both).unwrap(), (value, bytes.len()));
+    assert_eq!(
+        nbt::decode(&both),
+        Err(NbtError::TrailingBytes {
+            offset: bytes.len()
+        })
+    );
+    assert_eq!(nbt::decode_all(&[]).unwrap(), Vec::new());
+}
+
+#[test]
+fn test_insert_and_get_mut() {
+    let mut value = nbt::decode(&stone_bytes()).unwrap();
+    assert_eq!(
+        value.insert("version", Value::Int(1)),
+        Some(Value::Int(18_100_737))
+    );
+    assert_eq!(value.insert("extra", Value::Byte(1)), None);
+    *value.get_mut("name").unwrap() = Value::String("minecraft:dirt".to_string());
+
+    let names: Vec<&str> = value
+        .as_compound()
+        .unwrap()
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(names, ["name", "states", "version", "extra"]);
+    assert_eq!(value.get("name").unwrap().as_str(), Some("minecraft:dirt"));
+    assert_eq!(Value::Int(1).get_mut("name"), None);
+}
+
+#[test]
+fn test_decode_errors() {
+    let bytes = stone_bytes();
+    for len in 0..bytes.len() {
+        assert!(
+            matches!(
+                nbt::decode(&bytes[..len]),
+                Err(NbtError::UnexpectedEnd { .. })
+            ),
+            "prefix of {} bytes",
+            len
+        );
+    }
+
+    assert_eq!(
+        nbt::decode(&[13, 0, 0]),
+        Err(NbtError::InvalidTag { tag: 13, offset: 0 })
+    );
+    assert_eq!(
+        nbt::decode(&[0]),
+        Err(NbtError::InvalidTag { tag: 0, offset: 0 })
+    );
+    assert_eq!(
+        nbt::decode(&[8, 0, 0, 2, 0, 0xC3, 0x28]),
+        Err(NbtError::InvalidUtf8 { offset: 3 })
+    );
+    assert_eq!(
+        nbt::decode(&[7, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF]),
+        Err(NbtError::NegativeLength { offset: 3 })
+    );
+    // A non-empty list of End tags
+    assert_eq!(
+        nbt::decode(&[9, 0, 0, 0, 1, 0, 0, 0]),
+        Err(NbtError::InvalidTag { tag: 0, offset: 3 })
+    );
+    // A huge array length must not be trusted for the allocation
+    assert!(matches!(
+        nbt::decode(&[12, 0, 0, 0xFF, 0xFF, 0xFF, 0x7F]),
+        Err(NbtError::UnexpectedEnd { .. })
+    ));
+
+    // Lists of lists, each holding one element
+    let mut record = vec![9, 0, 0];
+    for _ in 0..600 {
+        record.extend_from_slice(&[9, 1, 0, 0, 0]);
+    }
+    assert_eq!(nbt::decode(&record), Err(NbtError::TooDeep));
+}
@@ -0,0 +1,154 @@
+use super::setup_world_db;
+use crate::DB;
+use crate::bedrock::{ActorId, ChunkKey, Dimension, OrphanReport, RecordTag};
+use crate::options::{ReadOptions, WriteOptions};
+
+/// Write one record of every orphan class next to records that are in use.
+fn write_world(db: &DB) {
+    let write_opts = WriteOptions::new();
+    // A complete chunk, and a legacy chunk versioned by `v`
+    db.put(
+        ChunkKey::new(0, 0, Dimension::Overworld, RecordTag::Version),
+        [40],
+        &write_opts,
+    )
+    .unwrap();
+    db.put_subchunk(0, 0, Dimension::Overworld, 0, [9], &write_opts)
+        .unwrap();
+    db.put(
+        ChunkKey::new(1, 0, Dimension::Nether, RecordTag::LegacyVersion),
+        [2],
+        &write_opts,
+    )
+    .unwrap();
+    db.put_subchunk(1, 0, Dimension::Nether, 2, [9], &write_opts)
+        .unwrap();
+    // Subchunks whose chunk lost its version; the Overworld chunk at
+    // (1, 0) does not borrow the Nether chunk's version
+    db.put_subchunk(5, 5, Dimension::Overworld, 1, [9], &write_opts)
+        .unwrap();
+    db.put_subchunk(1, 0, Dimension::Overworld, -2, [9], &write_opts)
+        .unwrap();
+
+    // A listed actor and one whose digest entry is gone
+    db.put_actor(
+        0,
+        0,
+        Dimension::Overworld,
+        ActorId([1; 8]),
+        b"",
+        &write_opts,
+    )
+    .unwrap();
+    db.put(ActorId([2; 8]).key(), b"", &write_opts).unwrap();
+}
+
+#[test]
+fn test_find_and_remove_orphans() {
+    let (db, _temp_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+    assert!(db.find_orphans(&read_opts).unwrap().is_empty());
+
+    write_world(&db);
+    let report = db.find_orphans(&read_opts).unwrap();
+    assert_eq!(report.actors, vec![ActorId([2; 8])]);
+    assert_eq!(
+        report.subchunks,
+        vec![
+            ChunkKey::subchunk(1, 0, Dimension::Overworld, -2),
+            ChunkKey::subchunk(5, 5, Dimension::Overworld, 1),
+        ]
+    );
+    assert_eq!(report.total(), 3);
+
+    assert_eq!(db.remove_orphans(&report, &WriteOptions::new()).unwrap(), 3);
+    assert_eq!(
+        db.find_orphans(&read_opts).unwrap(),
+        OrphanReport::default()
+    );
+    assert!(
+        db.actor_data(ActorId([1; 8]), &read_opts)
+            .unwrap()
+            .is_some()
+    );
+    assert!(
+        db.get_subchunk(1, 0, Dimension::Nether, 2, &read_opts)
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[test]
+fn test_find_orphans_malformed_digest() {
+    let (db, _temp_dir) = setup_world_db();
+    let mut key = b"digp".to_vec();
+    key.extend_from_slice(&[0; 8]);
+    db.put(key, [1, 2, 3], &WriteOptions::new()).unwrap();
+    assert!(db.find_orphans(&ReadOptions::new()).is_err());
+}
+
+#[cfg(feature = "nbt")]
+#[test]
+fn test_find_orphan_maps() {
+    use crate::bedrock::nbt::{self, Value};
+
+    let (db, _temp_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+    let write_opts = WriteOptions::new();
+    let map = |id: i64, parent: i64| {
+        nbt::encode(&Value::Compound(vec![
+            ("mapId".to_string(), Value::Long(id)),
+            ("parentMapId".to_string(), Value::Long(parent)),
+        ]))
+    };
+    let item = |map: i64| {
+        Value::Compound(vec![
+            (
+                "Name".to_string(),
+                Value::String("minecraft:filled_map".to_string()),
+            ),
+            (
+                "tag".to_string(),
+                Value::Compound(vec![("map_uuid".to_string(), Value::Long(map))]),
+            ),
+        ])
+    };
+
+    // Map 10 is held by the player, 11 is its zoomed-out parent, 12 hangs
+    // in an item frame, and 13 and -5 are not referenced
+    for (id, parent) in [(10, 11), (11, -1), (12, -1), (13, -1), (-5, -1)] {
+        db.put_map(id, map(id, parent), &write_opts).unwrap();
+    }
+    let player = Value::Compound(vec![("Inventory".to_string(), Value::list(vec![item(10)]))]);
+    db.put(b"~local_player", nbt::encode(&player), &write_opts)
+        .unwrap();
+    let frame = Value::Compound(vec![
+        ("id".to_string(), Value::String("ItemFrame".to_string())),
+        ("Item".to_string(), item(12)),
+    ]);
+    db.put(
+        ChunkKey::new(0, 0, Dimension::Overworld, RecordTag::BlockEntity),
+        nbt::encode(&frame),
+        &write_opts,
+    )
+    .unwrap();
+
+    let report = db.find_orphans(&read_opts).unwrap();
+    let mut maps = report.maps.clone();
+    maps.sort();
+    assert_eq!(maps, vec![-5, 13]);
+    assert_eq!(report.unreadable, 0);
+
+    db.remove_orphans(&report, &write_opts).unwrap();
+    let mut ids: Vec<i64> = db.map_ids(&read_opts).collect();
+    ids.sort();
+    assert_eq!(ids, vec![10, 11, 12]);
+
+    // An undecodable record could hold any reference, so no map is reported
+    db.put_map(14, map(14, -1), &write_opts).unwrap();
+    db.put(b"player_server_broken", [10, 0], &write_opts)
+        .unwrap();
+    let report = db.find_orphans(&read_opts).unwrap();
+    assert!(report.maps.is_empty());
+    assert_eq!(report.unreadable, 1);
+}
@@ -0,0 +1,167 @@
+use super::setup_world_db;
+use crate::DB;
+use crate::WriteBatch;
+use crate::bedrock::{BiomeSection, ChunkKey, Data2D, Data3D, Dimension, RecordTag};
+use crate::options::{ReadOptions, WriteOptions};
+use std::collections::BTreeMap;
+use std::ops::ControlFlow;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Fill the database with a world of 50,000 keys: 10,000 Overworld and 2,500
+/// Nether chunks of four records each.
+fn synthetic_world(db: &DB) {
+    let mut batch = WriteBatch::new();
+    let mut add_chunk = |x: i32, z: i32, dim: Dimension| {
+        let biome = (x * 7 + z).rem_euclid(40) as u32;
+        batch.put(ChunkKey::new(x, z, dim, RecordTag::Version), [40]);
+        if (x + z) % 5 == 0 {
+            let data2d = Data2D {
+                heights: [70; 256],
+                biomes: [biome as u8; 256],
+            };
+            batch.put(ChunkKey::new(x, z, dim, RecordTag::Data2D), data2d.encode());
+        } else {
+            let data3d = Data3D {
+                heights: [64; 256],
+                sections: vec![BiomeSection::Single(biome), BiomeSection::SameAsBelow],
+            };
+            batch.put(ChunkKey::new(x, z, dim, RecordTag::Data3D), data3d.encode());
+        }
+        batch.put(ChunkKey::subchunk(x, z, dim, -4), [8, 0]);
+        batch.put(ChunkKey::subchunk(x, z, dim, 3), [8, 0]);
+    };
+    for x in -50..50 {
+        for z in -50..50 {
+            add_chunk(x, z, Dimension::Overworld);
+        }
+    }
+    for x in -25..25 {
+        for z in -25..25 {
+            add_chunk(x, z, Dimension::Nether);
+        }
+    }
+    assert_eq!(batch.len(), 50_000);
+    db.write(&batch, &WriteOptions::new()).unwrap();
+    db.compact_all().unwrap();
+}
+
+#[test]
+fn test_par_map_reduce_matches_serial() {
+    let (db, _temp_dir) = setup_world_db();
+    synthetic_world(&db);
+    let read_opts = ReadOptions::new();
+
+    let mut serial = (0u64, 0u64);
+    db.scan(
+        None,
+        |_key, value| {
+            serial.0 += 1;
+            serial.1 += value.len() as u64;
+            ControlFlow::Continue(())
+        },
+        &read_opts,
+    )
+    .unwrap();
+    assert_eq!(serial.0, 50_000);
+
+    for n in [1, 3, 8] {
+        let parallel = db
+            .par_map_reduce(
+                n,
+                || (0u64, 0u64),
+                |(entries, bytes), _key, value| {
+                    *entries += 1;
+                    *bytes += value.len() as u64;
+                },
+                |a, b| (a.0 + b.0, a.1 + b.1),
+                &read_opts,
+            )
+            .unwrap();
+        assert_eq!(parallel, serial);
+    }
+}
+
+#[test]
+fn test_par_chunks_matches_serial() {
+    let (db, _temp_dir) = setup_world_db();
+    synthetic_world(&db);
+    let read_opts = ReadOptions::new();
+
+    for dim in [Dimension::Overworld, Dimension::Nether, Dimension::End] {
+        // Records per chunk, counted serially
+        let mut serial: BTreeMap<(i32, i32), usize> = BTreeMap::new();
+        db.scan(
+            None,
+            |key, _| {
+                if let Ok(key) = ChunkKey::parse(key)
+                    && key.dimension() == dim
+                {
+                    *serial.entry((key.x(), key.z())).or_default() += 1;
+                }
+                ControlFlow::Continue(())
+            },
+            &read_opts,
+        )
+        .unwrap();
+
+        let parallel = db
+            .par_chunks(
+                dim,
+                BTreeMap::new,
+                |chunks, chunk| {
+                    assert!(chunk.records.iter().all(|(key, _)| key.dimension() == dim));
+                    // Each chunk is seen once, with all of its records
+                    let previous = chunks.insert((chunk.x, chunk.z), chunk.records.len());
+                    assert_eq!(previous, None);
+                },
+                |mut a, b| {
+                    for (position, records) in b {
+                        assert_eq!(a.insert(position, records), None);
+                    }
+                    a
+                },
+                &read_opts,
+            )
+            .unwrap();
+        assert_eq!(parallel, serial);
+
+        assert_eq!(
+            db.par_biome_histogram(dim, &read_opts).unwrap(),
+            db.biome_histogram(dim, &read_opts).unwrap()
+        );
+    }
+    let histogram = db
+        .par_biome_histogram(Dimension::Nether, &read_opts)
+        .unwrap();
+    assert_eq!(histogram.chunks, 2500);
+    assert_eq!(histogram.missing, 0);
+}
+
+#[test]
+fn test_par_chunks_propagates_panic() {
+    let (db, _temp_dir) = setup_world_db();
+    synthetic_world(&db);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        db.par_chunks(
+            Dimension::Overworld,
+            || (),
+            |_, chunk| {
+                if (chunk.x, chunk.z) == (17, -3) {
+                    panic!("bad chunk");
+                }
+            },
+            |_, _| (),
+            &ReadOptions::new(),
+        )
+    }));
+    let message = result.unwrap_err();
+    assert_eq!(message.downcast_ref::<&str>(), Some(&"bad chunk"));
+
+    // The snapshot was released and the database stays usable
+    db.put(b"after", b"panic", &WriteOptions::new()).unwrap();
+    assert_eq!(
+        db.get(b"after", &ReadOptions::new()).unwrap(),
+        Some(b"panic".to_vec())
+    );
+}
@@ -0,0 +1,83 @@
+use super::setup_world_db;
+use crate::bedrock::PlayerKey;
+use crate::options::{ReadOptions, WriteOptions};
+
+#[test]
+fn test_player_key_parse() {
+    assert_eq!(PlayerKey::parse(b"~local_player"), Some(PlayerKey::Local));
+    assert_eq!(
+        PlayerKey::parse(b"player_-4851216391937452291"),
+        Some(PlayerKey::Client("-4851216391937452291".to_string()))
+    );
+    assert_eq!(
+        PlayerKey::parse(b"player_server_1b2a4f5e-0000-4c6d-8e7f-0123456789ab"),
+        Some(PlayerKey::Server(
+            "1b2a4f5e-0000-4c6d-8e7f-0123456789ab".to_string()
+        ))
+    );
+    assert_eq!(PlayerKey::parse(b"portals"), None);
+    assert_eq!(PlayerKey::parse(b"player\xff"), None);
+
+    for key in [
+        PlayerKey::Local,
+        PlayerKey::Client("abc".to_string()),
+        PlayerKey::Server("def".to_string()),
+    ] {
+        assert_eq!(PlayerKey::parse(&key.encode()), Some(key.clone()));
+        assert_eq!(key.to_string().into_bytes(), key.encode());
+    }
+}
+
+#[test]
+fn test_players_and_local_player() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    assert_eq!(db.players(&read_opts).count(), 0);
+    assert_eq!(db.local_player(&read_opts).unwrap(), None);
+
+    db.put(b"player_12345", b"legacy", &write_opts).unwrap();
+    db.put(b"player_server_aaaa", b"server a", &write_opts)
+        .unwrap();
+    db.put(b"player_server_bbbb", b"server b", &write_opts)
+        .unwrap();
+    db.put(b"portals", b"{}", &write_opts).unwrap();
+    db.put(b"playerx", b"{}", &write_opts).unwrap();
+    db.put(b"~local_player", b"host", &write_opts).unwrap();
+
+    let players: Vec<(PlayerKey, Vec<u8>)> = db.players(&read_opts).collect();
+    assert_eq!(
+        players,
+        [
+            (PlayerKey::Client("12345".to_string()), b"legacy".to_vec()),
+            (PlayerKey::Server("aaaa".to_string()), b"server a".to_vec()),
+            (PlayerKey::Server("bbbb".to_string()), b"server b".to_vec()),
+            (PlayerKey::Local, b"host".to_vec()),
+        ]
+    );
+    assert_eq!(db.local_player(&read_opts).unwrap(), Some(b"host".to_vec()));
+
+    db.put_player(
+        &PlayerKey::Server("aaaa".to_string()),
+        b"edited",
+        &write_opts,
+    )
+    .unwrap();
+    db.put_player(&PlayerKey::Local, b"edited host", &write_opts)
+        .unwrap();
+    db.put_player(&PlayerKey::Client("777".to_string()), b"new", &write_opts)
+        .unwrap();
+    assert_eq!(
+        db.get(b"player_server_aaaa", &read_opts).unwrap(),
+        Some(b"edited".to_vec())
+    );
+    assert_eq!(
+        db.get(b"player_777", &read_opts).unwrap(),
+        Some(b"new".to_vec())
+    );
+    assert_eq!(
+        db.local_player(&read_opts).unwrap(),
+        Some(b"edited host".to_vec())
+    );
+    assert_eq!(db.players(&read_opts).count(), 5);
+}
@@ -0,0 +1,172 @@
+use std::error::Error;
+
+use super::setup_world_db;
+use crate::bedrock::{
+    BiomeDataError, BiomeSection, ChunkKey, Data2D, Data3D, DecodeError, Dimension, FinalizedState,
+    LegacyTerrain, LegacyTerrainError, RecordDecode, RecordEncode, RecordTag,
+};
+use crate::options::{ReadOptions, WriteOptions};
+
+fn sample_data2d() -> Data2D {
+    let mut data = Data2D {
+        heights: [64; 256],
+        biomes: [1; 256],
+    };
+    data.heights[17] = 80;
+    data.biomes[255] = 21;
+    data
+}
+
+fn sample_data3d() -> Data3D {
+    Data3D {
+        heights: [70; 256],
+        sections: vec![
+            BiomeSection::Single(1),
+            BiomeSection::SameAsBelow,
+            BiomeSection::Single(4),
+        ],
+    }
+}
+
+#[test]
+fn test_record_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    let dim = Dimension::Nether;
+
+    let data2d = sample_data2d();
+    let data3d = sample_data3d();
+    db.put_record(3, -5, dim, &data2d, &write_opts).unwrap();
+    db.put_record(3, -5, dim, &data3d, &write_opts).unwrap();
+    db.put_record(3, -5, dim, &FinalizedState::Done, &write_opts)
+        .unwrap();
+
+    assert_eq!(
+        db.get_record::<Data2D>(3, -5, dim, &read_opts).unwrap(),
+        Some(data2d)
+    );
+    assert_eq!(
+        db.get_record::<Data3D>(3, -5, dim, &read_opts).unwrap(),
+        Some(data3d)
+    );
+    assert_eq!(
+        db.get_record::<FinalizedState>(3, -5, dim, &read_opts)
+            .unwrap(),
+        Some(FinalizedState::Done)
+    );
+    // Written under the tag of each type, in the same encoding as the typed accessors
+    assert_eq!(
+        db.finalized_state(3, -5, dim, &read_opts).unwrap(),
+        Some(FinalizedState::Done)
+    );
+    assert!(
+        db.get(ChunkKey::new(3, -5, dim, RecordTag::Data3D), &read_opts)
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[test]
+fn test_record_missing_and_invalid() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    let dim = Dimension::Overworld;
+
+    assert_eq!(
+        db.get_record::<Data2D>(0, 0, dim, &read_opts).unwrap(),
+        None
+    );
+    assert_eq!(
+        db.get_record::<FinalizedState>(0, 0, dim, &read_opts)
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        db.get_record::<LegacyTerrain>(0, 0, dim, &read_opts)
+            .unwrap(),
+        None
+    );
+
+    for tag in [
+        RecordTag::Data2D,
+        RecordTag::Data3D,
+        RecordTag::FinalizedState,
+        RecordTag::LegacyTerrain,
+    ] {
+        db.put(ChunkKey::new(0, 0, dim, tag), [1u8, 2, 3], &write_opts)
+            .unwrap();
+    }
+    let err = db.get_record::<Data2D>(0, 0, dim, &read_opts).unwrap_err();
+    assert!(err.contains("Data2D") && err.contains("(0, 0)"), "{}", err);
+    assert!(db.get_record::<Data3D>(0, 0, dim, &read_opts).is_err());
+    assert!(
+        db.get_record::<FinalizedState>(0, 0, dim, &read_opts)
+            .is_err()
+    );
+    assert!(
+        db.get_record::<LegacyTerrain>(0, 0, dim, &read_opts)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_decode_errors() {
+    assert_eq!(
+        FinalizedState::decode(&[2, 0, 0]),
+        Err(DecodeError::InvalidLength {
+            expected: 4,
+            actual: 3
+        })
+    );
+    assert_eq!(
+        <Data2D as RecordDecode>::decode(&[0; 10]),
+        Err(DecodeError::BiomeData(BiomeDataError::InvalidLength(10)))
+    );
+    let err = LegacyTerrain::decode(&[0; 5]).unwrap_err();
+    assert_eq!(
+        err,
+        DecodeError::LegacyTerrain(LegacyTerrainError::InvalidLength(5))
+    );
+    assert!(err.source().is_some());
+
+    let state = FinalizedState::Unknown(7);
+    assert_eq!(FinalizedState::decode(&state.encode()), Ok(state));
+    let data = sample_data2d();
+    assert_eq!(
+        <Data2D as RecordDecode>::decode(&RecordEncode::encode(&data)),
+        Ok(data)
+    );
+    assert_eq!(<Data3D as RecordDecode>::TAG, RecordTag::Data3D);
+}
+
+#[cfg(feature = "subchunk")]
+#[test]
+fn test_record_subchunk_by_y_index() {
+    use crate::bedrock::SubChunk;
+
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    let dim = Dimension::Nether;
+    let subchunk = SubChunk {
+        version: 9,
+        y_index: Some(-2),
+        layers: Vec::new(),
+    };
+
+    db.put_record_at(4, 7, dim, -2, &subchunk, &write_opts)
+        .unwrap();
+    assert_eq!(
+        db.get_subchunk(4, 7, dim, -2, &read_opts).unwrap(),
+        Some(subchunk.encode())
+    );
+    assert_eq!(
+        db.get_record_at::<SubChunk>(4, 7, dim, -2, &read_opts)
+            .unwrap(),
+        Some(subchunk)
+    );
+    assert_eq!(
+        db.get_record_at::<SubChunk>(4, 7, dim, 3, &read_opts)
+            .unwrap(),
+        None
+    );
+}
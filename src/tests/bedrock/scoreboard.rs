@@ -0,0 +1,196 @@
+use super::setup_world_db;
+use crate::bedrock::keys::SCOREBOARD;
+use crate::bedrock::nbt::{self, Value};
+use crate::bedrock::{ScoreOwner, Scoreboard};
+use crate::options::{ReadOptions, WriteOptions};
+
+fn compound(entries: Vec<(&str, Value)>) -> Value {
+    Value::Compound(
+        entries
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    )
+}
+
+/// A scoreboard as the game writes it, with one entry of each owner type.
+fn synthetic_scoreboard() -> Value {
+    compound(vec![
+        ("Criteria", Value::list(Vec::new())),
+        (
+            "DisplayObjectives",
+            Value::list(vec![compound(vec![
+                ("Name", Value::String("sidebar".to_string())),
+                ("ObjectiveName", Value::String("kills".to_string())),
+                ("SortOrder", Value::Byte(1)),
+            ])]),
+        ),
+        (
+            "Entries",
+            Value::list(vec![
+                compound(vec![
+                    ("IdentityType", Value::Byte(1)),
+                    ("PlayerId", Value::Long(-4_294_967_295)),
+                    ("ScoreboardId", Value::Long(1)),
+                ]),
+                compound(vec![
+                    ("EntityId", Value::Long(-21_474_836_479)),
+                    ("IdentityType", Value::Byte(2)),
+                    ("ScoreboardId", Value::Long(2)),
+                ]),
+                compound(vec![
+                    ("FakePlayerName", Value::String("#global".to_string())),
+                    ("IdentityType", Value::Byte(3)),
+                    ("ScoreboardId", Value::Long(3)),
+                    ("FutureField", Value::Int(9)),
+                ]),
+            ]),
+        ),
+        ("LastUniqueID", Value::Long(3)),
+        (
+            "Objectives",
+            Value::list(vec![compound(vec![
+                ("Criteria", Value::String("dummy".to_string())),
+                ("DisplayName", Value::String("Kills".to_string())),
+                ("Name", Value::String("kills".to_string())),
+                (
+                    "Scores",
+                    Value::list(vec![
+                        compound(vec![
+                            ("Score", Value::Int(12)),
+                            ("ScoreboardId", Value::Long(1)),
+                        ]),
+                        compound(vec![
+                            ("Score", Value::Int(-3)),
+                            ("ScoreboardId", Value::Long(3)),
+                        ]),
+                    ]),
+                ),
+            ])]),
+        ),
+    ])
+}
+
+#[test]
+fn test_scoreboard_parse() {
+    let scoreboard = Scoreboard::from_nbt(synthetic_scoreboard()).unwrap();
+
+    let owners: Vec<_> = scoreboard
+        .entries
+        .iter()
+        .map(|entry| (entry.scoreboard_id, entry.owner.clone()))
+        .collect();
+    assert_eq!(
+        owners,
+        [
+            (1, ScoreOwner::Player(-4_294_967_295)),
+            (2, ScoreOwner::Entity(-21_474_836_479)),
+            (3, ScoreOwner::Fake("#global".to_string())),
+        ]
+    );
+    assert_eq!(
+        scoreboard.entries[2].extra,
+        [("FutureField".to_string(), Value::Int(9))]
+    );
+
+    let objective = &scoreboard.objectives[0];
+    assert_eq!(
+        (
+            objective.name.as_str(),
+            objective.display_name.as_str(),
+            objective.criteria.as_str()
+        ),
+        ("kills", "Kills", "dummy")
+    );
+    let scores: Vec<_> = objective
+        .scores
+        .iter()
+        .map(|score| (score.scoreboard_id, score.score))
+        .collect();
+    assert_eq!(scores, [(1, 12), (3, -3)]);
+
+    let slot = &scoreboard.display_slots[0];
+    assert_eq!(
+        (
+            slot.name.as_str(),
+            slot.objective_name.as_str(),
+            slot.sort_order
+        ),
+        ("sidebar", "kills", 1)
+    );
+    assert_eq!(scoreboard.last_unique_id, Some(3));
+    assert_eq!(scoreboard.extra[0].0, "Criteria");
+}
+
+#[test]
+fn test_scoreboard_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    assert_eq!(db.scoreboard(&read_opts).unwrap(), None);
+
+    db.put(
+        SCOREBOARD,
+        nbt::encode(&synthetic_scoreboard()),
+        &write_opts,
+    )
+    .unwrap();
+    let mut scoreboard = db.scoreboard(&read_opts).unwrap().unwrap();
+
+    // Move the player's scores to another player and rename the fake one
+    for entry in &mut scoreboard.entries {
+        match &mut entry.owner {
+            ScoreOwner::Player(id) => *id = 77,
+            ScoreOwner::Fake(name) => *name = "#server".to_string(),
+            _ => {}
+        }
+    }
+    db.set_scoreboard(&scoreboard, &write_opts).unwrap();
+    let reread = db.scoreboard(&read_opts).unwrap().unwrap();
+    assert_eq!(reread, scoreboard);
+    assert_eq!(reread.entries[0].owner, ScoreOwner::Player(77));
+    assert_eq!(
+        reread.entries[2].owner,
+        ScoreOwner::Fake("#server".to_string())
+    );
+
+    let stored = nbt::decode(&db.get(SCOREBOARD, &read_opts).unwrap().unwrap()).unwrap();
+    let entry = &stored.get("Entries").and_then(Value::as_list).unwrap()[0];
+    assert_eq!(entry.get("PlayerId"), Some(&Value::Long(77)));
+    assert_eq!(entry.get("IdentityType"), Some(&Value::Byte(1)));
+    assert!(stored.get("Criteria").is_some());
+}
+
+#[test]
+fn test_scoreboard_unknown_and_invalid() {
+    let unknown = compound(vec![(
+        "Entries",
+        Value::list(vec![compound(vec![
+            ("IdentityType", Value::Byte(9)),
+            ("ScoreboardId", Value::Long(5)),
+            ("OwnerBlob", Value::ByteArray(vec![1, 2])),
+        ])]),
+    )]);
+    let scoreboard = Scoreboard::from_nbt(unknown.clone()).unwrap();
+    assert_eq!(scoreboard.entries[0].owner, ScoreOwner::Unknown(9));
+    assert_eq!(scoreboard.last_unique_id, None);
+    assert_eq!(
+        Scoreboard::from_nbt(scoreboard.to_nbt()).unwrap(),
+        scoreboard
+    );
+
+    let missing_owner = compound(vec![(
+        "Entries",
+        Value::list(vec![compound(vec![
+            ("IdentityType", Value::Byte(1)),
+            ("ScoreboardId", Value::Long(1)),
+        ])]),
+    )]);
+    let err = Scoreboard::from_nbt(missing_owner).unwrap_err();
+    assert!(err.contains("PlayerId"), "{}", err);
+    assert!(Scoreboard::from_nbt(Value::Int(1)).is_err());
+
+    let (db, _temp_dir) = setup_world_db();
+    db.put(SCOREBOARD, [0xffu8, 0], &WriteOptions::new())
+        .unwrap();
+    assert!(db.scoreboard(&ReadOptions::new()).is_err());
+}
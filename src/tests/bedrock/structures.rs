@@ -0,0 +1,80 @@
+use super::setup_world_db;
+use crate::options::{ReadOptions, WriteOptions};
+
+#[test]
+fn test_structure_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    assert!(db.structure_templates(&read_opts).unwrap().is_empty());
+
+    db.put_structure("house", b"house nbt", &write_opts)
+        .unwrap();
+    db.put_structure("mystructure:thing", b"thing nbt", &write_opts)
+        .unwrap();
+    db.put_structure("addon:tower:top", b"tower nbt", &write_opts)
+        .unwrap();
+
+    assert_eq!(
+        db.get(b"structuretemplate_mystructure:house", &read_opts)
+            .unwrap(),
+        Some(b"house nbt".to_vec())
+    );
+    assert_eq!(
+        db.get_structure("mystructure:house", &read_opts).unwrap(),
+        Some(b"house nbt".to_vec())
+    );
+    assert_eq!(
+        db.get_structure("thing", &read_opts).unwrap(),
+        Some(b"thing nbt".to_vec())
+    );
+    assert_eq!(
+        db.get_structure("addon:tower:top", &read_opts).unwrap(),
+        Some(b"tower nbt".to_vec())
+    );
+    assert_eq!(db.get_structure("tower:top", &read_opts).unwrap(), None);
+
+    // Keys without a namespace or name are not listed
+    db.put(b"structuretemplate_", b"", &write_opts).unwrap();
+    db.put(b"structuretemplate_nonamespace", b"", &write_opts)
+        .unwrap();
+    db.put(b"structuretemplate_ns:", b"", &write_opts).unwrap();
+    db.put(b"structuretemplate_:name", b"", &write_opts)
+        .unwrap();
+
+    let listed = db.structure_templates(&read_opts).unwrap();
+    assert_eq!(
+        listed,
+        [
+            ("addon".to_string(), "tower:top".to_string()),
+            ("mystructure".to_string(), "house".to_string()),
+            ("mystructure".to_string(), "thing".to_string()),
+        ]
+    );
+
+    db.delete_structure("house", &write_opts).unwrap();
+    db.delete_structure("addon:tower:top", &write_opts).unwrap();
+    db.delete_structure("addon:missing", &write_opts).unwrap();
+    assert_eq!(db.get_structure("house", &read_opts).unwrap(), None);
+    assert_eq!(
+        db.structure_templates(&read_opts).unwrap(),
+        [("mystructure".to_string(), "thing".to_string())]
+    );
+}
+
+#[test]
+fn test_structure_invalid_identifiers() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    for identifier in ["", ":", "ns:", ":name"] {
+        assert!(
+            db.get_structure(identifier, &read_opts).is_err(),
+            "{:?}",
+            identifier
+        );
+        assert!(db.put_structure(identifier, b"x", &write_opts).is_err());
+        assert!(db.delete_structure(identifier, &write_opts).is_err());
+    }
+    assert_eq!(db.count_keys(&read_opts).unwrap(), 0);
+}
@@ -0,0 +1,287 @@
+use super::{Rng, setup_world_db};
+use crate::bedrock::nbt::{self, Value};
+use crate::bedrock::{BlockStorage, Dimension, SubChunk, SubChunkError};
+use crate::options::{ReadOptions, WriteOptions};
+
+fn block_state(name: &str) -> Value {
+    Value::Compound(vec![
+        ("name".to_string(), Value::String(name.to_string())),
+        ("states".to_string(), Value::Compound(Vec::new())),
+        ("version".to_string(), Value::Int(18_100_737)),
+    ])
+}
+
+/// A layer header, the packed words and the palette, written out by hand.
+fn layer_bytes(bits: u8, words: &[u32], palette: &[&str]) -> Vec<u8> {
+    let mut bytes = vec![bits << 1];
+    for word in words {
+        bytes.extend_from_slice(&word.to_le_bytes());
+    }
+    bytes.extend_from_slice(&(palette.len() as i32).to_le_bytes());
+    for name in palette {
+        bytes.extend_from_slice(&[10, 0, 0, 8, 4, 0]);
+        bytes.extend_from_slice(b"name");
+        bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(name.as_bytes());
+        bytes.extend_from_slice(&[10, 6, 0]);
+        bytes.extend_from_slice(b"states");
+        bytes.push(0);
+        bytes.extend_from_slice(&[3, 7, 0]);
+        bytes.extend_from_slice(b"version");
+        bytes.extend_from_slice(&18_100_737i32.to_le_bytes());
+        bytes.push(0);
+    }
+    bytes
+}
+
+/// A version 8 subchunk whose bottom 4 layers are stone and the rest air,
+/// at 1 bit per block.
+fn version8_fixture() -> Vec<u8> {
+    let mut record = vec![8, 1];
+    // Each word holds two columns of 16 blocks
+    record.extend(layer_bytes(
+        1,
+        &[0x000F000F; 128],
+        &["minecraft:air", "minecraft:stone"],
+    ));
+    record
+}
+
+/// A version 9 subchunk at y index -4 with a waterlogged layer: blocks are
+/// stone, with seagrass at y = 1 of column (0, 0), which is also water in
+/// layer 1.
+fn version9_fixture() -> Vec<u8> {
+    let mut record = vec![9, 2, (-4i8) as u8];
+    // 4 bits per block, eight blocks per word
+    let mut words = [0u32; 512];
+    words[0] = 1 << 4;
+    record.extend(layer_bytes(
+        4,
+        &words,
+        &["minecraft:stone", "minecraft:seagrass"],
+    ));
+    let mut words = [0u32; 128];
+    words[0] = 1 << 1;
+    record.extend(layer_bytes(
+        1,
+        &words,
+        &["minecraft:air", "minecraft:water"],
+    ));
+    record
+}
+
+fn name_at(subchunk: &SubChunk, x: usize, y: usize, z: usize, layer: usize) -> Option<&str> {
+    subchunk.block_at(x, y, z, layer)?.get("name")?.as_str()
+}
+
+#[test]
+fn test_parse_version8_fixture() {
+    let record = version8_fixture();
+    let subchunk = SubChunk::parse(&record).unwrap();
+    assert_eq!(subchunk.version, 8);
+    assert_eq!(subchunk.y_index, None);
+    assert_eq!(subchunk.layers.len(), 1);
+    assert_eq!(
+        subchunk.palette(0).unwrap(),
+        [block_state("minecraft:air"), block_state("minecraft:stone")]
+    );
+    assert_eq!(subchunk.palette(1), None);
+    for (x, z) in [(0, 0), (7, 3), (15, 15)] {
+        for y in 0..16 {
+            let expected = if y < 4 {
+                "minecraft:stone"
+            } else {
+                "minecraft:air"
+            };
+            assert_eq!(name_at(&subchunk, x, y, z, 0), Some(expected));
+        }
+    }
+    assert_eq!(subchunk.block_at(0, 0, 0, 1), None);
+    assert_eq!(subchunk.encode(), record);
+}
+
+#[test]
+fn test_parse_version9_fixture() {
+    let record = version9_fixture();
+    let subchunk = SubChunk::parse(&record).unwrap();
+    assert_eq!(subchunk.version, 9);
+    assert_eq!(subchunk.y_index, Some(-4));
+    assert_eq!(subchunk.layers.len(), 2);
+    assert_eq!(name_at(&subchunk, 0, 1, 0, 0), Some("minecraft:seagrass"));
+    assert_eq!(name_at(&subchunk, 0, 1, 0, 1), Some("minecraft:water"));
+    assert_eq!(name_at(&subchunk, 0, 0, 0, 0), Some("minecraft:stone"));
+    assert_eq!(name_at(&subchunk, 0, 2, 0, 1), Some("minecraft:air"));
+    assert_eq!(name_at(&subchunk, 0, 1, 1, 0), Some("minecraft:stone"));
+    assert_eq!(name_at(&subchunk, 1, 1, 0, 1), Some("minecraft:air"));
+    // The palette only needs 1 bit, so the layer is written narrower
+    let encoded = subchunk.encode();
+    assert!(encoded.len() < record.len());
+    assert_eq!(SubChunk::parse(&encoded).unwrap(), subchunk);
+}
+
+#[test]
+fn test_round_trip_every_width() {
+    let mut rng = Rng::new(0x5eed);
+    let widths: [(u8, usize); 8] = [
+        (1, 2),
+        (2, 3),
+        (3, 7),
+        (4, 16),
+        (5, 17),
+        (6, 64),
+        (8, 200),
+        (16, 257),
+    ];
+    for (bits, palette_len) in widths {
+        let palette: Vec<Value> = (0..palette_len)
+            .map(|i| block_state(&format!("test:block_{}", i)))
+            .collect();
+        let mut indices: Vec<u16> = (0..4096)
+            .map(|_| (rng.next_u64() % palette_len as u64) as u16)
+            .collect();
+        // Make sure the widest index lands in the last, partly used word
+        indices[4095] = palette_len as u16 - 1;
+        let subchunk = SubChunk {
+            version: 9,
+            y_index: Some(bits as i8 - 4),
+            layers: vec![BlockStorage { palette, indices }],
+        };
+
+        let encoded = subchunk.encode();
+        assert_eq!(
+            encoded[3],
+            bits << 1,
+            "width for {} palette entries",
+            palette_len
+        );
+        let per_word = 32 / bits as usize;
+        let words = 4096usize.div_ceil(per_word);
+        let palette_offset = 4 + words * 4;
+        assert_eq!(
+            encoded[palette_offset..palette_offset + 4],
+            (palette_len as i32).to_le_bytes()
+        );
+
+        let parsed = SubChunk::parse(&encoded).unwrap();
+        assert_eq!(parsed, subchunk, "{} bits per block", bits);
+        for (i, &index) in subchunk.layers[0].indices.iter().enumerate() {
+            let (x, z, y) = (i / 256, i / 16 % 16, i % 16);
+            assert_eq!(
+                parsed.block_at(x, y, z, 0),
+                Some(&subchunk.layers[0].palette[index as usize])
+            );
+        }
+        assert_eq!(parsed.encode(), encoded);
+    }
+}
+
+#[test]
+fn test_parse_uniform_layer() {
+    // Recent versions write single-state layers with no words at all
+    let mut record = vec![9, 1, 3];
+    record.extend(layer_bytes(0, &[], &["minecraft:deepslate"]));
+    let subchunk = SubChunk::parse(&record).unwrap();
+    assert_eq!(name_at(&subchunk, 9, 9, 9, 0), Some("minecraft:deepslate"));
+    assert_eq!(subchunk.layers[0].indices, vec![0; 4096]);
+    assert_eq!(SubChunk::parse(&subchunk.encode()).unwrap(), subchunk);
+}
+
+#[test]
+fn test_parse_errors() {
+    for version in [0, 1, 2, 7, 10] {
+        assert_eq!(
+            SubChunk::parse(&[version, 1]),
+            Err(SubChunkError::UnsupportedVersion(version))
+        );
+    }
+    assert_eq!(
+        SubChunk::parse(&[]),
+        Err(SubChunkError::Truncated { offset: 0 })
+    );
+    assert_eq!(
+        SubChunk::parse(&[9, 1]),
+        Err(SubChunkError::Truncated { offset: 2 })
+    );
+
+    // Too few words for the width
+    let mut record = vec![8, 1, 2 << 1];
+    record.extend_from_slice(&[0; 255 * 4]);
+    assert_eq!(
+        SubChunk::parse(&record),
+        Err(SubChunkError::Truncated { offset: 3 })
+    );
+
+    let record = version8_fixture();
+    for len in 0..record.len() {
+        assert!(
+            SubChunk::parse(&record[..len]).is_err(),
+            "prefix of {} bytes",
+            len
+        );
+    }
+
+    assert_eq!(
+        SubChunk::parse(&[8, 1, 7 << 1]),
+        Err(SubChunkError::InvalidBitsPerBlock { layer: 0, bits: 7 })
+    );
+    assert_eq!(
+        SubChunk::parse(&[8, 1, 1 << 1 | 1]),
+        Err(SubChunkError::RuntimePalette { layer: 0 })
+    );
+
+    let mut record = vec![8, 1];
+    record.extend(layer_bytes(0, &[], &[]));
+    record[3..7].copy_from_slice(&(-1i32).to_le_bytes());
+    assert_eq!(
+        SubChunk::parse(&record),
+        Err(SubChunkError::NegativePaletteLength { layer: 0 })
+    );
+
+    // Index 2 with only two palette entries, in the second layer
+    let mut record = vec![8, 2];
+    record.extend(layer_bytes(1, &[0; 128], &["minecraft:air"]));
+    let mut words = [0u32; 256];
+    words[100] = 2 << 6;
+    record.extend(layer_bytes(
+        2,
+        &words,
+        &["minecraft:air", "minecraft:water"],
+    ));
+    assert_eq!(
+        SubChunk::parse(&record),
+        Err(SubChunkError::PaletteIndexOutOfRange { layer: 1, index: 2 })
+    );
+
+    let mut record = vec![8, 1];
+    record.extend(layer_bytes(0, &[], &[]));
+    record[3..7].copy_from_slice(&1i32.to_le_bytes());
+    record.extend_from_slice(&[10, 0, 0, 20, 0, 0]);
+    assert!(matches!(
+        SubChunk::parse(&record),
+        Err(SubChunkError::Nbt {
+            layer: 0,
+            offset: 7,
+            error: nbt::NbtError::InvalidTag { tag: 20, offset: 3 }
+        })
+    ));
+}
+
+#[test]
+fn test_parse_stored_subchunk() {
+    let (db, _temp_dir) = setup_world_db();
+    let subchunk = SubChunk::parse(&version9_fixture()).unwrap();
+    db.put_subchunk(
+        3,
+        -7,
+        Dimension::Nether,
+        -4,
+        subchunk.encode(),
+        &WriteOptions::new(),
+    )
+    .unwrap();
+    let blob = db
+        .get_subchunk(3, -7, Dimension::Nether, -4, &ReadOptions::new())
+        .unwrap()
+        .unwrap();
+    assert_eq!(SubChunk::parse(&blob).unwrap(), subchunk);
+}
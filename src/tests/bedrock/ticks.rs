@@ -0,0 +1,161 @@
+use super::setup_world_db;
+use crate::bedrock::nbt::Value;
+use crate::bedrock::{ChunkKey, Dimension, RecordTag};
+use crate::options::{ReadOptions, WriteOptions};
+
+fn tick(name: &str, time: i64, (x, y, z): (i32, i32, i32)) -> Value {
+    Value::Compound(vec![
+        (
+            "blockState".to_string(),
+            Value::Compound(vec![
+                ("name".to_string(), Value::String(name.to_string())),
+                ("states".to_string(), Value::Compound(Vec::new())),
+                ("version".to_string(), Value::Int(18_100_737)),
+            ]),
+        ),
+        ("time".to_string(), Value::Long(time)),
+        ("x".to_string(), Value::Int(x)),
+        ("y".to_string(), Value::Int(y)),
+        ("z".to_string(), Value::Int(z)),
+    ])
+}
+
+fn tick_record(current_tick: i32, ticks: Vec<Value>) -> Value {
+    Value::Compound(vec![
+        ("currentTick".to_string(), Value::Int(current_tick)),
+        ("tickList".to_string(), Value::list(ticks)),
+    ])
+}
+
+fn tick_positions(root: &Value) -> Vec<(i64, i64, i64)> {
+    root.get("tickList")
+        .and_then(Value::as_list)
+        .unwrap()
+        .iter()
+        .map(|tick| {
+            let axis = |name| tick.get(name).and_then(Value::as_i64).unwrap();
+            (axis("x"), axis("y"), axis("z"))
+        })
+        .collect()
+}
+
+#[test]
+fn test_pending_ticks_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    assert!(
+        db.pending_ticks(1, 2, Dimension::Overworld, &read_opts)
+            .unwrap()
+            .is_empty()
+    );
+
+    let root = tick_record(
+        5000,
+        vec![
+            tick("minecraft:water", 5003, (17, 62, 35)),
+            tick("minecraft:redstone_wire", 5010, (30, 70, 46)),
+        ],
+    );
+    db.set_pending_ticks(
+        1,
+        2,
+        Dimension::Overworld,
+        std::slice::from_ref(&root),
+        None,
+        &write_opts,
+    )
+    .unwrap();
+    assert_eq!(
+        db.pending_ticks(1, 2, Dimension::Overworld, &read_opts)
+            .unwrap(),
+        vec![root]
+    );
+
+    db.set_pending_ticks(1, 2, Dimension::Overworld, &[], None, &write_opts)
+        .unwrap();
+    assert_eq!(
+        db.get(
+            ChunkKey::new(1, 2, Dimension::Overworld, RecordTag::PendingTicks),
+            &read_opts
+        )
+        .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_ticks_offset_rewrite() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    let root = tick_record(
+        120,
+        vec![
+            tick("minecraft:grass_block", 0, (1, 64, 2)),
+            tick("minecraft:sapling", 0, (15, -10, 15)),
+        ],
+    );
+    // Copy the ticks of chunk (0, 0) to chunk (-3, 5), two subchunks higher
+    db.set_random_ticks(
+        -3,
+        5,
+        Dimension::Nether,
+        std::slice::from_ref(&root),
+        Some((-48, 32, 80)),
+        &write_opts,
+    )
+    .unwrap();
+
+    let moved = db
+        .random_ticks(-3, 5, Dimension::Nether, &read_opts)
+        .unwrap();
+    assert_eq!(moved.len(), 1);
+    assert_eq!(
+        tick_positions(&moved[0]),
+        vec![(-47, 96, 82), (-33, 22, 95)]
+    );
+    // Everything but the positions is unchanged
+    assert_eq!(moved[0].get("currentTick"), Some(&Value::Int(120)));
+    let moved_tick = &moved[0].get("tickList").unwrap().as_list().unwrap()[0];
+    let original_tick = &root.get("tickList").unwrap().as_list().unwrap()[0];
+    assert_eq!(
+        moved_tick.get("blockState"),
+        original_tick.get("blockState")
+    );
+    assert_eq!(moved_tick.get("time"), original_tick.get("time"));
+    assert!(
+        db.pending_ticks(-3, 5, Dimension::Nether, &read_opts)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_biome_state_and_invalid_records() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    assert_eq!(
+        db.biome_state(0, 0, Dimension::End, &read_opts).unwrap(),
+        None
+    );
+    db.set_biome_state(0, 0, Dimension::End, [1, 12, 3], &write_opts)
+        .unwrap();
+    assert_eq!(
+        db.biome_state(0, 0, Dimension::End, &read_opts).unwrap(),
+        Some(vec![1, 12, 3])
+    );
+
+    db.put(
+        ChunkKey::new(4, 4, Dimension::Overworld, RecordTag::RandomTicks),
+        [10, 0, 0, 3],
+        &write_opts,
+    )
+    .unwrap();
+    let err = db
+        .random_ticks(4, 4, Dimension::Overworld, &read_opts)
+        .unwrap_err();
+    assert!(
+        err.contains("RandomTicks") && err.contains("(4, 4)"),
+        "{}",
+        err
+    );
+}
@@ -0,0 +1,122 @@
+use super::setup_world_db;
+use crate::bedrock::{Dimension, VillageKey, VillageRecord, VillageRecords};
+use crate::options::{ReadOptions, WriteOptions};
+
+const UUID_A: &str = "0f1e2d3c-4b5a-6978-8796-a5b4c3d2e1f0";
+const UUID_B: &str = "11111111-2222-3333-4444-555555555555";
+const UUID_OLD: &str = "99999999-8888-7777-6666-555555555555";
+
+#[test]
+fn test_village_key_parse() {
+    let key = VillageKey::parse(format!("VILLAGE_TheEnd_{}_DWELLERS", UUID_A).as_bytes()).unwrap();
+    assert_eq!(
+        key,
+        VillageKey {
+            dimension: Some(Dimension::End),
+            uuid: UUID_A.to_string(),
+            record: VillageRecord::Dwellers,
+        }
+    );
+    assert_eq!(
+        key.encode(),
+        format!("VILLAGE_TheEnd_{}_DWELLERS", UUID_A).into_bytes()
+    );
+
+    let old = VillageKey::parse(format!("VILLAGE_{}_INFO", UUID_OLD).as_bytes()).unwrap();
+    assert_eq!(old.dimension, None);
+    assert_eq!(old.uuid, UUID_OLD);
+    assert_eq!(old.record, VillageRecord::Info);
+    assert_eq!(
+        old.encode(),
+        format!("VILLAGE_{}_INFO", UUID_OLD).into_bytes()
+    );
+
+    let custom = VillageKey {
+        dimension: Some(Dimension::Custom(7)),
+        uuid: UUID_B.to_string(),
+        record: VillageRecord::Poi,
+    };
+    assert_eq!(VillageKey::parse(&custom.encode()), Some(custom));
+
+    for bad in [
+        format!("VILLAGE_Overworld_{}_SHOPS", UUID_A),
+        format!("VILLAGE_Moon_{}_INFO", UUID_A),
+        format!("village_{}_INFO", UUID_A),
+        "VILLAGE__INFO".to_string(),
+        "VILLAGE_INFO".to_string(),
+    ] {
+        assert_eq!(VillageKey::parse(bad.as_bytes()), None, "{}", bad);
+    }
+}
+
+#[test]
+fn test_villages_and_delete_village() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    for (key, value) in [
+        (format!("VILLAGE_Overworld_{}_INFO", UUID_A), "a info"),
+        (
+            format!("VILLAGE_Overworld_{}_DWELLERS", UUID_A),
+            "a dwellers",
+        ),
+        (format!("VILLAGE_Overworld_{}_PLAYERS", UUID_A), "a players"),
+        (format!("VILLAGE_Overworld_{}_POI", UUID_A), "a poi"),
+        (format!("VILLAGE_Nether_{}_INFO", UUID_B), "b info"),
+        (format!("VILLAGE_{}_INFO", UUID_OLD), "old info"),
+        (format!("VILLAGE_{}_POI", UUID_OLD), "old poi"),
+        ("VILLAGE_garbage".to_string(), "ignored"),
+    ] {
+        db.put(key, value, &write_opts).unwrap();
+    }
+    db.put(b"~local_player", b"{}", &write_opts).unwrap();
+
+    let village_a = VillageRecords {
+        dimension: Some(Dimension::Overworld),
+        uuid: UUID_A.to_string(),
+        info: Some(b"a info".to_vec()),
+        dwellers: Some(b"a dwellers".to_vec()),
+        players: Some(b"a players".to_vec()),
+        poi: Some(b"a poi".to_vec()),
+    };
+    let village_b = VillageRecords {
+        dimension: Some(Dimension::Nether),
+        uuid: UUID_B.to_string(),
+        info: Some(b"b info".to_vec()),
+        ..Default::default()
+    };
+    let village_old = VillageRecords {
+        dimension: None,
+        uuid: UUID_OLD.to_string(),
+        info: Some(b"old info".to_vec()),
+        poi: Some(b"old poi".to_vec()),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        db.villages(Some(Dimension::Overworld), &read_opts).unwrap(),
+        vec![village_a.clone()]
+    );
+    assert_eq!(
+        db.villages(Some(Dimension::Nether), &read_opts).unwrap(),
+        vec![village_b.clone()]
+    );
+    assert!(
+        db.villages(Some(Dimension::End), &read_opts)
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        db.villages(None, &read_opts).unwrap(),
+        [village_old.clone(), village_b, village_a]
+    );
+
+    assert_eq!(db.delete_village(UUID_A, &write_opts).unwrap(), 4);
+    assert_eq!(db.delete_village(UUID_A, &write_opts).unwrap(), 0);
+    assert_eq!(db.delete_village(UUID_B, &write_opts).unwrap(), 1);
+    assert_eq!(db.villages(None, &read_opts).unwrap(), [village_old]);
+    assert_eq!(
+        db.get(b"VILLAGE_garbage", &read_opts).unwrap(),
+        Some(b"ignored".to_vec())
+    );
+}
@@ -0,0 +1,194 @@
+use super::{setup_world_db, write_synthetic_world};
+use crate::DB;
+use crate::bedrock::{ActorId, ChunkKey, Dimension, RecordTag};
+use crate::options::{Options, ReadOptions, WriteOptions};
+use std::collections::BTreeSet;
+
+fn all_keys(db: &DB) -> BTreeSet<Vec<u8>> {
+    db.iter(&ReadOptions::new()).map(|(key, _)| key).collect()
+}
+
+/// Write the same chunks, actors and villages into several dimensions,
+/// including one whose id equals a record tag byte.
+fn write_multi_dimension_world(db: &DB) -> [Dimension; 4] {
+    let write_opts = WriteOptions::new();
+    let dims = [
+        Dimension::Overworld,
+        Dimension::Nether,
+        Dimension::End,
+        Dimension::Custom(0x2c),
+    ];
+    for (i, dim) in dims.into_iter().enumerate() {
+        write_synthetic_world(db, &[(0, 0, dim), (-3, 7, dim)]);
+        db.put(
+            ChunkKey::new(0, 0, dim, RecordTag::BlockEntity),
+            b"be",
+            &write_opts,
+        )
+        .unwrap();
+        for n in 0..2u64 {
+            let id = ActorId((i as u64 * 10 + n).to_be_bytes());
+            db.put_actor(0, 0, dim, id, b"actor", &write_opts).unwrap();
+        }
+    }
+    for key in [
+        &b"VILLAGE_Nether_5b4b1c0e_INFO"[..],
+        b"VILLAGE_Nether_5b4b1c0e_POI",
+        b"VILLAGE_Overworld_77aa_INFO",
+        b"VILLAGE_5b4b1c0e_DWELLERS",
+    ] {
+        db.put(key, b"v", &write_opts).unwrap();
+    }
+    // An empty NBT compound in place of the synthetic `{}`
+    db.put(b"portals", [10u8, 0, 0, 0], &write_opts).unwrap();
+    dims
+}
+
+#[test]
+fn test_wipe_nether_keeps_other_dimensions() {
+    let (db, _temp_dir) = setup_world_db();
+    write_multi_dimension_world(&db);
+    let before = all_keys(&db);
+
+    let report = db
+        .wipe_dimension(Dimension::Nether, false, &WriteOptions::new())
+        .unwrap();
+    // Per chunk: Version, Data3D, FinalizedState and 3 subchunks, plus one BlockEntity
+    assert_eq!(report.chunk_records, 2 * 6 + 1);
+    assert_eq!(report.digests, 1);
+    assert_eq!(report.actors, 2);
+    assert_eq!(report.villages, 2);
+    assert!(!report.compacted);
+    assert_eq!(report.total(), 18);
+
+    let after = all_keys(&db);
+    let removed: BTreeSet<_> = before.difference(&after).cloned().collect();
+    assert_eq!(removed.len() as u64, report.total());
+    assert!(removed.iter().all(|key| !describe_is_other_dimension(key)));
+    for dim in [
+        Dimension::Overworld,
+        Dimension::End,
+        Dimension::Custom(0x2c),
+    ] {
+        assert!(db.chunk_exists(0, 0, dim, &ReadOptions::new()).unwrap());
+        assert_eq!(
+            db.actors_in_chunk(0, 0, dim, &ReadOptions::new())
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+    assert!(
+        !db.chunk_exists(0, 0, Dimension::Nether, &ReadOptions::new())
+            .unwrap()
+    );
+    assert!(after.contains(&b"VILLAGE_5b4b1c0e_DWELLERS"[..]));
+    assert!(after.contains(&b"VILLAGE_Overworld_77aa_INFO"[..]));
+
+    // Nothing is left to wipe
+    let again = db
+        .wipe_dimension(Dimension::Nether, true, &WriteOptions::new())
+        .unwrap();
+    assert_eq!(again.total(), 0);
+    assert!(!again.compacted);
+}
+
+/// Whether a key belongs to a dimension other than the Nether.
+fn describe_is_other_dimension(key: &[u8]) -> bool {
+    use crate::bedrock::{KeyDescription, describe_key};
+    match describe_key(key) {
+        KeyDescription::ChunkRecord { dim, .. } | KeyDescription::ActorDigest { dim, .. } => {
+            dim != Dimension::Nether
+        }
+        KeyDescription::Village(village) => village.dimension != Some(Dimension::Nether),
+        _ => false,
+    }
+}
+
+#[test]
+fn test_wipe_overworld_keeps_other_dimensions() {
+    let (db, _temp_dir) = setup_world_db();
+    let dims = write_multi_dimension_world(&db);
+
+    let report = db
+        .wipe_dimension(Dimension::Overworld, true, &WriteOptions::new())
+        .unwrap();
+    assert_eq!(report.chunk_records, 13);
+    assert_eq!(report.actors, 2);
+    assert_eq!(report.villages, 1);
+    assert!(report.compacted);
+
+    let read_opts = ReadOptions::new();
+    assert!(
+        !db.chunk_exists(0, 0, Dimension::Overworld, &read_opts)
+            .unwrap()
+    );
+    for dim in &dims[1..] {
+        assert!(db.chunk_exists(-3, 7, *dim, &read_opts).unwrap());
+        for y in [-4, 0, 3] {
+            assert!(
+                db.get_subchunk(0, 0, *dim, y, &read_opts)
+                    .unwrap()
+                    .is_some()
+            );
+        }
+    }
+    // String keys that also parse as Overworld chunk keys are kept
+    assert!(db.get(b"map_-12345", &read_opts).unwrap().is_some());
+    assert!(db.get(b"~local_player", &read_opts).unwrap().is_some());
+}
+
+#[test]
+fn test_wipe_read_only() {
+    let (db, temp_dir) = setup_world_db();
+    write_multi_dimension_world(&db);
+    drop(db);
+    let db = DB::open_read_only(temp_dir.path().join("db"), &Options::new()).unwrap();
+    assert!(
+        db.wipe_dimension(Dimension::Nether, false, &WriteOptions::new())
+            .is_err()
+    );
+}
+
+#[cfg(feature = "nbt")]
+#[test]
+fn test_wipe_removes_portals() {
+    use crate::bedrock::world_records::{Portal, Portals};
+
+    let (db, _temp_dir) = setup_world_db();
+    let write_opts = WriteOptions::new();
+    write_multi_dimension_world(&db);
+    let portal = |dimension, x| Portal {
+        dimension,
+        position: (x, 70, 0),
+        span: 2,
+        x_axis: 1,
+        z_axis: 0,
+        extra: Vec::new(),
+    };
+    let portals = Portals {
+        portals: vec![
+            portal(Dimension::Overworld, 8),
+            portal(Dimension::Nether, 1),
+            portal(Dimension::Nether, 2),
+        ],
+        ..Default::default()
+    };
+    db.set_portals(&portals, &write_opts).unwrap();
+
+    let report = db
+        .wipe_dimension(Dimension::Nether, false, &write_opts)
+        .unwrap();
+    assert_eq!(report.portals, 2);
+    let left = db.get_portals(&ReadOptions::new()).unwrap().unwrap();
+    assert_eq!(left.portals, [portal(Dimension::Overworld, 8)]);
+
+    // A malformed record fails the wipe before anything is deleted
+    db.put(b"portals", b"{}", &write_opts).unwrap();
+    let before = all_keys(&db);
+    assert!(
+        db.wipe_dimension(Dimension::End, false, &write_opts)
+            .is_err()
+    );
+    assert_eq!(all_keys(&db), before);
+}
@@ -0,0 +1,354 @@
+use super::{Rng, setup_world_db, write_synthetic_world};
+use crate::bedrock::{ChunkKey, Dimension, RecordTag};
+use crate::options::{ReadOptions, WriteOptions};
+use std::collections::HashSet;
+
+#[test]
+fn test_subchunk_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    for dim in [Dimension::Overworld, Dimension::Nether, Dimension::End] {
+        for y in [-4i8, -1, 0, 5, 19] {
+            let blob = format!("{}:{}", dim, y);
+            db.put_subchunk(3, -7, dim, y, &blob, &write_opts).unwrap();
+            let read = db.get_subchunk(3, -7, dim, y, &read_opts).unwrap();
+            assert_eq!(read.as_deref(), Some(blob.as_bytes()));
+        }
+    }
+    assert_eq!(
+        db.get_subchunk(3, -7, Dimension::Nether, 6, &read_opts)
+            .unwrap(),
+        None
+    );
+
+    // The raw keys follow the on-disk layout, with the y index as a signed byte
+    let mut overworld = Vec::new();
+    overworld.extend_from_slice(&3i32.to_le_bytes());
+    overworld.extend_from_slice(&(-7i32).to_le_bytes());
+    overworld.extend_from_slice(&[47, 0xfc]);
+    assert_eq!(
+        db.get(&overworld, &read_opts).unwrap().as_deref(),
+        Some(&b"overworld:-4"[..])
+    );
+
+    let mut end = Vec::new();
+    end.extend_from_slice(&3i32.to_le_bytes());
+    end.extend_from_slice(&(-7i32).to_le_bytes());
+    end.extend_from_slice(&2i32.to_le_bytes());
+    end.extend_from_slice(&[47, 19]);
+    assert_eq!(
+        db.get(&end, &read_opts).unwrap().as_deref(),
+        Some(&b"the_end:19"[..])
+    );
+}
+
+#[test]
+fn test_subchunks_of() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    assert!(
+        db.subchunks_of(3, -2, Dimension::Overworld, &read_opts)
+            .unwrap()
+            .is_empty()
+    );
+
+    for y in [19i8, 5, 0, -1, -4] {
+        db.put_subchunk(3, -2, Dimension::Overworld, y, [y as u8], &write_opts)
+            .unwrap();
+    }
+    // Neighbours: other dimensions, including one whose first id byte is the
+    // SubChunkPrefix tag, the chunk's other records and the next chunk
+    db.put_subchunk(3, -2, Dimension::Nether, 7, [0u8], &write_opts)
+        .unwrap();
+    db.put_subchunk(3, -2, Dimension::Custom(0x2f), 2, [0u8], &write_opts)
+        .unwrap();
+    db.put(
+        ChunkKey::new(3, -2, Dimension::Overworld, RecordTag::BlockEntity),
+        [0u8],
+        &write_opts,
+    )
+    .unwrap();
+    db.put_subchunk(4, -2, Dimension::Overworld, 1, [0u8], &write_opts)
+        .unwrap();
+
+    // On disk the index is the last key byte, so negative indices sort last
+    let stored: Vec<u8> = db
+        .iter(&read_opts)
+        .filter_map(|(key, _)| ChunkKey::parse(&key).ok())
+        .filter(|key| {
+            (key.x(), key.z(), key.dimension()) == (3, -2, Dimension::Overworld)
+                && key.tag() == RecordTag::SubChunkPrefix
+        })
+        .map(|key| *key.as_bytes().last().unwrap())
+        .collect();
+    assert_eq!(stored, [0x00, 0x05, 0x13, 0xfc, 0xff]);
+
+    assert_eq!(
+        db.subchunks_of(3, -2, Dimension::Overworld, &read_opts)
+            .unwrap(),
+        [-4, -1, 0, 5, 19]
+    );
+    assert_eq!(
+        db.subchunks_of(3, -2, Dimension::Nether, &read_opts)
+            .unwrap(),
+        [7]
+    );
+    assert_eq!(
+        db.subchunks_of(3, -2, Dimension::Custom(0x2f), &read_opts)
+            .unwrap(),
+        [2]
+    );
+    for y in [-4i8, -1, 0, 5, 19] {
+        assert_eq!(
+            db.get_subchunk(3, -2, Dimension::Overworld, y, &read_opts)
+                .unwrap(),
+            Some(vec![y as u8])
+        );
+    }
+}
+
+#[test]
+fn test_chunk_positions() {
+    let (db, _temp_dir) = setup_world_db();
+    let overworld = [(0, 0), (1, 0), (-1, -1), (i32::MAX, i32::MIN), (300, -20)];
+    let nether = [(0, 0), (-5, 7)];
+    let end = [(100, 100)];
+
+    let mut chunks = Vec::new();
+    chunks.extend(overworld.iter().map(|&(x, z)| (x, z, Dimension::Overworld)));
+    chunks.extend(nether.iter().map(|&(x, z)| (x, z, Dimension::Nether)));
+    chunks.extend(end.iter().map(|&(x, z)| (x, z, Dimension::End)));
+    write_synthetic_world(&db, &chunks);
+
+    let read_opts = ReadOptions::new();
+    for (dim, expected) in [
+        (Dimension::Overworld, &overworld[..]),
+        (Dimension::Nether, &nether[..]),
+        (Dimension::End, &end[..]),
+        (Dimension::Custom(7), &[][..]),
+    ] {
+        let positions: Vec<(i32, i32)> = db.chunk_positions(dim, &read_opts).collect();
+        let unique: HashSet<(i32, i32)> = positions.iter().copied().collect();
+        assert_eq!(positions.len(), unique.len(), "duplicates in {}", dim);
+        assert_eq!(unique, expected.iter().copied().collect::<HashSet<_>>());
+    }
+}
+
+#[test]
+fn test_chunk_positions_skip_map_keys() {
+    let (db, _temp_dir) = setup_world_db();
+    write_synthetic_world(
+        &db,
+        &[(0, 0, Dimension::Overworld), (-2, 3, Dimension::Overworld)],
+    );
+    // Nine bytes ending in a digit, like an Overworld chunk key
+    assert!(ChunkKey::parse(b"map_12345").is_ok());
+    db.put_map(12345, b"map", &WriteOptions::new()).unwrap();
+
+    let positions: HashSet<(i32, i32)> = db
+        .chunk_positions(Dimension::Overworld, &ReadOptions::new())
+        .collect();
+    assert_eq!(positions, HashSet::from([(0, 0), (-2, 3)]));
+}
+
+#[test]
+fn test_dimensions() {
+    let read_opts = ReadOptions::new();
+
+    let (db, _temp_dir) = setup_world_db();
+    assert_eq!(db.dimensions(&read_opts).unwrap(), []);
+    db.put(b"~local_player", b"{}", &WriteOptions::new())
+        .unwrap();
+    assert_eq!(db.dimensions(&read_opts).unwrap(), []);
+    // Has the layout of an Overworld chunk key
+    db.put_map(12345, b"map", &WriteOptions::new()).unwrap();
+    assert_eq!(db.dimensions(&read_opts).unwrap(), []);
+
+    write_synthetic_world(
+        &db,
+        &[(0, 0, Dimension::Overworld), (-3, 9, Dimension::Overworld)],
+    );
+    assert_eq!(db.dimensions(&read_opts).unwrap(), [Dimension::Overworld]);
+
+    let (db, _temp_dir) = setup_world_db();
+    write_synthetic_world(
+        &db,
+        &[
+            (0, 0, Dimension::Overworld),
+            (0, 0, Dimension::Nether),
+            (5, 5, Dimension::Overworld),
+            (-1, -1, Dimension::End),
+            (-1, -1, Dimension::Overworld),
+            // The id starts with the Version tag byte
+            (-1, -1, Dimension::Custom(0x2c)),
+            (i32::MAX, i32::MAX, Dimension::Custom(-1)),
+        ],
+    );
+    assert_eq!(
+        db.dimensions(&read_opts).unwrap(),
+        [
+            Dimension::Overworld,
+            Dimension::Nether,
+            Dimension::End,
+            Dimension::Custom(-1),
+            Dimension::Custom(0x2c),
+        ]
+    );
+}
+
+#[test]
+fn test_chunk_version() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    let dim = Dimension::Nether;
+
+    // Only the legacy record
+    db.put(
+        ChunkKey::new(0, 0, dim, RecordTag::LegacyVersion),
+        [7u8],
+        &write_opts,
+    )
+    .unwrap();
+    // Only the current record
+    db.put(
+        ChunkKey::new(1, 0, dim, RecordTag::Version),
+        [40u8],
+        &write_opts,
+    )
+    .unwrap();
+    // Both; the current one wins
+    db.put(
+        ChunkKey::new(2, 0, dim, RecordTag::LegacyVersion),
+        [9u8],
+        &write_opts,
+    )
+    .unwrap();
+    db.put(
+        ChunkKey::new(2, 0, dim, RecordTag::Version),
+        [22u8],
+        &write_opts,
+    )
+    .unwrap();
+    db.put(
+        ChunkKey::new(3, 0, dim, RecordTag::Version),
+        [],
+        &write_opts,
+    )
+    .unwrap();
+
+    assert_eq!(db.chunk_version(0, 0, dim, &read_opts).unwrap(), Some(7));
+    assert_eq!(db.chunk_version(1, 0, dim, &read_opts).unwrap(), Some(40));
+    assert_eq!(db.chunk_version(2, 0, dim, &read_opts).unwrap(), Some(22));
+    assert!(db.chunk_version(3, 0, dim, &read_opts).is_err());
+    assert_eq!(
+        db.chunk_version(0, 0, Dimension::Overworld, &read_opts)
+            .unwrap(),
+        None
+    );
+
+    db.set_chunk_version(0, 0, dim, 41, &write_opts).unwrap();
+    assert_eq!(db.chunk_version(0, 0, dim, &read_opts).unwrap(), Some(41));
+    assert_eq!(
+        db.get(
+            ChunkKey::new(0, 0, dim, RecordTag::LegacyVersion),
+            &read_opts
+        )
+        .unwrap(),
+        None
+    );
+    assert_eq!(
+        db.get(ChunkKey::new(0, 0, dim, RecordTag::Version), &read_opts)
+            .unwrap(),
+        Some(vec![41])
+    );
+}
+
+#[test]
+fn test_chunks_in_bounds() {
+    let (db, _temp_dir) = setup_world_db();
+    let read_opts = ReadOptions::new();
+    let mut rng = Rng::new(0xC0FFEE);
+
+    let mut chunks = Vec::new();
+    for _ in 0..300 {
+        let x = (rng.next_u64() % 41) as i32 - 20;
+        let z = (rng.next_u64() % 41) as i32 - 20;
+        let dim = [Dimension::Overworld, Dimension::Nether][(rng.next_u64() % 2) as usize];
+        chunks.push((x, z, dim));
+    }
+    // Extremes whose little-endian bytes sort far from their neighbours
+    chunks.push((i32::MIN, i32::MAX, Dimension::Overworld));
+    chunks.push((-1, 255, Dimension::Overworld));
+    chunks.push((-1, 256, Dimension::Overworld));
+    write_synthetic_world(&db, &chunks);
+
+    for dim in [Dimension::Overworld, Dimension::Nether, Dimension::End] {
+        let all: Vec<(i32, i32)> = db.chunk_positions(dim, &read_opts).collect();
+        for (min, max) in [
+            ((-5, -5), (5, 5)),
+            ((-20, -3), (-1, 3)),
+            ((0, -20), (0, 20)),
+            ((-1, 200), (0, 300)),
+            ((i32::MIN, i32::MAX - 1), (i32::MIN + 1, i32::MAX)),
+            ((3, 3), (2, 2)),
+        ] {
+            let mut expected: Vec<(i32, i32)> = all
+                .iter()
+                .copied()
+                .filter(|&(x, z)| (min.0..=max.0).contains(&x) && (min.1..=max.1).contains(&z))
+                .collect();
+            expected.sort();
+            let found: Vec<(i32, i32)> = db.chunks_in_bounds(dim, min, max, &read_opts).collect();
+            assert_eq!(found, expected, "{} in {:?}..={:?}", dim, min, max);
+        }
+    }
+}
+
+#[test]
+fn test_chunk_exists() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    write_synthetic_world(&db, &[(0, 0, Dimension::Overworld)]);
+    // Only a legacy version record, as saved before 1.16.100
+    db.put(
+        ChunkKey::new(-2, 5, Dimension::Overworld, RecordTag::LegacyVersion),
+        [15u8],
+        &write_opts,
+    )
+    .unwrap();
+    // Other records but no version: not a generated chunk
+    db.put(
+        ChunkKey::new(3, 3, Dimension::Overworld, RecordTag::Data2D),
+        [0u8; 768],
+        &write_opts,
+    )
+    .unwrap();
+    // A version record of the same position in another dimension
+    db.put(
+        ChunkKey::new(7, 7, Dimension::End, RecordTag::Version),
+        [40u8],
+        &write_opts,
+    )
+    .unwrap();
+
+    let dim = Dimension::Overworld;
+    assert!(db.chunk_exists(0, 0, dim, &read_opts).unwrap());
+    assert!(db.chunk_exists(-2, 5, dim, &read_opts).unwrap());
+    assert!(!db.chunk_exists(3, 3, dim, &read_opts).unwrap());
+    assert!(!db.chunk_exists(1, 0, dim, &read_opts).unwrap());
+    assert!(!db.chunk_exists(7, 7, dim, &read_opts).unwrap());
+    assert!(db.chunk_exists(7, 7, Dimension::End, &read_opts).unwrap());
+
+    let positions = [(1, 0), (7, 7), (-2, 5), (0, 0), (3, 3), (0, 0)];
+    assert_eq!(
+        db.chunks_exist(&positions, dim, &read_opts).unwrap(),
+        vec![false, false, true, true, false, true]
+    );
+    assert_eq!(
+        db.chunks_exist(&positions, Dimension::End, &read_opts)
+            .unwrap(),
+        vec![false, true, false, false, false, false]
+    );
+    assert!(db.chunks_exist(&[], dim, &read_opts).unwrap().is_empty());
+}
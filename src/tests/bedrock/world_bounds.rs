@@ -0,0 +1,100 @@
+use super::{setup_world_db, write_synthetic_world};
+use crate::bedrock::{ChunkBounds, Dimension};
+use crate::options::{ReadOptions, WriteOptions};
+
+fn bounds_of(chunks: &[(i32, i32, Dimension)], dim: Dimension) -> Option<ChunkBounds> {
+    let (db, _temp_dir) = setup_world_db();
+    write_synthetic_world(&db, chunks);
+    db.world_bounds(dim, &ReadOptions::new()).unwrap()
+}
+
+#[test]
+fn test_world_bounds() {
+    use Dimension::{End, Nether, Overworld};
+
+    let negative = bounds_of(
+        &[
+            (-1, -1, Overworld),
+            (-300, -2, Overworld),
+            (-5, -70, Overworld),
+        ],
+        Overworld,
+    )
+    .unwrap();
+    assert_eq!(
+        negative,
+        ChunkBounds {
+            min_x: -300,
+            min_z: -70,
+            max_x: -1,
+            max_z: -1
+        }
+    );
+    assert_eq!(negative.to_block_coords(), ((-4800, -1120), (-1, -1)));
+
+    let positive = bounds_of(
+        &[
+            (0, 0, Nether),
+            (255, 3, Nether),
+            (256, 1, Nether),
+            (7, 9, Overworld),
+        ],
+        Nether,
+    )
+    .unwrap();
+    assert_eq!(
+        positive,
+        ChunkBounds {
+            min_x: 0,
+            min_z: 0,
+            max_x: 256,
+            max_z: 3
+        }
+    );
+    assert_eq!(
+        (positive.width(), positive.depth(), positive.area()),
+        (257, 4, 1028)
+    );
+
+    let straddling = bounds_of(&[(-2, 5, End), (3, -4, End), (0, 0, End)], End).unwrap();
+    assert_eq!(straddling.to_block_coords(), ((-32, -64), (63, 95)));
+    assert_eq!(straddling.area(), 6 * 10);
+    assert!(straddling.contains(0, 5));
+    assert!(!straddling.contains(4, 0));
+
+    assert_eq!(bounds_of(&[(1, 1, Overworld)], End), None);
+
+    let extreme = ChunkBounds {
+        min_x: i32::MIN,
+        min_z: 0,
+        max_x: i32::MAX,
+        max_z: 0,
+    };
+    assert_eq!(extreme.area(), 1 << 32);
+    assert_eq!(extreme.to_block_coords().1.0, i32::MAX as i64 * 16 + 15);
+}
+
+#[test]
+fn test_world_bounds_skip_map_keys() {
+    let (db, _temp_dir) = setup_world_db();
+    write_synthetic_world(
+        &db,
+        &[(-3, 2, Dimension::Overworld), (4, 5, Dimension::Overworld)],
+    );
+    // Parses as an Overworld chunk at x = 0x5f70616d
+    db.put_map(12345, b"map", &WriteOptions::new()).unwrap();
+
+    let bounds = db
+        .world_bounds(Dimension::Overworld, &ReadOptions::new())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        bounds,
+        ChunkBounds {
+            min_x: -3,
+            min_z: 2,
+            max_x: 4,
+            max_z: 5
+        }
+    );
+}
@@ -0,0 +1,127 @@
+use std::fs;
+use std::path::Path;
+
+use crate::DB;
+use crate::bedrock::WorldDir;
+use crate::bedrock::nbt::{self, Value};
+use crate::options::{Options, ReadOptions, WriteOptions};
+
+fn level_dat_nbt() -> Value {
+    Value::Compound(vec![
+        (
+            "LevelName".to_string(),
+            Value::String("Old Name".to_string()),
+        ),
+        ("LastPlayed".to_string(), Value::Long(1_700_000_000)),
+        ("StorageVersion".to_string(), Value::Int(10)),
+        ("GameType".to_string(), Value::Int(1)),
+    ])
+}
+
+/// Build a minimal world directory with a hand-written `level.dat`.
+pub(crate) fn write_world(path: &Path, level_name: Option<&str>) {
+    let options = Options::bedrock();
+    options.create_if_missing(true);
+    let db = DB::open(path.join("db"), &options).unwrap();
+    db.put(b"~local_player", b"player", &WriteOptions::new())
+        .unwrap();
+    drop(db);
+
+    let payload = nbt::encode(&level_dat_nbt());
+    let mut data = 10i32.to_le_bytes().to_vec();
+    data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    data.extend_from_slice(&payload);
+    fs::write(path.join("level.dat"), data).unwrap();
+    if let Some(name) = level_name {
+        fs::write(path.join("levelname.txt"), name).unwrap();
+    }
+}
+
+#[test]
+fn test_world_dir_accessors() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    write_world(temp_dir.path(), Some("My World\n"));
+
+    let world = WorldDir::open(temp_dir.path()).unwrap();
+    assert_eq!(world.path(), temp_dir.path());
+    assert_eq!(world.name(), Some("My World"));
+    assert_eq!(world.last_played(), Some(1_700_000_000));
+    assert_eq!(world.storage_version(), 10);
+    assert_eq!(world.level_dat(), &level_dat_nbt());
+    assert_eq!(
+        world
+            .db()
+            .get(b"~local_player", &ReadOptions::new())
+            .unwrap(),
+        Some(b"player".to_vec())
+    );
+}
+
+#[test]
+fn test_world_dir_name_falls_back_to_level_dat() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    write_world(temp_dir.path(), None);
+    let world = WorldDir::open(temp_dir.path()).unwrap();
+    assert_eq!(world.name(), Some("Old Name"));
+}
+
+#[test]
+fn test_world_dir_save_level_dat() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    write_world(temp_dir.path(), None);
+    let original = fs::read(temp_dir.path().join("level.dat")).unwrap();
+
+    let mut world = WorldDir::open(temp_dir.path()).unwrap();
+    world.save_level_dat().unwrap();
+    assert_eq!(
+        fs::read(temp_dir.path().join("level.dat")).unwrap(),
+        original
+    );
+
+    world
+        .level_dat_mut()
+        .insert("LastPlayed", Value::Long(1_800_000_000));
+    world.save_level_dat().unwrap();
+    drop(world);
+    assert!(!temp_dir.path().join("level.dat.tmp").exists());
+
+    let world = WorldDir::open(temp_dir.path()).unwrap();
+    assert_eq!(world.last_played(), Some(1_800_000_000));
+    assert_eq!(world.storage_version(), 10);
+}
+
+#[test]
+fn test_world_dir_rejects_bad_layouts() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let err = WorldDir::open(temp_dir.path().join("missing"))
+        .err()
+        .unwrap();
+    assert!(err.contains("not a directory"), "{}", err);
+    let err = WorldDir::open(temp_dir.path()).err().unwrap();
+    assert!(err.contains("level.dat"), "{}", err);
+
+    write_world(temp_dir.path(), None);
+    let level_dat = temp_dir.path().join("level.dat");
+    let data = fs::read(&level_dat).unwrap();
+
+    fs::write(&level_dat, &data[..data.len() - 1]).unwrap();
+    let err = WorldDir::open(temp_dir.path()).err().unwrap();
+    assert!(err.contains("header announces"), "{}", err);
+
+    fs::write(&level_dat, &data[..4]).unwrap();
+    let err = WorldDir::open(temp_dir.path()).err().unwrap();
+    assert!(err.contains("too short"), "{}", err);
+
+    let mut not_compound = 10i32.to_le_bytes().to_vec();
+    let payload = nbt::encode(&Value::Int(1));
+    not_compound.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    not_compound.extend_from_slice(&payload);
+    fs::write(&level_dat, not_compound).unwrap();
+    let err = WorldDir::open(temp_dir.path()).err().unwrap();
+    assert!(err.contains("compound"), "{}", err);
+
+    fs::write(&level_dat, &data).unwrap();
+    fs::remove_dir_all(temp_dir.path().join("db")).unwrap();
+    let err = WorldDir::open(temp_dir.path()).err().unwrap();
+    assert!(err.contains("db"), "{}", err);
+}
@@ -0,0 +1,94 @@
+use super::{setup_world_db, write_synthetic_world};
+use crate::DB;
+use crate::bedrock::{ChunkKey, Dimension, RecordTag, WorldFormat};
+use crate::options::{ReadOptions, WriteOptions};
+
+/// Write a chunk the way versions before 1.0 did: 9-byte Overworld keys,
+/// one terrain record and optionally a `v` version record.
+fn write_legacy_chunk(db: &DB, x: i32, z: i32, with_version: bool) {
+    let write_opts = WriteOptions::new();
+    db.put(
+        ChunkKey::new(x, z, Dimension::Overworld, RecordTag::LegacyTerrain),
+        vec![0u8; 128],
+        &write_opts,
+    )
+    .unwrap();
+    db.put(
+        ChunkKey::new(x, z, Dimension::Overworld, RecordTag::Entity),
+        b"",
+        &write_opts,
+    )
+    .unwrap();
+    if with_version {
+        db.put(
+            ChunkKey::new(x, z, Dimension::Overworld, RecordTag::LegacyVersion),
+            [2u8],
+            &write_opts,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn test_legacy_keys_parse() {
+    // x = 3, z = -1, tag 'v'
+    let key = [3, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, b'v'];
+    let parsed = ChunkKey::parse(&key).unwrap();
+    assert_eq!((parsed.x(), parsed.z()), (3, -1));
+    assert_eq!(parsed.tag(), RecordTag::LegacyVersion);
+    let key = [3, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0x30];
+    assert_eq!(
+        ChunkKey::parse(&key).unwrap().tag(),
+        RecordTag::LegacyTerrain
+    );
+}
+
+#[test]
+fn test_detect_world_format() {
+    let read_opts = ReadOptions::new();
+
+    let (db, _temp_dir) = setup_world_db();
+    assert_eq!(db.detect_world_format(&read_opts).unwrap(), None);
+    db.put(b"~local_player", b"player", &WriteOptions::new())
+        .unwrap();
+    assert_eq!(db.detect_world_format(&read_opts).unwrap(), None);
+
+    let (legacy, _legacy_dir) = setup_world_db();
+    for x in -3..3 {
+        write_legacy_chunk(&legacy, x, x * 2, true);
+    }
+    assert_eq!(
+        legacy.detect_world_format(&read_opts).unwrap(),
+        Some(WorldFormat::Legacy)
+    );
+    let positions: Vec<_> = legacy
+        .chunk_positions(Dimension::Overworld, &read_opts)
+        .collect();
+    assert_eq!(positions.len(), 6);
+    assert!(positions.contains(&(-3, -6)));
+
+    let (terrain_only, _terrain_dir) = setup_world_db();
+    write_legacy_chunk(&terrain_only, 0, 0, false);
+    write_legacy_chunk(&terrain_only, 200, -7, false);
+    assert_eq!(
+        terrain_only.detect_world_format(&read_opts).unwrap(),
+        Some(WorldFormat::LegacyTerrainOnly)
+    );
+
+    let (modern, _modern_dir) = setup_world_db();
+    write_synthetic_world(
+        &modern,
+        &[(0, 0, Dimension::Overworld), (5, 5, Dimension::Nether)],
+    );
+    assert_eq!(
+        modern.detect_world_format(&read_opts).unwrap(),
+        Some(WorldFormat::Modern)
+    );
+
+    // A partly upgraded world
+    write_legacy_chunk(&modern, 100, 100, true);
+    assert_eq!(
+        modern.detect_world_format(&read_opts).unwrap(),
+        Some(WorldFormat::Mixed)
+    );
+}
@@ -0,0 +1,211 @@
+use super::setup_world_db;
+use crate::bedrock::Dimension;
+use crate::bedrock::keys::{AUTONOMOUS_ENTITIES, BIOME_DATA, MOB_EVENTS, PORTALS};
+use crate::bedrock::nbt::{self, Value};
+use crate::bedrock::world_records::{AutonomousEntities, BiomeData, MobEvents, Portals};
+use crate::options::{ReadOptions, WriteOptions};
+
+fn compound(entries: Vec<(&str, Value)>) -> Value {
+    Value::Compound(
+        entries
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    )
+}
+
+fn stored_nbt(db: &crate::DB, key: &[u8]) -> Value {
+    nbt::decode(&db.get(key, &ReadOptions::new()).unwrap().unwrap()).unwrap()
+}
+
+#[test]
+fn test_biome_data_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    assert_eq!(db.get_biome_data(&read_opts).unwrap(), None);
+
+    let record = compound(vec![
+        (
+            "list",
+            Value::list(vec![
+                compound(vec![
+                    ("id", Value::Byte(12)),
+                    ("snowAccumulation", Value::Float(0.25)),
+                ]),
+                compound(vec![
+                    ("id", Value::Byte(-116)),
+                    ("snowAccumulation", Value::Float(0.5)),
+                    ("futureLevel", Value::Short(3)),
+                ]),
+            ]),
+        ),
+        ("futureRoot", Value::String("kept".to_string())),
+    ]);
+    db.put(BIOME_DATA, nbt::encode(&record), &write_opts)
+        .unwrap();
+
+    let mut data = db.get_biome_data(&read_opts).unwrap().unwrap();
+    assert_eq!(data.biomes.len(), 2);
+    assert_eq!(data.biomes[1].biome, 140);
+    data.biomes[0].snow_accumulation = 0.75;
+    db.set_biome_data(&data, &write_opts).unwrap();
+
+    assert_eq!(db.get_biome_data(&read_opts).unwrap().unwrap(), data);
+    let stored = stored_nbt(&db, BIOME_DATA);
+    let biomes = stored.get("list").and_then(Value::as_list).unwrap();
+    assert_eq!(biomes[0].get("snowAccumulation"), Some(&Value::Float(0.75)));
+    assert_eq!(biomes[1].get("id"), Some(&Value::Byte(-116)));
+    assert_eq!(biomes[1].get("futureLevel"), Some(&Value::Short(3)));
+    assert_eq!(
+        stored.get("futureRoot"),
+        Some(&Value::String("kept".to_string()))
+    );
+}
+
+#[test]
+fn test_mob_events_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    let record = compound(vec![
+        ("events_enabled", Value::Byte(1)),
+        ("minecraft:ender_dragon_event", Value::Byte(1)),
+        ("minecraft:pillager_patrols_event", Value::Byte(1)),
+        ("minecraft:wandering_trader_event", Value::Byte(0)),
+        ("minecraft:future_event", Value::Byte(1)),
+    ]);
+    db.put(MOB_EVENTS, nbt::encode(&record), &write_opts)
+        .unwrap();
+
+    let mut events = db.get_mob_events(&read_opts).unwrap().unwrap();
+    assert_eq!(
+        (
+            events.events_enabled,
+            events.ender_dragon,
+            events.pillager_patrols,
+            events.wandering_trader
+        ),
+        (Some(true), Some(true), Some(true), Some(false))
+    );
+    events.pillager_patrols = Some(false);
+    db.set_mob_events(&events, &write_opts).unwrap();
+
+    let stored = stored_nbt(&db, MOB_EVENTS);
+    assert_eq!(
+        stored.get("minecraft:pillager_patrols_event"),
+        Some(&Value::Byte(0))
+    );
+    assert_eq!(stored.get("minecraft:future_event"), Some(&Value::Byte(1)));
+
+    // Toggles that were never stored are not written
+    db.set_mob_events(&MobEvents::default(), &write_opts)
+        .unwrap();
+    assert_eq!(stored_nbt(&db, MOB_EVENTS), Value::Compound(Vec::new()));
+}
+
+#[test]
+fn test_portals_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    let portal = |dim: i32, x: i32, extra: Option<(&str, Value)>| {
+        let mut entries = vec![
+            ("DimId", Value::Int(dim)),
+            ("Span", Value::Byte(2)),
+            ("TpX", Value::Int(x)),
+            ("TpY", Value::Int(64)),
+            ("TpZ", Value::Int(-30)),
+            ("Xa", Value::Byte(1)),
+            ("Za", Value::Byte(0)),
+        ];
+        entries.extend(extra);
+        compound(entries)
+    };
+    let record = compound(vec![(
+        "data",
+        compound(vec![
+            (
+                "PortalRecords",
+                Value::list(vec![
+                    portal(0, 100, None),
+                    portal(1, 12, Some(("Linked", Value::Byte(1)))),
+                ]),
+            ),
+            ("Version", Value::Int(2)),
+        ]),
+    )]);
+    db.put(PORTALS, nbt::encode(&record), &write_opts).unwrap();
+
+    let mut portals = db.get_portals(&read_opts).unwrap().unwrap();
+    assert_eq!(portals.portals.len(), 2);
+    assert_eq!(portals.portals[1].dimension, Dimension::Nether);
+    assert_eq!(portals.portals[0].position, (100, 64, -30));
+    assert_eq!(portals.portals[0].span, 2);
+    portals.portals.remove(0);
+    db.set_portals(&portals, &write_opts).unwrap();
+
+    assert_eq!(db.get_portals(&read_opts).unwrap().unwrap(), portals);
+    let data = stored_nbt(&db, PORTALS).get("data").unwrap().clone();
+    assert_eq!(data.get("Version"), Some(&Value::Int(2)));
+    let records = data.get("PortalRecords").and_then(Value::as_list).unwrap();
+    assert_eq!(records, [portal(1, 12, Some(("Linked", Value::Byte(1))))]);
+
+    assert_eq!(
+        Portals::from_nbt(Value::Compound(Vec::new())).unwrap(),
+        Portals::default()
+    );
+}
+
+#[test]
+fn test_autonomous_entities_round_trip() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    let trader = compound(vec![(
+        "identifier",
+        Value::String("minecraft:wandering_trader".to_string()),
+    )]);
+    let record = compound(vec![
+        ("AutonomousEntityList", Value::list(vec![trader.clone()])),
+        ("FutureCount", Value::Int(1)),
+    ]);
+    db.put(AUTONOMOUS_ENTITIES, nbt::encode(&record), &write_opts)
+        .unwrap();
+
+    let mut entities = db.get_autonomous_entities(&read_opts).unwrap().unwrap();
+    assert_eq!(entities.entities, [trader]);
+    entities.entities.clear();
+    db.set_autonomous_entities(&entities, &write_opts).unwrap();
+
+    let stored = stored_nbt(&db, AUTONOMOUS_ENTITIES);
+    assert_eq!(stored.get("FutureCount"), Some(&Value::Int(1)));
+    assert_eq!(
+        db.get_autonomous_entities(&read_opts).unwrap().unwrap(),
+        AutonomousEntities {
+            entities: Vec::new(),
+            extra: vec![("FutureCount".to_string(), Value::Int(1))],
+        }
+    );
+}
+
+#[test]
+fn test_world_records_invalid() {
+    let (db, _temp_dir) = setup_world_db();
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+    db.put(MOB_EVENTS, [1u8, 2, 3], &write_opts).unwrap();
+    let err = db.get_mob_events(&read_opts).unwrap_err();
+    assert!(err.contains("mobevents"), "{}", err);
+
+    let wrong_type = compound(vec![("list", Value::Int(3))]);
+    assert!(BiomeData::from_nbt(wrong_type).is_err());
+    let wrong_flag = compound(vec![("events_enabled", Value::Int(1))]);
+    assert!(MobEvents::from_nbt(wrong_flag).is_err());
+
+    // Entities must be compounds, or they could not be written back
+    let not_compounds = compound(vec![(
+        "AutonomousEntityList",
+        Value::list(vec![Value::Int(1)]),
+    )]);
+    let err = AutonomousEntities::from_nbt(not_compounds).unwrap_err();
+    assert!(err.contains("instead of a compound"), "{}", err);
+}
@@ -0,0 +1,150 @@
+use crate::options::{Options, ReadOptions, WriteOptions};
+use crate::{DB, SerdeCodec, SerdeError, WriteBatch};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tempfile::TempDir;
+
+fn setup_test_db(name: &str) -> (DB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join(name);
+
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(&db_path, &options).expect("Failed to open database");
+    (db, temp_dir)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Bookmark {
+    name: String,
+    position: (i32, i32),
+    tags: Vec<String>,
+    visits: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Job {
+    Idle,
+    Pruning { done: u64, total: u64 },
+    Copying(String, Option<i32>),
+}
+
+struct Json;
+
+impl SerdeCodec for Json {
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, String> {
+        serde_json::to_vec(value).map_err(|e| e.to_string())
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, String> {
+        serde_json::from_slice(bytes).map_err(|e| e.to_string())
+    }
+}
+
+fn bookmark() -> Bookmark {
+    Bookmark {
+        name: "spawn".to_string(),
+        position: (12, -300),
+        tags: vec!["base".to_string(), "portal".to_string()],
+        visits: BTreeMap::from([("alex".to_string(), 3), ("steve".to_string(), 11)]),
+    }
+}
+
+#[test]
+fn test_serde_round_trip() {
+    let (db, _temp_dir) = setup_test_db("test_serde_round_trip");
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    db.put_serde(b"bookmark", &bookmark(), &write_opts).unwrap();
+    assert_eq!(db.get_serde(b"bookmark", &read_opts), Ok(Some(bookmark())));
+
+    for (i, job) in [
+        Job::Idle,
+        Job::Pruning { done: 5, total: 9 },
+        Job::Copying("nether".to_string(), Some(-1)),
+        Job::Copying(String::new(), None),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let key = format!("job{}", i);
+        db.put_serde(&key, &job, &write_opts).unwrap();
+        assert_eq!(db.get_serde::<Job>(&key, &read_opts), Ok(Some(job)));
+    }
+
+    assert_eq!(db.get_serde::<Job>(b"missing", &read_opts), Ok(None));
+}
+
+#[test]
+fn test_serde_custom_codec() {
+    let (db, _temp_dir) = setup_test_db("test_serde_custom_codec");
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    let job = Job::Pruning { done: 1, total: 2 };
+    db.put_serde_with::<Json, _>(b"job", &job, &write_opts)
+        .unwrap();
+    assert_eq!(
+        db.get(b"job", &read_opts).unwrap().unwrap(),
+        br#"{"Pruning":{"done":1,"total":2}}"#
+    );
+    assert_eq!(
+        db.get_serde_with::<Json, Job>(b"job", &read_opts),
+        Ok(Some(job))
+    );
+    // A value in one codec is not readable with the other
+    assert!(matches!(
+        db.get_serde::<Job>(b"job", &read_opts),
+        Err(SerdeError::Decode(_))
+    ));
+}
+
+#[test]
+fn test_serde_decode_errors() {
+    let (db, _temp_dir) = setup_test_db("test_serde_decode_errors");
+    let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+
+    db.put_serde(b"bookmark", &bookmark(), &write_opts).unwrap();
+    let mut bytes = db.get(b"bookmark", &read_opts).unwrap().unwrap();
+
+    // Truncated
+    db.put(b"truncated", &bytes[..bytes.len() - 3], &write_opts)
+        .unwrap();
+    // An unknown enum variant
+    db.put(b"variant", 7u32.to_le_bytes(), &write_opts).unwrap();
+    // A string length far past the end
+    bytes[0] = 0xff;
+    db.put(b"corrupt", &bytes, &write_opts).unwrap();
+
+    let err = db.get_serde::<Bookmark>(b"truncated", &read_opts);
+    assert!(matches!(err, Err(SerdeError::Decode(_))), "{:?}", err);
+    let err = db.get_serde::<Job>(b"variant", &read_opts);
+    assert!(matches!(err, Err(SerdeError::Decode(_))), "{:?}", err);
+    let err = db.get_serde::<Bookmark>(b"corrupt", &read_opts);
+    assert!(matches!(err, Err(SerdeError::Decode(_))), "{:?}", err);
+    assert!(err.unwrap_err().to_string().starts_with("Failed to decode"));
+}
+
+#[test]
+fn test_serde_write_batch() {
+    let (db, _temp_dir) = setup_test_db("test_serde_write_batch");
+    let read_opts = ReadOptions::new();
+
+    let mut batch = WriteBatch::new();
+    batch.put_serde(b"bookmark", &bookmark()).unwrap();
+    batch.put_serde_with::<Json, _>(b"job", &Job::Idle).unwrap();
+    batch.put_serde(b"slice", &[1u16, 2, 3][..]).unwrap();
+    assert_eq!(batch.len(), 3);
+    db.write(&batch, &WriteOptions::new()).unwrap();
+
+    assert_eq!(db.get_serde(b"bookmark", &read_opts), Ok(Some(bookmark())));
+    assert_eq!(
+        db.get_serde_with::<Json, Job>(b"job", &read_opts),
+        Ok(Some(Job::Idle))
+    );
+    assert_eq!(
+        db.get_serde::<Vec<u16>>(b"slice", &read_opts),
+        Ok(Some(vec![1, 2, 3]))
+    );
+}
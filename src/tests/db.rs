@@ -213,10 +213,11 @@ fn test_db_compact_range() {
     }
 
     // Compact specific range
-    db.compact_range(Some(b"key_010"), Some(b"key_090"));
+    db.compact_range(Some(b"key_010"), Some(b"key_090"))
+        .expect("Compact range failed");
 
     // Compact entire database
-    db.compact_range(None, None);
+    db.compact_range(None, None).expect("Compaction failed");
 
     // Database should still work after compaction
     let read_opts = ReadOptions::new();
@@ -228,22 +229,45 @@ fn test_db_compact_range() {
 
 #[test]
 fn test_db_flush() {
-    let (db, _temp_dir) = setup_test_db("test_flush");
-    let write_opts = WriteOptions::new();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_flush");
+    let options = Options::new();
+    options.create_if_missing(true);
     let read_opts = ReadOptions::new();
 
-    // Add data
-    db.put(b"flush_key", b"flush_value", &write_opts)
+    let db = DB::open(&db_path, &options).expect("Failed to open database");
+    db.put(b"flush_key", b"flush_value", &WriteOptions::new())
         .expect("Put before flush failed");
 
-    // Flush (compact_range with None, None)
-    db.flush();
+    // Flushing only syncs the log; it must not write table files
+    db.flush().expect("Flush failed");
+    assert_eq!(db.disk_usage().unwrap().table_files, 0);
 
-    // Data should still be accessible
     let result = db
         .get(b"flush_key", &read_opts)
         .expect("Get after flush failed");
     assert_eq!(result, Some(b"flush_value".to_vec()));
+
+    drop(db);
+    let db = DB::open(&db_path, &options).expect("Failed to reopen database");
+    assert_eq!(
+        db.get(b"flush_key", &read_opts).unwrap(),
+        Some(b"flush_value".to_vec())
+    );
+}
+
+#[test]
+fn test_db_compact_all() {
+    let (db, _temp_dir) = setup_test_db("test_compact_all");
+    let write_opts = WriteOptions::new();
+    for i in 0..100 {
+        let key = format!("key_{:03}", i);
+        db.put(key.as_bytes(), b"value", &write_opts).unwrap();
+    }
+
+    db.compact_all().expect("Compaction failed");
+    assert!(db.disk_usage().unwrap().table_files > 0);
+    assert_eq!(db.count_keys(&ReadOptions::new()).unwrap(), 100);
 }
 
 #[test]
@@ -326,3 +350,917 @@ fn test_db_error_messages() {
     // Error message should be descriptive
     println!("Error message: {}", error);
 }
+
+#[test]
+fn test_db_destroy() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_destroy");
+
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    {
+        let db = DB::open(&db_path, &options).expect("Failed to open database");
+        db.put(b"key", b"value", &WriteOptions::new())
+            .expect("Put failed");
+    }
+    assert!(db_path.join("CURRENT").exists());
+
+    DB::destroy(&db_path, &options).expect("Destroy failed");
+    assert!(!db_path.join("CURRENT").exists());
+    assert!(!db_path.join("LOCK").exists());
+
+    let reopen_opts = Options::new();
+    assert!(DB::open(&db_path, &reopen_opts).is_err());
+}
+
+#[test]
+fn test_db_destroy_keeps_foreign_files() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_destroy_foreign");
+
+    let options = Options::new();
+    options.create_if_missing(true);
+    drop(DB::open(&db_path, &options).expect("Failed to open database"));
+
+    std::fs::write(db_path.join("notes.txt"), b"keep me").unwrap();
+
+    DB::destroy(&db_path, &options).expect("Destroy failed");
+    assert!(!db_path.join("CURRENT").exists());
+    assert!(db_path.join("notes.txt").exists());
+}
+
+#[test]
+fn test_db_destroy_while_open() {
+    let (db, temp_dir) = setup_test_db("test_destroy_open");
+    let db_path = temp_dir.path().join("test_destroy_open");
+
+    let result = DB::destroy(&db_path, &Options::new());
+    assert!(result.is_err());
+
+    // The open database must be unaffected
+    db.put(b"key", b"value", &WriteOptions::new())
+        .expect("Put after failed destroy failed");
+    assert!(db_path.join("CURRENT").exists());
+}
+
+#[test]
+fn test_db_repair() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_repair");
+
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    {
+        let db = DB::open(&db_path, &options).expect("Failed to open database");
+        let write_opts = WriteOptions::new();
+        for i in 0..50 {
+            let key = format!("key_{:03}", i);
+            db.put(key.as_bytes(), b"value", &write_opts)
+                .expect("Put failed");
+        }
+        db.compact_all().expect("Compaction failed");
+    }
+
+    // Remove the metadata files to simulate a damaged world
+    for entry in std::fs::read_dir(&db_path).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        if name == "CURRENT" || name.starts_with("MANIFEST-") {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+    assert!(DB::open(&db_path, &Options::new()).is_err());
+
+    DB::repair(&db_path, &Options::new()).expect("Repair failed");
+
+    let db = DB::open(&db_path, &Options::new()).expect("Failed to reopen database");
+    let read_opts = ReadOptions::new();
+    for i in 0..50 {
+        let key = format!("key_{:03}", i);
+        let result = db.get(key.as_bytes(), &read_opts).expect("Get failed");
+        assert_eq!(result, Some(b"value".to_vec()));
+    }
+}
+
+#[test]
+fn test_db_get_pinned() {
+    let (db, _temp_dir) = setup_test_db("test_get_pinned");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    let large = vec![0x5A; 64 * 1024];
+    db.put(b"a", b"first", &write_opts).expect("Put failed");
+    db.put(b"b\x00bin", &large, &write_opts)
+        .expect("Put failed");
+    db.put(b"c", b"", &write_opts).expect("Put failed");
+
+    for key in [&b"a"[..], b"b\x00bin", b"c"] {
+        let expected = db.get(key, &read_opts).expect("Get failed").unwrap();
+        let pinned = db
+            .get_pinned(key, &read_opts)
+            .expect("Get pinned failed")
+            .expect("Key should exist");
+        assert_eq!(&*pinned, expected.as_slice());
+    }
+}
+
+#[test]
+fn test_db_get_pinned_missing() {
+    let (db, _temp_dir) = setup_test_db("test_get_pinned_missing");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    // Missing key in an empty database
+    assert!(db.get_pinned(b"missing", &read_opts).unwrap().is_none());
+
+    // A prefix of an existing key, and a key past the end, are not matches
+    db.put(b"key_long", b"value", &write_opts)
+        .expect("Put failed");
+    assert!(db.get_pinned(b"key", &read_opts).unwrap().is_none());
+    assert!(db.get_pinned(b"zzz", &read_opts).unwrap().is_none());
+}
+
+#[test]
+fn test_db_get_pinned_is_stable() {
+    let (db, _temp_dir) = setup_test_db("test_get_pinned_stable");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    db.put(b"key", b"original", &write_opts)
+        .expect("Put failed");
+    let pinned = db.get_pinned(b"key", &read_opts).unwrap().unwrap();
+
+    // The pinned view keeps seeing the value it was read at
+    db.put(b"key", b"overwritten", &write_opts)
+        .expect("Put failed");
+    assert_eq!(pinned.as_slice(), b"original");
+    drop(pinned);
+
+    assert_eq!(
+        db.get(b"key", &read_opts).unwrap(),
+        Some(b"overwritten".to_vec())
+    );
+}
+
+#[test]
+fn test_db_multi_get_sorted() {
+    let (db, _temp_dir) = setup_test_db("test_multi_get_sorted");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    db.put(b"a", b"1", &write_opts).expect("Put failed");
+    db.put(b"c", b"3", &write_opts).expect("Put failed");
+    db.put(b"e", b"5", &write_opts).expect("Put failed");
+
+    let keys: [&[u8]; 6] = [b"a", b"b", b"c", b"c", b"d", b"e"];
+    let values = db.multi_get(&keys, &read_opts).expect("Multi get failed");
+    assert_eq!(
+        values,
+        vec![
+            Some(b"1".to_vec()),
+            None,
+            Some(b"3".to_vec()),
+            Some(b"3".to_vec()),
+            None,
+            Some(b"5".to_vec()),
+        ]
+    );
+}
+
+#[test]
+fn test_db_multi_get_unsorted() {
+    let (db, _temp_dir) = setup_test_db("test_multi_get_unsorted");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    db.put(b"key1", b"value1", &write_opts).expect("Put failed");
+    db.put(b"key3", b"value3", &write_opts).expect("Put failed");
+
+    let keys = vec![
+        b"key3".to_vec(),
+        b"missing".to_vec(),
+        b"key1".to_vec(),
+        b"key2".to_vec(),
+    ];
+    let values = db.multi_get(&keys, &read_opts).expect("Multi get failed");
+    assert_eq!(
+        values,
+        vec![
+            Some(b"value3".to_vec()),
+            None,
+            Some(b"value1".to_vec()),
+            None
+        ]
+    );
+
+    let empty: [&[u8]; 0] = [];
+    assert!(db.multi_get(&empty, &read_opts).unwrap().is_empty());
+}
+
+#[test]
+fn test_db_snapshot_release() {
+    let (db, _temp_dir) = setup_test_db("test_snapshot_release");
+    let write_opts = WriteOptions::new();
+
+    db.put(b"key", b"before", &write_opts).expect("Put failed");
+
+    let snapshot = db.snapshot();
+    let snap_opts = snapshot.read_options(&ReadOptions::new());
+    db.put(b"key", b"after", &write_opts).expect("Put failed");
+
+    assert_eq!(
+        db.multi_get(&[b"key"], &snap_opts).unwrap(),
+        vec![Some(b"before".to_vec())]
+    );
+    assert_eq!(
+        db.multi_get(&[b"key"], &ReadOptions::new()).unwrap(),
+        vec![Some(b"after".to_vec())]
+    );
+    drop(snap_opts);
+    drop(snapshot);
+}
+
+#[test]
+fn test_db_delete_range() {
+    let (db, _temp_dir) = setup_test_db("test_delete_range");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    for i in 0..100 {
+        let key = format!("key_{:03}", i);
+        db.put(key.as_bytes(), b"value", &write_opts)
+            .expect("Put failed");
+    }
+
+    // Tiny batches force several intermediate commits
+    let removed = db
+        .delete_range_with_batch_bytes(b"key_010", b"key_090", &write_opts, 64)
+        .expect("Delete range failed");
+    assert_eq!(removed, 80);
+
+    let remaining: Vec<Vec<u8>> = {
+        let mut iter = db.iter(&read_opts);
+        iter.seek_to_first();
+        iter.map(|(key, _)| key).collect()
+    };
+    assert_eq!(remaining.len(), 20);
+    assert_eq!(remaining[9], b"key_009");
+    assert_eq!(remaining[10], b"key_090");
+}
+
+#[test]
+fn test_db_delete_range_empty() {
+    let (db, _temp_dir) = setup_test_db("test_delete_range_empty");
+    let write_opts = WriteOptions::new();
+
+    db.put(b"a", b"1", &write_opts).expect("Put failed");
+    db.put(b"z", b"2", &write_opts).expect("Put failed");
+
+    // Reversed and zero-width ranges
+    assert_eq!(db.delete_range(b"m", b"c", &write_opts).unwrap(), 0);
+    assert_eq!(db.delete_range(b"a", b"a", &write_opts).unwrap(), 0);
+
+    // Range with no keys in it
+    assert_eq!(db.delete_range(b"b", b"y", &write_opts).unwrap(), 0);
+
+    assert!(db.get(b"a", &ReadOptions::new()).unwrap().is_some());
+    assert!(db.get(b"z", &ReadOptions::new()).unwrap().is_some());
+}
+
+#[test]
+fn test_db_open_read_only() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_read_only");
+
+    let options = Options::new();
+    options.create_if_missing(true);
+    {
+        let db = DB::open(&db_path, &options).expect("Failed to open database");
+        let write_opts = WriteOptions::new();
+        db.put(b"flushed", b"1", &write_opts).expect("Put failed");
+        db.compact_range(None, None).expect("Compaction failed");
+        db.put(b"logged", b"2", &write_opts).expect("Put failed");
+    }
+
+    let files_before: Vec<_> = std::fs::read_dir(&db_path)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+
+    {
+        let db = DB::open_read_only(&db_path, &Options::new()).expect("Read-only open failed");
+        assert!(db.is_read_only());
+
+        let read_opts = ReadOptions::new();
+        assert_eq!(db.get(b"flushed", &read_opts).unwrap(), Some(b"1".to_vec()));
+        assert_eq!(db.get(b"logged", &read_opts).unwrap(), Some(b"2".to_vec()));
+
+        let mut iter = db.iter(&read_opts);
+        iter.seek_to_first();
+        assert_eq!(iter.count(), 2);
+    }
+
+    let mut files_after: Vec<_> = std::fs::read_dir(&db_path)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    let mut files_before = files_before;
+    files_before.sort();
+    files_after.sort();
+    assert_eq!(files_before, files_after);
+}
+
+#[test]
+fn test_db_open_read_only_rejects_writes() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_read_only_writes");
+
+    let options = Options::new();
+    options.create_if_missing(true);
+    drop(DB::open(&db_path, &options).expect("Failed to open database"));
+
+    let db = DB::open_read_only(&db_path, &Options::new()).expect("Read-only open failed");
+    let write_opts = WriteOptions::new();
+
+    let mut batch = crate::WriteBatch::new();
+    batch.put(b"key", b"value");
+
+    for result in [
+        db.put(b"key", b"value", &write_opts),
+        db.delete(b"key", &write_opts),
+        db.write(&batch, &write_opts),
+        db.compact_range(None, None),
+    ] {
+        let error = result.unwrap_err();
+        assert!(error.contains("read-only"));
+    }
+    assert_eq!(db.get(b"key", &ReadOptions::new()).unwrap(), None);
+}
+
+#[test]
+fn test_db_open_read_only_missing() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let result = DB::open_read_only(temp_dir.path().join("missing"), &Options::new());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_db_path() {
+    let (db, temp_dir) = setup_test_db("test_path");
+    let expected = temp_dir.path().join("test_path").canonicalize().unwrap();
+    assert_eq!(db.path(), expected);
+    assert!(format!("{:?}", db).contains("test_path"));
+}
+
+#[test]
+fn test_db_path_relative() {
+    let cwd = std::env::current_dir().unwrap();
+    let temp_dir = tempfile::tempdir_in(&cwd).unwrap();
+    let relative = std::path::Path::new(".")
+        .join(temp_dir.path().file_name().unwrap())
+        .join("test_path_relative");
+    assert!(relative.is_relative());
+
+    let options = Options::new();
+    options.create_if_missing(true);
+    let db = DB::open(&relative, &options).expect("Failed to open database");
+
+    assert!(db.path().is_absolute());
+    assert_eq!(db.path(), relative.canonicalize().unwrap());
+}
+
+#[test]
+fn test_db_path_read_only() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_path_read_only");
+
+    let options = Options::new();
+    options.create_if_missing(true);
+    drop(DB::open(&db_path, &options).expect("Failed to open database"));
+
+    let db = DB::open_read_only(&db_path, &Options::new()).expect("Read-only open failed");
+    assert_eq!(db.path(), db_path.canonicalize().unwrap());
+
+    let error = db.delete(b"key", &WriteOptions::new()).unwrap_err();
+    assert!(error.contains("test_path_read_only"));
+}
+
+#[test]
+fn test_db_close_and_reopen() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_close");
+
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(&db_path, &options).expect("Failed to open database");
+    db.put(b"key", b"value", &WriteOptions::new())
+        .expect("Put failed");
+    db.close().expect("Close failed");
+
+    // The lock is released, so the database can be reopened immediately
+    let db = DB::open(&db_path, &options).expect("Failed to reopen database");
+    assert_eq!(
+        db.get(b"key", &ReadOptions::new()).unwrap(),
+        Some(b"value".to_vec())
+    );
+    db.close().expect("Second close failed");
+}
+
+#[test]
+fn test_db_close_read_only_cleans_up() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_close_read_only");
+
+    let options = Options::new();
+    options.create_if_missing(true);
+    DB::open(&db_path, &options)
+        .expect("Failed to open database")
+        .close()
+        .expect("Close failed");
+
+    let db = DB::open_read_only(&db_path, &Options::new()).expect("Read-only open failed");
+    let scratch = db
+        .inner
+        .scratch_dir
+        .clone()
+        .expect("Read-only handle has a scratch dir");
+    assert!(scratch.exists());
+
+    db.close().expect("Close failed");
+    assert!(!scratch.exists());
+}
+
+#[test]
+fn test_db_is_empty_and_count() {
+    let (db, _temp_dir) = setup_test_db("test_count_keys");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    assert!(db.is_empty(&read_opts).unwrap());
+    assert_eq!(db.count_keys(&read_opts).unwrap(), 0);
+    assert_eq!(db.count_prefix(b"a", &read_opts).unwrap(), 0);
+
+    db.put(b"a1", b"1", &write_opts).expect("Put failed");
+    db.put(b"a2", b"2", &write_opts).expect("Put failed");
+    db.put(b"b1", b"3", &write_opts).expect("Put failed");
+
+    assert!(!db.is_empty(&read_opts).unwrap());
+    assert_eq!(db.count_keys(&read_opts).unwrap(), 3);
+    assert_eq!(db.count_prefix(b"a", &read_opts).unwrap(), 2);
+    assert_eq!(db.count_prefix(b"b1", &read_opts).unwrap(), 1);
+    assert_eq!(db.count_prefix(b"c", &read_opts).unwrap(), 0);
+
+    db.delete(b"a1", &write_opts).expect("Delete failed");
+    db.delete(b"a2", &write_opts).expect("Delete failed");
+    db.delete(b"b1", &write_opts).expect("Delete failed");
+    assert!(db.is_empty(&read_opts).unwrap());
+}
+
+#[test]
+fn test_db_count_keys_large() {
+    let (db, _temp_dir) = setup_test_db("test_count_keys_large");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    let mut batch = crate::WriteBatch::new();
+    for i in 0..10_000 {
+        let key = format!("{}_{:05}", if i % 4 == 0 { "even" } else { "odd" }, i);
+        batch.put(key.as_bytes(), b"value");
+    }
+    db.write(&batch, &write_opts).expect("Batch write failed");
+
+    assert_eq!(db.count_keys(&read_opts).unwrap(), 10_000);
+    assert_eq!(db.count_prefix(b"even_", &read_opts).unwrap(), 2_500);
+    assert_eq!(db.count_prefix(b"odd_", &read_opts).unwrap(), 7_500);
+}
+
+#[test]
+fn test_db_shared_handle_threads() {
+    let (db, _temp_dir) = setup_test_db("test_shared_threads");
+    let write_opts = WriteOptions::new();
+
+    for i in 0..100 {
+        let key = format!("base_{:03}", i);
+        db.put(key.as_bytes(), b"base", &write_opts)
+            .expect("Put failed");
+    }
+
+    let writer = {
+        let db = db.clone();
+        std::thread::spawn(move || {
+            let write_opts = WriteOptions::new();
+            for i in 0..500 {
+                let key = format!("new_{:03}", i);
+                db.put(key.as_bytes(), b"new", &write_opts)
+                    .expect("Concurrent put failed");
+            }
+        })
+    };
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let db = db.clone();
+            std::thread::spawn(move || {
+                let read_opts = ReadOptions::new();
+                for _ in 0..20 {
+                    // Iterators borrow the thread's own handle
+                    let base_count = db.prefix_iter(b"base_", &read_opts).count();
+                    assert_eq!(base_count, 100);
+
+                    let value = db.get(b"base_050", &read_opts).expect("Get failed");
+                    assert_eq!(value, Some(b"base".to_vec()));
+                }
+            })
+        })
+        .collect();
+
+    writer.join().expect("Writer thread panicked");
+    for reader in readers {
+        reader.join().expect("Reader thread panicked");
+    }
+
+    assert_eq!(db.count_prefix(b"new_", &ReadOptions::new()).unwrap(), 500);
+}
+
+#[test]
+fn test_db_close_with_clones() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_close_clones");
+
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(&db_path, &options).expect("Failed to open database");
+    let other = db.clone();
+    other
+        .put(b"key", b"value", &WriteOptions::new())
+        .expect("Put through clone failed");
+
+    // The database stays open while another handle exists
+    assert!(db.close().is_err());
+    assert_eq!(
+        other.get(b"key", &ReadOptions::new()).unwrap(),
+        Some(b"value".to_vec())
+    );
+    other.close().expect("Close of last handle failed");
+
+    assert!(DB::open(&db_path, &options).is_ok());
+}
+
+#[test]
+fn test_update_insert_if_missing() {
+    let (db, _temp_dir) = setup_test_db("test_update_insert");
+    let read_opts = ReadOptions::new();
+    let write_opts = WriteOptions::new();
+
+    db.update(
+        b"counter",
+        |current| {
+            assert_eq!(current, None);
+            Some(b"1".to_vec())
+        },
+        &read_opts,
+        &write_opts,
+    )
+    .expect("Update failed");
+
+    assert_eq!(db.get(b"counter", &read_opts).unwrap(), Some(b"1".to_vec()));
+}
+
+#[test]
+fn test_update_modify_existing() {
+    let (db, _temp_dir) = setup_test_db("test_update_modify");
+    let read_opts = ReadOptions::new();
+    let write_opts = WriteOptions::new();
+
+    db.put(b"heightmap", &[1, 2, 3], &write_opts).unwrap();
+    db.update(
+        b"heightmap",
+        |current| {
+            let mut value = current.expect("Value should exist");
+            value[1] = 42;
+            Some(value)
+        },
+        &read_opts,
+        &write_opts,
+    )
+    .expect("Update failed");
+
+    assert_eq!(
+        db.get(b"heightmap", &read_opts).unwrap(),
+        Some(vec![1, 42, 3])
+    );
+}
+
+#[test]
+fn test_update_delete_via_none() {
+    let (db, _temp_dir) = setup_test_db("test_update_delete");
+    let read_opts = ReadOptions::new();
+    let write_opts = WriteOptions::new();
+
+    db.put(b"stale", b"value", &write_opts).unwrap();
+    db.update(b"stale", |_| None, &read_opts, &write_opts)
+        .expect("Update failed");
+
+    assert_eq!(db.get(b"stale", &read_opts).unwrap(), None);
+}
+
+#[test]
+fn test_put_if() {
+    let (db, _temp_dir) = setup_test_db("test_put_if");
+    let read_opts = ReadOptions::new();
+    let write_opts = WriteOptions::new();
+
+    // Missing key: only succeeds when absence is expected
+    assert!(
+        !db.put_if(b"key", Some(b"v0"), b"v1", &read_opts, &write_opts)
+            .unwrap()
+    );
+    assert_eq!(db.get(b"key", &read_opts).unwrap(), None);
+    assert!(
+        db.put_if(b"key", None, b"v1", &read_opts, &write_opts)
+            .unwrap()
+    );
+    assert_eq!(db.get(b"key", &read_opts).unwrap(), Some(b"v1".to_vec()));
+
+    // Mismatch leaves the value untouched
+    assert!(
+        !db.put_if(b"key", None, b"v2", &read_opts, &write_opts)
+            .unwrap()
+    );
+    assert!(
+        !db.put_if(b"key", Some(b"v0"), b"v2", &read_opts, &write_opts)
+            .unwrap()
+    );
+    assert_eq!(db.get(b"key", &read_opts).unwrap(), Some(b"v1".to_vec()));
+
+    // Success swaps the value
+    assert!(
+        db.put_if(b"key", Some(b"v1"), b"v2", &read_opts, &write_opts)
+            .unwrap()
+    );
+    assert_eq!(db.get(b"key", &read_opts).unwrap(), Some(b"v2".to_vec()));
+}
+
+#[test]
+fn test_put_if_contention() {
+    let (db, _temp_dir) = setup_test_db("test_put_if_contention");
+    db.put(b"counter", &0u32.to_le_bytes(), &WriteOptions::new())
+        .unwrap();
+
+    let increments_per_thread = 200;
+    let workers: Vec<_> = (0..2)
+        .map(|_| {
+            let db = db.clone();
+            std::thread::spawn(move || {
+                let (read_opts, write_opts) = (ReadOptions::new(), WriteOptions::new());
+                for _ in 0..increments_per_thread {
+                    loop {
+                        let current = db.get(b"counter", &read_opts).unwrap().unwrap();
+                        let n = u32::from_le_bytes(current[..].try_into().unwrap());
+                        let next = (n + 1).to_le_bytes();
+                        if db
+                            .put_if(b"counter", Some(&current), &next, &read_opts, &write_opts)
+                            .unwrap()
+                        {
+                            break;
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for worker in workers {
+        worker.join().expect("Worker thread panicked");
+    }
+
+    // No increment may be lost, however the threads interleaved
+    let value = db.get(b"counter", &ReadOptions::new()).unwrap().unwrap();
+    assert_eq!(
+        u32::from_le_bytes(value[..].try_into().unwrap()),
+        2 * increments_per_thread
+    );
+}
+
+#[test]
+fn test_get_into_reuses_buffer() {
+    let (db, _temp_dir) = setup_test_db("test_get_into");
+    let read_opts = ReadOptions::new();
+    let write_opts = WriteOptions::new();
+
+    for i in 0..50u8 {
+        let key = format!("key_{:02}", i);
+        db.put(key.as_bytes(), &vec![i; i as usize + 1], &write_opts)
+            .unwrap();
+    }
+    db.put(b"large", &[0x5A; 64 * 1024], &write_opts).unwrap();
+
+    let mut buf = Vec::new();
+    for i in 0..50u8 {
+        let key = format!("key_{:02}", i);
+        let len = db.get_into(key.as_bytes(), &mut buf, &read_opts).unwrap();
+        assert_eq!(len, Some(i as usize + 1));
+        assert_eq!(buf, vec![i; i as usize + 1]);
+    }
+
+    // Grows past the capacity left by the previous values
+    assert!(buf.capacity() < 64 * 1024);
+    assert_eq!(
+        db.get_into(b"large", &mut buf, &read_opts).unwrap(),
+        Some(64 * 1024)
+    );
+    assert!(buf.iter().all(|&b| b == 0x5A) && buf.len() == 64 * 1024);
+
+    assert_eq!(db.get_into(b"missing", &mut buf, &read_opts).unwrap(), None);
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_put_all_batch_boundaries() {
+    let (db, _temp_dir) = setup_test_db("test_put_all");
+    let read_opts = ReadOptions::new();
+
+    // Each entry is exactly 8 bytes, so batches fill up exactly at 16 bytes
+    let items: Vec<(Vec<u8>, Vec<u8>)> = (0..6u8)
+        .map(|i| (format!("key{}", i).into_bytes(), vec![i; 4]))
+        .collect();
+    let written = db
+        .put_all(items.clone(), &WriteOptions::new(), 16)
+        .expect("put_all failed");
+    assert_eq!(written, 6);
+
+    // A trailing partial batch is flushed too
+    let written = db
+        .put_all(
+            vec![(b"tail1", b"xyz"), (b"tail2", b"xyz"), (b"tail3", b"xyz")],
+            &WriteOptions::new(),
+            16,
+        )
+        .expect("put_all failed");
+    assert_eq!(written, 3);
+
+    for (key, value) in &items {
+        assert_eq!(db.get(key, &read_opts).unwrap().as_ref(), Some(value));
+    }
+    assert_eq!(db.count_keys(&read_opts).unwrap(), 9);
+}
+
+#[test]
+fn test_put_all_empty() {
+    let (db, _temp_dir) = setup_test_db("test_put_all_empty");
+    let items: Vec<(&[u8], &[u8])> = Vec::new();
+    assert_eq!(db.put_all(items, &WriteOptions::new(), 1024).unwrap(), 0);
+    assert!(db.is_empty(&ReadOptions::new()).unwrap());
+}
+
+#[test]
+fn test_disk_usage() {
+    let (db, _temp_dir) = setup_test_db("test_disk_usage");
+    let write_opts = WriteOptions::new();
+    for i in 0..2000u32 {
+        db.put(&i.to_be_bytes(), &[7; 256], &write_opts).unwrap();
+    }
+    db.compact_range(None, None).expect("Compaction failed");
+
+    let usage = db.disk_usage().expect("disk_usage failed");
+    assert!(usage.table_bytes > 0);
+    assert!(usage.table_files > 0);
+    assert_eq!(usage.manifest_files, 1);
+    assert_eq!(
+        usage.total_bytes(),
+        usage.table_bytes + usage.log_bytes + usage.manifest_bytes + usage.other_bytes
+    );
+
+    // The walk covers exactly the regular files in the directory
+    let on_disk: u64 = std::fs::read_dir(db.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().metadata().unwrap().len())
+        .sum();
+    assert_eq!(usage.total_bytes(), on_disk);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_disk_usage_ignores_symlinks() {
+    let (db, temp_dir) = setup_test_db("test_disk_usage_symlink");
+    let before = db.disk_usage().unwrap();
+
+    let outside = temp_dir.path().join("outside.ldb");
+    std::fs::write(&outside, vec![0u8; 4096]).unwrap();
+    std::os::unix::fs::symlink(&outside, db.path().join("000999.ldb")).unwrap();
+
+    assert_eq!(db.disk_usage().unwrap(), before);
+}
+
+#[test]
+fn test_leveldb_version() {
+    let (major, minor) = crate::leveldb_version();
+    assert!(major > 0);
+    assert!(minor >= 0);
+
+    let (db, _temp_dir) = setup_test_db("test_version_debug");
+    let debug = format!("{:?}", db);
+    assert!(debug.contains(&format!("leveldb_version: ({}, {})", major, minor)));
+}
+
+#[test]
+fn test_db_as_ref_arguments() {
+    let (db, _temp_dir) = setup_test_db("test_db_as_ref_arguments");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    let name = String::from("player_server_1");
+    let data: Vec<u8> = vec![1, 2, 3];
+    db.put(&name, &data, &write_opts).unwrap();
+    db.put(name.clone() + "_copy", data.clone(), &write_opts)
+        .unwrap();
+    db.put("literal", "text", &write_opts).unwrap();
+
+    assert_eq!(db.get(&name, &read_opts).unwrap(), Some(data.clone()));
+    assert_eq!(
+        db.get("player_server_1_copy", &read_opts).unwrap(),
+        Some(data)
+    );
+    let literal_key: Vec<u8> = b"literal".to_vec();
+    assert_eq!(
+        db.get(literal_key, &read_opts).unwrap(),
+        Some(b"text".to_vec())
+    );
+
+    db.delete(name, &write_opts).unwrap();
+    db.delete(String::from("literal"), &write_opts).unwrap();
+    assert_eq!(db.get("player_server_1", &read_opts).unwrap(), None);
+    assert_eq!(db.get("literal", &read_opts).unwrap(), None);
+}
+
+#[test]
+fn test_db_raw_round_trip() {
+    let (db, temp_dir) = setup_test_db("raw_round_trip");
+    db.put(b"key", b"value", &WriteOptions::new()).unwrap();
+
+    // Write through the raw handles, as a caller of an unwrapped API would
+    let write_options = WriteOptions::new();
+    let raw_write_options = unsafe { write_options.as_raw() };
+    std::mem::forget(write_options);
+    let write_options = unsafe { WriteOptions::from_raw(raw_write_options) };
+    unsafe {
+        let mut err = std::ptr::null_mut();
+        crate::sys::leveldb_put(
+            db.as_raw(),
+            write_options.as_raw(),
+            b"raw".as_ptr() as *const _,
+            3,
+            b"written".as_ptr() as *const _,
+            7,
+            &mut err,
+        );
+        assert!(err.is_null());
+    }
+
+    // Hand the database handle over without closing it
+    let raw = unsafe { db.as_raw() };
+    std::mem::forget(db);
+    let db = unsafe { DB::from_raw(raw, temp_dir.path().join("raw_round_trip")) };
+    assert_eq!(
+        db.path(),
+        temp_dir
+            .path()
+            .join("raw_round_trip")
+            .canonicalize()
+            .unwrap()
+    );
+    assert_eq!(
+        db.get(b"key", &ReadOptions::new()).unwrap(),
+        Some(b"value".to_vec())
+    );
+    assert_eq!(
+        db.get(b"raw", &ReadOptions::new()).unwrap(),
+        Some(b"written".to_vec())
+    );
+
+    let mut iter = db.iter(&ReadOptions::new());
+    iter.seek(b"key");
+    let raw_iter = unsafe { iter.as_raw() };
+    std::mem::forget(iter);
+    let iter = unsafe { crate::DBIterator::from_raw(&db, raw_iter) };
+    // Iteration continues from where the cursor was left
+    assert_eq!(
+        iter.collect::<Vec<_>>(),
+        vec![
+            (b"key".to_vec(), b"value".to_vec()),
+            (b"raw".to_vec(), b"written".to_vec())
+        ]
+    );
+
+    // The handle is owned again and closes normally
+    db.close().unwrap();
+    let db = DB::open(temp_dir.path().join("raw_round_trip"), &Options::new()).unwrap();
+    assert_eq!(
+        db.get(b"raw", &ReadOptions::new()).unwrap(),
+        Some(b"written".to_vec())
+    );
+}
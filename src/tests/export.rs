@@ -0,0 +1,234 @@
+use crate::DB;
+use crate::export::{FORMAT_VERSION, ImportMode, MAGIC};
+use crate::options::{Options, ReadOptions, WriteOptions};
+use crate::util::{Crc32, read_varint};
+use std::io::Read;
+use tempfile::TempDir;
+
+fn setup_test_db(name: &str) -> (DB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(temp_dir.path().join(name), &options).expect("Failed to open database");
+    (db, temp_dir)
+}
+
+/// Decode an export stream by hand, following `FORMAT_SPEC`.
+fn decode(stream: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let mut reader = stream;
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic).unwrap();
+    assert_eq!(magic, MAGIC);
+    let mut version = [0u8];
+    reader.read_exact(&mut version).unwrap();
+    assert_eq!(version[0], FORMAT_VERSION);
+
+    let mut records = Vec::new();
+    loop {
+        let mut tag = [0u8];
+        reader.read_exact(&mut tag).unwrap();
+        if tag[0] == 0 {
+            break;
+        }
+        let mut fields = [Vec::new(), Vec::new()];
+        for field in &mut fields {
+            let len = read_varint(&mut reader).unwrap() as usize;
+            field.resize(len, 0);
+            reader.read_exact(field).unwrap();
+        }
+        let [key, value] = fields;
+        records.push((key, value));
+    }
+
+    assert_eq!(read_varint(&mut reader).unwrap(), records.len() as u64);
+    let checked_len = stream.len() - reader.len();
+    let mut crc = Crc32::new();
+    crc.update(&stream[..checked_len]);
+    assert_eq!(reader, crc.finish().to_le_bytes());
+    records
+}
+
+#[test]
+fn test_crc32_check_value() {
+    let mut crc = Crc32::new();
+    crc.update(b"123456789");
+    assert_eq!(crc.finish(), 0xCBF4_3926);
+}
+
+#[test]
+fn test_export_binary_keys() {
+    let (db, _temp_dir) = setup_test_db("test_export_binary");
+    let write_opts = WriteOptions::new();
+    let entries: Vec<(Vec<u8>, Vec<u8>)> = vec![
+        (b"\0".to_vec(), b"nul key".to_vec()),
+        (b"a\0b".to_vec(), vec![0; 300]),
+        (vec![0xFF, 0x00, 0xFE], Vec::new()),
+    ];
+    for (key, value) in &entries {
+        db.put(key, value, &write_opts).unwrap();
+    }
+
+    let mut stream = Vec::new();
+    let stats = db
+        .export_to_writer(&mut stream, &ReadOptions::new())
+        .expect("Export failed");
+
+    assert_eq!(stats.records, 3);
+    assert_eq!(stats.key_bytes, 1 + 3 + 3);
+    assert_eq!(stats.value_bytes, 7 + 300);
+    assert_eq!(stats.bytes_written, stream.len() as u64);
+    assert_eq!(decode(&stream), entries);
+}
+
+#[test]
+fn test_export_empty_db() {
+    let (db, _temp_dir) = setup_test_db("test_export_empty");
+
+    let mut stream = Vec::new();
+    let stats = db
+        .export_to_writer(&mut stream, &ReadOptions::new())
+        .expect("Export failed");
+
+    assert_eq!(stats.records, 0);
+    // Header, end tag, record count and checksum
+    assert_eq!(stream.len(), 8 + 1 + 1 + 1 + 4);
+    assert!(decode(&stream).is_empty());
+}
+
+fn export_entries(entries: &[(&[u8], &[u8])]) -> Vec<u8> {
+    let (db, _temp_dir) = setup_test_db("source");
+    for (key, value) in entries {
+        db.put(key, value, &WriteOptions::new()).unwrap();
+    }
+    let mut stream = Vec::new();
+    db.export_to_writer(&mut stream, &ReadOptions::new())
+        .expect("Export failed");
+    stream
+}
+
+#[test]
+fn test_export_import_round_trip() {
+    let (source, _source_dir) = setup_test_db("test_round_trip_source");
+    let write_opts = WriteOptions::new();
+    source.put(b"\0\0", b"\0value\0", &write_opts).unwrap();
+    source
+        .put(b"chunk\0\x01", &[0xAB; 5000], &write_opts)
+        .unwrap();
+    for i in 0..500u32 {
+        source
+            .put(&i.to_be_bytes(), &i.to_le_bytes(), &write_opts)
+            .unwrap();
+    }
+
+    let mut stream = Vec::new();
+    source
+        .export_to_writer(&mut stream, &ReadOptions::new())
+        .expect("Export failed");
+
+    let (target, _target_dir) = setup_test_db("test_round_trip_target");
+    let stats = target
+        .import_from_reader(&mut stream.as_slice(), &write_opts, ImportMode::Overwrite)
+        .expect("Import failed");
+
+    assert_eq!(stats.records, 502);
+    assert_eq!(stats.written, 502);
+    assert_eq!(stats.skipped, 0);
+    let read_opts = ReadOptions::new();
+    assert!(source.iter(&read_opts).eq(target.iter(&read_opts)));
+}
+
+#[test]
+fn test_import_modes() {
+    let stream = export_entries(&[(b"a", b"new"), (b"b", b"new")]);
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    let (db, _temp_dir) = setup_test_db("test_import_overwrite");
+    db.put(b"a", b"old", &write_opts).unwrap();
+    let stats = db
+        .import_from_reader(&mut stream.as_slice(), &write_opts, ImportMode::Overwrite)
+        .unwrap();
+    assert_eq!((stats.written, stats.skipped), (2, 0));
+    assert_eq!(db.get(b"a", &read_opts).unwrap(), Some(b"new".to_vec()));
+
+    let (db, _temp_dir) = setup_test_db("test_import_skip");
+    db.put(b"a", b"old", &write_opts).unwrap();
+    let stats = db
+        .import_from_reader(
+            &mut stream.as_slice(),
+            &write_opts,
+            ImportMode::SkipExisting,
+        )
+        .unwrap();
+    assert_eq!((stats.written, stats.skipped), (1, 1));
+    assert_eq!(db.get(b"a", &read_opts).unwrap(), Some(b"old".to_vec()));
+    assert_eq!(db.get(b"b", &read_opts).unwrap(), Some(b"new".to_vec()));
+
+    let (db, _temp_dir) = setup_test_db("test_import_conflict");
+    db.put(b"b", b"old", &write_opts).unwrap();
+    let err = db
+        .import_from_reader(
+            &mut stream.as_slice(),
+            &write_opts,
+            ImportMode::ErrorOnConflict,
+        )
+        .unwrap_err();
+    assert!(err.contains("already exists"), "{}", err);
+    // The conflict stopped the import before the pending batch was committed
+    assert_eq!(db.get(b"a", &read_opts).unwrap(), None);
+    assert_eq!(db.get(b"b", &read_opts).unwrap(), Some(b"old".to_vec()));
+}
+
+#[test]
+fn test_import_truncated_stream() {
+    let stream = export_entries(&[(b"a", b"1"), (b"b", b"2"), (b"c", b"3")]);
+    let write_opts = WriteOptions::new();
+
+    for len in [0, 5, 9, 12, stream.len() - 1] {
+        let (db, _temp_dir) = setup_test_db("test_import_truncated");
+        let err = db
+            .import_from_reader(&mut &stream[..len], &write_opts, ImportMode::Overwrite)
+            .unwrap_err();
+        assert!(err.contains("truncated"), "{}: {}", len, err);
+        assert!(db.is_empty(&ReadOptions::new()).unwrap());
+    }
+}
+
+#[test]
+fn test_import_corrupt_stream() {
+    let stream = export_entries(&[(b"key", b"value")]);
+    let (db, _temp_dir) = setup_test_db("test_import_corrupt");
+    let write_opts = WriteOptions::new();
+
+    let mut flipped = stream.clone();
+    // First byte of the value: header, tag, key length, key, value length
+    flipped[8 + 1 + 1 + 1 + 3 + 1] ^= 0xFF;
+    let err = db
+        .import_from_reader(&mut flipped.as_slice(), &write_opts, ImportMode::Overwrite)
+        .unwrap_err();
+    assert!(err.contains("checksum mismatch"), "{}", err);
+
+    let mut bad_magic = stream.clone();
+    bad_magic[0] = b'X';
+    assert!(
+        db.import_from_reader(
+            &mut bad_magic.as_slice(),
+            &write_opts,
+            ImportMode::Overwrite
+        )
+        .is_err()
+    );
+
+    let mut bad_version = stream;
+    bad_version[8] = FORMAT_VERSION + 1;
+    assert!(
+        db.import_from_reader(
+            &mut bad_version.as_slice(),
+            &write_opts,
+            ImportMode::Overwrite
+        )
+        .is_err()
+    );
+    assert!(db.is_empty(&ReadOptions::new()).unwrap());
+}
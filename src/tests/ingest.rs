@@ -0,0 +1,92 @@
+use crate::options::{Compression, Options, ReadOptions, WriteOptions};
+use crate::{DB, IngestStats};
+use tempfile::TempDir;
+
+fn setup_test_db(name: &str) -> (DB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db = DB::open(temp_dir.path().join(name), &Options::bulk_load())
+        .expect("Failed to open database");
+    (db, temp_dir)
+}
+
+fn record(i: u32) -> ([u8; 4], Vec<u8>) {
+    (i.to_be_bytes(), format!("value-{}", i).into_bytes())
+}
+
+#[test]
+fn test_ingest_sorted_writes_everything() {
+    let (db, _temp_dir) = setup_test_db("ingest");
+
+    let stats = db
+        .ingest_sorted((0..100_000).map(record), &WriteOptions::new())
+        .unwrap();
+    assert_eq!(stats.records, 100_000);
+    assert_eq!(stats.key_bytes, 400_000);
+    assert_eq!(
+        stats.value_bytes,
+        (0..100_000u32)
+            .map(|i| record(i).1.len() as u64)
+            .sum::<u64>()
+    );
+    assert_eq!(stats.batches, 1);
+
+    let mut expected = (0..100_000).map(record);
+    for (key, value) in db.iter(&ReadOptions::new()) {
+        let (expected_key, expected_value) = expected.next().unwrap();
+        assert_eq!(key, expected_key);
+        assert_eq!(value, expected_value);
+    }
+    assert!(expected.next().is_none());
+
+    // Nothing to write leaves the database alone
+    assert_eq!(
+        db.ingest_sorted(Vec::<(Vec<u8>, Vec<u8>)>::new(), &WriteOptions::new())
+            .unwrap(),
+        IngestStats::default()
+    );
+}
+
+#[test]
+fn test_ingest_sorted_rejects_out_of_order_key() {
+    let (db, _temp_dir) = setup_test_db("ingest_unsorted");
+
+    // Large values fill several batches before the bad key
+    let value = vec![7u8; 64 * 1024];
+    let keys = (0..300u32).chain([299]).chain(300..310);
+    let err = db
+        .ingest_sorted(
+            keys.map(|i| (i.to_be_bytes(), &value)),
+            &WriteOptions::new(),
+        )
+        .unwrap_err();
+    assert!(
+        err.starts_with("key 0000012b (unknown (4 bytes) 0000012b) at index 300"),
+        "{}",
+        err
+    );
+    assert!(err.contains("256 records were written"), "{}", err);
+
+    // Only the committed batches are in the database
+    assert_eq!(db.iter(&ReadOptions::new()).count(), 256);
+    assert!(
+        db.get(299u32.to_be_bytes(), &ReadOptions::new())
+            .unwrap()
+            .is_none()
+    );
+
+    // Duplicates are out of order too
+    let err = db
+        .ingest_sorted([(b"a", b"1"), (b"a", b"2")], &WriteOptions::new())
+        .unwrap_err();
+    assert!(err.contains("at index 1"), "{}", err);
+}
+
+#[test]
+fn test_bulk_load_options() {
+    let settings = Options::bulk_load().settings();
+    assert!(settings.create_if_missing);
+    assert_eq!(settings.compression, Compression::ZlibRaw);
+    assert_eq!(settings.write_buffer_size, 128 * 1024 * 1024);
+    assert_eq!(settings.max_file_size, 64 * 1024 * 1024);
+    assert!(Options::bulk_load().validate().is_ok());
+}
@@ -1,5 +1,6 @@
 use crate::DB;
 use crate::options::{Options, ReadOptions, WriteOptions};
+use std::ops::{Bound, ControlFlow};
 use tempfile::TempDir;
 
 fn setup_test_db_with_data(name: &str, data: &[(&[u8], &[u8])]) -> (DB, TempDir) {
@@ -128,11 +129,8 @@ fn test_iterator_backward_iteration() {
     iter.seek_to_last();
 
     let mut collected = Vec::new();
-    while iter.valid() {
-        if let (Some(key), Some(value)) = (iter.key(), iter.value()) {
-            collected.push((key, value));
-        }
-        iter.prev();
+    while let Some(entry) = iter.prev() {
+        collected.push(entry);
     }
 
     // Backward iteration should give keys in reverse order
@@ -382,28 +380,757 @@ fn test_iterator_range_scan() {
     let (db, _temp_dir) = setup_test_db_with_data("test_range_scan", &test_data);
     let read_opts = ReadOptions::new();
 
+    // Scan range from "b" up to and including "date"
+    let fruits_in_range: Vec<Vec<u8>> = db
+        .range(b"b".to_vec()..=b"date".to_vec(), &read_opts)
+        .map(|(key, _)| key)
+        .collect();
+
+    assert_eq!(fruits_in_range.len(), 3);
+    assert_eq!(fruits_in_range[0], b"banana");
+    assert_eq!(fruits_in_range[1], b"cherry");
+    assert_eq!(fruits_in_range[2], b"date");
+}
+
+#[test]
+fn test_prefix_iter() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> = vec![
+        (b"apple", b"red"),
+        (b"banana", b"yellow"),
+        (b"blueberry", b"blue"),
+        (b"cherry", b"red"),
+        (b"date", b"brown"),
+    ];
+    let (db, _temp_dir) = setup_test_db_with_data("test_prefix_iter", &test_data);
+    let read_opts = ReadOptions::new();
+
+    let fruits: Vec<(Vec<u8>, Vec<u8>)> = db.prefix_iter(b"b", &read_opts).collect();
+    assert_eq!(
+        fruits,
+        vec![
+            (b"banana".to_vec(), b"yellow".to_vec()),
+            (b"blueberry".to_vec(), b"blue".to_vec()),
+        ]
+    );
+}
+
+#[test]
+fn test_prefix_iter_edge_cases() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> = vec![
+        (b"chunk", b"0"),
+        (b"chunk\x01", b"1"),
+        (b"chunk\x02", b"2"),
+        (b"other", b"3"),
+    ];
+    let (db, _temp_dir) = setup_test_db_with_data("test_prefix_edges", &test_data);
+    let read_opts = ReadOptions::new();
+
+    // Empty prefix iterates everything
+    assert_eq!(db.prefix_iter(b"", &read_opts).count(), 4);
+
+    // A prefix that is itself a stored key includes that key
+    let keys: Vec<Vec<u8>> = db
+        .prefix_iter(b"chunk", &read_opts)
+        .map(|(key, _)| key)
+        .collect();
+    assert_eq!(
+        keys,
+        vec![
+            b"chunk".to_vec(),
+            b"chunk\x01".to_vec(),
+            b"chunk\x02".to_vec()
+        ]
+    );
+
+    // Prefix past the last key, and prefix between keys
+    assert_eq!(db.prefix_iter(b"zzz", &read_opts).count(), 0);
+    assert_eq!(db.prefix_iter(b"d", &read_opts).count(), 0);
+
+    // Exhausted iterators stay exhausted
+    let mut iter = db.prefix_iter(b"other", &read_opts);
+    assert!(iter.next().is_some());
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+    assert!(iter.status().is_ok());
+}
+
+#[test]
+fn test_range_bounds() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> = vec![
+        (b"a", b"1"),
+        (b"b", b"2"),
+        (b"c", b"3"),
+        (b"d", b"4"),
+        (b"e", b"5"),
+    ];
+    let (db, _temp_dir) = setup_test_db_with_data("test_range_bounds", &test_data);
+    let read_opts = ReadOptions::new();
+
+    let keys = |range: (Bound<Vec<u8>>, Bound<Vec<u8>>)| -> Vec<Vec<u8>> {
+        db.range(range, &read_opts).map(|(key, _)| key).collect()
+    };
+    let inc = |key: &[u8]| Bound::Included(key.to_vec());
+    let exc = |key: &[u8]| Bound::Excluded(key.to_vec());
+    let all = |list: &[&[u8]]| -> Vec<Vec<u8>> { list.iter().map(|k| k.to_vec()).collect() };
+
+    assert_eq!(keys((inc(b"b"), inc(b"d"))), all(&[b"b", b"c", b"d"]));
+    assert_eq!(keys((inc(b"b"), exc(b"d"))), all(&[b"b", b"c"]));
+    assert_eq!(keys((exc(b"b"), inc(b"d"))), all(&[b"c", b"d"]));
+    assert_eq!(keys((exc(b"b"), exc(b"d"))), all(&[b"c"]));
+    assert_eq!(keys((Bound::Unbounded, exc(b"c"))), all(&[b"a", b"b"]));
+    assert_eq!(
+        keys((Bound::Unbounded, inc(b"c"))),
+        all(&[b"a", b"b", b"c"])
+    );
+    assert_eq!(keys((inc(b"d"), Bound::Unbounded)), all(&[b"d", b"e"]));
+    assert_eq!(keys((exc(b"d"), Bound::Unbounded)), all(&[b"e"]));
+    assert_eq!(keys((Bound::Unbounded, Bound::Unbounded)).len(), 5);
+
+    // Bounds that are not stored keys
+    assert_eq!(keys((inc(b"bb"), exc(b"dd"))), all(&[b"c", b"d"]));
+
+    // Standard range syntax
+    assert_eq!(
+        db.range(b"a".to_vec()..b"c".to_vec(), &read_opts).count(),
+        2
+    );
+    assert_eq!(db.range(..=b"c".to_vec(), &read_opts).count(), 3);
+    assert_eq!(db.range(.., &read_opts).count(), 5);
+}
+
+#[test]
+fn test_range_empty() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> = vec![(b"a", b"1"), (b"b", b"2")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_range_empty", &test_data);
+    let read_opts = ReadOptions::new();
+
+    // Reversed ranges yield nothing
+    assert_eq!(
+        db.range(b"b".to_vec()..b"a".to_vec(), &read_opts).count(),
+        0
+    );
+    assert_eq!(
+        db.range(
+            (
+                Bound::Excluded(b"a".to_vec()),
+                Bound::Included(b"a".to_vec())
+            ),
+            &read_opts
+        )
+        .count(),
+        0
+    );
+
+    // Zero-width and past-the-end ranges
+    assert_eq!(
+        db.range(b"a".to_vec()..b"a".to_vec(), &read_opts).count(),
+        0
+    );
+    assert_eq!(db.range(b"z".to_vec().., &read_opts).count(), 0);
+
+    let (empty_db, _empty_dir) = setup_test_db_with_data("test_range_empty_db", &[]);
+    assert_eq!(empty_db.range(.., &read_opts).count(), 0);
+}
+
+#[test]
+fn test_iterator_for_loop_without_seek() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> =
+        vec![(b"a", b"1"), (b"b", b"2"), (b"c", b"3")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_for_loop", &test_data);
+    let read_opts = ReadOptions::new();
+
+    let mut visited = Vec::new();
+    for (key, value) in db.iter(&read_opts) {
+        visited.push((key, value));
+    }
+
+    let expected: Vec<(Vec<u8>, Vec<u8>)> = test_data
+        .iter()
+        .map(|(k, v)| (k.to_vec(), v.to_vec()))
+        .collect();
+    assert_eq!(visited, expected);
+}
+
+#[test]
+fn test_iterator_explicit_seek_overrides_auto_position() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> =
+        vec![(b"a", b"1"), (b"b", b"2"), (b"c", b"3")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_seek_override", &test_data);
+    let read_opts = ReadOptions::new();
+
+    let mut iter = db.iter(&read_opts);
+    iter.seek(b"b");
+    let keys: Vec<Vec<u8>> = iter.map(|(k, _)| k).collect();
+    assert_eq!(keys, vec![b"b".to_vec(), b"c".to_vec()]);
+
+    // Seeking past the end must not fall back to the first key
     let mut iter = db.iter(&read_opts);
+    iter.seek(b"z");
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_iterator_exhausted_stays_exhausted() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> = vec![(b"a", b"1"), (b"b", b"2")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_exhausted", &test_data);
+    let read_opts = ReadOptions::new();
+
+    let mut iter = db.iter(&read_opts);
+    assert_eq!(iter.by_ref().count(), 2);
+    for _ in 0..5 {
+        assert_eq!(iter.next(), None);
+    }
+
+    // Adapters relying on the fused contract behave
+    let mut peekable = db.iter(&read_opts).fuse().peekable();
+    assert_eq!(peekable.by_ref().count(), 2);
+    assert!(peekable.peek().is_none());
+}
+
+#[test]
+fn test_iterator_seek_after_exhaustion() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> =
+        vec![(b"a", b"1"), (b"b", b"2"), (b"c", b"3")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_seek_revive", &test_data);
+    let read_opts = ReadOptions::new();
+
+    let mut iter = db.iter(&read_opts);
+    assert_eq!(iter.by_ref().count(), 3);
+    assert_eq!(iter.next(), None);
 
-    // Scan range from "b" to "d"
     iter.seek(b"b");
-    let mut fruits_in_range = Vec::new();
-    while iter.valid() {
-        if let Some(key) = iter.key() {
-            if key.starts_with(b"b") || key.starts_with(b"c") {
-                fruits_in_range.push(key);
-            } else if key.starts_with(b"d") {
-                // Include 'date' but stop after
-                fruits_in_range.push(key);
-                break;
+    assert_eq!(iter.next(), Some((b"b".to_vec(), b"2".to_vec())));
+    assert_eq!(iter.next(), Some((b"c".to_vec(), b"3".to_vec())));
+    assert_eq!(iter.next(), None);
+
+    iter.seek_to_first();
+    assert_eq!(iter.count(), 3);
+}
+
+#[test]
+fn test_iterator_rev() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> =
+        vec![(b"a", b"1"), (b"b", b"2"), (b"c", b"3"), (b"d", b"4")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_rev", &test_data);
+    let read_opts = ReadOptions::new();
+
+    let keys: Vec<Vec<u8>> = db.iter(&read_opts).rev().map(|(k, _)| k).collect();
+    assert_eq!(
+        keys,
+        vec![b"d".to_vec(), b"c".to_vec(), b"b".to_vec(), b"a".to_vec()]
+    );
+
+    // Reverse iteration from an explicit seek position stops at that position
+    let mut iter = db.iter(&read_opts);
+    iter.seek(b"b");
+    let keys: Vec<Vec<u8>> = iter.rev().map(|(k, _)| k).collect();
+    assert_eq!(keys, vec![b"d".to_vec(), b"c".to_vec(), b"b".to_vec()]);
+}
+
+#[test]
+fn test_iterator_interleaved_front_back() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> = vec![
+        (b"a", b"1"),
+        (b"b", b"2"),
+        (b"c", b"3"),
+        (b"d", b"4"),
+        (b"e", b"5"),
+    ];
+    let (db, _temp_dir) = setup_test_db_with_data("test_interleaved", &test_data);
+    let read_opts = ReadOptions::new();
+
+    let mut iter = db.iter(&read_opts);
+    let key = |item: Option<(Vec<u8>, Vec<u8>)>| item.map(|(k, _)| k);
+    assert_eq!(key(iter.next_back()), Some(b"e".to_vec()));
+    assert_eq!(key(iter.next()), Some(b"a".to_vec()));
+    assert_eq!(key(iter.next()), Some(b"b".to_vec()));
+    assert_eq!(key(iter.next_back()), Some(b"d".to_vec()));
+    assert_eq!(key(iter.next_back()), Some(b"c".to_vec()));
+
+    // The cursors have met: both ends are done
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    // Every entry is visited exactly once whatever the interleaving
+    let mut iter = db.iter(&read_opts);
+    let mut seen = Vec::new();
+    let mut from_back = false;
+    while let Some((k, _)) = if from_back {
+        iter.next_back()
+    } else {
+        iter.next()
+    } {
+        seen.push(k);
+        from_back = !from_back;
+    }
+    seen.sort();
+    let expected: Vec<Vec<u8>> = test_data.iter().map(|(k, _)| k.to_vec()).collect();
+    assert_eq!(seen, expected);
+}
+
+#[test]
+fn test_iterator_rev_empty() {
+    let (db, _temp_dir) = setup_test_db_with_data("test_rev_empty", &[]);
+    let read_opts = ReadOptions::new();
+
+    assert_eq!(db.iter(&read_opts).rev().count(), 0);
+    let mut iter = db.iter(&read_opts);
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_iterator_borrowed_accessors() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> =
+        vec![(b"a", b"1"), (b"b\0", b"\0two"), (b"c", b"")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_borrowed", &test_data);
+    let read_opts = ReadOptions::new();
+
+    let mut iter = db.iter(&read_opts);
+    assert_eq!(iter.key_ref(), None);
+    assert_eq!(iter.value_ref(), None);
+
+    iter.seek_to_first();
+    let mut visited = Vec::new();
+    while let (Some(key), Some(value)) = (iter.key_ref(), iter.value_ref()) {
+        assert_eq!(Some(key.to_vec()), iter.key());
+        assert_eq!(Some(value.to_vec()), iter.value());
+        visited.push((key.to_vec(), value.to_vec()));
+        iter.next_native();
+    }
+
+    let expected: Vec<(Vec<u8>, Vec<u8>)> = test_data
+        .iter()
+        .map(|(k, v)| (k.to_vec(), v.to_vec()))
+        .collect();
+    assert_eq!(visited, expected);
+    assert_eq!(iter.key_ref(), None);
+}
+
+#[test]
+fn test_scan_visits_everything() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> =
+        vec![(b"a", b"1"), (b"b", b"22"), (b"c", b"333")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_scan_all", &test_data);
+
+    let mut seen = Vec::new();
+    let stats = db
+        .scan(
+            None,
+            |key, value| {
+                seen.push((key.to_vec(), value.len()));
+                ControlFlow::Continue(())
+            },
+            &ReadOptions::new(),
+        )
+        .expect("Scan failed");
+
+    assert_eq!(stats.visited, 3);
+    assert!(!stats.stopped_early);
+    assert_eq!(
+        seen,
+        vec![(b"a".to_vec(), 1), (b"b".to_vec(), 2), (b"c".to_vec(), 3)]
+    );
+}
+
+#[test]
+fn test_scan_start_and_early_stop() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> = vec![
+        (b"a", b"1"),
+        (b"b", b"2"),
+        (b"c", b"3"),
+        (b"d", b"4"),
+        (b"e", b"5"),
+    ];
+    let (db, _temp_dir) = setup_test_db_with_data("test_scan_stop", &test_data);
+    let read_opts = ReadOptions::new();
+
+    // Starting between keys positions at the next one
+    let mut seen = Vec::new();
+    let stats = db
+        .scan(
+            Some(b"bb"),
+            |key, _| {
+                seen.push(key.to_vec());
+                if key == b"d" {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            },
+            &read_opts,
+        )
+        .unwrap();
+    assert_eq!(seen, vec![b"c".to_vec(), b"d".to_vec()]);
+    assert_eq!(stats.visited, 2);
+    assert!(stats.stopped_early);
+
+    let stats = db
+        .scan(Some(b"z"), |_, _| ControlFlow::Continue(()), &read_opts)
+        .unwrap();
+    assert_eq!(stats.visited, 0);
+}
+
+#[test]
+fn test_step_by_seek_skips_stripes() {
+    let (db, _temp_dir) = setup_test_db_with_data("test_step_by_seek", &[]);
+    let write_opts = WriteOptions::new();
+    for x in 0..10u8 {
+        for z in 0..10u8 {
+            db.put(&[b'x', x, b'z', z], &[x, z], &write_opts).unwrap();
+        }
+    }
+    let read_opts = ReadOptions::new();
+
+    // Jump to the start of the next x stripe after every hit
+    let mut iter = db.iter(&read_opts);
+    let hits: Vec<Vec<u8>> = iter
+        .step_by_seek(|key| vec![b'x', key[1] + 1])
+        .map(|(k, _)| k)
+        .collect();
+    let expected: Vec<Vec<u8>> = (0..10u8).map(|x| vec![b'x', x, b'z', 0]).collect();
+    assert_eq!(hits, expected);
+
+    // Starting from an explicit seek, with a target beyond the last key
+    let mut iter = db.iter(&read_opts);
+    iter.seek(&[b'x', 8, b'z', 5]);
+    let hits: Vec<Vec<u8>> = iter
+        .step_by_seek(|key| {
+            if key[1] == 9 {
+                vec![0xFF]
             } else {
-                break;
+                vec![b'x', key[1] + 1, b'z', 5]
             }
-        }
-        iter.next();
+        })
+        .map(|(k, _)| k)
+        .collect();
+    assert_eq!(hits, vec![vec![b'x', 8, b'z', 5], vec![b'x', 9, b'z', 5]]);
+}
+
+#[test]
+fn test_step_by_seek_non_advancing_target() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> =
+        vec![(b"a", b"1"), (b"b", b"2"), (b"c", b"3")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_step_no_advance", &test_data);
+    let read_opts = ReadOptions::new();
+
+    // Targets at or before the current key fall back to a plain next
+    let mut iter = db.iter(&read_opts);
+    assert_eq!(iter.step_by_seek(|key| key.to_vec()).count(), 3);
+
+    let mut iter = db.iter(&read_opts);
+    assert_eq!(iter.step_by_seek(|_| Vec::new()).count(), 3);
+
+    let (empty, _temp_dir) = setup_test_db_with_data("test_step_empty", &[]);
+    let mut iter = empty.iter(&read_opts);
+    assert_eq!(iter.step_by_seek(|key| key.to_vec()).count(), 0);
+}
+
+#[test]
+fn test_iter_rev_full() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> =
+        vec![(b"a", b"1"), (b"b", b"2"), (b"c", b"3")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_iter_rev", &test_data);
+
+    let collected: Vec<(Vec<u8>, Vec<u8>)> = db.iter_rev(&ReadOptions::new()).collect();
+    assert_eq!(
+        collected,
+        vec![
+            (b"c".to_vec(), b"3".to_vec()),
+            (b"b".to_vec(), b"2".to_vec()),
+            (b"a".to_vec(), b"1".to_vec()),
+        ]
+    );
+}
+
+#[test]
+fn test_iter_rev_seek() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> = vec![
+        (b"apple", b"fruit"),
+        (b"banana", b"fruit"),
+        (b"cherry", b"fruit"),
+        (b"date", b"fruit"),
+    ];
+    let (db, _temp_dir) = setup_test_db_with_data("test_iter_rev_seek", &test_data);
+    let read_opts = ReadOptions::new();
+    let keys = |iter: crate::RevIter<'_>| -> Vec<Vec<u8>> { iter.map(|(k, _)| k).collect() };
+
+    // Exact key
+    let mut iter = db.iter_rev(&read_opts);
+    iter.seek(b"banana");
+    assert_eq!(keys(iter), vec![b"banana".to_vec(), b"apple".to_vec()]);
+
+    // Between keys lands on the greatest smaller key
+    let mut iter = db.iter_rev(&read_opts);
+    iter.seek(b"carrot");
+    assert_eq!(keys(iter), vec![b"banana".to_vec(), b"apple".to_vec()]);
+
+    // Beyond the last key starts at the last key
+    let mut iter = db.iter_rev(&read_opts);
+    iter.seek(b"zucchini");
+    assert_eq!(keys(iter).len(), 4);
+
+    // Before the first key yields nothing
+    let mut iter = db.iter_rev(&read_opts);
+    iter.seek(b"aardvark");
+    assert_eq!(iter.next(), None);
+
+    // Exhausted iterators come back after a seek
+    let mut iter = db.iter_rev(&read_opts);
+    assert_eq!(iter.by_ref().count(), 4);
+    assert_eq!(iter.next(), None);
+    iter.seek_to_last();
+    assert_eq!(iter.next().map(|(k, _)| k), Some(b"date".to_vec()));
+}
+
+#[test]
+fn test_iter_rev_empty() {
+    let (db, _temp_dir) = setup_test_db_with_data("test_iter_rev_empty", &[]);
+    let read_opts = ReadOptions::new();
+
+    assert_eq!(db.iter_rev(&read_opts).count(), 0);
+    let mut iter = db.iter_rev(&read_opts);
+    iter.seek(b"anything");
+    assert_eq!(iter.next(), None);
+    assert!(iter.status().is_ok());
+}
+
+#[test]
+fn test_seek_for_prev() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> =
+        vec![(b"b", b"2"), (b"d", b"4"), (b"f", b"6")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_seek_for_prev", &test_data);
+    let read_opts = ReadOptions::new();
+    let mut iter = db.iter(&read_opts);
+
+    // Exact hit
+    iter.seek_for_prev(b"d");
+    assert_eq!(iter.key_ref(), Some(&b"d"[..]));
+
+    // Between keys
+    iter.seek_for_prev(b"e");
+    assert_eq!(iter.key_ref(), Some(&b"d"[..]));
+    iter.seek_for_prev(b"c\xff");
+    assert_eq!(iter.key_ref(), Some(&b"b"[..]));
+
+    // Before the first key
+    iter.seek_for_prev(b"a");
+    assert!(!iter.valid());
+
+    // After the last key
+    iter.seek_for_prev(b"z");
+    assert_eq!(iter.key_ref(), Some(&b"f"[..]));
+
+    // Forward iteration continues from the found key
+    iter.seek_for_prev(b"e");
+    let keys: Vec<Vec<u8>> = iter.map(|(k, _)| k).collect();
+    assert_eq!(keys, vec![b"d".to_vec(), b"f".to_vec()]);
+}
+
+#[test]
+fn test_seek_for_prev_empty() {
+    let (db, _temp_dir) = setup_test_db_with_data("test_seek_for_prev_empty", &[]);
+    let mut iter = db.iter(&ReadOptions::new());
+
+    iter.seek_for_prev(b"anything");
+    assert!(!iter.valid());
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_next_into_matches_owned_iterator() {
+    let (db, _temp_dir) = setup_test_db_with_data("test_next_into", &[]);
+    let write_opts = WriteOptions::new();
+    for i in 0..10_000u32 {
+        let value = vec![(i % 251) as u8; (i % 97) as usize];
+        db.put(format!("key_{:05}", i).as_bytes(), &value, &write_opts)
+            .unwrap();
     }
+    let read_opts = ReadOptions::new();
 
-    assert_eq!(fruits_in_range.len(), 3);
-    assert_eq!(fruits_in_range[0], b"banana");
-    assert_eq!(fruits_in_range[1], b"cherry");
-    assert_eq!(fruits_in_range[2], b"date");
+    let owned: Vec<(Vec<u8>, Vec<u8>)> = db.iter(&read_opts).collect();
+    assert_eq!(owned.len(), 10_000);
+
+    let mut iter = db.iter(&read_opts);
+    let (mut key, mut value) = (Vec::new(), Vec::new());
+    let mut lent = Vec::new();
+    while iter.next_into(&mut key, &mut value) {
+        lent.push((key.clone(), value.clone()));
+    }
+    assert_eq!(lent, owned);
+
+    // Exhausted until the next seek, which is honored like with next()
+    assert!(!iter.next_into(&mut key, &mut value));
+    iter.seek(b"key_09998");
+    assert!(iter.next_into(&mut key, &mut value));
+    assert_eq!(key, b"key_09998");
+    assert_eq!(iter.next(), owned.last().cloned());
+    assert!(!iter.next_into(&mut key, &mut value));
+    assert_eq!(key, b"key_09998");
+}
+
+#[test]
+fn test_next_into_meets_back_end() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> =
+        vec![(b"a", b"1"), (b"b", b"2"), (b"c", b"3")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_next_into_back", &test_data);
+    let mut iter = db.iter(&ReadOptions::new());
+    let (mut key, mut value) = (Vec::new(), Vec::new());
+
+    assert_eq!(iter.next_back(), Some((b"c".to_vec(), b"3".to_vec())));
+    assert!(iter.next_into(&mut key, &mut value));
+    assert_eq!((key.as_slice(), value.as_slice()), (&b"a"[..], &b"1"[..]));
+    assert!(iter.next_into(&mut key, &mut value));
+    assert_eq!(key, b"b");
+    assert!(!iter.next_into(&mut key, &mut value));
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn test_peek() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> =
+        vec![(b"a", b"1"), (b"b", b"2"), (b"c", b"3")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_peek", &test_data);
+    let mut iter = db.iter(&ReadOptions::new());
+
+    // A fresh iterator peeks at the first entry, repeatedly
+    assert_eq!(iter.peek(), Some((b"a".to_vec(), b"1".to_vec())));
+    assert_eq!(iter.peek_key(), Some(&b"a"[..]));
+    assert_eq!(iter.next(), Some((b"a".to_vec(), b"1".to_vec())));
+
+    assert_eq!(iter.peek_key(), Some(&b"b"[..]));
+    assert_eq!(iter.next(), Some((b"b".to_vec(), b"2".to_vec())));
+    assert_eq!(iter.peek(), iter.next());
+
+    assert_eq!(iter.peek(), None);
+    assert_eq!(iter.peek_key(), None);
+    assert_eq!(iter.next(), None);
+
+    // Peeking follows explicit seeks and stops where the back end stands
+    iter.seek(b"b");
+    assert_eq!(iter.peek_key(), Some(&b"b"[..]));
+    assert_eq!(iter.next_back(), Some((b"c".to_vec(), b"3".to_vec())));
+    assert_eq!(iter.peek_key(), Some(&b"b"[..]));
+    assert_eq!(iter.next(), Some((b"b".to_vec(), b"2".to_vec())));
+    assert_eq!(iter.peek(), None);
+}
+
+#[test]
+fn test_snapshot_iter() {
+    let test_data: Vec<(&'static [u8], &'static [u8])> = vec![(b"a", b"1"), (b"b", b"2")];
+    let (db, _temp_dir) = setup_test_db_with_data("test_snapshot_iter_owned", &test_data);
+    let write_opts = WriteOptions::new();
+
+    let mut iter = db.snapshot_iter(&ReadOptions::new());
+    assert_eq!(iter.next(), Some((b"a".to_vec(), b"1".to_vec())));
+
+    // Writes made while iterating are invisible to the iterator and its snapshot
+    db.put(b"c", b"3", &write_opts).expect("Put failed");
+    db.delete(b"b", &write_opts).expect("Delete failed");
+    assert_eq!(iter.next(), Some((b"b".to_vec(), b"2".to_vec())));
+    assert_eq!(iter.next(), None);
+    let pinned = iter.snapshot().read_options(&ReadOptions::new());
+    assert_eq!(db.get(b"b", &pinned).unwrap(), Some(b"2".to_vec()));
+
+    // Seeks go through to the wrapped iterator
+    iter.seek(b"b");
+    assert_eq!(iter.key_ref(), Some(&b"b"[..]));
+    assert_eq!(iter.count(), 1);
+}
+
+#[test]
+fn test_snapshot_iter_releases_snapshot() {
+    let (db, _temp_dir) = setup_test_db_with_data("test_snapshot_iter_release", &[]);
+    let write_opts = WriteOptions::new();
+    // Values that do not compress, so table sizes track the live data
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    for i in 0..256u32 {
+        let value: Vec<u8> = (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect();
+        db.put(&i.to_be_bytes(), &value, &write_opts).unwrap();
+    }
+    db.compact_all().unwrap();
+    let full = db.disk_usage().unwrap().table_bytes;
+    assert!(full > 512 * 1024);
+
+    let iter = db.snapshot_iter(&ReadOptions::new());
+    for i in 0..256u32 {
+        db.delete(&i.to_be_bytes(), &write_opts).unwrap();
+    }
+
+    // While the snapshot is alive compaction must keep the deleted values
+    db.compact_all().unwrap();
+    assert!(db.disk_usage().unwrap().table_bytes > full / 2);
+    assert_eq!(iter.count(), 256);
+
+    // Consuming the iterator dropped it, releasing the snapshot. LevelDB never
+    // recompacts the bottom level on its own, so push fresh tombstones down
+    // onto the retained values.
+    for i in 0..256u32 {
+        db.delete(&i.to_be_bytes(), &write_opts).unwrap();
+    }
+    db.compact_all().unwrap();
+    assert!(db.disk_usage().unwrap().table_bytes < full / 10);
+}
+
+#[test]
+fn test_prev_visits_every_entry() {
+    let (db, _temp_dir) = setup_test_db_with_data("test_prev_count", &[]);
+    let write_opts = WriteOptions::new();
+    for i in 0..100u32 {
+        db.put(&i.to_be_bytes(), &i.to_le_bytes(), &write_opts)
+            .unwrap();
+    }
+    let read_opts = ReadOptions::new();
+    let forward: Vec<_> = db.iter(&read_opts).collect();
+
+    let mut iter = db.iter(&read_opts);
+    iter.seek_to_last();
+    let mut backward = Vec::new();
+    while let Some(entry) = iter.prev() {
+        backward.push(entry);
+    }
+    backward.reverse();
+    assert_eq!(backward.len(), forward.len());
+    assert_eq!(backward, forward);
+
+    // A fresh iterator starts at the last key
+    let mut fresh = db.iter(&read_opts);
+    assert_eq!(fresh.prev().as_ref(), forward.last());
+    assert_eq!(fresh.prev().as_ref(), forward.get(98));
+}
+
+#[test]
+fn test_key_into_value_into_full_scan() {
+    let (db, _temp_dir) = setup_test_db_with_data("into_buffers", &[]);
+    for i in 0..1000u32 {
+        let value = vec![i as u8; (i % 50) as usize];
+        db.put(i.to_be_bytes(), value, &WriteOptions::new())
+            .unwrap();
+    }
+    let owned: Vec<(Vec<u8>, Vec<u8>)> = db.iter(&ReadOptions::new()).collect();
+
+    let mut iter = db.iter(&ReadOptions::new());
+    iter.seek_to_first();
+    let (mut key, mut value) = (Vec::with_capacity(64), Vec::with_capacity(64));
+    let (key_ptr, value_ptr) = (key.as_ptr(), value.as_ptr());
+    let mut scanned = Vec::new();
+    while iter.key_into(&mut key) && iter.value_into(&mut value) {
+        scanned.push((key.clone(), value.clone()));
+        iter.next_native();
+    }
+    assert_eq!(scanned, owned);
+    // The buffers were reused, not reallocated
+    assert_eq!((key.as_ptr(), value.as_ptr()), (key_ptr, value_ptr));
+
+    // An exhausted iterator leaves the buffers empty
+    assert!(key.is_empty());
+    assert!(!iter.value_into(&mut value));
+    assert!(value.is_empty());
 }
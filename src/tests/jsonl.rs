@@ -0,0 +1,176 @@
+use crate::DB;
+use crate::bedrock::describe_key;
+use crate::jsonl::KeyEncoding;
+use crate::options::{Options, ReadOptions, WriteOptions};
+use tempfile::TempDir;
+
+fn setup_test_db(name: &str) -> (DB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(temp_dir.path().join(name), &options).expect("Failed to open database");
+    (db, temp_dir)
+}
+
+fn entries(db: &DB) -> Vec<(Vec<u8>, Vec<u8>)> {
+    db.iter(&ReadOptions::new()).collect()
+}
+
+#[test]
+fn test_key_encodings() {
+    let cases: [(&[u8], &str, &str); 5] = [
+        (b"", "", ""),
+        (b"f", "66", "Zg=="),
+        (b"fo", "666f", "Zm8="),
+        (b"foo", "666f6f", "Zm9v"),
+        (&[0x00, 0xff, 0x10, 0xfb], "00ff10fb", "AP8Q+w=="),
+    ];
+    for (bytes, hex, base64) in cases {
+        assert_eq!(KeyEncoding::Hex.encode(bytes), hex);
+        assert_eq!(KeyEncoding::Base64.encode(bytes), base64);
+        assert_eq!(KeyEncoding::Hex.decode(hex).unwrap(), bytes);
+        assert_eq!(KeyEncoding::Base64.decode(base64).unwrap(), bytes);
+    }
+    assert_eq!(KeyEncoding::Hex.decode("00FF").unwrap(), [0x00, 0xff]);
+
+    for bad in ["abc", "zz", "0é"] {
+        assert!(KeyEncoding::Hex.decode(bad).is_err(), "{}", bad);
+    }
+    for bad in ["Zg=", "Z===", "Zg==Zg==", "Zm9*"] {
+        assert!(KeyEncoding::Base64.decode(bad).is_err(), "{}", bad);
+    }
+}
+
+#[test]
+fn test_jsonl_round_trip_binary_keys() {
+    let (db, _temp_dir) = setup_test_db("jsonl_source");
+    let write_opts = WriteOptions::new();
+    let mut chunk_key = Vec::new();
+    chunk_key.extend_from_slice(&(-3i32).to_le_bytes());
+    chunk_key.extend_from_slice(&7i32.to_le_bytes());
+    chunk_key.push(0x2f);
+    chunk_key.push(0xfc);
+    db.put(&chunk_key, [0x08, 0x01, 0x00], &write_opts).unwrap();
+    db.put(b"~local_player", b"\x0a\x00\x00\x00", &write_opts)
+        .unwrap();
+    db.put([0x00, b'"', b'\\', b'\n', 0xff], [], &write_opts)
+        .unwrap();
+    for i in 0..200u32 {
+        db.put(i.to_be_bytes(), vec![i as u8; i as usize], &write_opts)
+            .unwrap();
+    }
+
+    for encoding in [KeyEncoding::Hex, KeyEncoding::Base64] {
+        let mut out = Vec::new();
+        let stats = db.export_jsonl(&mut out, encoding).unwrap();
+        assert_eq!(stats.records, 203);
+        assert_eq!(stats.bytes_written, out.len() as u64);
+
+        let text = String::from_utf8(out.clone()).unwrap();
+        assert_eq!(text.lines().count(), 203);
+        let player_line = text
+            .lines()
+            .find(|line| line.contains(&encoding.encode(b"~local_player")))
+            .unwrap();
+        assert!(player_line.ends_with(&format!(
+            "\"description\":\"{}\"}}",
+            describe_key(b"~local_player")
+        )));
+
+        let (copy, _copy_dir) = setup_test_db("jsonl_copy");
+        let imported = copy
+            .import_jsonl(out.as_slice(), encoding, false, &write_opts)
+            .unwrap();
+        assert_eq!(imported.written, 203);
+        assert_eq!(imported.malformed, 0);
+        assert_eq!(entries(&copy), entries(&db));
+    }
+}
+
+#[test]
+fn test_import_jsonl_malformed_lines() {
+    let input = concat!(
+        "{\"key\":\"6131\",\"value\":\"01\"}\n",
+        "not json\n",
+        "\n",
+        "{\"value\":\"02\",\"note\":{\"list\":[1,-2.5e3,true,null,\"\\u00e9\\ud83d\\ude00\"]},\"key\":\"6132\"}\n",
+        "{\"key\":\"6133\"}\n",
+        "{\"key\":\"zz\",\"value\":\"03\"}\n",
+        "{\"key\":\"6134\",\"value\":\"04\"} trailing\n",
+        "  {\"description\":\"tab\\tand \\\"quote\\\"\", \"key\" : \"6135\" , \"value\" : \"\"}  \n",
+        "{\"key\":\"6136\",\"value\":\"06\"",
+    );
+
+    // Without skipping, the first malformed line stops the import
+    let (db, _temp_dir) = setup_test_db("jsonl_strict");
+    let err = db
+        .import_jsonl(
+            input.as_bytes(),
+            KeyEncoding::Hex,
+            false,
+            &WriteOptions::new(),
+        )
+        .unwrap_err();
+    assert!(err.starts_with("line 2: "), "{}", err);
+
+    let (db, _temp_dir) = setup_test_db("jsonl_skip");
+    let stats = db
+        .import_jsonl(
+            input.as_bytes(),
+            KeyEncoding::Hex,
+            true,
+            &WriteOptions::new(),
+        )
+        .unwrap();
+    assert_eq!(stats.written, 3);
+    assert_eq!(stats.malformed, 5);
+    let lines: Vec<u64> = stats.malformed_lines.iter().map(|l| l.line).collect();
+    assert_eq!(lines, [2, 5, 6, 7, 9]);
+    assert!(
+        stats.malformed_lines[1]
+            .reason
+            .contains("missing \"value\"")
+    );
+    assert!(stats.malformed_lines[2].reason.contains("invalid hex"));
+    assert_eq!(
+        entries(&db),
+        vec![
+            (b"a1".to_vec(), vec![1]),
+            (b"a2".to_vec(), vec![2]),
+            (b"a5".to_vec(), vec![]),
+        ]
+    );
+}
+
+#[test]
+fn test_import_jsonl_deeply_nested_field() {
+    let depth = 100_000;
+    let mut input = String::from("{\"key\":\"6131\",\"value\":\"01\"}\n");
+    input.push_str("{\"key\":\"6132\",\"value\":\"02\",\"x\":");
+    input.push_str(&"[".repeat(depth));
+    input.push_str(&"]".repeat(depth));
+    input.push_str("}\n{\"key\":\"6133\",\"value\":\"03\"}\n");
+
+    let (db, _temp_dir) = setup_test_db("jsonl_nested");
+    let stats = db
+        .import_jsonl(
+            input.as_bytes(),
+            KeyEncoding::Hex,
+            true,
+            &WriteOptions::new(),
+        )
+        .unwrap();
+    assert_eq!(stats.written, 2);
+    assert_eq!(stats.malformed, 1);
+    assert_eq!(stats.malformed_lines[0].line, 2);
+    assert!(
+        stats.malformed_lines[0].reason.contains("nested deeper"),
+        "{}",
+        stats.malformed_lines[0].reason
+    );
+    assert_eq!(
+        entries(&db),
+        vec![(b"a1".to_vec(), vec![1]), (b"a3".to_vec(), vec![3])]
+    );
+}
@@ -0,0 +1,172 @@
+use crate::DB;
+use crate::merge::{MergePolicy, MergeReport};
+use crate::options::{Options, ReadOptions, WriteOptions};
+use tempfile::TempDir;
+
+fn setup_test_db(name: &str, entries: &[(&str, &str)]) -> (DB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(temp_dir.path().join(name), &options).expect("Failed to open database");
+    for (key, value) in entries {
+        db.put(key, value, &WriteOptions::new()).unwrap();
+    }
+    (db, temp_dir)
+}
+
+/// A live world and a backup sharing the keys `b`, `c` and `d`.
+fn overlapping() -> ((DB, TempDir), (DB, TempDir)) {
+    (
+        setup_test_db(
+            "live",
+            &[
+                ("a", "live-a"),
+                ("b", "live-b"),
+                ("c", "same"),
+                ("d", "live-d"),
+            ],
+        ),
+        setup_test_db(
+            "backup",
+            &[
+                ("b", "backup-b"),
+                ("c", "same"),
+                ("d", "backup-d"),
+                ("e", "backup-e"),
+            ],
+        ),
+    )
+}
+
+fn entries(db: &DB) -> Vec<(String, String)> {
+    db.iter(&ReadOptions::new())
+        .map(|(key, value)| {
+            (
+                String::from_utf8(key).unwrap(),
+                String::from_utf8(value).unwrap(),
+            )
+        })
+        .collect()
+}
+
+fn pairs(entries: &[(&str, &str)]) -> Vec<(String, String)> {
+    entries
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_merge_prefer_self() {
+    let ((live, _live_dir), (backup, _backup_dir)) = overlapping();
+    let report = live
+        .merge_from(&backup, MergePolicy::PreferSelf, &WriteOptions::new())
+        .unwrap();
+    assert_eq!(
+        report,
+        MergeReport {
+            added: 1,
+            overwritten: 0,
+            skipped: 3,
+            batches: 1,
+        }
+    );
+    assert_eq!(
+        entries(&live),
+        pairs(&[
+            ("a", "live-a"),
+            ("b", "live-b"),
+            ("c", "same"),
+            ("d", "live-d"),
+            ("e", "backup-e"),
+        ])
+    );
+    // The source is left as it was
+    assert_eq!(entries(&backup).len(), 4);
+}
+
+#[test]
+fn test_merge_prefer_other() {
+    let ((live, _live_dir), (backup, _backup_dir)) = overlapping();
+    let report = live
+        .merge_from(&backup, MergePolicy::PreferOther, &WriteOptions::new())
+        .unwrap();
+    // `c` holds the same value on both sides, so nothing changes for it
+    assert_eq!(
+        (report.added, report.overwritten, report.skipped),
+        (1, 2, 1)
+    );
+    assert_eq!(
+        entries(&live),
+        pairs(&[
+            ("a", "live-a"),
+            ("b", "backup-b"),
+            ("c", "same"),
+            ("d", "backup-d"),
+            ("e", "backup-e"),
+        ])
+    );
+}
+
+#[test]
+fn test_merge_custom_concatenates() {
+    let ((live, _live_dir), (backup, _backup_dir)) = overlapping();
+    let mut seen = Vec::new();
+    let policy = MergePolicy::Custom(Box::new(|key, ours, theirs| {
+        seen.push(key.to_vec());
+        [ours, b"+", theirs].concat()
+    }));
+    let report = live
+        .merge_from(&backup, policy, &WriteOptions::new())
+        .unwrap();
+    assert_eq!(
+        (report.added, report.overwritten, report.skipped),
+        (1, 3, 0)
+    );
+    assert_eq!(seen, [b"b", b"c", b"d"]);
+    assert_eq!(
+        entries(&live),
+        pairs(&[
+            ("a", "live-a"),
+            ("b", "live-b+backup-b"),
+            ("c", "same+same"),
+            ("d", "live-d+backup-d"),
+            ("e", "backup-e"),
+        ])
+    );
+}
+
+#[test]
+fn test_merge_many_keys_in_batches() {
+    let (live, _live_dir) = setup_test_db("live_many", &[]);
+    let (backup, _backup_dir) = setup_test_db("backup_many", &[]);
+    let write_opts = WriteOptions::new();
+    let value = vec![7u8; 1024];
+    for i in 0..10_000u32 {
+        backup.put(i.to_be_bytes(), &value, &write_opts).unwrap();
+        if i % 2 == 0 {
+            live.put(i.to_be_bytes(), b"live", &write_opts).unwrap();
+        }
+    }
+
+    let report = live
+        .merge_from(&backup, MergePolicy::PreferOther, &write_opts)
+        .unwrap();
+    assert_eq!((report.added, report.overwritten), (5000, 5000));
+    assert!(report.batches > 1, "{:?}", report);
+    assert_eq!(live.iter(&ReadOptions::new()).count(), 10_000);
+    assert!(live.iter(&ReadOptions::new()).all(|(_, v)| v == value));
+}
+
+#[test]
+fn test_merge_into_read_only_fails() {
+    let ((live, live_dir), (backup, _backup_dir)) = overlapping();
+    drop(live);
+    let read_only = DB::open_read_only(live_dir.path().join("live"), &Options::new()).unwrap();
+    assert!(
+        read_only
+            .merge_from(&backup, MergePolicy::PreferOther, &WriteOptions::new())
+            .is_err()
+    );
+}
@@ -0,0 +1,102 @@
+use crate::options::{Options, ReadOptions, WriteOptions};
+use crate::write_batch::WriteBatch;
+use crate::{DB, ErrorKind, MetricsSnapshot};
+use tempfile::TempDir;
+
+fn setup_test_db() -> (DB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(temp_dir.path().join("db"), &options).expect("Failed to open database");
+    (db, temp_dir)
+}
+
+#[test]
+fn test_metrics_count_operations() {
+    let (db, _temp_dir) = setup_test_db();
+    assert_eq!(db.metrics().snapshot(), MetricsSnapshot::default());
+
+    let read = ReadOptions::new();
+    let write = WriteOptions::new();
+    db.put(b"key1", b"value1", &write).unwrap();
+    db.put(b"key2", b"longer value", &write).unwrap();
+    db.delete(b"key1", &write).unwrap();
+
+    let mut batch = WriteBatch::new();
+    batch.put(b"key3", b"batched");
+    batch.delete(b"key2");
+    let batch_size = batch.approximate_size();
+    db.write(&batch, &write).unwrap();
+
+    assert_eq!(db.get(b"key3", &read).unwrap(), Some(b"batched".to_vec()));
+    assert_eq!(db.get(b"key1", &read).unwrap(), None);
+    let mut buf = Vec::new();
+    assert_eq!(db.get_into(b"key3", &mut buf, &read).unwrap(), Some(7));
+    assert_eq!(db.get_pinned(b"key3", &read).unwrap().unwrap().len(), 7);
+    // Sorted keys go through one iterator, unsorted ones through `get`
+    db.multi_get(&[b"key1", b"key3"], &read).unwrap();
+    db.multi_get(&[b"key3", b"key1"], &read).unwrap();
+    db.iter(&read).count();
+
+    // Clones share the counters
+    let clone = db.clone();
+    clone.put(b"key4", b"v", &write).unwrap();
+
+    let metrics = db.metrics().snapshot();
+    assert_eq!(metrics.gets, 8);
+    assert_eq!(metrics.get_hits, 5);
+    assert_eq!(metrics.bytes_read, 5 * 7);
+    assert_eq!(metrics.puts, 3);
+    assert_eq!(metrics.deletes, 1);
+    assert_eq!(metrics.batch_commits, 1);
+    assert_eq!(
+        metrics.bytes_written,
+        (4 + 6) + (4 + 12) + 4 + batch_size as u64 + (4 + 1)
+    );
+    // get_pinned, the sorted multi_get and the explicit iterator
+    assert_eq!(metrics.iterators, 3);
+    assert_eq!(metrics.errors, [0; ErrorKind::ALL.len()]);
+    assert_eq!(metrics.read_latency.count(), 8);
+    assert_eq!(metrics.write_latency.count(), 5);
+    assert_eq!(metrics.write_latency.cumulative().last(), Some(&5));
+}
+
+#[test]
+fn test_metrics_count_errors_by_kind() {
+    let (db, temp_dir) = setup_test_db();
+    db.put(b"key", b"value", &WriteOptions::new()).unwrap();
+    drop(db);
+
+    let db = DB::open_read_only(temp_dir.path().join("db"), &Options::new()).unwrap();
+    assert!(db.put(b"key", b"value", &WriteOptions::new()).is_err());
+    assert!(db.delete(b"key", &WriteOptions::new()).is_err());
+    assert!(db.write(&WriteBatch::new(), &WriteOptions::new()).is_err());
+
+    let metrics = db.metrics().snapshot();
+    assert_eq!(metrics.errors(ErrorKind::ReadOnly), 3);
+    assert_eq!(metrics.errors(ErrorKind::Other), 0);
+    assert_eq!(metrics.puts, 1);
+    assert_eq!(metrics.deletes, 1);
+    assert_eq!(metrics.batch_commits, 0);
+    assert_eq!(metrics.bytes_written, 0);
+    assert_eq!(metrics.write_latency.count(), 3);
+}
+
+#[test]
+fn test_error_kind_classify() {
+    assert_eq!(
+        ErrorKind::classify("Corruption: bad block contents"),
+        ErrorKind::Corruption
+    );
+    assert_eq!(
+        ErrorKind::classify("IO error: db/LOCK: No such file or directory"),
+        ErrorKind::Io
+    );
+    assert_eq!(
+        ErrorKind::classify("Invalid argument: db does not exist"),
+        ErrorKind::InvalidArgument
+    );
+    assert_eq!(ErrorKind::classify("unknown error"), ErrorKind::Other);
+    assert_eq!(ErrorKind::Io.to_string(), "io");
+}
@@ -1,3 +1,29 @@
+// Most tests pass keys and values by reference, the way callers did before the
+// write methods became generic over `AsRef<[u8]>`; keep them that way.
+#![allow(clippy::needless_borrows_for_generic_args)]
+
 #[cfg(test)]
+#[cfg(feature = "async")]
+mod async_db;
+mod backup;
+mod batch_writer;
+mod bedrock;
+#[cfg(feature = "serde")]
+mod codec;
 mod db;
+mod export;
+mod ingest;
+mod integrity;
+// The original iterator tests drive `next` by hand; keep them as written.
+#[allow(clippy::while_let_on_iterator)]
 mod iterator;
+mod jsonl;
+mod merge;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod options;
+mod parallel;
+mod small_value;
+#[cfg(feature = "tracing")]
+mod trace;
+mod write_batch;
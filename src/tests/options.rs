@@ -0,0 +1,177 @@
+use crate::DB;
+use crate::options::{Compression, Options, OptionsError, OptionsIssueKind};
+
+fn issue_kinds(options: &Options) -> Vec<OptionsIssueKind> {
+    match options.validate() {
+        Ok(()) => Vec::new(),
+        Err(issues) => issues.into_iter().map(|issue| issue.kind).collect(),
+    }
+}
+
+#[test]
+fn test_options_validate_defaults() {
+    let options = Options::new();
+    assert!(options.validate().is_ok());
+}
+
+#[test]
+fn test_options_settings_record() {
+    let options = Options::new();
+    options.create_if_missing(true);
+    options.write_buffer_size(8 * 1024 * 1024);
+    options.bloom_filter(10);
+
+    let settings = options.settings();
+    assert!(settings.create_if_missing);
+    assert!(!settings.error_if_exists);
+    assert_eq!(settings.write_buffer_size, 8 * 1024 * 1024);
+    assert_eq!(settings.bloom_filter_bits, Some(10));
+}
+
+#[test]
+fn test_options_bedrock_preset() {
+    let options = Options::bedrock();
+    assert!(options.validate().is_ok());
+
+    let settings = options.settings();
+    assert!(!settings.create_if_missing);
+    assert_eq!(settings.compression, Compression::ZlibRaw);
+    assert_eq!(settings.block_size, 160 * 1024);
+    assert_eq!(settings.bloom_filter_bits, Some(10));
+}
+
+#[test]
+fn test_options_validate_zero_write_buffer() {
+    let options = Options::new();
+    options.write_buffer_size(0);
+    assert_eq!(
+        issue_kinds(&options),
+        vec![OptionsIssueKind::ZeroWriteBufferSize]
+    );
+}
+
+#[test]
+fn test_options_validate_zero_block_size() {
+    let options = Options::new();
+    options.block_size(0);
+    assert_eq!(issue_kinds(&options), vec![OptionsIssueKind::ZeroBlockSize]);
+}
+
+#[test]
+fn test_options_validate_block_larger_than_file() {
+    let options = Options::new();
+    options.block_size(64 * 1024);
+    options.max_file_size(16 * 1024);
+    assert_eq!(
+        issue_kinds(&options),
+        vec![OptionsIssueKind::BlockSizeExceedsMaxFileSize]
+    );
+}
+
+#[test]
+fn test_options_validate_zero_bloom_bits() {
+    let options = Options::new();
+    options.bloom_filter(0);
+    assert_eq!(
+        issue_kinds(&options),
+        vec![OptionsIssueKind::ZeroBloomFilterBits]
+    );
+}
+
+#[test]
+fn test_options_validate_reports_every_issue() {
+    let options = Options::new();
+    options.write_buffer_size(0);
+    options.bloom_filter(0);
+
+    let issues = options.validate().unwrap_err();
+    assert_eq!(issues.len(), 2);
+    assert!(issues.iter().all(|issue| !issue.message.is_empty()));
+}
+
+#[test]
+fn test_options_validate_error_if_exists() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_error_if_exists");
+
+    let options = Options::new();
+    options.create_if_missing(true);
+    drop(DB::open(&db_path, &options).expect("Failed to create database"));
+
+    options.error_if_exists(true);
+    assert!(options.validate().is_ok());
+
+    let issues = options.validate_for_path(&db_path).unwrap_err();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(
+        issues[0].kind,
+        OptionsIssueKind::ErrorIfExistsOnExistingDatabase
+    );
+
+    let error = DB::open(&db_path, &options).err().unwrap();
+    assert!(error.starts_with("invalid options:"));
+}
+
+#[test]
+fn test_options_validate_on_open_opt_out() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_validate_opt_out");
+
+    let options = Options::new();
+    options.create_if_missing(true);
+    options.bloom_filter(0);
+
+    let error = DB::open(&db_path, &options).err().unwrap();
+    assert!(error.contains("bloom_filter"));
+
+    options.validate_on_open(false);
+    assert!(DB::open(&db_path, &options).is_ok());
+}
+
+#[test]
+fn test_options_bloom_filter_outlives_options() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join("test_bloom_lifetime");
+
+    let db = {
+        let options = Options::new();
+        options.create_if_missing(true);
+        options.bloom_filter(10);
+        DB::open(&db_path, &options).expect("Failed to open database")
+    };
+
+    db.put(b"key", b"value", &Default::default()).unwrap();
+    db.compact_range(None, None).expect("Compaction failed");
+    assert_eq!(db.get(b"missing", &Default::default()).unwrap(), None);
+    assert_eq!(
+        db.get(b"key", &Default::default()).unwrap(),
+        Some(b"value".to_vec())
+    );
+}
+
+#[test]
+fn test_zstd_settings_unsupported() {
+    let options = Options::new();
+    options.compression(Compression::Zstd);
+    let before = options.settings();
+
+    let err = options.zstd_level(19).unwrap_err();
+    assert!(matches!(
+        err,
+        OptionsError::Unsupported {
+            option: "zstd_level",
+            ..
+        }
+    ));
+    assert!(err.to_string().starts_with("zstd_level is not supported"));
+
+    let err = options.zstd_dictionary(b"trained dictionary").unwrap_err();
+    assert!(matches!(
+        err,
+        OptionsError::Unsupported {
+            option: "zstd_dictionary",
+            ..
+        }
+    ));
+    assert_eq!(options.settings(), before);
+}
@@ -0,0 +1,196 @@
+use crate::options::{Options, ReadOptions, WriteOptions};
+use crate::{CompactRange, DB};
+use tempfile::TempDir;
+
+fn setup_test_db(name: &str, keys: impl IntoIterator<Item = Vec<u8>>) -> (DB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(temp_dir.path().join(name), &options).expect("Failed to open database");
+    let write_opts = WriteOptions::new();
+    for key in keys {
+        db.put(&key, &[0xAB; 32], &write_opts).unwrap();
+    }
+    (db, temp_dir)
+}
+
+/// Collect the keys falling into each range, asserting the ranges are well formed.
+fn keys_per_range(db: &DB, ranges: &[(Vec<u8>, Vec<u8>)]) -> Vec<Vec<Vec<u8>>> {
+    assert!(ranges.first().unwrap().0.is_empty());
+    assert!(ranges.last().unwrap().1.is_empty());
+    for pair in ranges.windows(2) {
+        // Adjacent ranges share their boundary and are strictly increasing
+        assert_eq!(pair[0].1, pair[1].0);
+        assert!(pair[0].0 < pair[0].1);
+    }
+
+    let read_opts = ReadOptions::new();
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            let mut keys = Vec::new();
+            let mut iter = db.iter(&read_opts);
+            iter.seek(start);
+            while let Some(key) = iter.key_ref() {
+                if !end.is_empty() && key >= end.as_slice() {
+                    break;
+                }
+                keys.push(key.to_vec());
+                iter.next_native();
+            }
+            keys
+        })
+        .collect()
+}
+
+#[test]
+fn test_split_ranges_cover_everything() {
+    let keys: Vec<Vec<u8>> = (0..5000u32).map(|i| i.to_be_bytes().to_vec()).collect();
+    let (db, _temp_dir) = setup_test_db("test_split_cover", keys.clone());
+    db.compact_all().unwrap();
+
+    for n in [1, 2, 4, 7] {
+        let ranges = db.split_ranges(n, &ReadOptions::new());
+        assert!(!ranges.is_empty() && ranges.len() <= n);
+
+        let per_range = keys_per_range(&db, &ranges);
+        let covered: Vec<Vec<u8>> = per_range.iter().flatten().cloned().collect();
+        assert_eq!(covered, keys);
+
+        // Uniform keys should split into reasonably balanced ranges
+        if n > 1 {
+            assert_eq!(ranges.len(), n);
+            let largest = per_range.iter().map(Vec::len).max().unwrap();
+            assert!(
+                largest < 2 * keys.len() / n,
+                "{:?}",
+                per_range.iter().map(Vec::len)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_split_ranges_small_and_empty() {
+    let (empty, _temp_dir) = setup_test_db("test_split_empty", Vec::new());
+    assert_eq!(
+        empty.split_ranges(4, &ReadOptions::new()),
+        vec![(Vec::new(), Vec::new())]
+    );
+
+    // Keys only in the memtable, with a long shared prefix
+    let keys: Vec<Vec<u8>> = ["chunk_a", "chunk_b", "chunk_c"]
+        .iter()
+        .map(|k| k.as_bytes().to_vec())
+        .collect();
+    let (db, _temp_dir) = setup_test_db("test_split_small", keys.clone());
+    let ranges = db.split_ranges(10, &ReadOptions::new());
+    assert!(ranges.len() <= 3);
+    let covered: Vec<Vec<u8>> = keys_per_range(&db, &ranges).concat();
+    assert_eq!(covered, keys);
+
+    // Huge requests are capped instead of sampling forever
+    let ranges = db.split_ranges(usize::MAX, &ReadOptions::new());
+    assert!(ranges.len() <= 3);
+    assert_eq!(keys_per_range(&db, &ranges).concat(), keys);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_scan_matches_serial_scan() {
+    use std::ops::ControlFlow;
+    use std::sync::Mutex;
+
+    let keys: Vec<Vec<u8>> = (0..3000u32)
+        .map(|i| format!("key_{:06}", i * 7).into_bytes())
+        .collect();
+    let (db, _temp_dir) = setup_test_db("test_par_scan", keys);
+    let read_opts = ReadOptions::new();
+
+    let mut serial = Vec::new();
+    db.scan(
+        None,
+        |key, _| {
+            serial.push(key.to_vec());
+            ControlFlow::Continue(())
+        },
+        &read_opts,
+    )
+    .unwrap();
+
+    let parallel = Mutex::new(Vec::new());
+    let stats = db
+        .par_scan(
+            4,
+            |key, _| {
+                parallel.lock().unwrap().push(key.to_vec());
+                ControlFlow::Continue(())
+            },
+            &read_opts,
+        )
+        .unwrap();
+
+    let mut parallel = parallel.into_inner().unwrap();
+    parallel.sort();
+    assert_eq!(stats.visited, serial.len() as u64);
+    assert!(!stats.stopped_early);
+    assert_eq!(parallel, serial);
+}
+
+#[test]
+fn test_compact_parallel_keeps_data() {
+    let keys: Vec<Vec<u8>> = (0..4000u32).map(|i| i.to_be_bytes().to_vec()).collect();
+    let (db, _temp_dir) = setup_test_db("test_compact_parallel", keys.clone());
+    let write_opts = WriteOptions::new();
+    for key in keys.iter().step_by(3) {
+        db.delete(key, &write_opts).unwrap();
+    }
+    let expected: Vec<(Vec<u8>, Vec<u8>)> = db.iter(&ReadOptions::new()).collect();
+
+    let bounds: Vec<[u8; 4]> = [1000u32, 2000, 3000].map(u32::to_be_bytes).to_vec();
+    let ranges: Vec<CompactRange> = vec![
+        (Some(&bounds[2][..]), None),
+        (None, Some(&bounds[0][..])),
+        (Some(&bounds[1][..]), Some(&bounds[2][..])),
+        (Some(&bounds[0][..]), Some(&bounds[1][..])),
+    ];
+    db.compact_parallel(&ranges, 3).unwrap();
+    let compacted: Vec<(Vec<u8>, Vec<u8>)> = db.iter(&ReadOptions::new()).collect();
+    assert_eq!(compacted, expected);
+
+    db.compact_parallel_split(4, 4).unwrap();
+    let compacted: Vec<(Vec<u8>, Vec<u8>)> = db.iter(&ReadOptions::new()).collect();
+    assert_eq!(compacted, expected);
+    assert_eq!(compacted.len(), keys.len() - keys.len().div_ceil(3));
+}
+
+#[test]
+fn test_compact_parallel_rejects_overlaps() {
+    let (db, temp_dir) = setup_test_db("test_compact_overlap", Vec::new());
+
+    let overlapping: [CompactRange; 3] = [
+        (Some(b"m"), None),
+        (None, Some(b"c")),
+        (Some(b"b"), Some(b"n")),
+    ];
+    let err = db.compact_parallel(&overlapping, 2).unwrap_err();
+    assert!(err.contains("ranges 1 and 2 overlap"), "{}", err);
+
+    let reversed: [CompactRange; 1] = [(Some(b"z"), Some(b"a"))];
+    assert!(db.compact_parallel(&reversed, 2).is_err());
+
+    // Shared boundaries and empty ranges are fine
+    let touching: [CompactRange; 3] = [
+        (None, Some(b"m")),
+        (Some(b"c"), Some(b"c")),
+        (Some(b"m"), None),
+    ];
+    db.compact_parallel(&touching, 0).unwrap();
+    db.compact_parallel(&[], 4).unwrap();
+
+    let path = temp_dir.path().join("test_compact_overlap");
+    drop(db);
+    let read_only = DB::open_read_only(&path, &Options::new()).unwrap();
+    assert!(read_only.compact_parallel_split(2, 2).is_err());
+}
@@ -0,0 +1,50 @@
+use crate::options::{Options, ReadOptions, WriteOptions};
+use crate::{DB, SmallValue};
+use tempfile::TempDir;
+
+fn setup_test_db(name: &str) -> (DB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(temp_dir.path().join(name), &options).expect("Failed to open database");
+    (db, temp_dir)
+}
+
+#[test]
+fn test_get_small_matches_get_across_boundary() {
+    const N: usize = 16;
+    let (db, _temp_dir) = setup_test_db("get_small");
+    let read_opts = ReadOptions::new();
+
+    for len in [0, 1, N - 1, N, N + 1, 4096] {
+        let key = format!("key{}", len);
+        let value: Vec<u8> = (0..len).map(|i| i as u8).collect();
+        db.put(&key, &value, &WriteOptions::new()).unwrap();
+
+        let small = db.get_small::<N>(&key, &read_opts).unwrap().unwrap();
+        let plain = db.get(&key, &read_opts).unwrap().unwrap();
+        assert_eq!(small.as_slice(), plain.as_slice(), "length {}", len);
+        assert_eq!(small.is_inline(), len <= N, "length {}", len);
+        assert_eq!(small.len(), len);
+        assert_eq!(small.into_vec(), plain);
+    }
+
+    assert_eq!(db.get_small::<N>(b"missing", &read_opts).unwrap(), None);
+}
+
+#[test]
+fn test_small_value_from_slice() {
+    let inline = SmallValue::<4>::from_slice(b"abcd");
+    assert!(inline.is_inline());
+    assert_eq!(&*inline, b"abcd");
+    assert_eq!(inline, SmallValue::<4>::from_slice(b"abcd"));
+
+    let heap = SmallValue::<4>::from_slice(b"abcde");
+    assert!(!heap.is_inline());
+    assert!(heap == *b"abcde".as_slice());
+    assert_eq!(format!("{:?}", heap), "SmallValue([97, 98, 99, 100, 101])");
+
+    // A zero-sized buffer still holds the empty value inline
+    assert!(SmallValue::<0>::from_slice(b"").is_inline());
+}
@@ -0,0 +1,164 @@
+use crate::options::{Options, ReadOptions, WriteOptions};
+use crate::{DB, WriteBatch};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Fields of a span or event, rendered as strings.
+#[derive(Clone, Default)]
+struct Fields(BTreeMap<String, String>);
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+/// Collect every event along with the fields of the span it fired in.
+#[derive(Clone, Default)]
+struct Collector(Arc<Mutex<Vec<BTreeMap<String, String>>>>);
+
+impl Collector {
+    fn take(&self) -> Vec<BTreeMap<String, String>> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Collector {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        ctx.span(id).unwrap().extensions_mut().insert(fields);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let span = ctx.span(id).unwrap();
+        if let Some(fields) = span.extensions_mut().get_mut::<Fields>() {
+            values.record(fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = ctx
+            .event_span(event)
+            .and_then(|span| span.extensions().get::<Fields>().cloned())
+            .unwrap_or_default();
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields.0);
+    }
+}
+
+fn field<'a>(event: &'a BTreeMap<String, String>, name: &str) -> &'a str {
+    event.get(name).map(String::as_str).unwrap_or_default()
+}
+
+fn open_test_db(temp_dir: &TempDir) -> DB {
+    let options = Options::new();
+    options.create_if_missing(true);
+    DB::open(temp_dir.path().join("traced"), &options).expect("Failed to open database")
+}
+
+#[test]
+fn test_operations_emit_events() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let collector = Collector::default();
+    let subscriber = tracing_subscriber::registry().with(collector.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        let db = open_test_db(&temp_dir);
+        let write_opts = WriteOptions::new();
+        db.put(b"~local_player", b"value", &write_opts).unwrap();
+        db.get(b"~local_player", &ReadOptions::new()).unwrap();
+        db.get(b"missing", &ReadOptions::new()).unwrap();
+        db.delete(b"~local_player", &write_opts).unwrap();
+        let mut batch = WriteBatch::new();
+        batch.put(b"a", b"1");
+        batch.put(b"b", b"2");
+        db.write(&batch, &write_opts).unwrap();
+        db.compact_range(None, None).unwrap();
+        drop(db.iter(&ReadOptions::new()));
+    });
+
+    let events = collector.take();
+    let ops: Vec<&str> = events.iter().map(|event| field(event, "op")).collect();
+    assert_eq!(
+        ops,
+        [
+            "open",
+            "put",
+            "get",
+            "get",
+            "delete",
+            "write",
+            "compact_range",
+            "iter"
+        ]
+    );
+    assert!(events.iter().all(|event| field(event, "outcome") == "ok"));
+    assert!(events.iter().all(|event| event.contains_key("duration_us")));
+
+    let put = &events[1];
+    assert_eq!(field(put, "key_len"), "13");
+    assert_eq!(field(put, "value_len"), "5");
+    assert_eq!(
+        field(put, "key"),
+        crate::bedrock::describe_key(b"~local_player").to_string()
+    );
+    assert_eq!(field(&events[2], "value_len"), "5");
+    assert!(!events[3].contains_key("value_len"));
+    assert_eq!(field(&events[5], "ops"), "2");
+}
+
+#[test]
+fn test_failed_operation_reports_error() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let collector = Collector::default();
+    let subscriber = tracing_subscriber::registry().with(collector.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        let options = Options::new();
+        options.create_if_missing(false);
+        assert!(DB::open(temp_dir.path().join("missing"), &options).is_err());
+    });
+
+    let events = collector.take();
+    assert_eq!(events.len(), 1);
+    assert_eq!(field(&events[0], "op"), "open");
+    assert_eq!(field(&events[0], "outcome"), "error");
+    assert!(!field(&events[0], "error").is_empty());
+}
+
+#[test]
+fn test_level_threshold_skips_point_operations() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let collector = Collector::default();
+    let subscriber =
+        tracing_subscriber::registry().with(collector.clone().with_filter(LevelFilter::DEBUG));
+
+    tracing::subscriber::with_default(subscriber, || {
+        let db = open_test_db(&temp_dir);
+        db.put(b"key", b"value", &WriteOptions::new()).unwrap();
+        db.get(b"key", &ReadOptions::new()).unwrap();
+        db.write(&WriteBatch::new(), &WriteOptions::new()).unwrap();
+    });
+
+    let ops: Vec<String> = collector
+        .take()
+        .iter()
+        .map(|event| field(event, "op").to_string())
+        .collect();
+    assert_eq!(ops, ["open", "write"]);
+}
@@ -0,0 +1,484 @@
+use crate::options::{Options, ReadOptions, WriteOptions};
+use crate::{BatchOp, DB, WriteBatch};
+use tempfile::TempDir;
+
+/// Size of the header [`WriteBatch::to_bytes`] writes before the records.
+const BATCH_HEADER: usize = 12;
+
+fn setup_test_db(name: &str) -> (DB, TempDir) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let db_path = temp_dir.path().join(name);
+
+    let options = Options::new();
+    options.create_if_missing(true);
+
+    let db = DB::open(&db_path, &options).expect("Failed to open database");
+    (db, temp_dir)
+}
+
+#[test]
+fn test_batch_write() {
+    let (db, _temp_dir) = setup_test_db("test_batch_write");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    db.put(b"old_key", b"old_value", &write_opts)
+        .expect("Put failed");
+
+    let mut batch = WriteBatch::new();
+    batch.put(b"key1", b"value1");
+    batch.put(b"key2", b"value2");
+    batch.delete(b"old_key");
+
+    db.write(&batch, &write_opts).expect("Batch write failed");
+
+    assert_eq!(
+        db.get(b"key1", &read_opts).unwrap(),
+        Some(b"value1".to_vec())
+    );
+    assert_eq!(
+        db.get(b"key2", &read_opts).unwrap(),
+        Some(b"value2".to_vec())
+    );
+    assert_eq!(db.get(b"old_key", &read_opts).unwrap(), None);
+}
+
+#[test]
+fn test_batch_write_delegates_to_db() {
+    let (db, _temp_dir) = setup_test_db("test_batch_delegate");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    let mut batch = WriteBatch::new();
+    batch.put(b"key", b"value");
+
+    batch.write(&db, &write_opts).expect("Batch write failed");
+    assert_eq!(db.get(b"key", &read_opts).unwrap(), Some(b"value".to_vec()));
+}
+
+#[test]
+fn test_batch_write_error_propagation() {
+    let (db, temp_dir) = setup_test_db("test_batch_write_error");
+    db.put(b"existing", b"value", &WriteOptions::new()).unwrap();
+    drop(db);
+    let read_only = DB::open_read_only(
+        temp_dir.path().join("test_batch_write_error"),
+        &Options::new(),
+    )
+    .unwrap();
+
+    let mut batch = WriteBatch::new();
+    batch.put(b"key", b"value");
+    batch.delete(b"existing");
+
+    // Both forms report the same error and apply nothing
+    let error = read_only.write(&batch, &WriteOptions::new()).unwrap_err();
+    assert!(error.contains("read-only"), "{}", error);
+    assert_eq!(
+        batch.write(&read_only, &WriteOptions::new()).unwrap_err(),
+        error
+    );
+
+    let read_opts = ReadOptions::new();
+    assert_eq!(read_only.get(b"key", &read_opts).unwrap(), None);
+    assert_eq!(
+        read_only.get(b"existing", &read_opts).unwrap(),
+        Some(b"value".to_vec())
+    );
+}
+
+#[test]
+fn test_batch_clear() {
+    let (db, _temp_dir) = setup_test_db("test_batch_clear");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    let mut batch = WriteBatch::new();
+    batch.put(b"discarded", b"value");
+    batch.clear();
+    batch.put(b"kept", b"value");
+
+    db.write(&batch, &write_opts).expect("Batch write failed");
+    assert_eq!(db.get(b"discarded", &read_opts).unwrap(), None);
+    assert_eq!(
+        db.get(b"kept", &read_opts).unwrap(),
+        Some(b"value".to_vec())
+    );
+}
+
+#[test]
+fn test_batch_last_operation_wins() {
+    let (db, _temp_dir) = setup_test_db("test_batch_order");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    let mut batch = WriteBatch::new();
+    batch.put(b"key", b"first");
+    batch.delete(b"key");
+    batch.put(b"key", b"second");
+
+    db.write(&batch, &write_opts).expect("Batch write failed");
+    assert_eq!(
+        db.get(b"key", &read_opts).unwrap(),
+        Some(b"second".to_vec())
+    );
+}
+
+#[test]
+fn test_batch_iter_ops() {
+    let mut batch = WriteBatch::new();
+    assert!(batch.iter_ops().is_empty());
+
+    batch.put(b"key1", b"value1");
+    batch.delete(b"key2");
+    batch.put(b"\x00binary\xff", b"");
+    batch.put(b"key1", b"value2");
+    batch.delete(b"");
+
+    assert_eq!(
+        batch.iter_ops(),
+        vec![
+            BatchOp::Put {
+                key: b"key1".to_vec(),
+                value: b"value1".to_vec(),
+            },
+            BatchOp::Delete {
+                key: b"key2".to_vec(),
+            },
+            BatchOp::Put {
+                key: b"\x00binary\xff".to_vec(),
+                value: Vec::new(),
+            },
+            BatchOp::Put {
+                key: b"key1".to_vec(),
+                value: b"value2".to_vec(),
+            },
+            BatchOp::Delete { key: Vec::new() },
+        ]
+    );
+
+    assert!(batch.contains_key(b"key1"));
+    assert!(batch.contains_key(b"key2"));
+    assert!(batch.contains_key(b""));
+    assert!(!batch.contains_key(b"key3"));
+
+    batch.clear();
+    assert!(batch.iter_ops().is_empty());
+    assert!(!batch.contains_key(b"key1"));
+}
+
+#[test]
+fn test_batch_for_each_op_panic() {
+    let mut batch = WriteBatch::new();
+    batch.put(b"a", b"1");
+    batch.put(b"b", b"2");
+
+    let mut visited = 0;
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        batch.for_each_op(|_, _| {
+            visited += 1;
+            panic!("visitor failed");
+        })
+    }));
+
+    // The panic surfaces after LevelDB returns and skips the remaining operations
+    assert!(result.is_err());
+    assert_eq!(visited, 1);
+    assert_eq!(batch.iter_ops().len(), 2);
+}
+
+#[test]
+fn test_batch_approximate_size() {
+    let mut batch = WriteBatch::new();
+    assert_eq!(batch.approximate_size(), 12);
+
+    // Tag byte, one-byte key length, key, one-byte value length, value
+    batch.put(b"key1", b"value1");
+    assert_eq!(batch.approximate_size(), 12 + 1 + 1 + 4 + 1 + 6);
+
+    // Tag byte, one-byte key length, key
+    batch.delete(b"key1");
+    assert_eq!(batch.approximate_size(), 25 + 1 + 1 + 4);
+
+    // Lengths of 128 and more take two varint bytes, 16384 and more three
+    batch.put(&[7u8; 200], &vec![0u8; 20_000]);
+    assert_eq!(batch.approximate_size(), 31 + 1 + 2 + 200 + 3 + 20_000);
+
+    batch.put(b"", b"");
+    assert_eq!(batch.approximate_size(), 20_237 + 3);
+
+    batch.clear();
+    assert_eq!(batch.approximate_size(), 12);
+    batch.delete(b"k");
+    assert_eq!(batch.approximate_size(), 15);
+}
+
+#[test]
+fn test_batch_len() {
+    let mut batch = WriteBatch::new();
+    assert_eq!(batch.len(), 0);
+    assert!(batch.is_empty());
+
+    batch.put(b"a", b"1");
+    batch.put(b"a", b"2");
+    batch.delete(b"b");
+    assert_eq!(batch.len(), 3);
+    assert!(!batch.is_empty());
+
+    let mut other = WriteBatch::new();
+    other.delete(b"a");
+    other.put(b"c", b"3");
+    batch.append(&other);
+    assert_eq!(batch.len(), 5);
+    assert_eq!(other.len(), 2);
+    assert_eq!(batch.iter_ops()[3..], other.iter_ops()[..]);
+    assert_eq!(
+        batch.approximate_size(),
+        WriteBatch::new().approximate_size() + 5 + 5 + 3 + 3 + 5
+    );
+
+    batch.append(&WriteBatch::new());
+    assert_eq!(batch.len(), 5);
+
+    batch.clear();
+    assert_eq!(batch.len(), 0);
+    assert!(batch.is_empty());
+}
+
+#[test]
+fn test_batch_append_write() {
+    let (db, _temp_dir) = setup_test_db("test_batch_append_write");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    let mut batch = WriteBatch::new();
+    batch.put(b"a", b"1");
+    let mut other = WriteBatch::new();
+    other.delete(b"a");
+    other.put(b"b", b"2");
+    batch.append(&other);
+    db.write(&batch, &write_opts).unwrap();
+
+    // Appended operations apply after the original ones
+    assert_eq!(db.get(b"a", &read_opts).unwrap(), None);
+    assert_eq!(db.get(b"b", &read_opts).unwrap(), Some(b"2".to_vec()));
+}
+
+#[test]
+fn test_write_empty_batch_is_noop() {
+    let (db, _temp_dir) = setup_test_db("test_write_empty_batch");
+    let write_opts = WriteOptions::new();
+    write_opts.sync(true);
+    let log_bytes = db.disk_usage().unwrap().log_bytes;
+
+    let mut batch = WriteBatch::new();
+    db.write(&batch, &write_opts).unwrap();
+    batch.write(&db, &write_opts).unwrap();
+    batch.put(b"a", b"1");
+    batch.clear();
+    db.write(&batch, &write_opts).unwrap();
+
+    // Nothing reached the write-ahead log
+    assert_eq!(db.disk_usage().unwrap().log_bytes, log_bytes);
+
+    batch.put(b"a", b"1");
+    db.write(&batch, &write_opts).unwrap();
+    assert!(db.disk_usage().unwrap().log_bytes > log_bytes);
+}
+
+#[test]
+fn test_batch_bytes_round_trip() {
+    let empty = WriteBatch::new();
+    let bytes = empty.to_bytes();
+    assert_eq!(bytes, [0u8; 12]);
+    assert!(WriteBatch::from_bytes(&bytes).unwrap().is_empty());
+
+    let mut batch = WriteBatch::new();
+    batch.put(b"\x00\xff\x80key", b"\x01\x02");
+    batch.delete(b"gone");
+    batch.put(b"empty", b"");
+    batch.put(&[9u8; 300], &vec![0xAB; 70_000]);
+    batch.delete(b"\x00\xff\x80key");
+
+    let bytes = batch.to_bytes();
+    assert_eq!(bytes.len(), batch.approximate_size());
+    assert_eq!(&bytes[8..12], &5u32.to_le_bytes());
+
+    let restored = WriteBatch::from_bytes(&bytes).unwrap();
+    assert_eq!(restored.iter_ops(), batch.iter_ops());
+    assert_eq!(restored.len(), batch.len());
+    assert_eq!(restored.approximate_size(), batch.approximate_size());
+    assert_eq!(restored.to_bytes(), bytes);
+}
+
+#[test]
+fn test_batch_from_bytes_rejects_corruption() {
+    let mut batch = WriteBatch::new();
+    batch.put(b"key", b"value");
+    batch.delete(b"other");
+    let bytes = batch.to_bytes();
+
+    assert!(WriteBatch::from_bytes(&bytes[..8]).is_err());
+    for len in BATCH_HEADER..bytes.len() {
+        // Every proper prefix either ends inside a record or has too few records
+        assert!(WriteBatch::from_bytes(&bytes[..len]).is_err(), "{}", len);
+    }
+
+    let mut trailing = bytes.clone();
+    trailing.push(0x00);
+    assert!(WriteBatch::from_bytes(&trailing).is_err());
+
+    let mut bad_tag = bytes.clone();
+    bad_tag[BATCH_HEADER] = 0x07;
+    assert!(
+        WriteBatch::from_bytes(&bad_tag)
+            .err()
+            .unwrap()
+            .contains("tag")
+    );
+
+    let mut bad_count = bytes;
+    bad_count[8] = 3;
+    assert!(
+        WriteBatch::from_bytes(&bad_count)
+            .err()
+            .unwrap()
+            .contains("counts")
+    );
+}
+
+#[test]
+fn test_batch_from_bytes_replays_on_fresh_db() {
+    let (db, _temp_dir) = setup_test_db("test_batch_replay_source");
+    let (replica, _replica_dir) = setup_test_db("test_batch_replay_target");
+    let write_opts = WriteOptions::new();
+
+    let mut journal = Vec::new();
+    for round in 0..3u8 {
+        let mut batch = WriteBatch::new();
+        for i in 0..20u8 {
+            batch.put(&[round, i], &[i; 3]);
+        }
+        batch.delete(&[round.saturating_sub(1), 5]);
+        batch.put(b"round", &[round]);
+        db.write(&batch, &write_opts).unwrap();
+        journal.push(batch.to_bytes());
+    }
+
+    for bytes in &journal {
+        let batch = WriteBatch::from_bytes(bytes).unwrap();
+        replica.write(&batch, &write_opts).unwrap();
+    }
+
+    let read_opts = ReadOptions::new();
+    let expected: Vec<_> = db.iter(&read_opts).collect();
+    assert_eq!(replica.iter(&read_opts).collect::<Vec<_>>(), expected);
+    assert_eq!(expected.len(), 3 * 20 - 2 + 1);
+}
+
+#[test]
+fn test_batch_delete_prefix() {
+    let (db, _temp_dir) = setup_test_db("test_batch_delete_prefix");
+    let write_opts = WriteOptions::new();
+    let read_opts = ReadOptions::new();
+
+    for i in 0..500u32 {
+        db.put(format!("chunk_a_{:04}", i).as_bytes(), b"x", &write_opts)
+            .unwrap();
+    }
+    for key in [
+        &b"chunk_"[..],
+        b"chunk_a",
+        b"chunk_b_0001",
+        b"chunk_` ",
+        b"other",
+    ] {
+        db.put(key, b"keep", &write_opts).unwrap();
+    }
+
+    let mut batch = WriteBatch::new();
+    batch.put(b"chunk_a_new", b"fresh");
+    assert_eq!(
+        batch.delete_prefix(&db, b"chunk_a_", &read_opts).unwrap(),
+        500
+    );
+    assert_eq!(batch.len(), 501);
+
+    // Nothing is deleted until the batch is written
+    assert!(db.get(b"chunk_a_0000", &read_opts).unwrap().is_some());
+    db.write(&batch, &write_opts).unwrap();
+
+    let remaining: Vec<Vec<u8>> = db.iter(&read_opts).map(|(key, _)| key).collect();
+    assert_eq!(
+        remaining,
+        vec![
+            b"chunk_".to_vec(),
+            b"chunk_` ".to_vec(),
+            b"chunk_a".to_vec(),
+            b"chunk_a_new".to_vec(),
+            b"chunk_b_0001".to_vec(),
+            b"other".to_vec(),
+        ]
+    );
+    // Only keys present in the database were queued for deletion
+    assert_eq!(
+        db.get(b"chunk_a_new", &read_opts).unwrap(),
+        Some(b"fresh".to_vec())
+    );
+}
+
+#[test]
+fn test_batch_delete_prefix_no_match() {
+    let (db, _temp_dir) = setup_test_db("test_batch_delete_prefix_none");
+    let write_opts = WriteOptions::new();
+    db.put(b"alpha", b"1", &write_opts).unwrap();
+    db.put(b"gamma", b"3", &write_opts).unwrap();
+
+    let mut batch = WriteBatch::new();
+    assert_eq!(
+        batch
+            .delete_prefix(&db, b"beta", &ReadOptions::new())
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        batch
+            .delete_prefix(&db, b"zeta", &ReadOptions::new())
+            .unwrap(),
+        0
+    );
+    assert!(batch.is_empty());
+}
+
+#[test]
+fn test_batch_as_ref_arguments() {
+    let (db, _temp_dir) = setup_test_db("test_batch_as_ref_arguments");
+    let read_opts = ReadOptions::new();
+
+    let mut batch = WriteBatch::new();
+    batch.put(String::from("key1"), vec![1u8, 2]);
+    batch.put("key2", "value2");
+    batch.put(vec![b'k', b'3'], [3u8; 4]);
+    batch.delete(String::from("key2"));
+    db.write(&batch, &WriteOptions::new()).unwrap();
+
+    assert_eq!(db.get("key1", &read_opts).unwrap(), Some(vec![1, 2]));
+    assert_eq!(db.get("key2", &read_opts).unwrap(), None);
+    assert_eq!(db.get("k3", &read_opts).unwrap(), Some(vec![3; 4]));
+}
+
+#[test]
+fn test_batch_from_raw_recounts_operations() {
+    let mut batch = WriteBatch::new();
+    batch.put(b"key1", b"value1");
+    batch.delete(b"key2");
+    let (len, size, ops) = (batch.len(), batch.approximate_size(), batch.iter_ops());
+
+    let raw = unsafe { batch.as_raw() };
+    std::mem::forget(batch);
+    let batch = unsafe { WriteBatch::from_raw(raw) };
+    assert_eq!(batch.len(), len);
+    assert_eq!(batch.approximate_size(), size);
+    assert_eq!(batch.iter_ops(), ops);
+    assert_eq!(batch.to_bytes().len(), size);
+}
@@ -0,0 +1,146 @@
+//! Spans and events for database operations, behind the `tracing` feature.
+//!
+//! Each instrumented operation opens a span named `bleveldb` carrying the
+//! operation and the sizes involved, and emits one event when it finishes
+//! with its duration and outcome. Keys are rendered with
+//! [`describe_key`](crate::bedrock::describe_key), but only once a subscriber
+//! has shown interest in the span.
+//!
+//! Point operations and iterator creation use the `TRACE` level, batch
+//! writes `DEBUG` and opening and compacting `INFO`, so a subscriber that
+//! filters out `TRACE` leaves the hot paths with a single cached check.
+//! Enabling one of tracing's `max_level_*` features removes them entirely.
+//!
+//! Without the feature, [`op_span!`] expands to a unit struct whose methods
+//! do nothing and compile away.
+
+/// Open the span of an operation at the given level, returning an [`Op`]
+/// that finishes it.
+///
+/// Extra `name = value` pairs become fields of the span.
+#[cfg(feature = "tracing")]
+macro_rules! op_span {
+    ($level:ident, $op:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        $crate::trace::Op::new(
+            tracing::span!(
+                tracing::Level::$level,
+                "bleveldb",
+                op = $op,
+                $($field = $value,)*
+                key = tracing::field::Empty,
+                value_len = tracing::field::Empty,
+                duration_us = tracing::field::Empty,
+                outcome = tracing::field::Empty,
+            ),
+            tracing::Level::$level,
+        )
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! op_span {
+    ($($tokens:tt)*) => {
+        $crate::trace::Op
+    };
+}
+
+pub(crate) use op_span;
+
+#[cfg(feature = "tracing")]
+pub(crate) use enabled::Op;
+
+#[cfg(feature = "tracing")]
+mod enabled {
+    use std::time::Instant;
+
+    use tracing::{Level, Span};
+
+    use crate::bedrock::describe_key;
+
+    /// An operation in progress, created by [`op_span!`].
+    pub(crate) struct Op {
+        span: Span,
+        level: Level,
+        start: Option<Instant>,
+    }
+
+    /// Emit an event at a level only known at run time.
+    macro_rules! event_at {
+        ($level:expr, $($args:tt)+) => {
+            match $level {
+                Level::ERROR => tracing::event!(Level::ERROR, $($args)+),
+                Level::WARN => tracing::event!(Level::WARN, $($args)+),
+                Level::INFO => tracing::event!(Level::INFO, $($args)+),
+                Level::DEBUG => tracing::event!(Level::DEBUG, $($args)+),
+                _ => tracing::event!(Level::TRACE, $($args)+),
+            }
+        };
+    }
+
+    impl Op {
+        pub(crate) fn new(span: Span, level: Level) -> Self {
+            let start = (!span.is_disabled()).then(Instant::now);
+            Op { span, level, start }
+        }
+
+        /// Record the key the operation works on.
+        pub(crate) fn key(&self, key: &[u8]) {
+            if !self.span.is_disabled() {
+                self.span
+                    .record("key", tracing::field::display(describe_key(key)));
+            }
+        }
+
+        /// Record the length of the value written or read.
+        pub(crate) fn value_len(&self, len: usize) {
+            self.span.record("value_len", len);
+        }
+
+        /// Record the outcome and duration, emit the completion event and
+        /// pass `result` through.
+        pub(crate) fn finish<T>(self, result: Result<T, String>) -> Result<T, String> {
+            let Some(start) = self.start else {
+                return result;
+            };
+            let duration_us = start.elapsed().as_micros() as u64;
+            self.span.record("duration_us", duration_us);
+            let _entered = self.span.enter();
+            match &result {
+                Ok(_) => {
+                    self.span.record("outcome", "ok");
+                    event_at!(self.level, duration_us, outcome = "ok", "done");
+                }
+                Err(e) => {
+                    self.span.record("outcome", "error");
+                    event_at!(
+                        self.level,
+                        duration_us,
+                        outcome = "error",
+                        error = %e,
+                        "failed"
+                    );
+                }
+            }
+            result
+        }
+    }
+}
+
+/// An operation in progress, created by [`op_span!`]; does nothing without
+/// the `tracing` feature.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Op;
+
+#[cfg(not(feature = "tracing"))]
+impl Op {
+    #[inline(always)]
+    pub(crate) fn key(&self, _key: &[u8]) {}
+
+    #[inline(always)]
+    pub(crate) fn value_len(&self, _len: usize) {}
+
+    #[inline(always)]
+    pub(crate) fn finish<T>(self, result: Result<T, String>) -> Result<T, String> {
+        result
+    }
+}
@@ -1,4 +1,7 @@
 use std::ffi::{CStr, CString};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::{env, fs, io, process};
 
 /// Convert a LevelDB error pointer into a Rust `String`.
 ///
@@ -20,3 +23,112 @@ pub(crate) fn error_message(err_ptr: *mut i8) -> String {
 pub(crate) fn to_cstring(s: &str) -> Option<CString> {
     CString::new(s).ok()
 }
+
+/// Convert a filesystem path into a C-compatible `CString` for LevelDB.
+///
+/// Returns an error message if the path contains null bytes.
+pub(crate) fn path_to_cstring(path: &Path) -> Result<CString, String> {
+    to_cstring(path.to_string_lossy().as_ref())
+        .ok_or_else(|| "invalid path: contains null byte".to_string())
+}
+
+/// Create a new, empty, uniquely named directory under the system temp directory.
+///
+/// The caller is responsible for removing it.
+pub(crate) fn unique_temp_dir(prefix: &str) -> io::Result<PathBuf> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    loop {
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir = env::temp_dir().join(format!("{}-{}-{}", prefix, process::id(), n));
+        match fs::create_dir(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Lookup table for the reflected CRC-32 (IEEE 802.3) polynomial.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Incremental CRC-32 (IEEE 802.3), the checksum used by zip and gzip.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Crc32(u32);
+
+impl Crc32 {
+    pub(crate) fn new() -> Self {
+        Self(0xFFFF_FFFF)
+    }
+
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = CRC32_TABLE[((self.0 ^ byte as u32) & 0xFF) as usize] ^ (self.0 >> 8);
+        }
+    }
+
+    pub(crate) fn finish(&self) -> u32 {
+        self.0 ^ 0xFFFF_FFFF
+    }
+}
+
+/// Append `value` to `buf` as an unsigned LEB128 varint.
+pub(crate) fn encode_varint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Return the number of bytes [`encode_varint`] uses for `value`.
+pub(crate) fn varint_len(value: u64) -> usize {
+    (64 - (value | 1).leading_zeros() as usize).div_ceil(7)
+}
+
+/// Read an unsigned LEB128 varint from `reader`.
+///
+/// Fails with `InvalidData` if the varint does not fit in a `u64`.
+pub(crate) fn read_varint(reader: &mut impl io::Read) -> io::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0u8];
+        reader.read_exact(&mut byte)?;
+        value |= u64::from(byte[0] & 0x7F) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "varint is too long",
+    ))
+}
+
+/// Return the smallest key that is greater than every key starting with `prefix`.
+///
+/// Returns `None` if there is no such key, i.e. `prefix` is empty or all `0xFF`.
+pub(crate) fn prefix_successor(prefix: &[u8]) -> Option<Vec<u8>> {
+    let last = prefix.iter().rposition(|&b| b != 0xFF)?;
+    let mut key = prefix[..=last].to_vec();
+    key[last] += 1;
+    Some(key)
+}
@@ -1,9 +1,106 @@
 use bleveldb_sys as sys;
-use std::ptr;
+use libc::{c_char, c_void, size_t};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
 
 use crate::DB;
-use crate::options::WriteOptions;
-use crate::util::error_message;
+use crate::iterator::DBIterator;
+use crate::options::{ReadOptions, WriteOptions};
+use crate::util::{encode_varint, read_varint, varint_len};
+
+/// Size of the sequence number and count header of LevelDB's batch encoding.
+const BATCH_HEADER_BYTES: usize = 12;
+
+/// Record tag LevelDB uses for deletions.
+const TAG_DELETE: u8 = 0x00;
+
+/// Record tag LevelDB uses for puts.
+const TAG_PUT: u8 = 0x01;
+
+/// A single operation recorded in a [`WriteBatch`].
+///
+/// Returned by [`WriteBatch::iter_ops`] in the order the operations were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchOp {
+    /// Insert or overwrite `key` with `value`.
+    Put { key: Vec<u8>, value: Vec<u8> },
+    /// Remove `key`.
+    Delete { key: Vec<u8> },
+}
+
+impl BatchOp {
+    /// Return the key this operation applies to.
+    pub fn key(&self) -> &[u8] {
+        match self {
+            BatchOp::Put { key, .. } | BatchOp::Delete { key } => key,
+        }
+    }
+}
+
+/// State shared with the `leveldb_writebatch_iterate` callbacks.
+struct IterateState<F> {
+    visit: F,
+    /// Payload of a panic raised by `visit`, re-raised once LevelDB returns.
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<F: FnMut(&[u8], Option<&[u8]>)> IterateState<F> {
+    /// Run `visit`, catching any panic so it never unwinds through C++ frames.
+    fn call(&mut self, key: &[u8], value: Option<&[u8]>) {
+        if self.panic.is_some() {
+            return;
+        }
+        let visit = &mut self.visit;
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| visit(key, value))) {
+            self.panic = Some(payload);
+        }
+    }
+}
+
+/// Turn a pointer and length from LevelDB into a slice, accepting null for empty data.
+unsafe fn bytes<'a>(ptr: *const c_char, len: size_t) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        unsafe { slice::from_raw_parts(ptr as *const u8, len) }
+    }
+}
+
+/// Split a length-prefixed field off the front of an encoded batch.
+fn read_record_field<'a>(records: &mut &'a [u8]) -> Result<&'a [u8], String> {
+    let len =
+        read_varint(records).map_err(|_| "corrupt write batch: truncated length".to_string())?;
+    if len > records.len() as u64 {
+        return Err("corrupt write batch: field runs past the end".to_string());
+    }
+    let (data, rest) = records.split_at(len as usize);
+    *records = rest;
+    Ok(data)
+}
+
+extern "C" fn put_trampoline<F: FnMut(&[u8], Option<&[u8]>)>(
+    state: *mut c_void,
+    key: *const c_char,
+    klen: size_t,
+    value: *const c_char,
+    vlen: size_t,
+) {
+    let state = unsafe { &mut *(state as *mut IterateState<F>) };
+    state.call(
+        unsafe { bytes(key, klen) },
+        Some(unsafe { bytes(value, vlen) }),
+    );
+}
+
+extern "C" fn delete_trampoline<F: FnMut(&[u8], Option<&[u8]>)>(
+    state: *mut c_void,
+    key: *const c_char,
+    klen: size_t,
+) {
+    let state = unsafe { &mut *(state as *mut IterateState<F>) };
+    state.call(unsafe { bytes(key, klen) }, None);
+}
 
 /// A batch of write operations (put/delete) that can be committed atomically.
 ///
@@ -42,7 +139,7 @@ use crate::util::error_message;
 /// batch.delete(b"old_key");
 ///
 /// // Commit all operations atomically
-/// batch.write(&db, &Default::default()).unwrap();
+/// db.write(&batch, &Default::default()).unwrap();
 /// ```
 ///
 /// ## Batch with error handling
@@ -54,20 +151,28 @@ use crate::util::error_message;
 /// let mut batch = WriteBatch::new();
 /// batch.put(b"data", b"important information");
 ///
-/// match batch.write(&db, &Default::default()) {
+/// match db.write(&batch, &Default::default()) {
 ///     Ok(()) => println!("Batch committed successfully"),
 ///     Err(e) => eprintln!("Failed to write batch: {}", e),
 /// }
 /// ```
 pub struct WriteBatch {
     raw: *mut sys::leveldb_writebatch_t,
+    /// Size of the batch's encoding, tracked because the C API cannot report it.
+    size: usize,
+    /// Number of operations in the batch.
+    count: usize,
 }
 
+// SAFETY: the batch is exclusively owned and LevelDB only touches it during
+// a call, so it can be built on one thread and written from another.
+unsafe impl Send for WriteBatch {}
+
 impl WriteBatch {
     /// Create a new, empty write batch.
     ///
     /// The batch starts with no operations. Use `put()` and `delete()` methods
-    /// to add operations to the batch, then call `write()` to commit them.
+    /// to add operations to the batch, then call `DB::write()` to commit them.
     ///
     /// # Returns
     ///
@@ -83,6 +188,8 @@ impl WriteBatch {
     pub fn new() -> Self {
         Self {
             raw: unsafe { sys::leveldb_writebatch_create() },
+            size: BATCH_HEADER_BYTES,
+            count: 0,
         }
     }
 
@@ -106,7 +213,8 @@ impl WriteBatch {
     /// batch.put(b"user:123", b"John Doe");
     /// batch.put(b"user:456", b"Jane Smith");
     /// ```
-    pub fn put(&mut self, key: &[u8], value: &[u8]) {
+    pub fn put(&mut self, key: impl AsRef<[u8]>, value: impl AsRef<[u8]>) {
+        let (key, value) = (key.as_ref(), value.as_ref());
         unsafe {
             sys::leveldb_writebatch_put(
                 self.raw,
@@ -116,6 +224,9 @@ impl WriteBatch {
                 value.len(),
             );
         }
+        self.size += 1 + varint_len(key.len() as u64) + key.len();
+        self.size += varint_len(value.len() as u64) + value.len();
+        self.count += 1;
     }
 
     /// Add a `delete` operation to the batch.
@@ -136,10 +247,69 @@ impl WriteBatch {
     /// batch.put(b"new_data", b"fresh value");
     /// batch.delete(b"old_data"); // Remove outdated data
     /// ```
-    pub fn delete(&mut self, key: &[u8]) {
+    pub fn delete(&mut self, key: impl AsRef<[u8]>) {
+        let key = key.as_ref();
         unsafe {
             sys::leveldb_writebatch_delete(self.raw, key.as_ptr() as *const _, key.len());
         }
+        self.size += 1 + varint_len(key.len() as u64) + key.len();
+        self.count += 1;
+    }
+
+    /// Queue a delete for every key in `db` that starts with `prefix`.
+    ///
+    /// The keys are collected from a snapshot (or the snapshot already set on
+    /// `options`), so the result is consistent even while `db` is being
+    /// written. Nothing is deleted until the batch is written, which lets the
+    /// deletions be combined with other edits, such as removing a chunk's old
+    /// records and writing its new ones in one atomic commit. Keys written
+    /// under the prefix after the scan are not affected. An empty prefix
+    /// matches every key.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database to scan for matching keys
+    /// * `prefix` - The key prefix to delete
+    /// * `options` - Read options for the scan
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - The number of delete operations queued
+    /// * `Err(String)` - If the scan failed; operations queued so far stay in the batch
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, WriteBatch, options::{Options, ReadOptions}};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// // Replace every record of the Overworld chunk at (0, 0) with fresh data
+    /// let chunk_prefix = [0u8; 8];
+    /// let mut batch = WriteBatch::new();
+    /// batch.delete_prefix(&db, &chunk_prefix, &ReadOptions::new()).unwrap();
+    /// batch.put(&[0, 0, 0, 0, 0, 0, 0, 0, 0x2c], &[41]);
+    /// db.write(&batch, &Default::default()).unwrap();
+    /// ```
+    pub fn delete_prefix(
+        &mut self,
+        db: &DB,
+        prefix: &[u8],
+        options: &ReadOptions,
+    ) -> Result<u64, String> {
+        let (_snapshot, read_options) = db.consistent_read_options(options);
+        let mut iter = DBIterator::new(db, &read_options);
+        iter.seek(prefix);
+
+        let mut queued = 0;
+        while let Some(key) = iter.key_ref() {
+            if !key.starts_with(prefix) {
+                break;
+            }
+            self.delete(key);
+            queued += 1;
+            iter.next_native();
+        }
+        iter.status()?;
+        Ok(queued)
     }
 
     /// Clear all operations from this batch.
@@ -166,13 +336,300 @@ impl WriteBatch {
         unsafe {
             sys::leveldb_writebatch_clear(self.raw);
         }
+        self.size = BATCH_HEADER_BYTES;
+        self.count = 0;
+    }
+
+    /// Return the number of operations in the batch.
+    ///
+    /// Every `put` and `delete` counts, including repeated operations on the
+    /// same key.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::WriteBatch;
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// batch.delete(b"key");
+    /// assert_eq!(batch.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Return `true` if the batch contains no operations.
+    ///
+    /// Writing an empty batch is a no-op; see [`DB::write`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::WriteBatch;
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// assert!(batch.is_empty());
+    /// batch.delete(b"key");
+    /// assert!(!batch.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Add every operation of `other` to the end of this batch.
+    ///
+    /// The operations keep their order, so writing the combined batch has the
+    /// same effect as writing this batch and then `other`, but atomically.
+    /// `other` is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The batch whose operations are copied
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::WriteBatch;
+    ///
+    /// let mut chunk_edits = WriteBatch::new();
+    /// chunk_edits.put(b"chunk", b"data");
+    /// let mut index_edits = WriteBatch::new();
+    /// index_edits.delete(b"stale_index");
+    ///
+    /// chunk_edits.append(&index_edits);
+    /// assert_eq!(chunk_edits.len(), 2);
+    /// ```
+    pub fn append(&mut self, other: &WriteBatch) {
+        other.for_each_op(|key, value| match value {
+            Some(value) => self.put(key, value),
+            None => self.delete(key),
+        });
+    }
+
+    /// Return the size of the batch as LevelDB encodes it, in bytes.
+    ///
+    /// This matches what LevelDB's `WriteBatch::ApproximateSize` reports: a
+    /// 12-byte header plus, for every operation, a tag byte and the
+    /// length-prefixed key and value. It is exact and cheap to call, so
+    /// writers can use it to commit before a batch grows too large. An empty
+    /// batch, including one that was just cleared, reports 12 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use bleveldb::{DB, WriteBatch, options::Options};
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// # let records: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
+    /// let mut batch = WriteBatch::new();
+    /// for (key, value) in &records {
+    ///     batch.put(key, value);
+    ///     if batch.approximate_size() >= 4 << 20 {
+    ///         db.write(&batch, &Default::default()).unwrap();
+    ///         batch.clear();
+    ///     }
+    /// }
+    /// db.write(&batch, &Default::default()).unwrap();
+    /// ```
+    pub fn approximate_size(&self) -> usize {
+        self.size
+    }
+
+    /// Return a copy of every operation in the batch, in insertion order.
+    ///
+    /// This walks LevelDB's internal representation of the batch, so it shows
+    /// exactly what a write would apply. It is meant for inspecting batches,
+    /// for example when debugging a failed import.
+    ///
+    /// # Returns
+    ///
+    /// The operations of the batch, oldest first.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::WriteBatch;
+    /// use bleveldb::write_batch::BatchOp;
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// batch.delete(b"old_key");
+    ///
+    /// for op in batch.iter_ops() {
+    ///     match op {
+    ///         BatchOp::Put { key, value } => println!("put {:?} ({} bytes)", key, value.len()),
+    ///         BatchOp::Delete { key } => println!("delete {:?}", key),
+    ///     }
+    /// }
+    /// ```
+    pub fn iter_ops(&self) -> Vec<BatchOp> {
+        let mut ops = Vec::new();
+        self.for_each_op(|key, value| {
+            ops.push(match value {
+                Some(value) => BatchOp::Put {
+                    key: key.to_vec(),
+                    value: value.to_vec(),
+                },
+                None => BatchOp::Delete { key: key.to_vec() },
+            })
+        });
+        ops
+    }
+
+    /// Check whether the batch contains a put or delete for `key`.
+    ///
+    /// The batch is scanned without copying its contents.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look for
+    ///
+    /// # Returns
+    ///
+    /// `true` if at least one operation in the batch applies to `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::WriteBatch;
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.delete(b"old_key");
+    /// assert!(batch.contains_key(b"old_key"));
+    /// assert!(!batch.contains_key(b"other_key"));
+    /// ```
+    pub fn contains_key(&self, key: &[u8]) -> bool {
+        let mut found = false;
+        self.for_each_op(|op_key, _| found |= op_key == key);
+        found
+    }
+
+    /// Call `visit` with the key and, for puts, the value of every operation.
+    ///
+    /// A panic raised by `visit` is caught before it can unwind into LevelDB,
+    /// and re-raised once the iteration has returned.
+    pub(crate) fn for_each_op<F: FnMut(&[u8], Option<&[u8]>)>(&self, visit: F) {
+        let mut state = IterateState { visit, panic: None };
+        unsafe {
+            sys::leveldb_writebatch_iterate(
+                self.raw,
+                &mut state as *mut IterateState<F> as *mut c_void,
+                put_trampoline::<F>,
+                delete_trampoline::<F>,
+            );
+        }
+        if let Some(payload) = state.panic {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    /// Serialize the batch into a self-contained byte buffer.
+    ///
+    /// The buffer uses LevelDB's own batch encoding: a 12-byte header holding a
+    /// zero sequence number and the little-endian `u32` operation count,
+    /// followed by one record per operation (`0x01` with the length-prefixed
+    /// key and value for puts, `0x00` with the length-prefixed key for
+    /// deletes; lengths are LEB128 varints). Its length equals
+    /// [`approximate_size`](WriteBatch::approximate_size). Restore it with
+    /// [`from_bytes`](WriteBatch::from_bytes), for example to replay a journal
+    /// of pending batches on another machine.
+    ///
+    /// # Returns
+    ///
+    /// The encoded batch.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::WriteBatch;
+    ///
+    /// let mut batch = WriteBatch::new();
+    /// batch.put(b"key", b"value");
+    /// let bytes = batch.to_bytes();
+    ///
+    /// let restored = WriteBatch::from_bytes(&bytes).unwrap();
+    /// assert_eq!(restored.iter_ops(), batch.iter_ops());
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.size);
+        bytes.extend_from_slice(&[0; 8]);
+        bytes.extend_from_slice(&(self.count as u32).to_le_bytes());
+        self.for_each_op(|key, value| {
+            bytes.push(if value.is_some() { TAG_PUT } else { TAG_DELETE });
+            for field in std::iter::once(key).chain(value) {
+                encode_varint(field.len() as u64, &mut bytes);
+                bytes.extend_from_slice(field);
+            }
+        });
+        bytes
+    }
+
+    /// Rebuild a batch from bytes produced by [`to_bytes`](WriteBatch::to_bytes).
+    ///
+    /// The whole buffer is validated: an unknown record tag, a length running
+    /// past the end of the buffer, trailing bytes or a header count that does
+    /// not match the records are all rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - An encoded batch
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(WriteBatch)` - A batch with the same operations, in the same order
+    /// * `Err(String)` - If `bytes` is not a valid encoded batch
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bleveldb::{DB, WriteBatch, options::Options};
+    ///
+    /// # let db = DB::open("test_db", &Options::default()).unwrap();
+    /// let journal_entry = std::fs::read("pending.batch").unwrap();
+    /// let batch = WriteBatch::from_bytes(&journal_entry).unwrap();
+    /// db.write(&batch, &Default::default()).unwrap();
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<WriteBatch, String> {
+        if bytes.len() < BATCH_HEADER_BYTES {
+            return Err("corrupt write batch: shorter than its header".to_string());
+        }
+        let (header, mut records) = bytes.split_at(BATCH_HEADER_BYTES);
+        let expected = u32::from_le_bytes(header[8..].try_into().unwrap()) as usize;
+
+        let mut batch = WriteBatch::new();
+        while let Some((&tag, rest)) = records.split_first() {
+            records = rest;
+            match tag {
+                TAG_PUT => {
+                    let key = read_record_field(&mut records)?;
+                    let value = read_record_field(&mut records)?;
+                    batch.put(key, value);
+                }
+                TAG_DELETE => batch.delete(read_record_field(&mut records)?),
+                other => {
+                    return Err(format!(
+                        "corrupt write batch: unknown record tag 0x{:02x}",
+                        other
+                    ));
+                }
+            }
+        }
+
+        if batch.len() != expected {
+            return Err(format!(
+                "corrupt write batch: header counts {} operations but {} were found",
+                expected,
+                batch.len()
+            ));
+        }
+        Ok(batch)
     }
 
     /// Write this batch to the database atomically.
     ///
     /// This method commits all operations in the batch to the database in a
     /// single atomic transaction. Either all operations succeed, or none are
-    /// applied.
+    /// applied. It is the same as [`DB::write`] with the receiver swapped.
     ///
     /// # Arguments
     ///
@@ -243,14 +700,76 @@ impl WriteBatch {
     /// }
     /// ```
     pub fn write(&self, db: &DB, options: &WriteOptions) -> Result<(), String> {
-        unsafe {
-            let mut err = ptr::null_mut();
-            sys::leveldb_write(db.raw(), options.raw(), self.raw, &mut err);
-            if !err.is_null() {
-                return Err(error_message(err as *mut _));
+        db.write(self, options)
+    }
+
+    /// Get the raw pointer to the underlying LevelDB write batch.
+    ///
+    /// # Safety
+    ///
+    /// This method is for internal use only. The returned pointer should not be
+    /// stored or used outside the lifetime of this `WriteBatch` instance.
+    ///
+    /// # Returns
+    ///
+    /// A raw pointer to the underlying `leveldb_writebatch_t`.
+    pub(crate) fn raw(&self) -> *mut sys::leveldb_writebatch_t {
+        self.raw
+    }
+
+    /// Return the raw LevelDB write batch, for calling
+    /// [`sys`](crate::sys) functions this crate does not wrap.
+    ///
+    /// # Safety
+    ///
+    /// The pointer stays owned by this `WriteBatch`: the caller must not
+    /// destroy it or use it after the batch is dropped. Operations must only
+    /// be added or cleared through the batch's own methods, or
+    /// [`len`](WriteBatch::len) and
+    /// [`approximate_size`](WriteBatch::approximate_size) go stale.
+    ///
+    /// # Returns
+    ///
+    /// The `leveldb_writebatch_t` pointer
+    pub unsafe fn as_raw(&self) -> *mut sys::leveldb_writebatch_t {
+        self.raw
+    }
+
+    /// Wrap a raw LevelDB write batch, taking ownership of it.
+    ///
+    /// The pointer is destroyed when the returned batch is dropped. The
+    /// operations it already holds are walked once to count them and
+    /// compute their encoded size.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must come from `leveldb_writebatch_create` and must not be
+    /// destroyed or wrapped by anything else.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The write batch to take ownership of
+    ///
+    /// # Returns
+    ///
+    /// A `WriteBatch` owning the pointer
+    pub unsafe fn from_raw(raw: *mut sys::leveldb_writebatch_t) -> Self {
+        let mut batch = Self {
+            raw,
+            size: BATCH_HEADER_BYTES,
+            count: 0,
+        };
+        let (mut size, mut count) = (0, 0);
+        batch.for_each_op(|key, value| {
+            size += 1;
+            for field in std::iter::once(key).chain(value) {
+                size += varint_len(field.len() as u64) + field.len();
             }
-        }
-        Ok(())
+            count += 1;
+        });
+        batch.size += size;
+        batch.count = count;
+        batch
     }
 }
 
@@ -277,7 +796,7 @@ impl Drop for WriteBatch {
     /// # Note
     ///
     /// If a batch is dropped without being written, all operations in the batch
-    /// are lost. To persist the operations, call `write()` before the batch is dropped.
+    /// are lost. To persist the operations, call `DB::write()` before the batch is dropped.
     fn drop(&mut self) {
         unsafe {
             sys::leveldb_writebatch_destroy(self.raw);